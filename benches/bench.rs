@@ -9,6 +9,47 @@ fn readme(c: &mut Criterion) {
     });
 }
 
+fn large(c: &mut Criterion) {
+    // About the size of the `CommonMark` spec.
+    let ascii = fs::read_to_string("readme.md").unwrap().repeat(16);
+    // Same, but with multibyte characters around attention, which is where
+    // characters (instead of bytes) are decoded.
+    let multibyte = ascii.replace('*', "é*ü");
+    let mut group = c.benchmark_group("large");
+    group.sample_size(20);
+    group.bench_with_input(BenchmarkId::new("large", "ascii"), &ascii, |b, s| {
+        b.iter(|| markdown::to_html(s));
+    });
+    group.bench_with_input(
+        BenchmarkId::new("large", "multibyte"),
+        &multibyte,
+        |b, s| {
+            b.iter(|| markdown::to_html(s));
+        },
+    );
+    group.finish();
+}
+
+fn many_blocks(c: &mut Criterion) {
+    // Lots of small blocks, and lots of lines, which used to take quadratic
+    // time, because of how events of each line were injected.
+    let paragraphs = "a\n\n".repeat(20_000);
+    let lines = "<a\n".repeat(20_000);
+    let mut group = c.benchmark_group("many");
+    group.sample_size(10);
+    group.bench_with_input(
+        BenchmarkId::new("many", "paragraphs"),
+        &paragraphs,
+        |b, s| {
+            b.iter(|| markdown::to_html(s));
+        },
+    );
+    group.bench_with_input(BenchmarkId::new("many", "lines"), &lines, |b, s| {
+        b.iter(|| markdown::to_html(s));
+    });
+    group.finish();
+}
+
 // fn one_and_a_half_mb(c: &mut Criterion) {
 //     let doc = fs::read_to_string("../a-dump-of-markdown/markdown.md").unwrap();
 //     let mut group = c.benchmark_group("giant");
@@ -20,5 +61,5 @@ fn readme(c: &mut Criterion) {
 // }
 // , one_and_a_half_mb
 
-criterion_group!(benches, readme);
+criterion_group!(benches, readme, large, many_blocks);
 criterion_main!(benches);
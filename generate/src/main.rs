@@ -10,7 +10,7 @@ use std::fs;
 #[tokio::main]
 async fn main() {
     commonmark().await;
-    punctuation().await;
+    unicode().await;
 }
 
 async fn commonmark() {
@@ -98,10 +98,24 @@ fn commonmark() -> Result<(), message::Message> {{
     fs::write(code_url, doc).unwrap();
 }
 
-async fn punctuation() {
+async fn unicode() {
+    let code_url = "src/util/unicode.rs";
+
+    let doc = format!(
+        "//! Info on Unicode.
+
+{}
+{}",
+        punctuation().await,
+        grapheme_extend().await
+    );
+
+    fs::write(code_url, doc).unwrap();
+}
+
+async fn punctuation() -> String {
     let url = "https://www.unicode.org/Public/UCD/latest/ucd/UnicodeData.txt";
     let data_url = "unicode-data.txt";
-    let code_url = "src/util/unicode.rs";
 
     let value = if let Ok(value) = fs::read_to_string(data_url) {
         value
@@ -135,10 +149,8 @@ async fn punctuation() {
         .map(|c| c.0)
         .collect::<Vec<_>>();
 
-    let doc = format!(
-        "//! Info on Unicode.
-
-/// List of characters that are considered punctuation.
+    format!(
+        "/// List of characters that are considered punctuation.
 ///
 /// > 👉 **Important**: this module is generated by `generate/src/main.rs`.
 /// > It is generate from the latest Unicode data.
@@ -159,7 +171,71 @@ pub const PUNCTUATION: [char; {}] = [
 ",
     found.len(),
     found.iter().map(|d| format!("    '\\u{{{}}}',", d)).collect::<Vec<_>>().join("\n")
-    );
+    )
+}
 
-    fs::write(code_url, doc).unwrap();
+async fn grapheme_extend() -> String {
+    let url = "https://www.unicode.org/Public/UCD/latest/ucd/DerivedCoreProperties.txt";
+    let data_url = "derived-core-properties.txt";
+
+    let value = if let Ok(value) = fs::read_to_string(data_url) {
+        value
+    } else {
+        let value = reqwest::get(url).await.unwrap().text().await.unwrap();
+
+        fs::write(data_url, value.clone()).unwrap();
+
+        value
+    };
+
+    let mut found: Vec<(u32, u32)> = vec![];
+
+    for line in value.lines() {
+        let cells = line
+            .split('#')
+            .next()
+            .unwrap()
+            .split(';')
+            .collect::<Vec<_>>();
+
+        if cells.len() != 2 || cells[1].trim() != "Grapheme_Extend" {
+            continue;
+        }
+
+        let mut bounds = cells[0].trim().split("..");
+        let start = u32::from_str_radix(bounds.next().unwrap(), 16).unwrap();
+        let end = bounds
+            .next()
+            .map_or(start, |d| u32::from_str_radix(d, 16).unwrap());
+
+        // Merge adjacent ranges.
+        match found.last_mut() {
+            Some(last) if last.1 + 1 == start => last.1 = end,
+            _ => found.push((start, end)),
+        }
+    }
+
+    format!(
+        "/// List of ranges of characters that extend the grapheme before them.
+///
+/// > 👉 **Important**: this module is generated by `generate/src/main.rs`.
+/// > It is generate from the latest Unicode data.
+///
+/// Used to count columns in graphemes.
+/// The list is sorted by code point.
+///
+/// ## References
+///
+/// *   [*§ 3.1 Default Grapheme Cluster Boundary Specification* in `UAX #29`](https://www.unicode.org/reports/tr29/#Default_Grapheme_Cluster_Table)
+pub const GRAPHEME_EXTEND: [(char, char); {}] = [
+{}
+];
+",
+        found.len(),
+        found
+            .iter()
+            .map(|d| format!("    ('\\u{{{:04X}}}', '\\u{{{:04X}}}'),", d.0, d.1))
+            .collect::<Vec<_>>()
+            .join("\n")
+    )
 }
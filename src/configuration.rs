@@ -1,8 +1,12 @@
+use crate::resolve::Resolver;
 use crate::util::{
+    constant::{SAFE_NESTING_DEPTH_MAX, TAB_SIZE, THEMATIC_BREAK_MARKER_COUNT_MIN},
+    highlight::CodeHighlighterFn,
     line_ending::LineEnding,
     mdx::{EsmParse as MdxEsmParse, ExpressionParse as MdxExpressionParse},
+    slug::{HeadingIdFn, WikilinkHrefFn},
 };
-use alloc::{boxed::Box, fmt, string::String};
+use alloc::{boxed::Box, fmt, rc::Rc, string::String, vec::Vec};
 
 /// Control which constructs are enabled.
 ///
@@ -39,6 +43,13 @@ pub struct Constructs {
     ///       ^^^   ^^^^^
     /// ```
     pub attention: bool,
+    /// Attribute list (after heading (atx) text and fenced code info).
+    ///
+    /// ```markdown
+    /// > | # a {#b .c}
+    ///         ^^^^^^^
+    /// ```
+    pub attributes: bool,
     /// Autolink.
     ///
     /// ```markdown
@@ -99,6 +110,31 @@ pub struct Constructs {
     ///     ^^^^^^^^^^
     /// ```
     pub definition: bool,
+    /// Directive (container).
+    ///
+    /// ```markdown
+    /// > | :::a[b]{c}
+    ///     ^^^^^^^^^^
+    /// > | d
+    ///     ^
+    /// > | :::
+    ///     ^^^
+    /// ```
+    pub directive_container: bool,
+    /// Directive (leaf).
+    ///
+    /// ```markdown
+    /// > | ::a[b]{c}
+    ///     ^^^^^^^^^
+    /// ```
+    pub directive_leaf: bool,
+    /// Directive (text).
+    ///
+    /// ```markdown
+    /// > | a :b[c]{d} e
+    ///       ^^^^^^^^
+    /// ```
+    pub directive_text: bool,
     /// Frontmatter.
     ///
     /// ````markdown
@@ -171,6 +207,13 @@ pub struct Constructs {
     ///   | b
     /// ```
     pub hard_break_trailing: bool,
+    /// Hashtag.
+    ///
+    /// ```markdown
+    /// > | a #b c
+    ///       ^^
+    /// ```
+    pub hashtag: bool,
     /// Heading (atx).
     ///
     /// ```markdown
@@ -208,6 +251,13 @@ pub struct Constructs {
     ///       ^^
     /// ```
     pub label_start_image: bool,
+    /// Label start (inline footnote).
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^^
+    /// ```
+    pub label_start_inline_footnote: bool,
     /// Label start (link).
     ///
     /// ```markdown
@@ -229,6 +279,13 @@ pub struct Constructs {
     ///     ^^^
     /// ```
     pub list_item: bool,
+    /// Mark (highlight).
+    ///
+    /// ```markdown
+    /// > | a ==b== c
+    ///       ^^^^^
+    /// ```
+    pub mark: bool,
     /// Math (flow).
     ///
     /// ```markdown
@@ -320,6 +377,13 @@ pub struct Constructs {
     /// > Otherwise, expressions are parsed with a basic algorithm that only
     /// > cares about braces.
     pub mdx_jsx_text: bool,
+    /// Mention.
+    ///
+    /// ```markdown
+    /// > | a @b c
+    ///       ^^
+    /// ```
+    pub mention: bool,
     /// Thematic break.
     ///
     /// ```markdown
@@ -327,6 +391,13 @@ pub struct Constructs {
     ///     ^^^
     /// ```
     pub thematic_break: bool,
+    /// Wikilink.
+    ///
+    /// ```markdown
+    /// > | a [[b|c]] d
+    ///       ^^^^^^^
+    /// ```
+    pub wikilink: bool,
 }
 
 impl Default for Constructs {
@@ -341,6 +412,7 @@ impl Default for Constructs {
     fn default() -> Self {
         Self {
             attention: true,
+            attributes: false,
             autolink: true,
             block_quote: true,
             character_escape: true,
@@ -349,6 +421,9 @@ impl Default for Constructs {
             code_fenced: true,
             code_text: true,
             definition: true,
+            directive_container: false,
+            directive_leaf: false,
+            directive_text: false,
             frontmatter: false,
             gfm_autolink_literal: false,
             gfm_label_start_footnote: false,
@@ -358,14 +433,17 @@ impl Default for Constructs {
             gfm_task_list_item: false,
             hard_break_escape: true,
             hard_break_trailing: true,
+            hashtag: false,
             heading_atx: true,
             heading_setext: true,
             html_flow: true,
             html_text: true,
             label_start_image: true,
+            label_start_inline_footnote: false,
             label_start_link: true,
             label_end: true,
             list_item: true,
+            mark: false,
             math_flow: false,
             math_text: false,
             mdx_esm: false,
@@ -373,7 +451,9 @@ impl Default for Constructs {
             mdx_expression_text: false,
             mdx_jsx_flow: false,
             mdx_jsx_text: false,
+            mention: false,
             thematic_break: true,
+            wikilink: false,
         }
     }
 }
@@ -434,6 +514,269 @@ impl Constructs {
     }
 }
 
+/// Configuration that describes which HTML is allowed.
+///
+/// Used as [`html_sanitization`][CompileOptions::html_sanitization].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::HtmlSanitization;
+/// # fn main() {
+///
+/// // Allow bold, italic, and images (w/o event handlers):
+/// let sanitization = HtmlSanitization {
+///   tag_names: vec!["b".into(), "i".into(), "img".into()],
+///   attribute_names: vec!["src".into(), "alt".into()],
+/// };
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HtmlSanitization {
+    /// Names of tags that are allowed, such as `b` or `img`.
+    ///
+    /// Names are compared case-insensitively.
+    /// Other tags are encoded, so that they show as text.
+    pub tag_names: Vec<String>,
+    /// Names of attributes that are allowed on allowed tags, such as `alt`.
+    ///
+    /// Names are compared case-insensitively.
+    /// Other attributes are dropped.
+    /// The values of `href` and `src` attributes are sanitized just like
+    /// the URLs of links and images.
+    pub attribute_names: Vec<String>,
+}
+
+/// What to do with the URL attribute of links and images whose URL is
+/// dropped, because its protocol is not allowed.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::DroppedLinkBehavior;
+/// # fn main() {
+///
+/// // Use `href="#"` instead of `href=""`:
+/// let hash = DroppedLinkBehavior::Hash;
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DroppedLinkBehavior {
+    /// An empty attribute.
+    ///
+    /// ## Example
+    ///
+    /// ```html
+    /// <a href="">
+    /// ```
+    #[default]
+    Empty,
+    /// No attribute.
+    ///
+    /// ## Example
+    ///
+    /// ```html
+    /// <a>
+    /// ```
+    Omit,
+    /// An attribute set to `#`.
+    ///
+    /// ## Example
+    ///
+    /// ```html
+    /// <a href="#">
+    /// ```
+    Hash,
+}
+
+/// How to compile the alignment of GFM table cells to HTML.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::GfmTableCellAlign;
+/// # fn main() {
+///
+/// // Use `style="text-align:center"` instead of `align="center"`:
+/// let style = GfmTableCellAlign::Style;
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum GfmTableCellAlign {
+    /// The `align` attribute, like GitHub.
+    ///
+    /// ## Example
+    ///
+    /// ```html
+    /// <td align="center">
+    /// ```
+    #[default]
+    Attribute,
+    /// A `text-align` declaration in the `style` attribute.
+    ///
+    /// ## Example
+    ///
+    /// ```html
+    /// <td style="text-align:center">
+    /// ```
+    Style,
+}
+
+/// Which syntax to use for void elements and boolean attributes in HTML.
+///
+/// This only affects the HTML that `markdown-rs` generates, not raw HTML
+/// written in the markdown.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::HtmlSyntax;
+/// # fn main() {
+///
+/// // Use `<br>` instead of `<br />`:
+/// let html = HtmlSyntax::Html;
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum HtmlSyntax {
+    /// Syntax that works in both HTML and XHTML: void elements are closed,
+    /// and boolean attributes have an empty value.
+    ///
+    /// ## Example
+    ///
+    /// ```html
+    /// <input type="checkbox" disabled="" />
+    /// ```
+    #[default]
+    Polyglot,
+    /// HTML syntax: void elements are not closed, and boolean attributes have
+    /// no value.
+    ///
+    /// ## Example
+    ///
+    /// ```html
+    /// <input type="checkbox" disabled>
+    /// ```
+    Html,
+    /// XHTML syntax: void elements are closed, and boolean attributes repeat
+    /// their name as their value.
+    /// This is useful when embedding the output in XML.
+    ///
+    /// ## Example
+    ///
+    /// ```html
+    /// <input type="checkbox" disabled="disabled" />
+    /// ```
+    Xhtml,
+}
+
+/// Which named character references (such as `&amp;`) are supported.
+///
+/// Numeric character references (such as `&#38;` and `&#x26;`) are always
+/// supported.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::CharacterReferences;
+/// # fn main() {
+///
+/// // Only support `&amp;`, `&apos;`, `&gt;`, `&lt;`, and `&quot;`:
+/// let xml = CharacterReferences::XmlMinimal;
+///
+/// // Only support `&shrug;`:
+/// let custom = CharacterReferences::Custom(vec![("shrug".into(), "¯\\_(ツ)_/¯".into())]);
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum CharacterReferences {
+    /// The 2125 names from HTML 5, like `CommonMark`.
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// &amp; &copy; &CounterClockwiseContourIntegral;
+    /// ```
+    #[default]
+    Html5,
+    /// The 5 names predefined in XML: `amp`, `apos`, `gt`, `lt`, and `quot`.
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// &amp; &apos; &gt; &lt; &quot;
+    /// ```
+    XmlMinimal,
+    /// Custom names, each with the value it decodes to.
+    ///
+    /// Names are case-sensitive, and must be ASCII alphanumerics, with at
+    /// most 31 of them.
+    Custom(Vec<(String, String)>),
+}
+
+/// How to count the `column` of points.
+///
+/// Positional info (such as in mdast) has a `column` for each point.
+/// Editors and language servers count columns in different units, which
+/// differ for characters outside of ASCII.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::ColumnUnit;
+/// # fn main() {
+///
+/// // Count columns in UTF-16 code units, like language servers:
+/// let utf16 = ColumnUnit::Utf16;
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColumnUnit {
+    /// UTF-8 bytes.
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// 😀 a
+    ///      ^-- column 6
+    /// ```
+    #[default]
+    Bytes,
+    /// Unicode code points (Rust `char`s).
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// 😀 a
+    ///      ^-- column 3
+    /// ```
+    CodePoints,
+    /// UTF-16 code units, like JavaScript and the language server protocol.
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// 😀 a
+    ///      ^-- column 4
+    /// ```
+    Utf16,
+    /// Graphemes (user-perceived characters).
+    ///
+    /// Characters that combine with the character before them, such as
+    /// characters with the Unicode `Grapheme_Extend` property (combining
+    /// marks, vowel signs, variation selectors, emoji modifiers), emoji joined
+    /// by a zero-width joiner, and the second regional indicator of a flag, do
+    /// not take up a column.
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// 👍🏽 a
+    ///      ^-- column 3
+    /// ```
+    Graphemes,
+}
+
 /// Configuration that describes how to compile to HTML.
 ///
 /// You likely either want to turn on the dangerous options
@@ -466,8 +809,9 @@ impl Constructs {
 /// # }
 /// ```
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Default)]
 pub struct CompileOptions {
+    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
     /// Whether to allow (dangerous) HTML.
     ///
     /// The default is `false`, which still parses the HTML according to
@@ -553,330 +897,436 @@ pub struct CompileOptions {
     /// ```
     pub allow_dangerous_protocol: bool,
 
-    // To do: `doc_markdown` is broken.
-    #[allow(clippy::doc_markdown)]
-    /// Default line ending to use when compiling to HTML, for line endings not
-    /// in `value`.
+    /// Which protocols to allow in links and images.
     ///
-    /// Generally, `markdown-rs` copies line endings (`\r`, `\n`, `\r\n`) in
-    /// the markdown document over to the compiled HTML.
-    /// In some cases, such as `> a`, CommonMark requires that extra line
-    /// endings are added: `<blockquote>\n<p>a</p>\n</blockquote>`.
+    /// The default is `None`, which uses the safe protocols described in
+    /// [`allow_dangerous_protocol`][CompileOptions::allow_dangerous_protocol]
+    /// (for images: `http`, `https`; for links: `http`, `https`, `irc`,
+    /// `ircs`, `mailto`, `xmpp`).
     ///
-    /// To create that line ending, the document is checked for the first line
-    /// ending that is used.
-    /// If there is no line ending, `default_line_ending` is used.
-    /// If that isn’t configured, `\n` is used.
+    /// Pass a list of protocols (without `:`) to allow only those, in both
+    /// links and images.
+    /// Protocols are compared case-insensitively.
+    /// URLs that have no protocol (such as `./some/page.html`, `#a`, or `?b`)
+    /// are always allowed.
+    /// URLs with other protocols are dropped.
+    ///
+    /// This has no effect if `allow_dangerous_protocol` is `true`.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html, to_html_with_options, CompileOptions, LineEnding, Options};
+    /// use markdown::{to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` uses `\n` by default:
+    /// // Allow a custom protocol:
+    /// let options = Options {
+    ///     compile: CompileOptions {
+    ///       allowed_protocols: Some(vec!["https".into(), "app".into()]),
+    ///       ..CompileOptions::default()
+    ///     },
+    ///     ..Options::default()
+    /// };
+    ///
     /// assert_eq!(
-    ///     to_html("> a"),
-    ///     "<blockquote>\n<p>a</p>\n</blockquote>"
+    ///     to_html_with_options("<app:settings>", &options)?,
+    ///     "<p><a href=\"app:settings\">app:settings</a></p>"
     /// );
     ///
-    /// // Define `default_line_ending` to configure the default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "> a",
-    ///         &Options {
-    ///             compile: CompileOptions {
-    ///               default_line_ending: LineEnding::CarriageReturnLineFeed,
-    ///               ..CompileOptions::default()
-    ///             },
-    ///             ..Options::default()
-    ///         }
-    ///     )?,
-    ///     "<blockquote>\r\n<p>a</p>\r\n</blockquote>"
+    ///     to_html_with_options("<mailto:a>", &options)?,
+    ///     "<p><a href=\"\">mailto:a</a></p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub default_line_ending: LineEnding,
+    pub allowed_protocols: Option<Vec<String>>,
 
-    /// Textual label to use for the footnotes section.
+    /// Prefix to use before the info string of code (fenced) in its class.
     ///
-    /// The default value is `"Footnotes"`.
-    /// Change it when the markdown is not in English.
+    /// The default is `"language-"`, which is what `CommonMark` recommends
+    /// and what highlighters such as `highlight.js` and Prism expect.
+    /// Change it when using a highlighter that expects something else.
     ///
-    /// This label is typically hidden visually (assuming a `sr-only` CSS class
-    /// is defined that does that), and thus affects screen readers only.
-    /// If you do have such a class, but want to show this section to everyone,
-    /// pass different attributes with the `gfm_footnote_label_attributes`
-    /// option.
+    /// Only the first word of the info string is used (`rust` in
+    /// ` ```rust ignore`); the rest, the meta string, is not used (see
+    /// [`code_meta_attribute`][CompileOptions::code_meta_attribute]).
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `"Footnotes"` is used by default:
+    /// // `"language-"` is used by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
-    ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     to_html("```rust ignore\na\n```"),
+    ///     "<pre><code class=\"language-rust\">a\n</code></pre>"
     /// );
     ///
-    /// // Pass `gfm_footnote_label` to use something else:
+    /// // Pass `code_block_class_prefix` to use something else:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "```rust ignore\na\n```",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               gfm_footnote_label: Some("Notes de bas de page".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///               code_block_class_prefix: Some("lang-".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Notes de bas de page</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<pre><code class=\"lang-rust\">a\n</code></pre>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_label: Option<String>,
+    pub code_block_class_prefix: Option<String>,
 
-    /// HTML tag name to use for the footnote label element.
+    /// Function to highlight code (fenced) with.
     ///
-    /// The default value is `"h2"`.
-    /// Change it to match your document structure.
+    /// The default is `None`, which compiles the code to escaped text.
+    /// Pass a function to highlight code yourself, such as with `syntect`.
+    /// It is called with the language (the first word of the info string,
+    /// or [`default_code_language`][CompileOptions::default_code_language],
+    /// or an empty string) and the code, and returns the HTML to use inside
+    /// the `<code>` element.
+    /// Both are passed as they are in the markdown: the function must escape
+    /// the code itself.
+    /// The code includes its final line ending, if there is code.
     ///
-    /// This label is typically hidden visually (assuming a `sr-only` CSS class
-    /// is defined that does that), and thus affects screen readers only.
-    /// If you do have such a class, but want to show this section to everyone,
-    /// pass different attributes with the `gfm_footnote_label_attributes`
-    /// option.
+    /// This does not apply to code (indented) or math (flow).
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// use std::rc::Rc;
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `"h2"` is used by default:
+    /// // `markdown-rs` compiles code to escaped text by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
-    ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     to_html("```js\na < b\n```"),
+    ///     "<pre><code class=\"language-js\">a &lt; b\n</code></pre>"
     /// );
     ///
-    /// // Pass `gfm_footnote_label_tag_name` to use something else:
+    /// // Pass `code_highlighter` to compile it yourself:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "```js\na < b\n```",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               gfm_footnote_label_tag_name: Some("h1".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///               code_highlighter: Some(Rc::new(|lang, code| {
+    ///                   format!("<span class=\"{}\">{}</span>", lang, code.replace('<', "&lt;"))
+    ///               })),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h1 id=\"footnote-label\" class=\"sr-only\">Footnotes</h1>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<pre><code class=\"language-js\"><span class=\"js\">a &lt; b\n</span></code></pre>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_label_tag_name: Option<String>,
+    pub code_highlighter: Option<Rc<CodeHighlighterFn>>,
 
-    /// Attributes to use on the footnote label.
-    ///
-    /// The default value is `"class=\"sr-only\""`.
-    /// Change it to show the label and add other attributes.
-    ///
-    /// This label is typically hidden visually (assuming a `sr-only` CSS class
-    /// is defined that does that), and thus affects screen readers only.
-    /// If you do have such a class, but want to show this section to everyone,
-    /// pass an empty string.
-    /// You can also add different attributes.
+    /// Whether to wrap each line of code (fenced, indented) in a span.
     ///
-    /// > 👉 **Note**: `id="footnote-label"` is always added, because footnote
-    /// > calls use it with `aria-describedby` to provide an accessible label.
+    /// The default is `false`, which outputs the code as is.
+    /// Pass `true` to wrap each line in `<span class="line">`, so that line
+    /// numbers can be added with a CSS counter.
+    /// The line ending at the end of the code does not make an extra line.
+    /// Math (flow) is not affected.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `"class=\"sr-only\""` is used by default:
+    /// // Lines are not wrapped by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
-    ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     to_html("```\na\nb\n```"),
+    ///     "<pre><code>a\nb\n</code></pre>"
     /// );
     ///
-    /// // Pass `gfm_footnote_label_attributes` to use something else:
+    /// // Pass `code_line_numbers: true` to wrap them:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "```\na\nb\n```",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               gfm_footnote_label_attributes: Some("class=\"footnote-heading\"".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///               code_line_numbers: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"footnote-heading\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<pre><code><span class=\"line\">a</span>\n<span class=\"line\">b</span>\n</code></pre>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_label_attributes: Option<String>,
+    pub code_line_numbers: bool,
 
-    /// Textual label to describe the backreference back to footnote calls.
-    ///
-    /// The default value is `"Back to content"`.
-    /// Change it when the markdown is not in English.
+    /// Whether to add the meta string of code (fenced) as a `data-meta`
+    /// attribute.
     ///
-    /// This label is used in the `aria-label` attribute on each backreference
-    /// (the `↩` links).
-    /// It affects users of assistive technology.
+    /// The default is `false`, which drops the meta string: the words in the
+    /// info string after the first (`{1,3}` in ` ```js {1,3}`).
+    /// Pass `true` to add it to the `code` element, such as for a
+    /// highlighter that uses it to highlight lines.
+    /// The meta string is encoded, and character escapes and character
+    /// references in it work.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `"Back to content"` is used by default:
+    /// // The meta string is not used by default:
+    /// assert_eq!(
+    ///     to_html("```js {1,3}\na\n```"),
+    ///     "<pre><code class=\"language-js\">a\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `code_meta_attribute: true` to add it:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
+    ///         "```js {1,3}\na\n```",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               code_meta_attribute: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<pre><code class=\"language-js\" data-meta=\"{1,3}\">a\n</code></pre>"
     /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub code_meta_attribute: bool,
+
+    /// Whether to show definitions as HTML comments.
     ///
-    /// // Pass `gfm_footnote_back_label` to use something else:
+    /// The default is `false`, which does not generate anything for
+    /// definitions (`[a]: b`), as they are only used to resolve references.
+    /// Pass `true` to generate `<!-- definition: a -> b -->` comments where
+    /// they are, which can help when debugging why a reference does not
+    /// resolve.
+    /// The label is shown as it is in the markdown, the destination with
+    /// character escapes and character references decoded.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // Definitions generate nothing by default:
+    /// assert_eq!(to_html("[A]: b\n\n[a]"), "<p><a href=\"b\">a</a></p>");
+    ///
+    /// // Pass `debug_definitions: true` to see them:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "[A]: b\n\n[a]",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               gfm_footnote_back_label: Some("Arrière".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///               debug_definitions: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Arrière\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<!-- definition: A -> b -->\n<p><a href=\"b\">a</a></p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_back_label: Option<String>,
+    pub debug_definitions: bool,
 
-    /// Prefix to use before the `id` attribute on footnotes to prevent them
-    /// from *clobbering*.
+    /// Whether to show the markers of lists as attributes.
     ///
-    /// The default is `"user-content-"`.
-    /// Pass `Some("".into())` for trusted markdown and when you are careful
-    /// with polyfilling.
-    /// You could pass a different prefix.
+    /// The default is `false`, which does not show the marker used by a list,
+    /// as HTML does not differ between `*`, `+`, and `-` in unordered lists,
+    /// or between `.` and `)` in ordered lists.
+    /// Pass `true` to add a `data-marker` attribute with it to `<ul>` and
+    /// `<ol>`, which can help when debugging why items do not form one list
+    /// (a different marker starts a new list).
     ///
-    /// DOM clobbering is this:
+    /// ## Examples
     ///
-    /// ```html
-    /// <p id="x"></p>
-    /// <script>alert(x) // `x` now refers to the `p#x` DOM element</script>
     /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// The above example shows that elements are made available by browsers,
-    /// by their ID, on the `window` object.
-    /// This is a security risk because you might be expecting some other
-    /// variable at that place.
-    /// It can also break polyfills.
-    /// Using a prefix solves these problems.
+    /// // Markers are not shown by default:
+    /// assert_eq!(to_html("1) a"), "<ol>\n<li>a</li>\n</ol>");
+    ///
+    /// // Pass `debug_list_markers: true` to see them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "1) a",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               debug_list_markers: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<ol data-marker=\")\">\n<li>a</li>\n</ol>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub debug_list_markers: bool,
+
+    /// Language to use for code (fenced) without info string.
+    ///
+    /// The default is `None`, which does not add a class to such code.
+    /// Pass a language, such as `"text"`, to add a class for it, like for
+    /// an info string, as some highlighters need an explicit class.
+    ///
+    /// Code (fenced) with an info string, code (indented), and math (flow)
+    /// are not affected.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `"user-content-"` is used by default:
+    /// // No class is used by default:
+    /// assert_eq!(to_html("```\na\n```"), "<pre><code>a\n</code></pre>");
+    ///
+    /// // Pass `default_code_language` to use a class:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
+    ///         "```\na\n```",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               default_code_language: Some("text".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<pre><code class=\"language-text\">a\n</code></pre>"
     /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub default_code_language: Option<String>,
+
+    // To do: `doc_markdown` is broken.
+    #[allow(clippy::doc_markdown)]
+    /// Line ending to use when compiling to HTML.
     ///
-    /// // Pass `gfm_footnote_clobber_prefix` to use something else:
+    /// Generally, `markdown-rs` copies line endings (`\r`, `\n`, `\r\n`) in
+    /// the markdown document over to the compiled HTML.
+    /// In some cases, such as `> a`, CommonMark requires that extra line
+    /// endings are added: `<blockquote>\n<p>a</p>\n</blockquote>`.
+    ///
+    /// When this is `None` (default), the document is checked for the first
+    /// line ending that is used, and that is used for the added line endings.
+    /// If there is no line ending, `\n` is used.
+    ///
+    /// When this is `Some`, the given line ending is used for *all* line
+    /// endings in the HTML: both added ones and ones copied from the document.
+    /// That makes the output consistent, even if the document mixes them.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, LineEnding, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` uses `\n` by default:
+    /// assert_eq!(
+    ///     to_html("> a"),
+    ///     "<blockquote>\n<p>a</p>\n</blockquote>"
+    /// );
+    ///
+    /// // Define `default_line_ending` to configure it:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "> a\nb",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               gfm_footnote_clobber_prefix: Some("".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///               default_line_ending: Some(LineEnding::CarriageReturnLineFeed),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#fn-a\" id=\"fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"fn-a\">\n<p>b <a href=\"#fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<blockquote>\r\n<p>a\r\nb</p>\r\n</blockquote>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_clobber_prefix: Option<String>,
+    pub default_line_ending: Option<LineEnding>,
 
-    /// Whether or not GFM task list html `<input>` items are enabled.
+    /// What to do with the `href` of links, and `src` of images, whose URL
+    /// is dropped.
     ///
-    /// This determines whether or not the user of the browser is able
-    /// to click and toggle generated checkbox items. The default is false.
+    /// URLs are dropped when their protocol is not allowed (see
+    /// [`allow_dangerous_protocol`][CompileOptions::allow_dangerous_protocol]
+    /// and [`allowed_protocols`][CompileOptions::allowed_protocols]), such
+    /// as `javascript:alert(1)`.
+    /// The default is [`DroppedLinkBehavior::Empty`][], which uses an empty
+    /// attribute (`href=""`).
+    /// Pass [`DroppedLinkBehavior::Omit`][] to leave the attribute out, or
+    /// [`DroppedLinkBehavior::Hash`][] to use `#` (`href="#"`).
+    ///
+    /// Empty URLs in markdown (such as `[a]()`) are not dropped, so they
+    /// are not affected.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, DroppedLinkBehavior, Options};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // With `gfm_task_list_item_checkable`, generated `<input type="checkbox" />`
-    /// // tags do not contain the attribute `disabled=""` and are thus toggleable by
-    /// // browser users.
+    /// // An empty `href` is used by default:
+    /// assert_eq!(
+    ///     to_html("[a](javascript:alert(1))"),
+    ///     "<p><a href=\"\">a</a></p>"
+    /// );
+    ///
+    /// // Pass `dropped_link_behavior: DroppedLinkBehavior::Omit` to leave it out:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "* [x] y.",
+    ///         "[a](javascript:alert(1))",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///                 gfm_task_list_item_checkable: true,
-    ///                 ..CompileOptions::gfm()
-    ///             }
+    ///               dropped_link_behavior: DroppedLinkBehavior::Omit,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<ul>\n<li><input type=\"checkbox\" checked=\"\" /> y.</li>\n</ul>"
+    ///     "<p><a>a</a></p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_task_list_item_checkable: bool,
+    pub dropped_link_behavior: DroppedLinkBehavior,
 
-    /// Whether to support the GFM tagfilter.
+    /// Textual label to use for the footnotes section.
     ///
-    /// This option does nothing if `allow_dangerous_html` is not turned on.
-    /// The default is `false`, which does not apply the GFM tagfilter to HTML.
-    /// Pass `true` for output that is a bit closer to GitHub’s actual output.
+    /// The default value is `"Footnotes"`.
+    /// Change it when the markdown is not in English.
     ///
-    /// The tagfilter is kinda weird and kinda useless.
-    /// The tag filter is a naïve attempt at XSS protection.
-    /// You should use a proper HTML sanitizing algorithm instead.
+    /// This label is typically hidden visually (assuming a `sr-only` CSS class
+    /// is defined that does that), and thus affects screen readers only.
+    /// If you do have such a class, but want to show this section to everyone,
+    /// pass different attributes with the `gfm_footnote_label_attributes`
+    /// option.
     ///
     /// ## Examples
     ///
@@ -884,59 +1334,1146 @@ pub struct CompileOptions {
     /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // With `allow_dangerous_html`, `markdown-rs` passes HTML through untouched:
+    /// // `"Footnotes"` is used by default:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "<iframe>",
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_label` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
     ///         &Options {
     ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               allow_dangerous_html: true,
-    ///               ..CompileOptions::default()
+    ///               gfm_footnote_label: Some("Notes de bas de page".into()),
+    ///               ..CompileOptions::gfm()
     ///             }
     ///         }
     ///     )?,
-    ///     "<iframe>"
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Notes de bas de page</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
     /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_label: Option<String>,
+
+    /// HTML tag name to use for the footnote label element.
     ///
-    /// // Pass `gfm_tagfilter: true` to make some of that safe:
+    /// The default value is `"h2"`.
+    /// Change it to match your document structure.
+    ///
+    /// This label is typically hidden visually (assuming a `sr-only` CSS class
+    /// is defined that does that), and thus affects screen readers only.
+    /// If you do have such a class, but want to show this section to everyone,
+    /// pass different attributes with the `gfm_footnote_label_attributes`
+    /// option.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"h2"` is used by default:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "<iframe>",
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_label_tag_name` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
     ///         &Options {
     ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               allow_dangerous_html: true,
-    ///               gfm_tagfilter: true,
-    ///               ..CompileOptions::default()
+    ///               gfm_footnote_label_tag_name: Some("h1".into()),
+    ///               ..CompileOptions::gfm()
     ///             }
     ///         }
     ///     )?,
-    ///     "&lt;iframe>"
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h1 id=\"footnote-label\" class=\"sr-only\">Footnotes</h1>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    ///
-    /// ## References
-    ///
-    /// *   [*§ 6.1 Disallowed Raw HTML (extension)* in GFM](https://github.github.com/gfm/#disallowed-raw-html-extension-)
-    /// *   [`cmark-gfm#extensions/tagfilter.c`](https://github.com/github/cmark-gfm/blob/master/extensions/tagfilter.c)
-    pub gfm_tagfilter: bool,
-}
+    pub gfm_footnote_label_tag_name: Option<String>,
 
-impl CompileOptions {
-    /// GFM.
+    /// Attributes to use on the footnote label.
     ///
-    /// GFM stands for **GitHub flavored markdown**.
-    /// On the compilation side, GFM turns on the GFM tag filter.
-    /// The tagfilter is useless, but it’s included here for consistency, and
-    /// this method exists for parity to parse options.
+    /// The default value is `"class=\"sr-only\""`.
+    /// Change it to show the label and add other attributes.
     ///
-    /// For more information, see the GFM specification:
-    /// <https://github.github.com/gfm/>.
-    pub fn gfm() -> Self {
-        Self {
+    /// This label is typically hidden visually (assuming a `sr-only` CSS class
+    /// is defined that does that), and thus affects screen readers only.
+    /// If you do have such a class, but want to show this section to everyone,
+    /// pass an empty string.
+    /// You can also add different attributes.
+    ///
+    /// > 👉 **Note**: `id="footnote-label"` is always added, because footnote
+    /// > calls use it with `aria-describedby` to provide an accessible label.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"class=\"sr-only\""` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_label_attributes` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_label_attributes: Some("class=\"footnote-heading\"".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"footnote-heading\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_label_attributes: Option<String>,
+
+    /// Textual label to describe the backreference back to footnote calls.
+    ///
+    /// The default value is `"Back to content"`.
+    /// Change it when the markdown is not in English.
+    ///
+    /// This label is used in the `aria-label` attribute on each backreference
+    /// (the `↩` links).
+    /// It affects users of assistive technology.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"Back to content"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_back_label` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_back_label: Some("Arrière".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Arrière\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_back_label: Option<String>,
+
+    /// Prefix to use before the `id` attribute on footnotes to prevent them
+    /// from *clobbering*.
+    ///
+    /// The default is `"user-content-"`.
+    /// Pass `Some("".into())` for trusted markdown and when you are careful
+    /// with polyfilling.
+    /// You could pass a different prefix.
+    ///
+    /// DOM clobbering is this:
+    ///
+    /// ```html
+    /// <p id="x"></p>
+    /// <script>alert(x) // `x` now refers to the `p#x` DOM element</script>
+    /// ```
+    ///
+    /// The above example shows that elements are made available by browsers,
+    /// by their ID, on the `window` object.
+    /// This is a security risk because you might be expecting some other
+    /// variable at that place.
+    /// It can also break polyfills.
+    /// Using a prefix solves these problems.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"user-content-"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_clobber_prefix` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_clobber_prefix: Some("".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#fn-a\" id=\"fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"fn-a\">\n<p>b <a href=\"#fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_clobber_prefix: Option<String>,
+
+    /// Whether or not GFM task list html `<input>` items are enabled.
+    ///
+    /// This determines whether or not the user of the browser is able
+    /// to click and toggle generated checkbox items. The default is false.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // With `gfm_task_list_item_checkable`, generated `<input type="checkbox" />`
+    /// // tags do not contain the attribute `disabled=""` and are thus toggleable by
+    /// // browser users.
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "* [x] y.",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///                 gfm_task_list_item_checkable: true,
+    ///                 ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<ul>\n<li><input type=\"checkbox\" checked=\"\" /> y.</li>\n</ul>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_task_list_item_checkable: bool,
+
+    /// How to compile the alignment of GFM table cells.
+    ///
+    /// The default is [`GfmTableCellAlign::Attribute`][], which uses the
+    /// `align` attribute, like GitHub.
+    /// Pass [`GfmTableCellAlign::Style`][] to use a `text-align` declaration
+    /// in the `style` attribute instead, as the `align` attribute is
+    /// deprecated in HTML.
+    /// Cells without alignment get neither.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, GfmTableCellAlign, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` follows GitHub by default:
+    /// assert_eq!(
+    ///     to_html_with_options("| a |\n| :-: |", &Options::gfm())?,
+    ///     "<table>\n<thead>\n<tr>\n<th align=\"center\">a</th>\n</tr>\n</thead>\n</table>"
+    /// );
+    ///
+    /// // Pass `GfmTableCellAlign::Style` to use CSS:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "| a |\n| :-: |",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_table_cell_align: GfmTableCellAlign::Style,
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<table>\n<thead>\n<tr>\n<th style=\"text-align:center\">a</th>\n</tr>\n</thead>\n</table>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_table_cell_align: GfmTableCellAlign,
+
+    /// Whether to support the GFM tagfilter.
+    ///
+    /// This option does nothing if `allow_dangerous_html` is not turned on.
+    /// The default is `false`, which does not apply the GFM tagfilter to HTML.
+    /// Pass `true` for output that is a bit closer to GitHub’s actual output.
+    ///
+    /// The tagfilter is kinda weird and kinda useless.
+    /// The tag filter is a naïve attempt at XSS protection.
+    /// You should use a proper HTML sanitizing algorithm instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // With `allow_dangerous_html`, `markdown-rs` passes HTML through untouched:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<iframe>",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               ..CompileOptions::default()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<iframe>"
+    /// );
+    ///
+    /// // Pass `gfm_tagfilter: true` to make some of that safe:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<iframe>",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               gfm_tagfilter: true,
+    ///               ..CompileOptions::default()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "&lt;iframe>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## References
+    ///
+    /// *   [*§ 6.1 Disallowed Raw HTML (extension)* in GFM](https://github.github.com/gfm/#disallowed-raw-html-extension-)
+    /// *   [`cmark-gfm#extensions/tagfilter.c`](https://github.com/github/cmark-gfm/blob/master/extensions/tagfilter.c)
+    pub gfm_tagfilter: bool,
+
+    /// Whether to turn soft line breaks into hard breaks.
+    ///
+    /// The default is `false`, which keeps line endings in paragraphs as
+    /// they are.
+    /// Pass `true` to turn every line ending in a paragraph into a `<br />`,
+    /// like how comments on GitHub work.
+    /// Line endings between blocks and in code are not affected.
+    /// Hard breaks (a backslash or two or more spaces before a line ending)
+    /// work as normal.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` follows CommonMark, which keeps line endings by default:
+    /// assert_eq!(to_html("a\nb"), "<p>a\nb</p>");
+    ///
+    /// // Pass `hard_line_breaks: true` to turn them into breaks:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a\nb",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               hard_line_breaks: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a<br />\nb</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub hard_line_breaks: bool,
+
+    /// Template to make URLs for hashtags with.
+    ///
+    /// The default is `None`, which does not link hashtags, but keeps them as
+    /// text.
+    /// Pass a template such as `/t/{tag}` to link them, where `{tag}` is
+    /// replaced by the tag (without the `#`).
+    /// The resulting URL is sanitized like the URLs of other links.
+    ///
+    /// This has no effect if the `hashtag` construct is not turned on.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a #rust b",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 hashtag: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             compile: CompileOptions {
+    ///               hashtag_url: Some("/t/{tag}".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a <a href=\"/t/rust\">#rust</a> b</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub hashtag_url: Option<String>,
+
+    /// Whether to add `id` attributes to headings.
+    ///
+    /// The default is `false`, which does not add IDs.
+    /// Pass `true` to generate GitHub-style slugs from the text of headings,
+    /// so that they can be linked to.
+    ///
+    /// The slug is made from the text of the heading (without markup), by
+    /// lowercasing it, removing everything that is not a letter, a digit,
+    /// a space, a dash, or an underscore, and turning spaces into dashes.
+    /// When a slug was already used in the document, `-1`, `-2`, etc. is
+    /// added to it.
+    /// With [`attributes`][Constructs::attributes], IDs in attribute lists
+    /// (such as `# a {#b}`) count as used too, wherever they are in the
+    /// document.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not add IDs to headings by default:
+    /// assert_eq!(to_html("## *Hi* there"), "<h2><em>Hi</em> there</h2>");
+    ///
+    /// // Pass `heading_ids: true` to add them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "## *Hi* there\n\n## Hi there",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               heading_ids: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<h2 id=\"hi-there\"><em>Hi</em> there</h2>\n<h2 id=\"hi-there-1\">Hi there</h2>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## References
+    ///
+    /// *   [`github-slugger`](https://github.com/Flet/github-slugger)
+    pub heading_ids: bool,
+
+    /// Function to make IDs for headings with.
+    ///
+    /// The default is `None`, which uses GitHub-style slugs (see
+    /// [`heading_ids`][CompileOptions::heading_ids]).
+    /// Pass a function to use a different scheme: it is given the text of a
+    /// heading (without markup), and returns the ID to use.
+    /// When an ID was already used in the document, `-1`, `-2`, etc. is still
+    /// added to it.
+    /// When an empty string is returned, no ID is added.
+    ///
+    /// This has no effect if `heading_ids` is `false`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options};
+    /// use std::rc::Rc;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "## Hi there\n\n## Hi there",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               heading_ids: true,
+    ///               heading_id_fn: Some(Rc::new(|value| value.replace(' ', "_"))),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<h2 id=\"Hi_there\">Hi there</h2>\n<h2 id=\"Hi_there-1\">Hi there</h2>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub heading_id_fn: Option<Rc<HeadingIdFn>>,
+
+    /// Which HTML to allow, when `allow_dangerous_html` is off.
+    ///
+    /// The default is `None`, which encodes all HTML (when
+    /// [`allow_dangerous_html`][CompileOptions::allow_dangerous_html] is
+    /// off) so that it shows as text.
+    /// Pass an [`HtmlSanitization`][] to keep some tags and attributes, such
+    /// as `<b>`, while still encoding other tags (such as `<script>`) and
+    /// dropping other attributes (such as `onerror`).
+    /// Comments, declarations, and instructions are encoded too.
+    ///
+    /// This has no effect if `allow_dangerous_html` is `true`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, HtmlSanitization, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` encodes HTML by default:
+    /// assert_eq!(to_html("<b>a</b>"), "<p>&lt;b&gt;a&lt;/b&gt;</p>");
+    ///
+    /// // Pass `html_sanitization` to allow some HTML:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<b onclick=\"x()\">a</b><script>",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               html_sanitization: Some(HtmlSanitization {
+    ///                 tag_names: vec!["b".into()],
+    ///                 attribute_names: vec![],
+    ///               }),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><b>a</b>&lt;script&gt;</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub html_sanitization: Option<HtmlSanitization>,
+
+    /// Which syntax to use for void elements and boolean attributes.
+    ///
+    /// The default is [`HtmlSyntax::Polyglot`]: void elements are closed
+    /// with ` />` (such as `<br />`) and boolean attributes have an empty
+    /// value (such as `disabled=""`), which works in both HTML and XHTML.
+    ///
+    /// This only affects the HTML that `markdown-rs` generates, not raw HTML
+    /// written in the markdown.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, HtmlSyntax, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` closes void elements by default:
+    /// assert_eq!(to_html("***"), "<hr />");
+    ///
+    /// // Pass `html_syntax: HtmlSyntax::Xhtml` to generate XHTML:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "* [x] a",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               html_syntax: HtmlSyntax::Xhtml,
+    ///               ..CompileOptions::default()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<ul>\n<li><input type=\"checkbox\" disabled=\"disabled\" checked=\"checked\" /> a</li>\n</ul>"
+    /// );
+    ///
+    /// // Pass `html_syntax: HtmlSyntax::Html` to generate HTML:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "***",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               html_syntax: HtmlSyntax::Html,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<hr>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub html_syntax: HtmlSyntax,
+
+    /// Whether to render images that are alone in a paragraph as figures.
+    ///
+    /// The default is `false`, which renders such images in a `<p>`.
+    /// Pass `true` to render a paragraph that contains exactly one image, and
+    /// nothing else except whitespace, as a `<figure>` instead.
+    /// A `<figcaption>` is added with the title of the image, or when there
+    /// is no title, its alt; when both are empty, it is omitted.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` renders lone images in paragraphs by default:
+    /// assert_eq!(to_html("![a](b)"), "<p><img src=\"b\" alt=\"a\" /></p>");
+    ///
+    /// // Pass `image_figure: true` to render them as figures:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "![a](b)",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               image_figure: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<figure><img src=\"b\" alt=\"a\" /><figcaption>a</figcaption></figure>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub image_figure: bool,
+
+    /// Whether to lazy load images.
+    ///
+    /// The default is `false`, which does not add a `loading` attribute to
+    /// images.
+    /// Pass `true` to add `loading="lazy"` to images (both resources and
+    /// references), so that browsers can defer loading them until they are
+    /// needed.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not add `loading` to images by default:
+    /// assert_eq!(to_html("![a](b)"), "<p><img src=\"b\" alt=\"a\" /></p>");
+    ///
+    /// // Pass `image_lazy_loading: true` to add it:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "![a](b)",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               image_lazy_loading: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><img src=\"b\" alt=\"a\" loading=\"lazy\" /></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub image_lazy_loading: bool,
+
+    /// Value to use as the `rel` attribute of links.
+    ///
+    /// The default is `None`, which adds no `rel` attribute (unless
+    /// [`open_external_links_in_new_tab`][CompileOptions::open_external_links_in_new_tab]
+    /// is on).
+    /// Pass something like `"nofollow ugc"` to add it to all links made from
+    /// markdown: links, autolinks (including email autolinks and GFM autolink
+    /// literals), and wikilinks.
+    /// Links made for GFM footnotes are not affected.
+    /// If `open_external_links_in_new_tab` is on, its `noopener` is merged
+    /// with this value.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not add `rel` to links by default:
+    /// assert_eq!(to_html("[a](/b)"), "<p><a href=\"/b\">a</a></p>");
+    ///
+    /// // Pass `link_rel` to add it:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[a](/b) <c@d.e>",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               link_rel: Some("nofollow ugc".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"/b\" rel=\"nofollow ugc\">a</a> <a href=\"mailto:c@d.e\" rel=\"nofollow ugc\">c@d.e</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub link_rel: Option<String>,
+
+    /// Maximum size of the HTML, in bytes.
+    ///
+    /// The default is `None`, which means there is no limit.
+    /// Pass a number to stop compiling and return an error when the HTML
+    /// gets bigger than it.
+    /// Small documents can turn into a lot of HTML, such as when a reference
+    /// to a long definition is used many times, so this is useful to
+    /// protect memory when rendering untrusted markdown.
+    ///
+    /// The size is checked as HTML is generated, so compiling stops soon
+    /// after the limit is reached.
+    /// GFM footnote definitions count as soon as they are compiled, even
+    /// when they end up unused.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let options = Options {
+    ///     compile: CompileOptions {
+    ///       max_output_size: Some(20),
+    ///       ..CompileOptions::default()
+    ///     },
+    ///     ..Options::default()
+    /// };
+    ///
+    /// // Small HTML is fine:
+    /// assert_eq!(to_html_with_options("*a*", &options)?, "<p><em>a</em></p>");
+    ///
+    /// // Bigger HTML is an error:
+    /// assert!(to_html_with_options("*a* *b* *c*", &options).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub max_output_size: Option<usize>,
+
+    /// Template to make URLs for mentions with.
+    ///
+    /// The default is `None`, which does not link mentions, but keeps them as
+    /// text.
+    /// Pass a template such as `/u/{name}` to link them, where `{name}` is
+    /// replaced by the name (without the `@`).
+    /// The resulting URL is sanitized like the URLs of other links.
+    ///
+    /// This has no effect if the `mention` construct is not turned on.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a @alice b",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 mention: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             compile: CompileOptions {
+    ///               mention_url: Some("/u/{name}".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a <a href=\"/u/alice\">@alice</a> b</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub mention_url: Option<String>,
+
+    /// Whether to lowercase tag and attribute names in HTML.
+    ///
+    /// The default is `false`.
+    /// Pass `true` to turn, for example, `<DIV Class="X">` into
+    /// `<div class="X">`, for consistent output.
+    /// Attribute values, text, and comments are not changed.
+    /// The content of `<pre>`, `<script>`, `<style>`, and `<textarea>` is not
+    /// changed either, as case can matter there.
+    ///
+    /// This only affects HTML in markdown when it is not encoded, which is
+    /// the case with
+    /// [`allow_dangerous_html`][CompileOptions::allow_dangerous_html] or
+    /// [`html_sanitization`][CompileOptions::html_sanitization].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps the case of tags by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<DIV Class=\"X\">",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<DIV Class=\"X\">"
+    /// );
+    ///
+    /// // Pass `normalize_html_tags: true` to lowercase them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<DIV Class=\"X\">",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               normalize_html_tags: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<div class=\"X\">"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub normalize_html_tags: bool,
+
+    /// Whether to open external links in a new tab.
+    ///
+    /// The default is `false`.
+    /// Pass `true` to add `target="_blank" rel="noopener"` to links (and
+    /// autolinks) whose URL starts with `http:` or `https:`.
+    /// Relative links (such as `/a` or `#b`) are not changed.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not add attributes to links by default:
+    /// assert_eq!(to_html("[a](https://b)"), "<p><a href=\"https://b\">a</a></p>");
+    ///
+    /// // Pass `open_external_links_in_new_tab: true` to add them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[a](https://b) [c](/d)",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               open_external_links_in_new_tab: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"https://b\" target=\"_blank\" rel=\"noopener\">a</a> <a href=\"/d\">c</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub open_external_links_in_new_tab: bool,
+
+    /// Whether to add `data-line` attributes to block elements.
+    ///
+    /// The default is `false`, which does not add them.
+    /// Pass `true` to add the line in the source where a block starts to
+    /// paragraphs, headings, list items, block quotes, and code (flow).
+    /// That can be used to map the output back to the source, such as to
+    /// sync scrolling between an editor and a preview.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not add lines by default:
+    /// assert_eq!(to_html("# a\n\nb"), "<h1>a</h1>\n<p>b</p>");
+    ///
+    /// // Pass `source_lines: true` to add them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "# a\n\nb",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               source_lines: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<h1 data-line=\"1\">a</h1>\n<p data-line=\"3\">b</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## References
+    ///
+    /// *   [`--sourcepos` in `cmark`](https://github.com/commonmark/cmark/blob/master/man/man1/cmark.1)
+    pub source_lines: bool,
+
+    /// Whether to remove spaces and tabs at the end of lines in the HTML.
+    ///
+    /// The default is `false`, which keeps them.
+    /// Pass `true` to remove them, such as to get output that passes
+    /// linters.
+    /// Whitespace in preformatted text (code (indented), code (fenced), math
+    /// (flow), and HTML (flow) starting with `<pre>`, `<script>`, `<style>`,
+    /// or `<textarea>`) is kept, as it is part of the content.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps whitespace at the end of lines by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<div>  \n\n*a*",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<div>  \n<p><em>a</em></p>"
+    /// );
+    ///
+    /// // Pass `trim_trailing_whitespace: true` to remove it:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<div>  \n\n*a*",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               trim_trailing_whitespace: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<div>\n<p><em>a</em></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub trim_trailing_whitespace: bool,
+
+    /// Whether to not encode HTML in code (dangerous).
+    ///
+    /// The default is `false`, which encodes characters such as `<` and `&`
+    /// in the contents of code (flow, such as fenced code, and text).
+    ///
+    /// Pass `true` for trusted content (such as input that is already
+    /// sanitized) to pass the contents of code through as they are.
+    /// Only the contents of code are affected: everything else (including
+    /// the info string of fenced code) is still encoded.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` is safe by default:
+    /// assert_eq!(to_html("`<b>`"), "<p><code>&lt;b&gt;</code></p>");
+    ///
+    /// // Turn `unsafe_raw_code` on to not encode code:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "`<b>`",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               unsafe_raw_code: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><code><b></code></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub unsafe_raw_code: bool,
+
+    /// Function to make URLs for wikilinks with.
+    ///
+    /// The default is `None`, which uses GitHub-style slugs of the target
+    /// (`[[Page Name]]` links to `page-name`).
+    /// Pass a function to use a different scheme: it is given the target of
+    /// a wikilink, and returns the URL to use.
+    /// The URL is still sanitized (see
+    /// [`allow_dangerous_protocol`][CompileOptions::allow_dangerous_protocol]).
+    ///
+    /// This has no effect if the `wikilink` construct is not turned on.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Constructs, Options, ParseOptions};
+    /// use std::rc::Rc;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[[Page Name|a]]",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 wikilink: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             compile: CompileOptions {
+    ///               wikilink_href_fn: Some(Rc::new(|value| format!("/wiki/{}", value.replace(' ', "_")))),
+    ///               ..CompileOptions::default()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"/wiki/Page_Name\">a</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub wikilink_href_fn: Option<Rc<WikilinkHrefFn>>,
+    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
+}
+
+impl fmt::Debug for CompileOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompileOptions")
+            .field("allow_dangerous_html", &self.allow_dangerous_html)
+            .field("allow_dangerous_protocol", &self.allow_dangerous_protocol)
+            .field("allowed_protocols", &self.allowed_protocols)
+            .field("code_block_class_prefix", &self.code_block_class_prefix)
+            .field(
+                "code_highlighter",
+                &self.code_highlighter.as_ref().map(|_d| "[Function]"),
+            )
+            .field("code_line_numbers", &self.code_line_numbers)
+            .field("code_meta_attribute", &self.code_meta_attribute)
+            .field("debug_definitions", &self.debug_definitions)
+            .field("debug_list_markers", &self.debug_list_markers)
+            .field("default_code_language", &self.default_code_language)
+            .field("default_line_ending", &self.default_line_ending)
+            .field("dropped_link_behavior", &self.dropped_link_behavior)
+            .field("gfm_footnote_label", &self.gfm_footnote_label)
+            .field(
+                "gfm_footnote_label_tag_name",
+                &self.gfm_footnote_label_tag_name,
+            )
+            .field(
+                "gfm_footnote_label_attributes",
+                &self.gfm_footnote_label_attributes,
+            )
+            .field("gfm_footnote_back_label", &self.gfm_footnote_back_label)
+            .field(
+                "gfm_footnote_clobber_prefix",
+                &self.gfm_footnote_clobber_prefix,
+            )
+            .field(
+                "gfm_task_list_item_checkable",
+                &self.gfm_task_list_item_checkable,
+            )
+            .field("gfm_table_cell_align", &self.gfm_table_cell_align)
+            .field("gfm_tagfilter", &self.gfm_tagfilter)
+            .field("hard_line_breaks", &self.hard_line_breaks)
+            .field("hashtag_url", &self.hashtag_url)
+            .field("heading_ids", &self.heading_ids)
+            .field(
+                "heading_id_fn",
+                &self.heading_id_fn.as_ref().map(|_d| "[Function]"),
+            )
+            .field("html_sanitization", &self.html_sanitization)
+            .field("html_syntax", &self.html_syntax)
+            .field("image_figure", &self.image_figure)
+            .field("image_lazy_loading", &self.image_lazy_loading)
+            .field("link_rel", &self.link_rel)
+            .field("max_output_size", &self.max_output_size)
+            .field("mention_url", &self.mention_url)
+            .field("normalize_html_tags", &self.normalize_html_tags)
+            .field(
+                "open_external_links_in_new_tab",
+                &self.open_external_links_in_new_tab,
+            )
+            .field("source_lines", &self.source_lines)
+            .field("trim_trailing_whitespace", &self.trim_trailing_whitespace)
+            .field("unsafe_raw_code", &self.unsafe_raw_code)
+            .field(
+                "wikilink_href_fn",
+                &self.wikilink_href_fn.as_ref().map(|_d| "[Function]"),
+            )
+            .finish()
+    }
+}
+
+impl CompileOptions {
+    /// GFM.
+    ///
+    /// GFM stands for **GitHub flavored markdown**.
+    /// On the compilation side, GFM turns on the GFM tag filter.
+    /// The tagfilter is useless, but it’s included here for consistency, and
+    /// this method exists for parity to parse options.
+    ///
+    /// For more information, see the GFM specification:
+    /// <https://github.github.com/gfm/>.
+    pub fn gfm() -> Self {
+        Self {
             gfm_tagfilter: true,
             ..Self::default()
         }
@@ -969,156 +2506,493 @@ impl CompileOptions {
 #[allow(clippy::struct_excessive_bools)]
 pub struct ParseOptions {
     // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
+    /// Which named character references to support.
+    ///
+    /// The default is `CharacterReferences::Html5`, which follows
+    /// `CommonMark`.
+    /// Unsupported names are not character references, so they are kept as
+    /// text.
+    ///
+    /// This option does nothing if `character_reference` is not turned on in
+    /// `constructs`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CharacterReferences, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` supports the named character references from HTML 5 by default:
+    /// assert_eq!(to_html("&amp; &copy;"), "<p>&amp; ©</p>");
+    ///
+    /// // Pass `character_references` to choose which are supported:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "&amp; &copy;",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               character_references: CharacterReferences::XmlMinimal,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>&amp; &amp;copy;</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub character_references: CharacterReferences,
+
+    /// Unit to count the `column` of points in.
+    ///
+    /// The default is `ColumnUnit::Bytes`, which counts each byte of a
+    /// character as a column.
+    /// Editors typically count graphemes, and language servers UTF-16 code
+    /// units, so pass `ColumnUnit::Graphemes` or `ColumnUnit::Utf16` to match
+    /// them, when dealing with characters outside of ASCII (such as CJK,
+    /// combining marks, or emoji).
+    ///
+    /// This does not affect the `offset` of points, which is always in bytes.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_mdast, ColumnUnit, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` counts columns in bytes by default:
+    /// let tree = to_mdast("😀 *a*", &ParseOptions::default())?;
+    /// let emphasis = &tree.children().unwrap()[0].children().unwrap()[1];
+    /// assert_eq!(emphasis.position().unwrap().start.column, 6);
+    ///
+    /// // Pass `column_unit` to count in something else:
+    /// let tree = to_mdast(
+    ///     "😀 *a*",
+    ///     &ParseOptions {
+    ///         column_unit: ColumnUnit::Utf16,
+    ///         ..ParseOptions::default()
+    ///     },
+    /// )?;
+    /// let emphasis = &tree.children().unwrap()[0].children().unwrap()[1];
+    /// assert_eq!(emphasis.position().unwrap().start.column, 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub column_unit: ColumnUnit,
+
     /// Which constructs to enable and disable.
     ///
-    /// The default is to follow `CommonMark`.
+    /// The default is to follow `CommonMark`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` follows CommonMark by default:
+    /// assert_eq!(
+    ///     to_html("    indented code?"),
+    ///     "<pre><code>indented code?\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `constructs` to choose what to enable and disable:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "    indented code?",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 code_indented: false,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>indented code?</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub constructs: Constructs,
+
+    /// Whether to support GFM strikethrough with a single tilde
+    ///
+    /// This option does nothing if `gfm_strikethrough` is not turned on in
+    /// `constructs`.
+    /// This option does not affect strikethrough with double tildes.
+    ///
+    /// The default is `true`, which follows how markdown on `github.com`
+    /// works, as strikethrough with single tildes is supported.
+    /// Pass `false`, to follow the GFM spec more strictly, by not allowing
+    /// strikethrough with single tildes.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` supports single tildes by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "~a~",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><del>a</del></p>"
+    /// );
+    ///
+    /// // Pass `gfm_strikethrough_single_tilde: false` to turn that off:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "~a~",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///               gfm_strikethrough_single_tilde: false,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>~a~</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_strikethrough_single_tilde: bool,
+
+    /// Whether pipes in code in GFM tables are kept in that code.
+    ///
+    /// This option does nothing if `gfm_table` is not turned on in
+    /// `constructs`.
+    ///
+    /// The default is `false`, which follows GFM and how markdown on
+    /// `github.com` works: pipes split cells, also when they are in code, so
+    /// they must be escaped (`` `a\|b` ``).
+    /// Pass `true` to not split cells at pipes in code (`` `a|b` ``).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` splits cells at pipes in code by default:
+    /// assert_eq!(
+    ///     to_html_with_options("| a |\n| - |\n| `b|c` |", &Options::gfm())?,
+    ///     "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>`b</td>\n</tr>\n</tbody>\n</table>"
+    /// );
+    ///
+    /// // Pass `gfm_table_pipes_in_code: true` to keep them in code:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "| a |\n| - |\n| `b|c` |",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               gfm_table_pipes_in_code: true,
+    ///               ..ParseOptions::gfm()
+    ///             },
+    ///             ..Options::gfm()
+    ///         }
+    ///     )?,
+    ///     "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td><code>b|c</code></td>\n</tr>\n</tbody>\n</table>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_table_pipes_in_code: bool,
+
+    /// Whether to support math (text) with a single dollar
+    ///
+    /// This option does nothing if `math_text` is not turned on in
+    /// `constructs`.
+    /// This option does not affect math (text) with two or more dollars.
+    ///
+    /// The default is `true`, which is more close to how code (text) and
+    /// Pandoc work, as it allows math with a single dollar to form.
+    /// However, single dollars can interfere with “normal” dollars in text.
+    /// Pass `false`, to only allow math (text) to form when two or more
+    /// dollars are used.
+    /// If you pass `false`, you can still use two or more dollars for text
+    /// math.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` supports single dollars by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><code class=\"language-math math-inline\">a</code></p>"
+    /// );
+    ///
+    /// // Pass `math_text_single_dollar: false` to turn that off:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               math_text_single_dollar: false,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>$a$</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub math_text_single_dollar: bool,
+
+    /// Maximum depth of nested containers and attention.
+    ///
+    /// The default is `None`, which does not limit how deep things can be
+    /// nested.
+    /// Pass a number to limit it, which is useful when parsing untrusted
+    /// markdown, as some pathological input (such as thousands of nested
+    /// block quotes or list items, or a very long run of `*` on both sides
+    /// of a word) takes a long time to parse.
+    ///
+    /// Containers (block quotes, list items, GFM footnote definitions, and
+    /// directive containers) are not opened when that would exceed the
+    /// maximum depth, so their markers are parsed as something else.
+    /// A sequence of attention markers (`*`, `_`, `~`) forms at most this
+    /// many emphasis, strong, or strikethrough; remaining markers are text.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html, to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` follows CommonMark by default:
+    /// // `markdown-rs` does not limit nesting by default:
     /// assert_eq!(
-    ///     to_html("    indented code?"),
-    ///     "<pre><code>indented code?\n</code></pre>"
+    ///     to_html("> > > a"),
+    ///     "<blockquote>\n<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n</blockquote>\n</blockquote>"
     /// );
     ///
-    /// // Pass `constructs` to choose what to enable and disable:
+    /// // Pass `max_nesting_depth` to limit it:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "    indented code?",
+    ///         "> > > a",
     ///         &Options {
     ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 code_indented: false,
-    ///                 ..Constructs::default()
-    ///               },
+    ///               max_nesting_depth: Some(2),
     ///               ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p>indented code?</p>"
+    ///     "<blockquote>\n<blockquote>\n<p>&gt; a</p>\n</blockquote>\n</blockquote>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub constructs: Constructs,
+    pub max_nesting_depth: Option<usize>,
 
-    /// Whether to support GFM strikethrough with a single tilde
+    /// Functions to change events with, after parsing.
     ///
-    /// This option does nothing if `gfm_strikethrough` is not turned on in
-    /// `constructs`.
-    /// This option does not affect strikethrough with double tildes.
+    /// The default is to have no custom resolvers.
     ///
-    /// The default is `true`, which follows how markdown on `github.com`
-    /// works, as strikethrough with single tildes is supported.
-    /// Pass `false`, to follow the GFM spec more strictly, by not allowing
-    /// strikethrough with single tildes.
+    /// Each resolver has an ID and a function.
+    /// When the whole document is parsed, the functions are called in order
+    /// with all events (see [`event`][crate::event]) and the markdown, and
+    /// can change those events, before they are compiled.
+    /// This is useful for plugins, such as to add things into headings.
+    /// When several resolvers have the same ID, only the first is used.
+    ///
+    /// > 👉 **Note**: events must stay balanced: each enter must have a
+    /// > matching exit, in the right order, and only names that are compiled
+    /// > (not ones that are compiled away while parsing) can be added.
+    /// > Otherwise, compiling panics.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::{event::Name, to_html, to_html_with_options, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` supports single tildes by default:
+    /// // `markdown-rs` has no custom resolvers by default:
+    /// assert_eq!(to_html("*a*"), "<p><em>a</em></p>");
+    ///
+    /// // Pass `resolvers` to change events:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "~a~",
+    ///         "*a*",
     ///         &Options {
     ///             parse: ParseOptions {
-    ///               constructs: Constructs::gfm(),
+    ///               resolvers: vec![(
+    ///                   "emphasis-to-strong".into(),
+    ///                   Box::new(|events, _value| {
+    ///                       for event in events {
+    ///                           if event.name == Name::Emphasis {
+    ///                               event.name = Name::Strong;
+    ///                           }
+    ///                       }
+    ///                   }),
+    ///               )],
     ///               ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><del>a</del></p>"
+    ///     "<p><strong>a</strong></p>"
     /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub resolvers: Vec<(String, Box<Resolver>)>,
+
+    /// Whether to turn straight quotes, dashes, and dots into typographic
+    /// punctuation.
     ///
-    /// // Pass `gfm_strikethrough_single_tilde: false` to turn that off:
+    /// The default is `false`.
+    ///
+    /// When `true`, straight quotes (`"`, `'`) turn into curly quotes
+    /// (`“`, `”`, `‘`, `’`), two dashes (`--`) into an en dash (`–`), three
+    /// dashes (`---`) into an em dash (`—`), and three dots (`...`) into an
+    /// ellipsis (`…`).
+    /// This only happens in text: not in code, autolinks, HTML, and such.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` leaves punctuation alone by default:
+    /// assert_eq!(
+    ///     to_html("\"a\" -- b's..."),
+    ///     "<p>&quot;a&quot; -- b's...</p>"
+    /// );
+    ///
+    /// // Pass `smart_punctuation: true` to turn it into typographic punctuation:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "~a~",
+    ///         "\"a\" -- b's...",
     ///         &Options {
     ///             parse: ParseOptions {
-    ///               constructs: Constructs::gfm(),
-    ///               gfm_strikethrough_single_tilde: false,
+    ///               smart_punctuation: true,
     ///               ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p>~a~</p>"
+    ///     "<p>“a” – b’s…</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_strikethrough_single_tilde: bool,
+    pub smart_punctuation: bool,
 
-    /// Whether to support math (text) with a single dollar
+    /// Size of a tab stop.
     ///
-    /// This option does nothing if `math_text` is not turned on in
-    /// `constructs`.
-    /// This option does not affect math (text) with two or more dollars.
+    /// The default is `4`, as in `CommonMark`.
     ///
-    /// The default is `true`, which is more close to how code (text) and
-    /// Pandoc work, as it allows math with a single dollar to form.
-    /// However, single dollars can interfere with “normal” dollars in text.
-    /// Pass `false`, to only allow math (text) to form when two or more
-    /// dollars are used.
-    /// If you pass `false`, you can still use two or more dollars for text
-    /// math.
+    /// Tabs are expanded to the next tab stop, which affects the `column` of
+    /// points (such as in positional info in mdast).
+    /// This is also the amount of indentation needed for
+    /// [code (indented)][Constructs::code_indented], and the most indentation
+    /// other flow constructs (such as headings) can have.
+    ///
+    /// Parsing with a tab size of `0` results in an error.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` supports single dollars by default:
+    /// // `markdown-rs` uses a tab size of 4 by default:
+    /// assert_eq!(to_html("  a"), "<p>a</p>");
+    ///
+    /// // Pass `tab_size: 2` to need less indentation for code:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "$a$",
+    ///         "  a",
     ///         &Options {
     ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 math_text: true,
-    ///                 ..Constructs::default()
-    ///               },
+    ///               tab_size: 2,
     ///               ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><code class=\"language-math math-inline\">a</code></p>"
+    ///     "<pre><code>a\n</code></pre>"
     /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub tab_size: usize,
+
+    /// Number of markers needed for a thematic break.
     ///
-    /// // Pass `math_text_single_dollar: false` to turn that off:
+    /// The default is `3`, as in `CommonMark`.
+    ///
+    /// Some flavors require more markers, to prevent accidental thematic
+    /// breaks.
+    /// Lines with fewer markers are then not thematic breaks, but can still
+    /// form something else, such as a paragraph or a heading (setext)
+    /// underline.
+    /// All markers must still be the same character, with only spaces or tabs
+    /// between them.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` needs 3 markers by default:
+    /// assert_eq!(to_html("***"), "<hr />");
+    ///
+    /// // Pass `thematic_break_min: 4` to need more markers:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "$a$",
+    ///         "***",
     ///         &Options {
     ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 math_text: true,
-    ///                 ..Constructs::default()
-    ///               },
-    ///               math_text_single_dollar: false,
+    ///               thematic_break_min: 4,
     ///               ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p>$a$</p>"
+    ///     "<p>***</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub math_text_single_dollar: bool,
+    pub thematic_break_min: usize,
 
     /// Function to parse expressions with.
     ///
@@ -1153,12 +3027,27 @@ pub struct ParseOptions {
 impl fmt::Debug for ParseOptions {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ParseOptions")
+            .field("character_references", &self.character_references)
+            .field("column_unit", &self.column_unit)
             .field("constructs", &self.constructs)
             .field(
                 "gfm_strikethrough_single_tilde",
                 &self.gfm_strikethrough_single_tilde,
             )
+            .field("gfm_table_pipes_in_code", &self.gfm_table_pipes_in_code)
             .field("math_text_single_dollar", &self.math_text_single_dollar)
+            .field("max_nesting_depth", &self.max_nesting_depth)
+            .field(
+                "resolvers",
+                &self
+                    .resolvers
+                    .iter()
+                    .map(|d| (&d.0, "[Function]"))
+                    .collect::<Vec<_>>(),
+            )
+            .field("smart_punctuation", &self.smart_punctuation)
+            .field("tab_size", &self.tab_size)
+            .field("thematic_break_min", &self.thematic_break_min)
             .field(
                 "mdx_expression_parse",
                 &self.mdx_expression_parse.as_ref().map(|_d| "[Function]"),
@@ -1175,9 +3064,17 @@ impl Default for ParseOptions {
     /// `CommonMark` defaults.
     fn default() -> Self {
         Self {
+            character_references: CharacterReferences::default(),
+            column_unit: ColumnUnit::default(),
             constructs: Constructs::default(),
             gfm_strikethrough_single_tilde: true,
+            gfm_table_pipes_in_code: false,
             math_text_single_dollar: true,
+            max_nesting_depth: None,
+            resolvers: Vec::new(),
+            smart_punctuation: false,
+            tab_size: TAB_SIZE,
+            thematic_break_min: THEMATIC_BREAK_MARKER_COUNT_MIN,
             mdx_expression_parse: None,
             mdx_esm_parse: None,
         }
@@ -1271,6 +3168,473 @@ impl Options {
             compile: CompileOptions::gfm(),
         }
     }
+
+    /// Safe.
+    ///
+    /// For untrusted markdown, such as what users write in web apps.
+    /// This is the recommended way to turn such markdown into HTML.
+    ///
+    /// It is `CommonMark` (see [`Options::default()`][]) with:
+    ///
+    /// *   [`allow_dangerous_html`][CompileOptions::allow_dangerous_html]:
+    ///     `false`, so HTML is encoded
+    /// *   [`allow_dangerous_protocol`][CompileOptions::allow_dangerous_protocol]:
+    ///     `false`, so URLs with dangerous protocols (such as `javascript:`)
+    ///     are dropped
+    /// *   [`gfm_tagfilter`][CompileOptions::gfm_tagfilter]:
+    ///     `true`, so some dangerous tags are encoded even if HTML is allowed
+    ///     later
+    /// *   [`max_nesting_depth`][ParseOptions::max_nesting_depth]:
+    ///     `Some(32)`, so pathological nesting does not take long to parse
+    ///
+    /// Change fields on the result to configure more, such as to turn on
+    /// GFM constructs.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options("<script>alert(1)</script>", &Options::safe())?,
+    ///     "&lt;script&gt;alert(1)&lt;/script&gt;"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn safe() -> Self {
+        Self {
+            parse: ParseOptions {
+                max_nesting_depth: Some(SAFE_NESTING_DEPTH_MAX),
+                ..ParseOptions::default()
+            },
+            compile: CompileOptions {
+                allow_dangerous_html: false,
+                allow_dangerous_protocol: false,
+                gfm_tagfilter: true,
+                ..CompileOptions::default()
+            },
+        }
+    }
+
+    /// Create a builder.
+    ///
+    /// Fields that are not set on the builder use their default values.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, LineEnding, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let options = Options::builder()
+    ///     .allow_dangerous_html(true)
+    ///     .default_line_ending(LineEnding::CarriageReturn)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options("> <i>a</i>", &options)?,
+    ///     "<blockquote>\r<p><i>a</i></p>\r</blockquote>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn builder() -> OptionsBuilder {
+        OptionsBuilder::default()
+    }
+}
+
+/// Builder for [`Options`][].
+///
+/// Create one with [`Options::builder()`][], chain setters, and finish with
+/// [`build()`][OptionsBuilder::build].
+/// See the fields of [`ParseOptions`][] and [`CompileOptions`][] for what
+/// each setter does.
+#[derive(Debug, Default)]
+pub struct OptionsBuilder {
+    /// Options being built.
+    options: Options,
+}
+
+impl OptionsBuilder {
+    /// Finish, turning the builder into [`Options`][].
+    #[must_use]
+    pub fn build(self) -> Options {
+        self.options
+    }
+
+    /// Set all parse options.
+    #[must_use]
+    pub fn parse(mut self, parse: ParseOptions) -> Self {
+        self.options.parse = parse;
+        self
+    }
+
+    /// Set all compile options.
+    #[must_use]
+    pub fn compile(mut self, compile: CompileOptions) -> Self {
+        self.options.compile = compile;
+        self
+    }
+
+    /// Set [`character_references`][ParseOptions::character_references].
+    #[must_use]
+    pub fn character_references(mut self, value: CharacterReferences) -> Self {
+        self.options.parse.character_references = value;
+        self
+    }
+
+    /// Set [`column_unit`][ParseOptions::column_unit].
+    #[must_use]
+    pub fn column_unit(mut self, value: ColumnUnit) -> Self {
+        self.options.parse.column_unit = value;
+        self
+    }
+
+    /// Set [`constructs`][ParseOptions::constructs].
+    #[must_use]
+    pub fn constructs(mut self, constructs: Constructs) -> Self {
+        self.options.parse.constructs = constructs;
+        self
+    }
+
+    /// Set [`gfm_strikethrough_single_tilde`][ParseOptions::gfm_strikethrough_single_tilde].
+    #[must_use]
+    pub fn gfm_strikethrough_single_tilde(mut self, value: bool) -> Self {
+        self.options.parse.gfm_strikethrough_single_tilde = value;
+        self
+    }
+
+    /// Set [`gfm_table_pipes_in_code`][ParseOptions::gfm_table_pipes_in_code].
+    #[must_use]
+    pub fn gfm_table_pipes_in_code(mut self, value: bool) -> Self {
+        self.options.parse.gfm_table_pipes_in_code = value;
+        self
+    }
+
+    /// Set [`math_text_single_dollar`][ParseOptions::math_text_single_dollar].
+    #[must_use]
+    pub fn math_text_single_dollar(mut self, value: bool) -> Self {
+        self.options.parse.math_text_single_dollar = value;
+        self
+    }
+
+    /// Set [`max_nesting_depth`][ParseOptions::max_nesting_depth].
+    #[must_use]
+    pub fn max_nesting_depth(mut self, value: usize) -> Self {
+        self.options.parse.max_nesting_depth = Some(value);
+        self
+    }
+
+    /// Add a resolver to [`resolvers`][ParseOptions::resolvers].
+    #[must_use]
+    pub fn resolver(mut self, id: impl Into<String>, value: Box<Resolver>) -> Self {
+        self.options.parse.resolvers.push((id.into(), value));
+        self
+    }
+
+    /// Set [`resolvers`][ParseOptions::resolvers].
+    #[must_use]
+    pub fn resolvers(mut self, value: Vec<(String, Box<Resolver>)>) -> Self {
+        self.options.parse.resolvers = value;
+        self
+    }
+
+    /// Set [`smart_punctuation`][ParseOptions::smart_punctuation].
+    #[must_use]
+    pub fn smart_punctuation(mut self, value: bool) -> Self {
+        self.options.parse.smart_punctuation = value;
+        self
+    }
+
+    /// Set [`tab_size`][ParseOptions::tab_size].
+    #[must_use]
+    pub fn tab_size(mut self, value: usize) -> Self {
+        self.options.parse.tab_size = value;
+        self
+    }
+
+    /// Set [`thematic_break_min`][ParseOptions::thematic_break_min].
+    #[must_use]
+    pub fn thematic_break_min(mut self, value: usize) -> Self {
+        self.options.parse.thematic_break_min = value;
+        self
+    }
+
+    /// Set [`mdx_expression_parse`][ParseOptions::mdx_expression_parse].
+    #[must_use]
+    pub fn mdx_expression_parse(mut self, value: Box<MdxExpressionParse>) -> Self {
+        self.options.parse.mdx_expression_parse = Some(value);
+        self
+    }
+
+    /// Set [`mdx_esm_parse`][ParseOptions::mdx_esm_parse].
+    #[must_use]
+    pub fn mdx_esm_parse(mut self, value: Box<MdxEsmParse>) -> Self {
+        self.options.parse.mdx_esm_parse = Some(value);
+        self
+    }
+
+    /// Set [`allow_dangerous_html`][CompileOptions::allow_dangerous_html].
+    #[must_use]
+    pub fn allow_dangerous_html(mut self, value: bool) -> Self {
+        self.options.compile.allow_dangerous_html = value;
+        self
+    }
+
+    /// Set [`allow_dangerous_protocol`][CompileOptions::allow_dangerous_protocol].
+    #[must_use]
+    pub fn allow_dangerous_protocol(mut self, value: bool) -> Self {
+        self.options.compile.allow_dangerous_protocol = value;
+        self
+    }
+
+    /// Set [`allowed_protocols`][CompileOptions::allowed_protocols].
+    #[must_use]
+    pub fn allowed_protocols(mut self, value: Vec<String>) -> Self {
+        self.options.compile.allowed_protocols = Some(value);
+        self
+    }
+
+    /// Set [`code_block_class_prefix`][CompileOptions::code_block_class_prefix].
+    #[must_use]
+    pub fn code_block_class_prefix(mut self, value: impl Into<String>) -> Self {
+        self.options.compile.code_block_class_prefix = Some(value.into());
+        self
+    }
+
+    /// Set [`code_highlighter`][CompileOptions::code_highlighter].
+    #[must_use]
+    pub fn code_highlighter(mut self, value: Rc<CodeHighlighterFn>) -> Self {
+        self.options.compile.code_highlighter = Some(value);
+        self
+    }
+
+    /// Set [`code_line_numbers`][CompileOptions::code_line_numbers].
+    #[must_use]
+    pub fn code_line_numbers(mut self, value: bool) -> Self {
+        self.options.compile.code_line_numbers = value;
+        self
+    }
+
+    /// Set [`code_meta_attribute`][CompileOptions::code_meta_attribute].
+    #[must_use]
+    pub fn code_meta_attribute(mut self, value: bool) -> Self {
+        self.options.compile.code_meta_attribute = value;
+        self
+    }
+
+    /// Set [`debug_definitions`][CompileOptions::debug_definitions].
+    #[must_use]
+    pub fn debug_definitions(mut self, value: bool) -> Self {
+        self.options.compile.debug_definitions = value;
+        self
+    }
+
+    /// Set [`debug_list_markers`][CompileOptions::debug_list_markers].
+    #[must_use]
+    pub fn debug_list_markers(mut self, value: bool) -> Self {
+        self.options.compile.debug_list_markers = value;
+        self
+    }
+
+    /// Set [`default_code_language`][CompileOptions::default_code_language].
+    #[must_use]
+    pub fn default_code_language(mut self, value: impl Into<String>) -> Self {
+        self.options.compile.default_code_language = Some(value.into());
+        self
+    }
+
+    /// Set [`default_line_ending`][CompileOptions::default_line_ending].
+    #[must_use]
+    pub fn default_line_ending(mut self, value: LineEnding) -> Self {
+        self.options.compile.default_line_ending = Some(value);
+        self
+    }
+
+    /// Set [`dropped_link_behavior`][CompileOptions::dropped_link_behavior].
+    #[must_use]
+    pub fn dropped_link_behavior(mut self, value: DroppedLinkBehavior) -> Self {
+        self.options.compile.dropped_link_behavior = value;
+        self
+    }
+
+    /// Set [`gfm_footnote_label`][CompileOptions::gfm_footnote_label].
+    #[must_use]
+    pub fn gfm_footnote_label(mut self, value: impl Into<String>) -> Self {
+        self.options.compile.gfm_footnote_label = Some(value.into());
+        self
+    }
+
+    /// Set [`gfm_footnote_label_tag_name`][CompileOptions::gfm_footnote_label_tag_name].
+    #[must_use]
+    pub fn gfm_footnote_label_tag_name(mut self, value: impl Into<String>) -> Self {
+        self.options.compile.gfm_footnote_label_tag_name = Some(value.into());
+        self
+    }
+
+    /// Set [`gfm_footnote_label_attributes`][CompileOptions::gfm_footnote_label_attributes].
+    #[must_use]
+    pub fn gfm_footnote_label_attributes(mut self, value: impl Into<String>) -> Self {
+        self.options.compile.gfm_footnote_label_attributes = Some(value.into());
+        self
+    }
+
+    /// Set [`gfm_footnote_back_label`][CompileOptions::gfm_footnote_back_label].
+    #[must_use]
+    pub fn gfm_footnote_back_label(mut self, value: impl Into<String>) -> Self {
+        self.options.compile.gfm_footnote_back_label = Some(value.into());
+        self
+    }
+
+    /// Set [`gfm_footnote_clobber_prefix`][CompileOptions::gfm_footnote_clobber_prefix].
+    #[must_use]
+    pub fn gfm_footnote_clobber_prefix(mut self, value: impl Into<String>) -> Self {
+        self.options.compile.gfm_footnote_clobber_prefix = Some(value.into());
+        self
+    }
+
+    /// Set [`gfm_task_list_item_checkable`][CompileOptions::gfm_task_list_item_checkable].
+    #[must_use]
+    pub fn gfm_task_list_item_checkable(mut self, value: bool) -> Self {
+        self.options.compile.gfm_task_list_item_checkable = value;
+        self
+    }
+
+    /// Set [`gfm_table_cell_align`][CompileOptions::gfm_table_cell_align].
+    #[must_use]
+    pub fn gfm_table_cell_align(mut self, value: GfmTableCellAlign) -> Self {
+        self.options.compile.gfm_table_cell_align = value;
+        self
+    }
+
+    /// Set [`gfm_tagfilter`][CompileOptions::gfm_tagfilter].
+    #[must_use]
+    pub fn gfm_tagfilter(mut self, value: bool) -> Self {
+        self.options.compile.gfm_tagfilter = value;
+        self
+    }
+
+    /// Set [`hard_line_breaks`][CompileOptions::hard_line_breaks].
+    #[must_use]
+    pub fn hard_line_breaks(mut self, value: bool) -> Self {
+        self.options.compile.hard_line_breaks = value;
+        self
+    }
+
+    /// Set [`hashtag_url`][CompileOptions::hashtag_url].
+    #[must_use]
+    pub fn hashtag_url(mut self, value: impl Into<String>) -> Self {
+        self.options.compile.hashtag_url = Some(value.into());
+        self
+    }
+
+    /// Set [`heading_ids`][CompileOptions::heading_ids].
+    #[must_use]
+    pub fn heading_ids(mut self, value: bool) -> Self {
+        self.options.compile.heading_ids = value;
+        self
+    }
+
+    /// Set [`heading_id_fn`][CompileOptions::heading_id_fn].
+    #[must_use]
+    pub fn heading_id_fn(mut self, value: Rc<HeadingIdFn>) -> Self {
+        self.options.compile.heading_id_fn = Some(value);
+        self
+    }
+
+    /// Set [`html_sanitization`][CompileOptions::html_sanitization].
+    #[must_use]
+    pub fn html_sanitization(mut self, value: HtmlSanitization) -> Self {
+        self.options.compile.html_sanitization = Some(value);
+        self
+    }
+
+    /// Set [`html_syntax`][CompileOptions::html_syntax].
+    #[must_use]
+    pub fn html_syntax(mut self, value: HtmlSyntax) -> Self {
+        self.options.compile.html_syntax = value;
+        self
+    }
+
+    /// Set [`image_figure`][CompileOptions::image_figure].
+    #[must_use]
+    pub fn image_figure(mut self, value: bool) -> Self {
+        self.options.compile.image_figure = value;
+        self
+    }
+
+    /// Set [`image_lazy_loading`][CompileOptions::image_lazy_loading].
+    #[must_use]
+    pub fn image_lazy_loading(mut self, value: bool) -> Self {
+        self.options.compile.image_lazy_loading = value;
+        self
+    }
+
+    /// Set [`link_rel`][CompileOptions::link_rel].
+    #[must_use]
+    pub fn link_rel(mut self, value: impl Into<String>) -> Self {
+        self.options.compile.link_rel = Some(value.into());
+        self
+    }
+
+    /// Set [`max_output_size`][CompileOptions::max_output_size].
+    #[must_use]
+    pub fn max_output_size(mut self, value: usize) -> Self {
+        self.options.compile.max_output_size = Some(value);
+        self
+    }
+
+    /// Set [`mention_url`][CompileOptions::mention_url].
+    #[must_use]
+    pub fn mention_url(mut self, value: impl Into<String>) -> Self {
+        self.options.compile.mention_url = Some(value.into());
+        self
+    }
+
+    /// Set [`normalize_html_tags`][CompileOptions::normalize_html_tags].
+    #[must_use]
+    pub fn normalize_html_tags(mut self, value: bool) -> Self {
+        self.options.compile.normalize_html_tags = value;
+        self
+    }
+
+    /// Set [`open_external_links_in_new_tab`][CompileOptions::open_external_links_in_new_tab].
+    #[must_use]
+    pub fn open_external_links_in_new_tab(mut self, value: bool) -> Self {
+        self.options.compile.open_external_links_in_new_tab = value;
+        self
+    }
+
+    /// Set [`source_lines`][CompileOptions::source_lines].
+    #[must_use]
+    pub fn source_lines(mut self, value: bool) -> Self {
+        self.options.compile.source_lines = value;
+        self
+    }
+
+    /// Set [`trim_trailing_whitespace`][CompileOptions::trim_trailing_whitespace].
+    #[must_use]
+    pub fn trim_trailing_whitespace(mut self, value: bool) -> Self {
+        self.options.compile.trim_trailing_whitespace = value;
+        self
+    }
+
+    /// Set [`unsafe_raw_code`][CompileOptions::unsafe_raw_code].
+    #[must_use]
+    pub fn unsafe_raw_code(mut self, value: bool) -> Self {
+        self.options.compile.unsafe_raw_code = value;
+        self
+    }
+
+    /// Set [`wikilink_href_fn`][CompileOptions::wikilink_href_fn].
+    #[must_use]
+    pub fn wikilink_href_fn(mut self, value: Rc<WikilinkHrefFn>) -> Self {
+        self.options.compile.wikilink_href_fn = Some(value);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -1372,7 +3736,7 @@ mod tests {
 
         assert_eq!(
             format!("{:?}", ParseOptions::default()),
-            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, mdx_expression_parse: None, mdx_esm_parse: None }",
+            "ParseOptions { character_references: Html5, column_unit: Bytes, constructs: Constructs { attention: true, attributes: false, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, directive_container: false, directive_leaf: false, directive_text: false, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, hashtag: false, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_inline_footnote: false, label_start_link: true, label_end: true, list_item: true, mark: false, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, mention: false, thematic_break: true, wikilink: false }, gfm_strikethrough_single_tilde: true, gfm_table_pipes_in_code: false, math_text_single_dollar: true, max_nesting_depth: None, resolvers: [], smart_punctuation: false, tab_size: 4, thematic_break_min: 3, mdx_expression_parse: None, mdx_esm_parse: None }",
             "should support `Debug` trait"
         );
         assert_eq!(
@@ -1385,7 +3749,7 @@ mod tests {
                 })),
                 ..Default::default()
             }),
-            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, mdx_expression_parse: Some(\"[Function]\"), mdx_esm_parse: Some(\"[Function]\") }",
+            "ParseOptions { character_references: Html5, column_unit: Bytes, constructs: Constructs { attention: true, attributes: false, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, directive_container: false, directive_leaf: false, directive_text: false, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, hashtag: false, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_inline_footnote: false, label_start_link: true, label_end: true, list_item: true, mark: false, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, mention: false, thematic_break: true, wikilink: false }, gfm_strikethrough_single_tilde: true, gfm_table_pipes_in_code: false, math_text_single_dollar: true, max_nesting_depth: None, resolvers: [], smart_punctuation: false, tab_size: 4, thematic_break_min: 3, mdx_expression_parse: Some(\"[Function]\"), mdx_esm_parse: Some(\"[Function]\") }",
             "should support `Debug` trait on mdx functions"
         );
     }
@@ -1414,6 +3778,27 @@ mod tests {
             options.gfm_tagfilter,
             "should support safe `gfm` shortcut (1)"
         );
+
+        assert_eq!(
+            format!(
+                "{:?}",
+                CompileOptions {
+                    heading_id_fn: Some(Rc::new(|value| value.into())),
+                    ..Default::default()
+                }
+            ),
+            "CompileOptions { allow_dangerous_html: false, allow_dangerous_protocol: false, allowed_protocols: None, code_block_class_prefix: None, code_highlighter: None, code_line_numbers: false, code_meta_attribute: false, debug_definitions: false, debug_list_markers: false, default_code_language: None, default_line_ending: None, dropped_link_behavior: Empty, gfm_footnote_label: None, gfm_footnote_label_tag_name: None, gfm_footnote_label_attributes: None, gfm_footnote_back_label: None, gfm_footnote_clobber_prefix: None, gfm_task_list_item_checkable: false, gfm_table_cell_align: Attribute, gfm_tagfilter: false, hard_line_breaks: false, hashtag_url: None, heading_ids: false, heading_id_fn: Some(\"[Function]\"), html_sanitization: None, html_syntax: Polyglot, image_figure: false, image_lazy_loading: false, link_rel: None, max_output_size: None, mention_url: None, normalize_html_tags: false, open_external_links_in_new_tab: false, source_lines: false, trim_trailing_whitespace: false, unsafe_raw_code: false, wikilink_href_fn: None }",
+            "should support `Debug` trait"
+        );
+
+        let options = CompileOptions {
+            heading_id_fn: Some(Rc::new(|value| value.into())),
+            ..Default::default()
+        };
+        assert!(
+            options.clone().heading_id_fn.is_some(),
+            "should support `Clone` trait, w/ functions"
+        );
     }
 
     #[test]
@@ -1,5 +1,5 @@
-//! Attention (emphasis, strong, optionally GFM strikethrough) occurs in the
-//! [text][] content type.
+//! Attention (emphasis, strong, optionally GFM strikethrough and mark) occurs
+//! in the [text][] content type.
 //!
 //! ## Grammar
 //!
@@ -9,6 +9,7 @@
 //! ```bnf
 //! attention_sequence ::= 1*'*' | 1*'_'
 //! gfm_attention_sequence ::= 1*'~'
+//! mark_attention_sequence ::= 1*'='
 //! ```
 //!
 //! Sequences are matched together to form attention based on which character
@@ -29,6 +30,11 @@
 //! HTML.
 //! See [*§ 4.7.2 The `del` element*][html-del] in the HTML spec for more info.
 //!
+//! When equals sequences of two markers match, they together relate to the
+//! `<mark>` element in HTML.
+//! See [*§ 4.5.23 The `mark` element*][html-mark] in the HTML spec for more
+//! info.
+//!
 //! ## Recommendation
 //!
 //! It is recommended to use asterisks for emphasis/strong attention when
@@ -49,6 +55,10 @@
 //! While `github.com` allows single tildes too, it technically prohibits it in
 //! their spec.
 //!
+//! Mark attention (highlight) is not part of `CommonMark` or GFM, but several
+//! other markdown flavors support it with two equals signs.
+//! Sequences of one or more than two equals signs do not form mark.
+//!
 //! ## Tokens
 //!
 //! *   [`Emphasis`][Name::Emphasis]
@@ -57,6 +67,9 @@
 //! *   [`GfmStrikethrough`][Name::GfmStrikethrough]
 //! *   [`GfmStrikethroughSequence`][Name::GfmStrikethroughSequence]
 //! *   [`GfmStrikethroughText`][Name::GfmStrikethroughText]
+//! *   [`Mark`][Name::Mark]
+//! *   [`MarkSequence`][Name::MarkSequence]
+//! *   [`MarkText`][Name::MarkText]
 //! *   [`Strong`][Name::Strong]
 //! *   [`StrongSequence`][Name::StrongSequence]
 //! *   [`StrongText`][Name::StrongText]
@@ -75,6 +88,7 @@
 //! [html-em]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-em-element
 //! [html-strong]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-strong-element
 //! [html-del]: https://html.spec.whatwg.org/multipage/edits.html#the-del-element
+//! [html-mark]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-mark-element
 
 use crate::event::{Event, Kind, Name, Point};
 use crate::resolve::Name as ResolveName;
@@ -85,6 +99,7 @@ use crate::util::char::{
     after_index as char_after_index, before_index as char_before_index, classify_opt,
     Kind as CharacterKind,
 };
+use crate::{Constructs, ParseOptions};
 use alloc::{vec, vec::Vec};
 
 /// Attentention sequence that we can take markers from.
@@ -104,6 +119,8 @@ struct Sequence {
     end_point: Point,
     /// The number of markers we can still use.
     size: usize,
+    /// The number of times this sequence was matched.
+    matched: usize,
     /// Whether this sequence can open attention.
     open: bool,
     /// Whether this sequence can close attention.
@@ -117,12 +134,9 @@ struct Sequence {
 ///     ^
 /// ```
 pub fn start(tokenizer: &mut Tokenizer) -> State {
-    // Emphasis/strong:
-    if (tokenizer.parse_state.options.constructs.attention
-        && matches!(tokenizer.current, Some(b'*' | b'_')))
-        // GFM strikethrough:
-        || (tokenizer.parse_state.options.constructs.gfm_strikethrough && tokenizer.current == Some(b'~'))
-    {
+    if tokenizer.current.map_or(false, |byte| {
+        marker(&tokenizer.parse_state.options.constructs, byte)
+    }) {
         tokenizer.tokenize_state.marker = tokenizer.current.unwrap();
         tokenizer.enter(Name::AttentionSequence);
         State::Retry(StateName::AttentionInside)
@@ -154,25 +168,34 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
     // Find all sequences, gather info about them.
     let mut sequences = get_sequences(tokenizer);
 
+    let max = tokenizer.parse_state.options.max_nesting_depth;
+    // Indices of earlier sequences that can still open, in order.
+    // Keeping them separately, and not removing used sequences from
+    // `sequences`, keeps this linear for long paragraphs with lots of
+    // attention.
+    let mut openers: Vec<usize> = vec![];
+
     // Now walk through them and match them.
     let mut close = 0;
 
     while close < sequences.len() {
         let sequence_close = &sequences[close];
-        let mut next_index = close + 1;
+        let mut matched = false;
 
-        // Find a sequence that can close.
-        if sequence_close.close {
-            let mut open = close;
+        // Find a sequence that can close, and was not matched too often.
+        if sequence_close.close && max.map_or(true, |max| sequence_close.matched < max) {
+            let mut opener = openers.len();
 
             // Now walk back to find an opener.
-            while open > 0 {
-                open -= 1;
+            while opener > 0 {
+                opener -= 1;
 
+                let open = openers[opener];
                 let sequence_open = &sequences[open];
 
                 // An opener matching our closer:
                 if sequence_open.open
+                    && max.map_or(true, |max| sequence_open.matched < max)
                     && sequence_close.marker == sequence_open.marker
                     && sequence_close.stack == sequence_open.stack
                 {
@@ -187,36 +210,58 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
                         continue;
                     }
 
-                    // For GFM strikethrough:
-                    // * both sequences must have the same size
-                    // * more than 2 markers don’t work
-                    // * one marker is prohibited by the spec, but supported by GH
-                    if sequence_close.marker == b'~'
-                        && (sequence_close.size != sequence_open.size
-                            || sequence_close.size > 2
-                            || sequence_close.size == 1
-                                && !tokenizer.parse_state.options.gfm_strikethrough_single_tilde)
-                    {
+                    if !sizes_match(tokenizer.parse_state.options, sequence_open, sequence_close) {
                         continue;
                     }
 
                     // We found a match!
-                    next_index = match_sequences(tokenizer, &mut sequences, open, close);
+                    match_sequences(tokenizer, &mut sequences, open, close);
+
+                    // Now we make sure that we can’t have misnested
+                    // attention:
+                    //
+                    // ```html
+                    // <em>a <strong>b</em> c</strong>
+                    // ```
+                    //
+                    // Do that by marking everything between it as no
+                    // longer possible to open anything.
+                    // Theoretically we should mark as `close: false` too,
+                    // but we don’t look for closers backwards, so it’s not
+                    // needed.
+                    for index in openers.drain(opener + 1..) {
+                        sequences[index].open = false;
+                    }
+
+                    if sequences[open].size == 0 {
+                        openers.pop();
+                    }
 
+                    matched = true;
                     break;
                 }
             }
         }
 
-        close = next_index;
+        // Stay on this closing sequence for the next iteration if it was
+        // used: it might close more things.
+        if !matched || sequences[close].size == 0 {
+            if sequences[close].open && sequences[close].size > 0 {
+                openers.push(close);
+            }
+
+            close += 1;
+        }
     }
 
     // Mark remaining sequences as data.
     let mut index = 0;
     while index < sequences.len() {
         let sequence = &sequences[index];
-        tokenizer.events[sequence.index].name = Name::Data;
-        tokenizer.events[sequence.index + 1].name = Name::Data;
+        if sequence.size > 0 {
+            tokenizer.events[sequence.index].name = Name::Data;
+            tokenizer.events[sequence.index + 1].name = Name::Data;
+        }
         index += 1;
     }
 
@@ -224,8 +269,49 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
     None
 }
 
+/// Check whether `byte` is a marker that can form attention with
+/// `constructs`.
+fn marker(constructs: &Constructs, byte: u8) -> bool {
+    match byte {
+        // Emphasis/strong.
+        b'*' | b'_' => constructs.attention,
+        // GFM strikethrough.
+        b'~' => constructs.gfm_strikethrough,
+        // Mark.
+        b'=' => constructs.mark,
+        _ => false,
+    }
+}
+
+/// Check whether `char` is a marker that can form attention with
+/// `constructs`.
+fn marker_char(constructs: &Constructs, char: Option<char>) -> bool {
+    char.map_or(false, |char| {
+        char.is_ascii() && marker(constructs, char as u8)
+    })
+}
+
+/// Check whether the sizes of an opening and a closing sequence (with the
+/// same marker) allow them to match.
+fn sizes_match(options: &ParseOptions, open: &Sequence, close: &Sequence) -> bool {
+    match close.marker {
+        // For GFM strikethrough:
+        // * both sequences must have the same size
+        // * more than 2 markers don’t work
+        // * one marker is prohibited by the spec, but supported by GH
+        b'~' => {
+            close.size == open.size
+                && (close.size == 2 || (close.size == 1 && options.gfm_strikethrough_single_tilde))
+        }
+        // For mark, both sequences must have exactly 2 markers.
+        b'=' => close.size == 2 && open.size == 2,
+        _ => true,
+    }
+}
+
 /// Get sequences.
 fn get_sequences(tokenizer: &mut Tokenizer) -> Vec<Sequence> {
+    let constructs = &tokenizer.parse_state.options.constructs;
     let mut index = 0;
     let mut stack = vec![];
     let mut sequences = vec![];
@@ -243,18 +329,15 @@ fn get_sequences(tokenizer: &mut Tokenizer) -> Vec<Sequence> {
                 let before = classify_opt(before_char);
                 let after_char = char_after_index(tokenizer.parse_state.bytes, exit.point.index);
                 let after = classify_opt(after_char);
+                // For regular attention markers (not strikethrough or mark),
+                // the other attention markers can be used around them.
+                let regular = matches!(marker, b'*' | b'_');
                 let open = after == CharacterKind::Other
                     || (after == CharacterKind::Punctuation && before != CharacterKind::Other)
-                    // For regular attention markers (not strikethrough), the
-                    // other attention markers can be used around them
-                    || (marker != b'~' && matches!(after_char, Some('*' | '_')))
-                    || (marker != b'~' && tokenizer.parse_state.options.constructs.gfm_strikethrough && matches!(after_char, Some('~')));
+                    || (regular && marker_char(constructs, after_char));
                 let close = before == CharacterKind::Other
                     || (before == CharacterKind::Punctuation && after != CharacterKind::Other)
-                    || (marker != b'~' && matches!(before_char, Some('*' | '_')))
-                    || (marker != b'~'
-                        && tokenizer.parse_state.options.constructs.gfm_strikethrough
-                        && matches!(before_char, Some('~')));
+                    || (regular && marker_char(constructs, before_char));
 
                 sequences.push(Sequence {
                     index,
@@ -262,6 +345,7 @@ fn get_sequences(tokenizer: &mut Tokenizer) -> Vec<Sequence> {
                     start_point: enter.point.clone(),
                     end_point: exit.point.clone(),
                     size: exit.point.index - enter.point.index,
+                    matched: 0,
                     open: if marker == b'_' {
                         open && (before != CharacterKind::Other || !close)
                     } else {
@@ -288,19 +372,16 @@ fn get_sequences(tokenizer: &mut Tokenizer) -> Vec<Sequence> {
 }
 
 /// Match two sequences.
+///
+/// Fully used sequences are not removed from `sequences`, they are left with
+/// a `size` of `0`.
 #[allow(clippy::too_many_lines)]
 fn match_sequences(
     tokenizer: &mut Tokenizer,
-    sequences: &mut Vec<Sequence>,
+    sequences: &mut [Sequence],
     open: usize,
     close: usize,
-) -> usize {
-    // Where to move to next.
-    // Stay on this closing sequence for the next iteration: it
-    // might close more things.
-    // It’s changed if sequences are removed.
-    let mut next = close;
-
+) {
     // Number of markers to use from the sequence.
     let take = if sequences[open].size > 1 && sequences[close].size > 1 {
         2
@@ -308,35 +389,15 @@ fn match_sequences(
         1
     };
 
-    // We’re *on* a closing sequence, with a matching opening
-    // sequence.
-    // Now we make sure that we can’t have misnested attention:
-    //
-    // ```html
-    // <em>a <strong>b</em> c</strong>
-    // ```
-    //
-    // Do that by marking everything between it as no longer
-    // possible to open anything.
-    // Theoretically we should mark as `close: false` too, but
-    // we don’t look for closers backwards, so it’s not needed.
-    let mut between = open + 1;
-
-    while between < close {
-        sequences[between].open = false;
-        between += 1;
-    }
-
-    let (group_name, seq_name, text_name) = if sequences[open].marker == b'~' {
-        (
+    let (group_name, seq_name, text_name) = match sequences[open].marker {
+        b'~' => (
             Name::GfmStrikethrough,
             Name::GfmStrikethroughSequence,
             Name::GfmStrikethroughText,
-        )
-    } else if take == 1 {
-        (Name::Emphasis, Name::EmphasisSequence, Name::EmphasisText)
-    } else {
-        (Name::Strong, Name::StrongSequence, Name::StrongText)
+        ),
+        b'=' => (Name::Mark, Name::MarkSequence, Name::MarkText),
+        _ if take == 1 => (Name::Emphasis, Name::EmphasisSequence, Name::EmphasisText),
+        _ => (Name::Strong, Name::StrongSequence, Name::StrongText),
     };
     let open_index = sequences[open].index;
     let close_index = sequences[close].index;
@@ -344,6 +405,8 @@ fn match_sequences(
     let close_enter = sequences[close].start_point.clone();
 
     // No need to worry about `VS`, because sequences are only actual characters.
+    sequences[open].matched += 1;
+    sequences[close].matched += 1;
     sequences[open].size -= take;
     sequences[close].size -= take;
     sequences[open].end_point.column -= take;
@@ -417,7 +480,6 @@ fn match_sequences(
 
     // Remove closing sequence if fully used.
     if sequences[close].size == 0 {
-        sequences.remove(close);
         tokenizer.map.add(close_index, 2, vec![]);
     } else {
         // Shift remaining closing sequence forward.
@@ -427,14 +489,10 @@ fn match_sequences(
         tokenizer.events[close_index].point = sequences[close].start_point.clone();
     }
 
+    // Remove opening sequence if fully used.
     if sequences[open].size == 0 {
-        sequences.remove(open);
         tokenizer.map.add(open_index, 2, vec![]);
-        // Everything shifts one to the left, account for it in next iteration.
-        next -= 1;
     } else {
         tokenizer.events[open_index + 1].point = sequences[open].end_point.clone();
     }
-
-    next
 }
@@ -22,6 +22,8 @@
 //! However, for security reasons, several numeric character references parse
 //! fine but are not rendered as their corresponding character.
 //! They are instead replaced by a U+FFFD REPLACEMENT CHARACTER (`�`).
+//! Like in HTML, C1 control characters that are assigned in Windows-1252
+//! are instead replaced by what they represent there (so `&#x80;` is `€`).
 //! See [`decode_numeric`][decode_numeric] for more info.
 //!
 //! To escape ASCII punctuation characters, use the terser
@@ -41,6 +43,8 @@
 //! and `amp` are both allowed but other cases are not.
 //! See [`CHARACTER_REFERENCES`][character_references] for which
 //! names match.
+//! Which names are supported can be changed with
+//! [`character_references`][character_references_option].
 //!
 //! ## Recommendation
 //!
@@ -66,13 +70,14 @@
 //! [character_escape]: crate::construct::character_reference
 //! [decode_numeric]: crate::util::character_reference::decode_numeric
 //! [character_references]: crate::util::constant::CHARACTER_REFERENCES
+//! [character_references_option]: crate::ParseOptions::character_references
 //! [html]: https://html.spec.whatwg.org/multipage/parsing.html#character-reference-state
 
 use crate::event::Name;
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
 use crate::util::{
-    character_reference::{decode_named, value_max, value_test},
+    character_reference::{decode_named_with, value_max, value_test},
     slice::Slice,
 };
 
@@ -171,7 +176,12 @@ pub fn value(tokenizer: &mut Tokenizer) -> State {
                 tokenizer.point.index,
             );
 
-            if decode_named(slice.as_str(), true).is_none() {
+            if decode_named_with(
+                slice.as_str(),
+                &tokenizer.parse_state.options.character_references,
+            )
+            .is_none()
+            {
                 tokenizer.tokenize_state.marker = 0;
                 tokenizer.tokenize_state.size = 0;
                 return State::Nok;
@@ -13,6 +13,9 @@
 //! blank_line ::= *space_or_tab
 //! ```
 //!
+//! The `4` above is the size of a tab stop, which can be changed with
+//! [`tab_size`][crate::ParseOptions::tab_size].
+//!
 //! As this construct occurs in flow, like all flow constructs, it must be
 //! followed by an eol (line ending) or eof (end of file).
 //!
@@ -54,10 +57,9 @@
 //! [html_pre]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-pre-element
 
 use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
-use crate::event::Name;
+use crate::event::{Content, Link, Name};
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
-use crate::util::constant::TAB_SIZE;
 
 /// Start of code (indented).
 ///
@@ -77,7 +79,8 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
     {
         tokenizer.enter(Name::CodeIndented);
         tokenizer.attempt(State::Next(StateName::CodeIndentedAtBreak), State::Nok);
-        State::Retry(space_or_tab_min_max(tokenizer, TAB_SIZE, TAB_SIZE))
+        let size = tokenizer.parse_state.options.tab_size;
+        State::Retry(space_or_tab_min_max(tokenizer, size, size))
     } else {
         State::Nok
     }
@@ -100,7 +103,14 @@ pub fn at_break(tokenizer: &mut Tokenizer) -> State {
             State::Retry(StateName::CodeIndentedFurtherStart)
         }
         _ => {
-            tokenizer.enter(Name::CodeFlowChunk);
+            tokenizer.enter_link(
+                Name::CodeFlowChunk,
+                Link {
+                    previous: None,
+                    next: None,
+                    content: Content::Raw,
+                },
+            );
             State::Retry(StateName::CodeIndentedInside)
         }
     }
@@ -146,18 +156,23 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
 ///   |     bbb
 /// ```
 pub fn further_start(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer.lazy || tokenizer.pierce {
-        return State::Nok;
-    }
-
-    if tokenizer.current == Some(b'\n') {
+    // Whether the next line is lazy, or whether new containers pierce into
+    // it, is only known once we are on it: the line ending is still part of
+    // the current line.
+    // A line ending at the start of a line is on such a next line.
+    if tokenizer.at_line_start() && (tokenizer.lazy || tokenizer.pierce) {
+        State::Nok
+    } else if tokenizer.current == Some(b'\n') {
         tokenizer.enter(Name::LineEnding);
         tokenizer.consume();
         tokenizer.exit(Name::LineEnding);
         State::Next(StateName::CodeIndentedFurtherStart)
+    } else if tokenizer.lazy || tokenizer.pierce {
+        State::Nok
     } else {
         tokenizer.attempt(State::Ok, State::Next(StateName::CodeIndentedFurtherBegin));
-        State::Retry(space_or_tab_min_max(tokenizer, TAB_SIZE, TAB_SIZE))
+        let size = tokenizer.parse_state.options.tab_size;
+        State::Retry(space_or_tab_min_max(tokenizer, size, size))
     }
 }
 
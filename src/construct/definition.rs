@@ -316,6 +316,7 @@ pub fn after_whitespace(tokenizer: &mut Tokenizer) -> State {
                     Slice::from_position(
                         tokenizer.parse_state.bytes,
                         &Position::from_exit_event(&tokenizer.events, tokenizer.tokenize_state.end),
+                        tokenizer.parse_state.options.tab_size,
                     )
                     .as_str(),
                 ),
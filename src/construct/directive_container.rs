@@ -0,0 +1,375 @@
+//! Directive (container) occurs in the [document][] content type.
+//!
+//! ## Grammar
+//!
+//! Directive (container) forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! directive_container ::= directive_container_open *( eol *line ) [ eol directive_container_close ]
+//!
+//! ; Restriction: the number of markers in the closing sequence must be equal
+//! ; to or greater than the number of markers in the opening sequence.
+//! directive_container_open ::= *space_or_tab 3*':' directive_name [directive_label] [directive_attributes] *space_or_tab
+//! directive_container_close ::= *space_or_tab 3*':' *space_or_tab
+//!
+//! ; See the `partial_directive_name`, `partial_directive_label`, and
+//! ; `partial_directive_attributes` constructs for the BNF of those parts.
+//! ```
+//!
+//! As this construct occurs in document, the lines between the opening and
+//! closing fence are interpreted as more containers and flow.
+//! That means that they can include, for example, paragraphs, lists, and
+//! other directives.
+//! To nest container directives, use more markers for the outer one.
+//!
+//! If the closing fence is missing, the directive continues until the end of
+//! the container it is in, or the end of the document.
+//!
+//! Directives are a generic extension point: the name decides what a
+//! directive means, and a downstream consumer decides how to handle it.
+//! Container directives are useful for things with content, such as callouts.
+//!
+//! ## HTML
+//!
+//! Directives have no meaning in HTML.
+//! When compiling to HTML, directives (and their label and content) are
+//! dropped.
+//! Turn markdown into [mdast][crate::mdast] to map directives to something
+//! else.
+//!
+//! ## Tokens
+//!
+//! *   [`DirectiveContainer`][Name::DirectiveContainer]
+//! *   [`DirectiveContainerFence`][Name::DirectiveContainerFence]
+//! *   [`DirectiveContainerSequence`][Name::DirectiveContainerSequence]
+//! *   [`SpaceOrTab`][Name::SpaceOrTab]
+//! *   see [`partial_directive_name`][directive_name],
+//!     [`partial_directive_label`][directive_label], and
+//!     [`partial_directive_attributes`][directive_attributes] for more
+//!
+//! ## References
+//!
+//! *   [`directive-container.js` in `micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive/blob/main/dev/lib/directive-container.js)
+//! *   [Generic directives/plugins syntax](https://talk.commonmark.org/t/generic-directives-plugins-syntax/444)
+//!
+//! [document]: crate::construct::document
+//! [directive_name]: crate::construct::partial_directive_name
+//! [directive_label]: crate::construct::partial_directive_label
+//! [directive_attributes]: crate::construct::partial_directive_attributes
+
+use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::constant::DIRECTIVE_CONTAINER_SEQUENCE_SIZE_MIN;
+
+/// Start of directive (container).
+///
+/// ```markdown
+/// > | :::a[b]{c}
+///     ^
+///   | d
+///   | :::
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.directive_container {
+        tokenizer.enter(Name::DirectiveContainer);
+        tokenizer.enter(Name::DirectiveContainerFence);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(State::Next(StateName::DirectiveContainerBefore), State::Nok);
+            State::Retry(space_or_tab_min_max(
+                tokenizer,
+                0,
+                if tokenizer.parse_state.options.constructs.code_indented {
+                    tokenizer.parse_state.options.tab_size - 1
+                } else {
+                    usize::MAX
+                },
+            ))
+        } else {
+            State::Retry(StateName::DirectiveContainerBefore)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// After optional whitespace, at `:`.
+///
+/// ```markdown
+/// > | :::a[b]{c}
+///     ^
+///   | d
+///   | :::
+/// ```
+pub fn before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::DirectiveContainerSequence);
+        State::Retry(StateName::DirectiveContainerSequence)
+    } else {
+        State::Nok
+    }
+}
+
+/// In opening sequence.
+///
+/// ```markdown
+/// > | :::a[b]{c}
+///      ^
+///   | d
+///   | :::
+/// ```
+pub fn sequence(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.tokenize_state.size += 1;
+        tokenizer.consume();
+        State::Next(StateName::DirectiveContainerSequence)
+    } else if tokenizer.tokenize_state.size >= DIRECTIVE_CONTAINER_SEQUENCE_SIZE_MIN {
+        tokenizer.tokenize_state.document_container_stack
+            [tokenizer.tokenize_state.document_continued]
+            .size = tokenizer.tokenize_state.size;
+        tokenizer.tokenize_state.size = 0;
+        tokenizer.exit(Name::DirectiveContainerSequence);
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveContainerNameAfter),
+            State::Nok,
+        );
+        State::Retry(StateName::DirectiveNameStart)
+    } else {
+        tokenizer.tokenize_state.size = 0;
+        State::Nok
+    }
+}
+
+/// After name, at optional label.
+///
+/// ```markdown
+/// > | :::a[b]{c}
+///         ^
+///   | d
+///   | :::
+/// ```
+pub fn name_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'[') {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveContainerLabelAfter),
+            State::Next(StateName::DirectiveContainerLabelAfter),
+        );
+        State::Retry(StateName::DirectiveLabelStart)
+    } else {
+        State::Retry(StateName::DirectiveContainerLabelAfter)
+    }
+}
+
+/// After optional label, at optional attributes.
+///
+/// ```markdown
+/// > | :::a[b]{c}
+///            ^
+///   | d
+///   | :::
+/// ```
+pub fn label_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'{') {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveContainerAttributesAfter),
+            State::Next(StateName::DirectiveContainerAttributesAfter),
+        );
+        State::Retry(StateName::DirectiveAttributesStart)
+    } else {
+        State::Retry(StateName::DirectiveContainerAttributesAfter)
+    }
+}
+
+/// After optional attributes, at optional whitespace.
+///
+/// ```markdown
+/// > | :::a[b]{c}
+///               ^
+///   | d
+///   | :::
+/// ```
+pub fn attributes_after(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(State::Next(StateName::DirectiveContainerAfter), State::Nok);
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Retry(StateName::DirectiveContainerAfter)
+    }
+}
+
+/// After opening fence, at eol or eof.
+///
+/// ```markdown
+/// > | :::a[b]{c}
+///               ^
+///   | d
+///   | :::
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::DirectiveContainerFence);
+            State::Ok
+        }
+        _ => State::Nok,
+    }
+}
+
+/// Start of directive (container) continuation.
+///
+/// Lines continue the directive until a closing fence is found.
+/// A closed directive does not continue.
+///
+/// ```markdown
+///   | :::a
+/// > | b
+///     ^
+/// > | :::
+///     ^
+/// ```
+pub fn cont_start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.tokenize_state.document_container_stack
+        [tokenizer.tokenize_state.document_continued]
+        .size
+        == 0
+    {
+        State::Nok
+    } else {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveContainerContClosed),
+            State::Next(StateName::DirectiveContainerContOpen),
+        );
+        State::Retry(StateName::DirectiveContainerCloseStart)
+    }
+}
+
+/// At a line that does not close the directive.
+///
+/// ```markdown
+///   | :::a
+/// > | b
+///     ^
+///   | :::
+/// ```
+pub fn cont_open(_tokenizer: &mut Tokenizer) -> State {
+    State::Ok
+}
+
+/// After a closing fence.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///        ^
+/// ```
+pub fn cont_closed(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.document_container_stack
+        [tokenizer.tokenize_state.document_continued]
+        .size = 0;
+    State::Ok
+}
+
+/// Start of closing fence.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///     ^
+/// ```
+pub fn close_start(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter(Name::DirectiveContainerFence);
+
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveContainerCloseBefore),
+            State::Nok,
+        );
+        State::Retry(space_or_tab_min_max(
+            tokenizer,
+            0,
+            if tokenizer.parse_state.options.constructs.code_indented {
+                tokenizer.parse_state.options.tab_size - 1
+            } else {
+                usize::MAX
+            },
+        ))
+    } else {
+        State::Retry(StateName::DirectiveContainerCloseBefore)
+    }
+}
+
+/// In closing fence, after optional whitespace, at `:`.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///     ^
+/// ```
+pub fn close_before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::DirectiveContainerSequence);
+        State::Retry(StateName::DirectiveContainerCloseSequence)
+    } else {
+        State::Nok
+    }
+}
+
+/// In closing sequence.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///      ^
+/// ```
+pub fn close_sequence(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.tokenize_state.size += 1;
+        tokenizer.consume();
+        State::Next(StateName::DirectiveContainerCloseSequence)
+    } else if tokenizer.tokenize_state.size
+        >= tokenizer.tokenize_state.document_container_stack
+            [tokenizer.tokenize_state.document_continued]
+            .size
+    {
+        tokenizer.tokenize_state.size = 0;
+        tokenizer.exit(Name::DirectiveContainerSequence);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(
+                State::Next(StateName::DirectiveContainerCloseAfter),
+                State::Nok,
+            );
+            State::Retry(space_or_tab(tokenizer))
+        } else {
+            State::Retry(StateName::DirectiveContainerCloseAfter)
+        }
+    } else {
+        tokenizer.tokenize_state.size = 0;
+        State::Nok
+    }
+}
+
+/// After closing fence, at eol or eof.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///        ^
+/// ```
+pub fn close_after(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::DirectiveContainerFence);
+            State::Ok
+        }
+        _ => State::Nok,
+    }
+}
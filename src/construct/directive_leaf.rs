@@ -0,0 +1,187 @@
+//! Directive (leaf) occurs in the [flow][] content type.
+//!
+//! ## Grammar
+//!
+//! Directive (leaf) forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! directive_leaf ::= *space_or_tab '::' directive_name [directive_label] [directive_attributes] *space_or_tab
+//!
+//! ; See the `partial_directive_name`, `partial_directive_label`, and
+//! ; `partial_directive_attributes` constructs for the BNF of those parts.
+//! ```
+//!
+//! As this construct occurs in flow, like all flow constructs, it must be
+//! followed by an eol (line ending) or eof (end of file).
+//!
+//! Directives are a generic extension point: the name decides what a
+//! directive means, and a downstream consumer decides how to handle it.
+//! Leaf directives are useful for things without content, such as embeds.
+//!
+//! ## HTML
+//!
+//! Directives have no meaning in HTML.
+//! When compiling to HTML, directives (and their label) are dropped.
+//! Turn markdown into [mdast][crate::mdast] to map directives to something
+//! else.
+//!
+//! ## Tokens
+//!
+//! *   [`DirectiveLeaf`][Name::DirectiveLeaf]
+//! *   [`DirectiveLeafSequence`][Name::DirectiveLeafSequence]
+//! *   [`SpaceOrTab`][Name::SpaceOrTab]
+//! *   see [`partial_directive_name`][directive_name],
+//!     [`partial_directive_label`][directive_label], and
+//!     [`partial_directive_attributes`][directive_attributes] for more
+//!
+//! ## References
+//!
+//! *   [`directive-leaf.js` in `micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive/blob/main/dev/lib/directive-leaf.js)
+//! *   [Generic directives/plugins syntax](https://talk.commonmark.org/t/generic-directives-plugins-syntax/444)
+//!
+//! [flow]: crate::construct::flow
+//! [directive_name]: crate::construct::partial_directive_name
+//! [directive_label]: crate::construct::partial_directive_label
+//! [directive_attributes]: crate::construct::partial_directive_attributes
+
+use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::constant::DIRECTIVE_LEAF_SEQUENCE_SIZE;
+
+/// Start of directive (leaf).
+///
+/// ```markdown
+/// > | ::a[b]{c}
+///     ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.directive_leaf {
+        tokenizer.enter(Name::DirectiveLeaf);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(State::Next(StateName::DirectiveLeafBefore), State::Nok);
+            State::Retry(space_or_tab_min_max(
+                tokenizer,
+                0,
+                if tokenizer.parse_state.options.constructs.code_indented {
+                    tokenizer.parse_state.options.tab_size - 1
+                } else {
+                    usize::MAX
+                },
+            ))
+        } else {
+            State::Retry(StateName::DirectiveLeafBefore)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// After optional whitespace, at `:`.
+///
+/// ```markdown
+/// > | ::a[b]{c}
+///     ^
+/// ```
+pub fn before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::DirectiveLeafSequence);
+        State::Retry(StateName::DirectiveLeafSequence)
+    } else {
+        State::Nok
+    }
+}
+
+/// In sequence.
+///
+/// ```markdown
+/// > | ::a[b]{c}
+///      ^
+/// ```
+pub fn sequence(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.tokenize_state.size += 1;
+        tokenizer.consume();
+        State::Next(StateName::DirectiveLeafSequence)
+    } else if tokenizer.tokenize_state.size == DIRECTIVE_LEAF_SEQUENCE_SIZE {
+        tokenizer.tokenize_state.size = 0;
+        tokenizer.exit(Name::DirectiveLeafSequence);
+        tokenizer.attempt(State::Next(StateName::DirectiveLeafNameAfter), State::Nok);
+        State::Retry(StateName::DirectiveNameStart)
+    } else {
+        tokenizer.tokenize_state.size = 0;
+        State::Nok
+    }
+}
+
+/// After name, at optional label.
+///
+/// ```markdown
+/// > | ::a[b]{c}
+///        ^
+/// ```
+pub fn name_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'[') {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveLeafLabelAfter),
+            State::Next(StateName::DirectiveLeafLabelAfter),
+        );
+        State::Retry(StateName::DirectiveLabelStart)
+    } else {
+        State::Retry(StateName::DirectiveLeafLabelAfter)
+    }
+}
+
+/// After optional label, at optional attributes.
+///
+/// ```markdown
+/// > | ::a[b]{c}
+///           ^
+/// ```
+pub fn label_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'{') {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveLeafAttributesAfter),
+            State::Next(StateName::DirectiveLeafAttributesAfter),
+        );
+        State::Retry(StateName::DirectiveAttributesStart)
+    } else {
+        State::Retry(StateName::DirectiveLeafAttributesAfter)
+    }
+}
+
+/// After optional attributes, at optional whitespace.
+///
+/// ```markdown
+/// > | ::a[b]{c}
+///              ^
+/// ```
+pub fn attributes_after(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(State::Next(StateName::DirectiveLeafAfter), State::Nok);
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Retry(StateName::DirectiveLeafAfter)
+    }
+}
+
+/// After directive (leaf), at eol or eof.
+///
+/// ```markdown
+/// > | ::a[b]{c}
+///              ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::DirectiveLeaf);
+            // Feel free to interrupt.
+            tokenizer.interrupt = false;
+            State::Ok
+        }
+        _ => State::Nok,
+    }
+}
@@ -0,0 +1,115 @@
+//! Directive (text) occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Directive (text) forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: the byte before must not be `:`.
+//! directive_text ::= ':' directive_name [directive_label] [directive_attributes]
+//!
+//! ; See the `partial_directive_name`, `partial_directive_label`, and
+//! ; `partial_directive_attributes` constructs for the BNF of those parts.
+//! ```
+//!
+//! Directives are a generic extension point: the name decides what a
+//! directive means, and a downstream consumer decides how to handle it.
+//!
+//! ## HTML
+//!
+//! Directives have no meaning in HTML.
+//! When compiling to HTML, directives (and their label) are dropped.
+//! Turn markdown into [mdast][crate::mdast] to map directives to something
+//! else.
+//!
+//! ## Tokens
+//!
+//! *   [`DirectiveText`][Name::DirectiveText]
+//! *   [`DirectiveTextMarker`][Name::DirectiveTextMarker]
+//! *   see [`partial_directive_name`][directive_name],
+//!     [`partial_directive_label`][directive_label], and
+//!     [`partial_directive_attributes`][directive_attributes] for more
+//!
+//! ## References
+//!
+//! *   [`directive-text.js` in `micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive/blob/main/dev/lib/directive-text.js)
+//! *   [Generic directives/plugins syntax](https://talk.commonmark.org/t/generic-directives-plugins-syntax/444)
+//!
+//! [text]: crate::construct::text
+//! [directive_name]: crate::construct::partial_directive_name
+//! [directive_label]: crate::construct::partial_directive_label
+//! [directive_attributes]: crate::construct::partial_directive_attributes
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+
+/// Start of directive (text).
+///
+/// ```markdown
+/// > | a :b[c]{d} e
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.directive_text
+        && tokenizer.current == Some(b':')
+        && tokenizer.previous != Some(b':')
+    {
+        tokenizer.enter(Name::DirectiveText);
+        tokenizer.enter(Name::DirectiveTextMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::DirectiveTextMarker);
+        tokenizer.attempt(State::Next(StateName::DirectiveTextNameAfter), State::Nok);
+        State::Next(StateName::DirectiveNameStart)
+    } else {
+        State::Nok
+    }
+}
+
+/// After name, at optional label.
+///
+/// ```markdown
+/// > | a :b[c]{d} e
+///         ^
+/// ```
+pub fn name_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'[') {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveTextLabelAfter),
+            State::Next(StateName::DirectiveTextLabelAfter),
+        );
+        State::Retry(StateName::DirectiveLabelStart)
+    } else {
+        State::Retry(StateName::DirectiveTextLabelAfter)
+    }
+}
+
+/// After optional label, at optional attributes.
+///
+/// ```markdown
+/// > | a :b[c]{d} e
+///            ^
+/// ```
+pub fn label_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'{') {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveTextAfter),
+            State::Next(StateName::DirectiveTextAfter),
+        );
+        State::Retry(StateName::DirectiveAttributesStart)
+    } else {
+        State::Retry(StateName::DirectiveTextAfter)
+    }
+}
+
+/// After directive (text).
+///
+/// ```markdown
+/// > | a :b[c]{d} e
+///               ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.exit(Name::DirectiveText);
+    State::Ok
+}
@@ -1,13 +1,15 @@
 //! The document content type.
 //!
 //! **Document** represents the containers, such as block quotes, list items,
-//! or GFM footnotes, which structure the document and contain other sections.
+//! GFM footnotes, or directives, which structure the document and contain
+//! other sections.
 //!
 //! The constructs found in flow are:
 //!
 //! *   [Block quote][crate::construct::block_quote]
 //! *   [List item][crate::construct::list_item]
 //! *   [GFM: Footnote definition][crate::construct::gfm_footnote_definition]
+//! *   [Directive (container)][crate::construct::directive_container]
 
 use crate::event::{Content, Event, Kind, Link, Name};
 use crate::message;
@@ -101,6 +103,7 @@ pub fn container_existing_before(tokenizer: &mut Tokenizer) -> State {
 
         let name = match container.kind {
             Container::BlockQuote => StateName::BlockQuoteContStart,
+            Container::DirectiveContainer => StateName::DirectiveContainerContStart,
             Container::GfmFootnoteDefinition => StateName::GfmFootnoteDefinitionContStart,
             Container::ListItem => StateName::ListItemContStart,
         };
@@ -155,6 +158,13 @@ pub fn container_new_before(tokenizer: &mut Tokenizer) -> State {
         }
     }
 
+    // Don’t open containers deeper than allowed.
+    if let Some(max) = tokenizer.parse_state.options.max_nesting_depth {
+        if tokenizer.tokenize_state.document_continued >= max {
+            return State::Retry(StateName::DocumentContainersAfter);
+        }
+    }
+
     // Check for a new container.
     // Block quote?
     // Add a new container at the end of the stack.
@@ -234,7 +244,33 @@ pub fn container_new_before_not_list(tokenizer: &mut Tokenizer) -> State {
 ///     ^
 /// ```
 pub fn container_new_before_not_footnote_definition(tokenizer: &mut Tokenizer) -> State {
-    // It wasn’t a new block quote, list item, or footnote definition.
+    // Directive (container)?
+    // We replace the empty footnote definition container for this new
+    // directive one.
+    tokenizer.tokenize_state.document_container_stack
+        [tokenizer.tokenize_state.document_continued] = ContainerState {
+        kind: Container::DirectiveContainer,
+        blank_initial: false,
+        size: 0,
+    };
+
+    tokenizer.attempt(
+        State::Next(StateName::DocumentContainerNewAfter),
+        State::Next(StateName::DocumentContainerNewBeforeNotDirectiveContainer),
+    );
+    State::Retry(StateName::DirectiveContainerStart)
+}
+
+/// At new container, but not a block quote, list item, footnote definition,
+/// or directive (container).
+//
+/// ```markdown
+/// > | a
+///     ^
+/// ```
+pub fn container_new_before_not_directive_container(tokenizer: &mut Tokenizer) -> State {
+    // It wasn’t a new block quote, list item, footnote definition, or
+    // directive.
     // Swap the new container (in the middle) with the existing one (at the end).
     // Drop what was in the middle.
     tokenizer
@@ -254,7 +290,7 @@ pub fn container_new_before_not_footnote_definition(tokenizer: &mut Tokenizer) -
 ///       ^
 /// ```
 pub fn container_new_after(tokenizer: &mut Tokenizer) -> State {
-    // It was a new block quote, list item, or footnote definition.
+    // It was a new block quote, list item, footnote definition, or directive.
     // Swap the new container (in the middle) with the existing one (at the end).
     // Take the new container.
     let container = tokenizer
@@ -406,6 +442,16 @@ pub fn flow_end(tokenizer: &mut Tokenizer) -> State {
     //     ^
     //   | ```
     // ```
+    //
+    // Do not accept:
+    //
+    // ```markdown
+    //   | * a
+    //   |   -
+    // > | b
+    //     ^
+    //   | ```
+    // ```
     let mut document_lazy_continuation_current = false;
     let mut stack_index = child.stack.len();
 
@@ -424,7 +470,7 @@ pub fn flow_end(tokenizer: &mut Tokenizer) -> State {
     if !document_lazy_continuation_current && !child.events.is_empty() {
         let before = skip::opt_back(&child.events, child.events.len() - 1, &[Name::LineEnding]);
         let name = &child.events[before].name;
-        if name == &Name::Content || name == &Name::HeadingSetextUnderline {
+        if name == &Name::Content {
             document_lazy_continuation_current = true;
         }
     }
@@ -498,6 +544,7 @@ fn exit_containers(tokenizer: &mut Tokenizer, phase: &Phase) -> Result<(), messa
         while let Some(container) = stack_close.pop() {
             let name = match container.kind {
                 Container::BlockQuote => Name::BlockQuote,
+                Container::DirectiveContainer => Name::DirectiveContainer,
                 Container::GfmFootnoteDefinition => Name::GfmFootnoteDefinition,
                 Container::ListItem => Name::ListItem,
             };
@@ -12,6 +12,7 @@
 //!
 //! *   [Blank line][crate::construct::blank_line]
 //! *   [Code (indented)][crate::construct::code_indented]
+//! *   [Directive (leaf)][crate::construct::directive_leaf]
 //! *   [Heading (atx)][crate::construct::heading_atx]
 //! *   [Heading (setext)][crate::construct::heading_setext]
 //! *   [HTML (flow)][crate::construct::html_flow]
@@ -20,6 +21,18 @@
 //! *   [MDX JSX (flow)][crate::construct::mdx_jsx_flow]
 //! *   [Raw (flow)][crate::construct::raw_flow] (code (fenced), math (flow))
 //! *   [Thematic break][crate::construct::thematic_break]
+//!
+//! The order in which these constructs are tried matters, as the first one
+//! that matches wins.
+//! [`start`][] first tries a construct based on the first byte of the line
+//! (such as `#` for heading (atx)), and otherwise constructs are tried in
+//! the order of the `before_*` states below: blank line, code (indented),
+//! raw (flow), HTML (flow), MDX JSX (flow), heading (atx), heading
+//! (setext), thematic break, directive (leaf), MDX expression (flow), GFM
+//! table, and finally content.
+//! This order is fixed: states are values of [`StateName`][], not
+//! functions, so new constructs are added by adding a state name and a
+//! `before_*` state here.
 
 use crate::event::Name;
 use crate::state::{Name as StateName, State};
@@ -60,6 +73,14 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
             );
             State::Retry(StateName::ThematicBreakStart)
         }
+        // Note: a GFM table can also start with `:`.
+        Some(b':') => {
+            tokenizer.attempt(
+                State::Next(StateName::FlowAfter),
+                State::Next(StateName::FlowBeforeGfmTable),
+            );
+            State::Retry(StateName::DirectiveLeafStart)
+        }
         Some(b'<') => {
             tokenizer.attempt(
                 State::Next(StateName::FlowAfter),
@@ -196,11 +217,25 @@ pub fn before_heading_setext(tokenizer: &mut Tokenizer) -> State {
 pub fn before_thematic_break(tokenizer: &mut Tokenizer) -> State {
     tokenizer.attempt(
         State::Next(StateName::FlowAfter),
-        State::Next(StateName::FlowBeforeMdxExpression),
+        State::Next(StateName::FlowBeforeDirectiveLeaf),
     );
     State::Retry(StateName::ThematicBreakStart)
 }
 
+/// At directive (leaf).
+///
+/// ```markdown
+/// > | ::a
+///     ^
+/// ```
+pub fn before_directive_leaf(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::FlowAfter),
+        State::Next(StateName::FlowBeforeMdxExpression),
+    );
+    State::Retry(StateName::DirectiveLeafStart)
+}
+
 /// At MDX expression (flow).
 ///
 /// ```markdown
@@ -631,6 +631,7 @@ pub fn resolve(tokenizer: &mut Tokenizer) {
                 let slice = Slice::from_position(
                     tokenizer.parse_state.bytes,
                     &Position::from_exit_event(&tokenizer.events, index),
+                    tokenizer.parse_state.options.tab_size,
                 );
                 let bytes = slice.bytes;
                 let mut byte_index = 0;
@@ -675,8 +676,12 @@ pub fn resolve(tokenizer: &mut Tokenizer) {
                                     point: point.clone(),
                                     link: None,
                                 });
-                                point = point
-                                    .shift_to(tokenizer.parse_state.bytes, start_index + range.0);
+                                point = point.shift_to(
+                                    tokenizer.parse_state.bytes,
+                                    start_index + range.0,
+                                    tokenizer.parse_state.options.tab_size,
+                                    tokenizer.parse_state.options.column_unit,
+                                );
                                 replace.push(Event {
                                     kind: Kind::Exit,
                                     name: Name::Data,
@@ -692,8 +697,12 @@ pub fn resolve(tokenizer: &mut Tokenizer) {
                                 point: point.clone(),
                                 link: None,
                             });
-                            point =
-                                point.shift_to(tokenizer.parse_state.bytes, start_index + range.1);
+                            point = point.shift_to(
+                                tokenizer.parse_state.bytes,
+                                start_index + range.1,
+                                tokenizer.parse_state.options.tab_size,
+                                tokenizer.parse_state.options.column_unit,
+                            );
                             replace.push(Event {
                                 kind: Kind::Exit,
                                 name: range.2.clone(),
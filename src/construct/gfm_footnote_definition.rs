@@ -170,7 +170,7 @@ use crate::event::{Content, Link, Name};
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
 use crate::util::{
-    constant::{LINK_REFERENCE_SIZE_MAX, TAB_SIZE},
+    constant::LINK_REFERENCE_SIZE_MAX,
     normalize_identifier::normalize_identifier,
     skip,
     slice::{Position, Slice},
@@ -200,7 +200,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 tokenizer,
                 1,
                 if tokenizer.parse_state.options.constructs.code_indented {
-                    TAB_SIZE - 1
+                    tokenizer.parse_state.options.tab_size - 1
                 } else {
                     usize::MAX
                 },
@@ -340,6 +340,7 @@ pub fn label_after(tokenizer: &mut Tokenizer) -> State {
                 Slice::from_position(
                     tokenizer.parse_state.bytes,
                     &Position::from_exit_event(&tokenizer.events, end),
+                    tokenizer.parse_state.options.tab_size,
                 )
                 .as_str(),
             );
@@ -400,7 +401,11 @@ pub fn cont_start(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn cont_blank(tokenizer: &mut Tokenizer) -> State {
     if matches!(tokenizer.current, Some(b'\t' | b' ')) {
-        State::Retry(space_or_tab_min_max(tokenizer, 0, TAB_SIZE))
+        State::Retry(space_or_tab_min_max(
+            tokenizer,
+            0,
+            tokenizer.parse_state.options.tab_size,
+        ))
     } else {
         State::Ok
     }
@@ -415,8 +420,9 @@ pub fn cont_blank(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn cont_filled(tokenizer: &mut Tokenizer) -> State {
     if matches!(tokenizer.current, Some(b'\t' | b' ')) {
-        // Consume exactly `TAB_SIZE`.
-        State::Retry(space_or_tab_min_max(tokenizer, TAB_SIZE, TAB_SIZE))
+        // Consume exactly `tab_size`.
+        let size = tokenizer.parse_state.options.tab_size;
+        State::Retry(space_or_tab_min_max(tokenizer, size, size))
     } else {
         State::Nok
     }
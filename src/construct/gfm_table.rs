@@ -132,6 +132,10 @@
 //! > 👉 **Note**: no other character can be escaped like this.
 //! > Escaping pipes in code does not work when not inside a table, either.
 //!
+//! Pipes in code that are not escaped split cells, like on GitHub.
+//! Pass [`gfm_table_pipes_in_code`][gfm_table_pipes_in_code] to instead
+//! keep them in code: `` | `a|b` | `` is then one cell.
+//!
 //! ## HTML
 //!
 //! GFM tables relate to several HTML elements: `<table>`, `<tbody>`, `<td>`,
@@ -218,6 +222,7 @@
 //! [text]: crate::construct::text
 //! [attention]: crate::construct::attention
 //! [raw_text]: crate::construct::raw_text
+//! [gfm_table_pipes_in_code]: crate::ParseOptions::gfm_table_pipes_in_code
 //! [html_table]: https://html.spec.whatwg.org/multipage/tables.html#the-table-element
 //! [html_tbody]: https://html.spec.whatwg.org/multipage/tables.html#the-tbody-element
 //! [html_td]: https://html.spec.whatwg.org/multipage/tables.html#the-td-element
@@ -231,7 +236,7 @@ use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
-use crate::util::{constant::TAB_SIZE, skip::opt_back as skip_opt_back};
+use crate::util::skip::opt_back as skip_opt_back;
 use alloc::vec;
 
 /// Start of a GFM table.
@@ -287,7 +292,7 @@ pub fn head_row_before(tokenizer: &mut Tokenizer) -> State {
             tokenizer,
             0,
             if tokenizer.parse_state.options.constructs.code_indented {
-                TAB_SIZE - 1
+                tokenizer.parse_state.options.tab_size - 1
             } else {
                 usize::MAX
             },
@@ -399,6 +404,11 @@ pub fn head_row_data(tokenizer: &mut Tokenizer) -> State {
             tokenizer.exit(Name::Data);
             State::Retry(StateName::GfmTableHeadRowBreak)
         }
+        Some(b'`') if tokenizer.parse_state.options.gfm_table_pipes_in_code => {
+            tokenizer.tokenize_state.end =
+                code_end(tokenizer.parse_state.bytes, tokenizer.point.index);
+            State::Retry(StateName::GfmTableHeadRowCode)
+        }
         _ => {
             let name = if tokenizer.current == Some(b'\\') {
                 StateName::GfmTableHeadRowEscape
@@ -421,7 +431,7 @@ pub fn head_row_data(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn head_row_escape(tokenizer: &mut Tokenizer) -> State {
     match tokenizer.current {
-        Some(b'\\' | b'|') => {
+        Some(b'\\' | b'`' | b'|') => {
             tokenizer.consume();
             State::Next(StateName::GfmTableHeadRowData)
         }
@@ -429,6 +439,24 @@ pub fn head_row_escape(tokenizer: &mut Tokenizer) -> State {
     }
 }
 
+/// In table head row code, with `gfm_table_pipes_in_code`.
+///
+/// ```markdown
+/// > | | `a|b` |
+///       ^^^^^
+///   | | ----- |
+///   | | c     |
+/// ```
+pub fn head_row_code(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.point.index < tokenizer.tokenize_state.end && tokenizer.current.is_some() {
+        tokenizer.consume();
+        State::Next(StateName::GfmTableHeadRowCode)
+    } else {
+        tokenizer.tokenize_state.end = 0;
+        State::Retry(StateName::GfmTableHeadRowData)
+    }
+}
+
 /// Before delimiter row.
 ///
 /// ```markdown
@@ -460,7 +488,7 @@ pub fn head_delimiter_start(tokenizer: &mut Tokenizer) -> State {
                     tokenizer,
                     0,
                     if tokenizer.parse_state.options.constructs.code_indented {
-                        TAB_SIZE - 1
+                        tokenizer.parse_state.options.tab_size - 1
                     } else {
                         usize::MAX
                     },
@@ -737,6 +765,11 @@ pub fn body_row_data(tokenizer: &mut Tokenizer) -> State {
             tokenizer.exit(Name::Data);
             State::Retry(StateName::GfmTableBodyRowBreak)
         }
+        Some(b'`') if tokenizer.parse_state.options.gfm_table_pipes_in_code => {
+            tokenizer.tokenize_state.end =
+                code_end(tokenizer.parse_state.bytes, tokenizer.point.index);
+            State::Retry(StateName::GfmTableBodyRowCode)
+        }
         _ => {
             let name = if tokenizer.current == Some(b'\\') {
                 StateName::GfmTableBodyRowEscape
@@ -759,7 +792,7 @@ pub fn body_row_data(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn body_row_escape(tokenizer: &mut Tokenizer) -> State {
     match tokenizer.current {
-        Some(b'\\' | b'|') => {
+        Some(b'\\' | b'`' | b'|') => {
             tokenizer.consume();
             State::Next(StateName::GfmTableBodyRowData)
         }
@@ -767,6 +800,65 @@ pub fn body_row_escape(tokenizer: &mut Tokenizer) -> State {
     }
 }
 
+/// In table body row code, with `gfm_table_pipes_in_code`.
+///
+/// ```markdown
+///   | | a     |
+///   | | ----- |
+/// > | | `b|c` |
+///       ^^^^^
+/// ```
+pub fn body_row_code(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.point.index < tokenizer.tokenize_state.end && tokenizer.current.is_some() {
+        tokenizer.consume();
+        State::Next(StateName::GfmTableBodyRowCode)
+    } else {
+        tokenizer.tokenize_state.end = 0;
+        State::Retry(StateName::GfmTableBodyRowData)
+    }
+}
+
+/// Find where code that starts with the sequence of grave accents at `index`
+/// ends, on the same line.
+///
+/// If there is no closing sequence of the same size, the opening sequence
+/// is not code, and its end is returned instead.
+///
+/// ```markdown
+/// > | | `a|b` |
+///       ^-- from
+///            ^-- to
+/// ```
+fn code_end(bytes: &[u8], index: usize) -> usize {
+    let mut index = index;
+    let start = index;
+
+    while index < bytes.len() && bytes[index] == b'`' {
+        index += 1;
+    }
+
+    let size = index - start;
+    let sequence_end = index;
+
+    while index < bytes.len() && !matches!(bytes[index], b'\n' | b'\r') {
+        if bytes[index] == b'`' {
+            let close_start = index;
+
+            while index < bytes.len() && bytes[index] == b'`' {
+                index += 1;
+            }
+
+            if index - close_start == size {
+                return index;
+            }
+        } else {
+            index += 1;
+        }
+    }
+
+    sequence_end
+}
+
 /// Resolve GFM table.
 pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
     let mut index = 0;
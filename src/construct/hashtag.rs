@@ -0,0 +1,146 @@
+//! Hashtag occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Hashtag forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: the character before must not be `hashtag_before`.
+//! ; Restriction: at least one `tag_char` must not be `ascii_digit`.
+//! ; Restriction: must not be followed by `'#'`.
+//! hashtag ::= '#' tag_char *(*('-' | '_') tag_char)
+//!
+//! hashtag_before ::= '#' | '&' | ascii_alphanumeric | unicode_other
+//! tag_char ::= ascii_alphanumeric | unicode_other
+//! unicode_other ::= char - ascii - unicode_whitespace - unicode_punctuation
+//! ```
+//!
+//! Dashes and underscores can occur in a tag, but not at its end, so that
+//! trailing punctuation, such as in `#tag.` or `_#tag_`, is not included.
+//! Tags that are only digits, such as `#1`, are not hashtags, as they are
+//! typically references to issues.
+//!
+//! Like [GFM autolink literals][gfm_autolink_literal], hashtags are not
+//! links when they occur in links: they are then compiled as text.
+//!
+//! ## HTML
+//!
+//! Hashtags relate to the `<a>` element in HTML, when
+//! [`hashtag_url`][hashtag_url] is passed.
+//! The template is used to generate the `href` attribute.
+//! See [*§ 4.5.1 The `a` element*][html_a] in the HTML spec for more info.
+//! Without a template, hashtags are compiled as text.
+//!
+//! ## Tokens
+//!
+//! *   [`Hashtag`][Name::Hashtag]
+//!
+//! ## References
+//!
+//! *   [*Hashtags* on Mastodon](https://docs.joinmastodon.org/user/posting/#hashtags)
+//!
+//! [text]: crate::construct::text
+//! [gfm_autolink_literal]: crate::construct::gfm_autolink_literal
+//! [hashtag_url]: crate::CompileOptions::hashtag_url
+//! [html_a]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-a-element
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::char::{after_index, before_index, classify, classify_opt, Kind as CharacterKind};
+
+/// Start of hashtag.
+///
+/// ```markdown
+/// > | a #b c
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.hashtag
+        && tokenizer.current == Some(b'#')
+        && !matches!(tokenizer.previous, Some(b'#' | b'&'))
+        && classify_opt(before_index(
+            tokenizer.parse_state.bytes,
+            tokenizer.point.index,
+        )) != CharacterKind::Other
+    {
+        if let Some(end) = tag_end(tokenizer.parse_state.bytes, tokenizer.point.index + 1) {
+            tokenizer.tokenize_state.end = end;
+            tokenizer.enter(Name::Hashtag);
+            tokenizer.consume();
+            return State::Next(StateName::HashtagInside);
+        }
+    }
+
+    State::Nok
+}
+
+/// In tag.
+///
+/// ```markdown
+/// > | a #b c
+///        ^
+/// ```
+pub fn inside(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.point.index < tokenizer.tokenize_state.end {
+        // The tag was found in the bytes, but the text might end before it
+        // does.
+        if tokenizer.current.is_none() {
+            tokenizer.tokenize_state.end = 0;
+            State::Nok
+        } else {
+            tokenizer.consume();
+            State::Next(StateName::HashtagInside)
+        }
+    } else {
+        tokenizer.tokenize_state.end = 0;
+        tokenizer.exit(Name::Hashtag);
+        State::Ok
+    }
+}
+
+/// Move past a tag.
+///
+/// ```markdown
+/// > | a #b-c. d
+///        ^-- from
+///           ^-- to
+/// ```
+fn tag_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut index = start;
+    let mut end = None;
+    let mut digits_only = true;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'-' | b'_' if end.is_some() => index += 1,
+            b'0'..=b'9' => {
+                index += 1;
+                end = Some(index);
+            }
+            b'A'..=b'Z' | b'a'..=b'z' => {
+                index += 1;
+                digits_only = false;
+                end = Some(index);
+            }
+            // Another hashtag directly after this one: neither is one.
+            b'#' => return None,
+            0x80..=0xFF => match after_index(bytes, index) {
+                Some(char) if classify(char) == CharacterKind::Other => {
+                    index += char.len_utf8();
+                    digits_only = false;
+                    end = Some(index);
+                }
+                _ => break,
+            },
+            _ => break,
+        }
+    }
+
+    if digits_only {
+        None
+    } else {
+        end
+    }
+}
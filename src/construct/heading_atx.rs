@@ -33,6 +33,12 @@
 //! > See [*§ atx, the true structured text format* on `aaronsw.com`][atx] for
 //! > more info.
 //!
+//! When [`attributes`][attributes] are turned on, an attribute list (such as
+//! `{#a .b c=d}`) after whitespace at the end of the text is not part of the
+//! text.
+//! Instead, it is used for the attributes of the heading in HTML.
+//! It is not in mdast, as headings there have no attributes.
+//!
 //! ## HTML
 //!
 //! Headings in markdown relate to the `<h1>` through `<h6>` elements in HTML.
@@ -46,6 +52,7 @@
 //! ## Tokens
 //!
 //! *   [`HeadingAtx`][Name::HeadingAtx]
+//! *   [`HeadingAtxAttributes`][Name::HeadingAtxAttributes]
 //! *   [`HeadingAtxSequence`][Name::HeadingAtxSequence]
 //! *   [`HeadingAtxText`][Name::HeadingAtxText]
 //! *   [`SpaceOrTab`][Name::SpaceOrTab]
@@ -56,6 +63,7 @@
 //! *   [*§ 4.2 ATX headings* in `CommonMark`](https://spec.commonmark.org/0.31/#atx-headings)
 //!
 //! [flow]: crate::construct::flow
+//! [attributes]: crate::Constructs::attributes
 //! [heading_setext]: crate::construct::heading_setext
 //! [hard_break_escape]: crate::construct::hard_break_escape
 //! [html]: https://html.spec.whatwg.org/multipage/sections.html#the-h1,-h2,-h3,-h4,-h5,-and-h6-elements
@@ -68,8 +76,11 @@ use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
-use crate::util::constant::{HEADING_ATX_OPENING_FENCE_SIZE_MAX, TAB_SIZE};
+use crate::util::{
+    attributes::parse as parse_attributes, constant::HEADING_ATX_OPENING_FENCE_SIZE_MAX,
+};
 use alloc::vec;
+use core::str;
 
 /// Start of a heading (atx).
 ///
@@ -86,7 +97,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 tokenizer,
                 0,
                 if tokenizer.parse_state.options.constructs.code_indented {
-                    TAB_SIZE - 1
+                    tokenizer.parse_state.options.tab_size - 1
                 } else {
                     usize::MAX
                 },
@@ -230,32 +241,67 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
             } else {
                 if let Some(start) = data_start {
                     // If `start` is some, `end` is too.
-                    let end = data_end.unwrap();
+                    let mut end = data_end.unwrap();
+
+                    if tokenizer.parse_state.options.constructs.attributes {
+                        if let Some(attributes_start) = attributes(tokenizer, start, end) {
+                            tokenizer.map.add(
+                                attributes_start,
+                                end - attributes_start + 1,
+                                vec![
+                                    Event {
+                                        kind: Kind::Enter,
+                                        name: Name::HeadingAtxAttributes,
+                                        point: tokenizer.events[attributes_start].point.clone(),
+                                        link: None,
+                                    },
+                                    Event {
+                                        kind: Kind::Exit,
+                                        name: Name::HeadingAtxAttributes,
+                                        point: tokenizer.events[end].point.clone(),
+                                        link: None,
+                                    },
+                                ],
+                            );
+
+                            // Find the end of the text before the attributes.
+                            end = attributes_start - 1;
+                            while end > start
+                                && !(tokenizer.events[end].kind == Kind::Exit
+                                    && tokenizer.events[end].name == Name::Data)
+                            {
+                                end -= 1;
+                            }
+                        }
+                    }
 
-                    tokenizer.map.add(
-                        start,
-                        0,
-                        vec![Event {
-                            kind: Kind::Enter,
-                            name: Name::HeadingAtxText,
-                            point: tokenizer.events[start].point.clone(),
-                            link: None,
-                        }],
-                    );
+                    // There is text if the attributes did not start at it.
+                    if end > start {
+                        tokenizer.map.add(
+                            start,
+                            0,
+                            vec![Event {
+                                kind: Kind::Enter,
+                                name: Name::HeadingAtxText,
+                                point: tokenizer.events[start].point.clone(),
+                                link: None,
+                            }],
+                        );
 
-                    // Remove everything between the start and the end.
-                    tokenizer.map.add(start + 1, end - start - 1, vec![]);
+                        // Remove everything between the start and the end.
+                        tokenizer.map.add(start + 1, end - start - 1, vec![]);
 
-                    tokenizer.map.add(
-                        end + 1,
-                        0,
-                        vec![Event {
-                            kind: Kind::Exit,
-                            name: Name::HeadingAtxText,
-                            point: tokenizer.events[end].point.clone(),
-                            link: None,
-                        }],
-                    );
+                        tokenizer.map.add(
+                            end + 1,
+                            0,
+                            vec![Event {
+                                kind: Kind::Exit,
+                                name: Name::HeadingAtxText,
+                                point: tokenizer.events[end].point.clone(),
+                                link: None,
+                            }],
+                        );
+                    }
                 }
 
                 heading_inside = false;
@@ -278,3 +324,37 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
     tokenizer.map.consume(&mut tokenizer.events);
     None
 }
+
+/// Find an attribute list at the end of the data in a heading.
+///
+/// Returns the index of the enter of the first data event that is part of
+/// the attribute list, which is at `start` if there is no text before it.
+fn attributes(tokenizer: &Tokenizer, start: usize, end: usize) -> Option<usize> {
+    let bytes = tokenizer.parse_state.bytes;
+    let end_index = tokenizer.events[end].point.index;
+
+    if bytes[end_index - 1] != b'}' {
+        return None;
+    }
+
+    let mut index = start;
+
+    while index < end {
+        let event = &tokenizer.events[index];
+
+        // Data is split at whitespace, so an attribute list can only start at
+        // the start of data.
+        if event.kind == Kind::Enter
+            && event.name == Name::Data
+            && bytes[event.point.index] == b'{'
+            && parse_attributes(str::from_utf8(&bytes[event.point.index..end_index]).unwrap())
+                .is_some()
+        {
+            return Some(index);
+        }
+
+        index += 1;
+    }
+
+    None
+}
@@ -76,7 +76,7 @@ use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
-use crate::util::{constant::TAB_SIZE, skip};
+use crate::util::skip;
 use alloc::vec;
 
 /// At start of heading (setext) underline.
@@ -107,7 +107,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 tokenizer,
                 0,
                 if tokenizer.parse_state.options.constructs.code_indented {
-                    TAB_SIZE - 1
+                    tokenizer.parse_state.options.tab_size - 1
                 } else {
                     usize::MAX
                 },
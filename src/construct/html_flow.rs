@@ -105,7 +105,7 @@ use crate::event::Name;
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
 use crate::util::{
-    constant::{HTML_BLOCK_NAMES, HTML_CDATA_PREFIX, HTML_RAW_NAMES, HTML_RAW_SIZE_MAX, TAB_SIZE},
+    constant::{HTML_BLOCK_NAMES, HTML_CDATA_PREFIX, HTML_RAW_NAMES, HTML_RAW_SIZE_MAX},
     slice::Slice,
 };
 
@@ -142,7 +142,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                     kind: Name::HtmlFlowData,
                     min: 0,
                     max: if tokenizer.parse_state.options.constructs.code_indented {
-                        TAB_SIZE - 1
+                        tokenizer.parse_state.options.tab_size - 1
                     } else {
                         usize::MAX
                     },
@@ -48,6 +48,9 @@
 //! The title is formed, optionally, on either `<a>` or `<img>`.
 //! When matched with a [gfm label start (footnote)][gfm_label_start_footnote],
 //! no reference or resource can follow the label end.
+//! The same goes for a
+//! [label start (inline footnote)][label_start_inline_footnote], which does
+//! not need a definition, but does need some text.
 //!
 //! For info on how to encode characters in URLs, see
 //! [`destination`][destination].
@@ -81,6 +84,8 @@
 //! the HTML, see above).
 //! But it’s not possible to use links (or footnotes, which result in links)
 //! in links.
+//! Links can be used in inline footnotes though, as their content ends up in
+//! the footnote section instead of in a link.
 //! The “deepest” link (or footnote) wins.
 //! To illustrate:
 //!
@@ -130,6 +135,7 @@
 //! *   [`Data`][Name::Data]
 //! *   [`GfmFootnoteCall`][Name::GfmFootnoteCall]
 //! *   [`Image`][Name::Image]
+//! *   [`InlineFootnote`][Name::InlineFootnote]
 //! *   [`Label`][Name::Label]
 //! *   [`LabelEnd`][Name::LabelEnd]
 //! *   [`LabelMarker`][Name::LabelMarker]
@@ -170,6 +176,7 @@
 //! [label]: crate::construct::partial_label
 //! [label_start_image]: crate::construct::label_start_image
 //! [label_start_link]: crate::construct::label_start_link
+//! [label_start_inline_footnote]: crate::construct::label_start_inline_footnote
 //! [gfm_label_start_footnote]: crate::construct::gfm_label_start_footnote
 //! [definition]: crate::construct::definition
 //! [autolink]: crate::construct::autolink
@@ -181,17 +188,19 @@
 
 use crate::construct::partial_space_or_tab_eol::space_or_tab_eol;
 use crate::event::{Event, Kind, Name};
+use crate::message;
 use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::{Label, LabelKind, LabelStart, Tokenizer};
+use crate::unist;
 use crate::util::{
     constant::RESOURCE_DESTINATION_BALANCE_MAX,
     normalize_identifier::normalize_identifier,
     skip,
     slice::{Position, Slice},
 };
-use alloc::{string::String, vec};
+use alloc::{boxed::Box, format, string::String, vec};
 
 /// Start of label end.
 ///
@@ -263,6 +272,15 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
         Slice::from_indices(tokenizer.parse_state.bytes, indices.0, indices.1).as_str(),
     );
 
+    // Inline footnotes do not need a definition, but do need content.
+    if start.kind == LabelKind::InlineFootnote {
+        return State::Retry(if indices.0 == indices.1 {
+            StateName::LabelEndNok
+        } else {
+            StateName::LabelEndOk
+        });
+    }
+
     // See if this matches a footnote definition.
     if start.kind == LabelKind::GfmFootnote {
         if tokenizer.parse_state.gfm_footnote_definitions.contains(&id) {
@@ -279,6 +297,39 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
 
     let defined = tokenizer.parse_state.definitions.contains(&id);
 
+    // Warn about shortcut (`[a]`) and collapsed (`[a][]`) references that
+    // do not match a definition.
+    // Resources (`[a](b)`) do not need one, the label of a full reference
+    // (`[a][b]`) is checked when it is not a reference itself, and empty
+    // labels (`[]`) can never be references.
+    if !defined
+        && !id.is_empty()
+        && tokenizer.parse_state.diagnostics.is_some()
+        && match tokenizer.current {
+            Some(b'(') => false,
+            Some(b'[') => tokenizer.parse_state.bytes.get(tokenizer.point.index + 1) == Some(&b']'),
+            _ => true,
+        }
+    {
+        let start = &tokenizer.tokenize_state.label_starts[start_index];
+        let mut label = String::new();
+        if start.kind == LabelKind::GfmUndefinedFootnote {
+            label.push('^');
+        }
+        label.push_str(
+            Slice::from_indices(tokenizer.parse_state.bytes, indices.0, indices.1).as_str(),
+        );
+        tokenizer.tokenize_state.diagnostics.push(message::Message {
+            place: Some(Box::new(message::Place::Position(unist::Position {
+                start: tokenizer.events[start.start.0].point.to_unist(),
+                end: tokenizer.point.to_unist(),
+            }))),
+            reason: format!("Unexpected reference to undefined definition `{}`", label),
+            rule_id: Box::new("undefined-reference".into()),
+            source: Box::new("markdown-rs".into()),
+        });
+    }
+
     match tokenizer.current {
         // Resource (`[asd](fgh)`)?
         Some(b'(') => {
@@ -351,11 +402,15 @@ pub fn ok(tokenizer: &mut Tokenizer) -> State {
     // longer viable for use (as they would otherwise contain a link).
     // These link starts are still looking for balanced closing brackets, so
     // we can’t remove them, but we can mark them.
+    // Inline footnotes can contain links, but not other inline footnotes.
     if label_start.kind != LabelKind::Image {
+        let inline_footnote = label_start.kind == LabelKind::InlineFootnote;
         let mut index = 0;
         while index < tokenizer.tokenize_state.label_starts.len() {
             let label_start = &mut tokenizer.tokenize_state.label_starts[index];
-            if label_start.kind != LabelKind::Image {
+            if label_start.kind != LabelKind::Image
+                && (inline_footnote || label_start.kind != LabelKind::InlineFootnote)
+            {
                 label_start.inactive = true;
             }
             index += 1;
@@ -605,6 +660,7 @@ pub fn reference_full_after(tokenizer: &mut Tokenizer) -> State {
                         &[Name::ReferenceString],
                     ),
                 ),
+                tokenizer.parse_state.options.tab_size,
             )
             .as_str(),
         ))
@@ -693,6 +749,8 @@ fn inject_labels(tokenizer: &mut Tokenizer, labels: &[Label]) {
         let label = &labels[index];
         let group_name = if label.kind == LabelKind::GfmFootnote {
             Name::GfmFootnoteCall
+        } else if label.kind == LabelKind::InlineFootnote {
+            Name::InlineFootnote
         } else if label.kind == LabelKind::Image {
             Name::Image
         } else {
@@ -0,0 +1,93 @@
+//! Label start (inline footnote) occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Label start (inline footnote) forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! label_start_inline_footnote ::= '^' '['
+//! ```
+//!
+//! ## HTML
+//!
+//! Label start (inline footnote) does not, on its own, relate to anything in
+//! HTML.
+//! When matched with a [label end][label_end], they together relate to `<sup>`
+//! and `<a>` elements in HTML, and the text between the brackets is used as
+//! the content of a footnote definition.
+//! See [*§ 4.5.19 The `sub` and `sup` elements*][html_sup] and
+//! [*§ 4.5.1 The `a` element*][html_a] in the HTML spec for more info.
+//! Without an end, the characters (`^[`) are output.
+//!
+//! Inline footnotes are numbered together with GFM footnote calls, in the
+//! order in which they occur, and are included in the same footnote section.
+//!
+//! ## Tokens
+//!
+//! *   [`LabelInlineFootnote`][Name::LabelInlineFootnote]
+//! *   [`LabelInlineFootnoteMarker`][Name::LabelInlineFootnoteMarker]
+//! *   [`LabelMarker`][Name::LabelMarker]
+//!
+//! ## References
+//!
+//! *   [*Footnotes* in Pandoc](https://pandoc.org/MANUAL.html#footnotes)
+//!
+//! [text]: crate::construct::text
+//! [label_end]: crate::construct::label_end
+//! [html_a]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-a-element
+//! [html_sup]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-sub-and-sup-elements
+
+use crate::event::Name;
+use crate::resolve::Name as ResolveName;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::{LabelKind, LabelStart, Tokenizer};
+
+/// Start of label (inline footnote) start.
+///
+/// ```markdown
+/// > | a ^[b] c
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer
+        .parse_state
+        .options
+        .constructs
+        .label_start_inline_footnote
+        && tokenizer.current == Some(b'^')
+    {
+        tokenizer.enter(Name::LabelInlineFootnote);
+        tokenizer.enter(Name::LabelInlineFootnoteMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::LabelInlineFootnoteMarker);
+        State::Next(StateName::LabelStartInlineFootnoteOpen)
+    } else {
+        State::Nok
+    }
+}
+
+/// After `^`, at `[`.
+///
+/// ```markdown
+/// > | a ^[b] c
+///        ^
+/// ```
+pub fn open(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'[') => {
+            tokenizer.enter(Name::LabelMarker);
+            tokenizer.consume();
+            tokenizer.exit(Name::LabelMarker);
+            tokenizer.exit(Name::LabelInlineFootnote);
+            tokenizer.tokenize_state.label_starts.push(LabelStart {
+                kind: LabelKind::InlineFootnote,
+                start: (tokenizer.events.len() - 6, tokenizer.events.len() - 1),
+                inactive: false,
+            });
+            tokenizer.register_resolver_before(ResolveName::Label);
+            State::Ok
+        }
+        _ => State::Nok,
+    }
+}
@@ -65,7 +65,7 @@ use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
 use crate::util::{
-    constant::{LIST_ITEM_VALUE_SIZE_MAX, TAB_SIZE},
+    constant::LIST_ITEM_VALUE_SIZE_MAX,
     skip,
     slice::{Position, Slice},
 };
@@ -87,7 +87,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 tokenizer,
                 0,
                 if tokenizer.parse_state.options.constructs.code_indented {
-                    TAB_SIZE - 1
+                    tokenizer.parse_state.options.tab_size - 1
                 } else {
                     usize::MAX
                 },
@@ -232,7 +232,11 @@ pub fn marker_after_filled(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn whitespace(tokenizer: &mut Tokenizer) -> State {
     tokenizer.attempt(State::Next(StateName::ListItemWhitespaceAfter), State::Nok);
-    State::Retry(space_or_tab_min_max(tokenizer, 1, TAB_SIZE))
+    State::Retry(space_or_tab_min_max(
+        tokenizer,
+        1,
+        tokenizer.parse_state.options.tab_size,
+    ))
 }
 
 /// After acceptable whitespace.
@@ -291,6 +295,7 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
                 start: &tokenizer.events[start].point,
                 end: &tokenizer.point,
             },
+            tokenizer.parse_state.options.tab_size,
         )
         .len();
 
@@ -201,6 +201,7 @@ fn parse_esm(tokenizer: &mut Tokenizer) -> State {
         tokenizer.tokenize_state.start,
         &[Name::MdxEsmData, Name::LineEnding],
         &[],
+        tokenizer.parse_state.options.tab_size,
     );
 
     // Parse and handle what was signaled back.
@@ -40,7 +40,6 @@ use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max}
 use crate::event::Name;
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
-use crate::util::constant::TAB_SIZE;
 
 /// Start of MDX: JSX (flow).
 ///
@@ -58,7 +57,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 tokenizer,
                 0,
                 if tokenizer.parse_state.options.constructs.code_indented {
-                    TAB_SIZE - 1
+                    tokenizer.parse_state.options.tab_size - 1
                 } else {
                     usize::MAX
                 },
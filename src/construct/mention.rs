@@ -0,0 +1,134 @@
+//! Mention occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Mention forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: the character before must not be `mention_before`.
+//! ; Restriction: must not be followed by `'@'`.
+//! mention ::= '@' ascii_alphanumeric *(*('-' | '.' | '_') ascii_alphanumeric)
+//!
+//! mention_before ::= '+' | '-' | '.' | '/' | '@' | '_' | ascii_alphanumeric | unicode_other
+//! unicode_other ::= char - ascii - unicode_whitespace - unicode_punctuation
+//! ```
+//!
+//! Dashes, dots, and underscores can occur in a name, but not at its end, so
+//! that trailing punctuation, such as in `@alice.`, is not included.
+//!
+//! Mentions must not be preceded by something that can occur in an email
+//! address, so that `alice@example.com` is not a mention (it is an email
+//! [GFM autolink literal][gfm_autolink_literal] instead, when that construct
+//! is on).
+//! For the same reason, a name followed by another `@`, such as in
+//! `@alice@example.com`, is not a mention.
+//!
+//! Like GFM autolink literals, mentions are not links when they occur in
+//! links: they are then compiled as text.
+//!
+//! ## HTML
+//!
+//! Mentions relate to the `<a>` element in HTML, when
+//! [`mention_url`][mention_url] is passed.
+//! The template is used to generate the `href` attribute.
+//! See [*§ 4.5.1 The `a` element*][html_a] in the HTML spec for more info.
+//! Without a template, mentions are compiled as text.
+//!
+//! ## Tokens
+//!
+//! *   [`Mention`][Name::Mention]
+//!
+//! ## References
+//!
+//! *   [*Mentions* on GitHub](https://docs.github.com/en/get-started/writing-on-github/getting-started-with-writing-and-formatting-on-github/basic-writing-and-formatting-syntax#mentioning-people-and-teams)
+//!
+//! [text]: crate::construct::text
+//! [gfm_autolink_literal]: crate::construct::gfm_autolink_literal
+//! [mention_url]: crate::CompileOptions::mention_url
+//! [html_a]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-a-element
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::char::{before_index, classify_opt, Kind as CharacterKind};
+
+/// Start of mention.
+///
+/// ```markdown
+/// > | a @b c
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.mention
+        && tokenizer.current == Some(b'@')
+        && !matches!(
+            tokenizer.previous,
+            Some(b'+' | b'-' | b'.' | b'/' | b'@' | b'_')
+        )
+        && classify_opt(before_index(
+            tokenizer.parse_state.bytes,
+            tokenizer.point.index,
+        )) != CharacterKind::Other
+    {
+        if let Some(end) = name_end(tokenizer.parse_state.bytes, tokenizer.point.index + 1) {
+            tokenizer.tokenize_state.end = end;
+            tokenizer.enter(Name::Mention);
+            tokenizer.consume();
+            return State::Next(StateName::MentionInside);
+        }
+    }
+
+    State::Nok
+}
+
+/// In name.
+///
+/// ```markdown
+/// > | a @b c
+///        ^
+/// ```
+pub fn inside(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.point.index < tokenizer.tokenize_state.end {
+        // The name was found in the bytes, but the text might end before it
+        // does.
+        if tokenizer.current.is_none() {
+            tokenizer.tokenize_state.end = 0;
+            State::Nok
+        } else {
+            tokenizer.consume();
+            State::Next(StateName::MentionInside)
+        }
+    } else {
+        tokenizer.tokenize_state.end = 0;
+        tokenizer.exit(Name::Mention);
+        State::Ok
+    }
+}
+
+/// Move past a name.
+///
+/// ```markdown
+/// > | a @b.c. d
+///        ^-- from
+///           ^-- to
+/// ```
+fn name_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut index = start;
+    let mut end = None;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'-' | b'.' | b'_' if end.is_some() => index += 1,
+            b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z' => {
+                index += 1;
+                end = Some(index);
+            }
+            // An email address or a mention on another server.
+            b'@' => return None,
+            _ => break,
+        }
+    }
+
+    end
+}
@@ -62,27 +62,38 @@
 //!
 //! The following constructs are extensions found in markdown:
 //!
+//! *   [directive (container)][directive_container]
+//! *   [directive (leaf)][directive_leaf]
+//! *   [directive (text)][directive_text]
 //! *   [frontmatter][]
 //! *   [gfm autolink literal][gfm_autolink_literal]
 //! *   [gfm footnote definition][gfm_footnote_definition]
 //! *   [gfm label start footnote][gfm_label_start_footnote]
 //! *   [gfm table][gfm_table]
 //! *   [gfm task list item check][gfm_task_list_item_check]
+//! *   [hashtag][]
+//! *   [label start (inline footnote)][label_start_inline_footnote]
 //! *   [mdx esm][mdx_esm]
 //! *   [mdx expression (flow)][mdx_expression_flow]
 //! *   [mdx expression (text)][mdx_expression_text]
 //! *   [mdx jsx (flow)][mdx_jsx_flow]
 //! *   [mdx jsx (text)][mdx_jsx_text]
+//! *   [mention][]
+//! *   [wikilink][]
 //!
 //! There are also several small subroutines typically used in different places:
 //!
 //! *   [bom][partial_bom]
 //! *   [data][partial_data]
 //! *   [destination][partial_destination]
+//! *   [directive attributes][partial_directive_attributes]
+//! *   [directive label][partial_directive_label]
+//! *   [directive name][partial_directive_name]
 //! *   [label][partial_label]
 //! *   [mdx expression][partial_mdx_expression]
 //! *   [mdx jsx][partial_mdx_jsx]
 //! *   [non lazy continuation][partial_non_lazy_continuation]
+//! *   [smart punctuation][partial_smart_punctuation]
 //! *   [space or tab][partial_space_or_tab]
 //! *   [space or tab, eol][partial_space_or_tab_eol]
 //! *   [title][partial_title]
@@ -157,6 +168,9 @@ pub mod character_reference;
 pub mod code_indented;
 pub mod content;
 pub mod definition;
+pub mod directive_container;
+pub mod directive_leaf;
+pub mod directive_text;
 pub mod document;
 pub mod flow;
 pub mod frontmatter;
@@ -166,12 +180,14 @@ pub mod gfm_label_start_footnote;
 pub mod gfm_table;
 pub mod gfm_task_list_item_check;
 pub mod hard_break_escape;
+pub mod hashtag;
 pub mod heading_atx;
 pub mod heading_setext;
 pub mod html_flow;
 pub mod html_text;
 pub mod label_end;
 pub mod label_start_image;
+pub mod label_start_inline_footnote;
 pub mod label_start_link;
 pub mod list_item;
 pub mod mdx_esm;
@@ -179,14 +195,19 @@ pub mod mdx_expression_flow;
 pub mod mdx_expression_text;
 pub mod mdx_jsx_flow;
 pub mod mdx_jsx_text;
+pub mod mention;
 pub mod paragraph;
 pub mod partial_bom;
 pub mod partial_data;
 pub mod partial_destination;
+pub mod partial_directive_attributes;
+pub mod partial_directive_label;
+pub mod partial_directive_name;
 pub mod partial_label;
 pub mod partial_mdx_expression;
 pub mod partial_mdx_jsx;
 pub mod partial_non_lazy_continuation;
+pub mod partial_smart_punctuation;
 pub mod partial_space_or_tab;
 pub mod partial_space_or_tab_eol;
 pub mod partial_title;
@@ -196,3 +217,4 @@ pub mod raw_text;
 pub mod string;
 pub mod text;
 pub mod thematic_break;
+pub mod wikilink;
@@ -13,6 +13,13 @@
 //!
 //! Don’t use BOMs.
 //!
+//! ## Positional info
+//!
+//! The BOM is not removed from the document before parsing, so that
+//! offsets of points are indices into the original value.
+//! That means that things after it are shifted by its 3 bytes: in
+//! `\u{FEFF}# a`, the heading starts at column 4, offset 3.
+//!
 //! ## Tokens
 //!
 //! *   [`ByteOrderMark`][Name::ByteOrderMark]
@@ -0,0 +1,344 @@
+//! Directive attributes occur in [directive (container)][directive_container],
+//! [directive (leaf)][directive_leaf], and [directive (text)][directive_text].
+//!
+//! ## Grammar
+//!
+//! Directive attributes form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! directive_attributes ::= '{' *space_or_tab *(attribute *space_or_tab) '}'
+//!
+//! attribute ::= '#' 1*shortcut_byte | '.' 1*shortcut_byte | name [ '=' value ]
+//! name ::= (ascii_alphabetic | ':' | '_') *(ascii_alphanumeric | '-' | '.' | ':' | '_')
+//! value ::= '"' *(line - '"') '"' | "'" *(line - "'") "'" | 1*unquoted_byte
+//!
+//! unquoted_byte ::= text - '"' - "'" - '<' - '=' - '>' - '`' - '}'
+//! shortcut_byte ::= unquoted_byte - '#' - '.'
+//! ```
+//!
+//! Attributes can be used as `#a` (an id), `.b` (a class), and `c` or `c=d`
+//! or `c="d"` (any other attribute).
+//! Values are interpreted as the [string][] content type.
+//! That means that [character escapes][character_escape] and
+//! [character references][character_reference] are allowed.
+//!
+//! Attributes cannot contain line endings.
+//!
+//! ## Tokens
+//!
+//! *   [`DirectiveAttribute`][Name::DirectiveAttribute]
+//! *   [`DirectiveAttributeClass`][Name::DirectiveAttributeClass]
+//! *   [`DirectiveAttributeClassMarker`][Name::DirectiveAttributeClassMarker]
+//! *   [`DirectiveAttributeId`][Name::DirectiveAttributeId]
+//! *   [`DirectiveAttributeIdMarker`][Name::DirectiveAttributeIdMarker]
+//! *   [`DirectiveAttributeInitializerMarker`][Name::DirectiveAttributeInitializerMarker]
+//! *   [`DirectiveAttributeName`][Name::DirectiveAttributeName]
+//! *   [`DirectiveAttributeValue`][Name::DirectiveAttributeValue]
+//! *   [`DirectiveAttributeValueMarker`][Name::DirectiveAttributeValueMarker]
+//! *   [`DirectiveAttributes`][Name::DirectiveAttributes]
+//! *   [`DirectiveAttributesMarker`][Name::DirectiveAttributesMarker]
+//! *   [`SpaceOrTab`][Name::SpaceOrTab]
+//!
+//! ## References
+//!
+//! *   [`factory-attributes.js` in `micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive/blob/main/dev/lib/factory-attributes.js)
+//!
+//! [directive_container]: crate::construct::directive_container
+//! [directive_leaf]: crate::construct::directive_leaf
+//! [directive_text]: crate::construct::directive_text
+//! [string]: crate::construct::string
+//! [character_escape]: crate::construct::character_escape
+//! [character_reference]: crate::construct::character_reference
+
+use crate::construct::partial_space_or_tab::space_or_tab;
+use crate::event::{Content, Link, Name};
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+
+/// Start of directive attributes.
+///
+/// ```markdown
+/// > | :a{b}
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    debug_assert_eq!(tokenizer.current, Some(b'{'), "expected `{{`");
+    tokenizer.enter(Name::DirectiveAttributes);
+    tokenizer.enter(Name::DirectiveAttributesMarker);
+    tokenizer.consume();
+    tokenizer.exit(Name::DirectiveAttributesMarker);
+    State::Next(StateName::DirectiveAttributesBetween)
+}
+
+/// Between attributes, at optional whitespace.
+///
+/// ```markdown
+/// > | :a{ b c }
+///        ^ ^ ^
+/// ```
+pub fn between(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveAttributesBefore),
+            State::Nok,
+        );
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Retry(StateName::DirectiveAttributesBefore)
+    }
+}
+
+/// Before an attribute or `}`.
+///
+/// ```markdown
+/// > | :a{b #c .d}
+///        ^ ^  ^ ^
+/// ```
+pub fn before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'}') => {
+            tokenizer.enter(Name::DirectiveAttributesMarker);
+            tokenizer.consume();
+            tokenizer.exit(Name::DirectiveAttributesMarker);
+            tokenizer.exit(Name::DirectiveAttributes);
+            State::Ok
+        }
+        Some(b'#') => {
+            tokenizer.tokenize_state.token_1 = Name::DirectiveAttributeIdMarker;
+            tokenizer.tokenize_state.token_2 = Name::DirectiveAttributeId;
+            State::Retry(StateName::DirectiveAttributesShortcutStart)
+        }
+        Some(b'.') => {
+            tokenizer.tokenize_state.token_1 = Name::DirectiveAttributeClassMarker;
+            tokenizer.tokenize_state.token_2 = Name::DirectiveAttributeClass;
+            State::Retry(StateName::DirectiveAttributesShortcutStart)
+        }
+        Some(b':' | b'A'..=b'Z' | b'_' | b'a'..=b'z') => {
+            tokenizer.enter(Name::DirectiveAttribute);
+            tokenizer.enter(Name::DirectiveAttributeName);
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesNameInside)
+        }
+        _ => State::Nok,
+    }
+}
+
+/// At `#` or `.` of a shortcut.
+///
+/// ```markdown
+/// > | :a{#b .c}
+///        ^  ^
+/// ```
+pub fn shortcut_start(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter(Name::DirectiveAttribute);
+    tokenizer.enter(tokenizer.tokenize_state.token_1.clone());
+    tokenizer.consume();
+    tokenizer.exit(tokenizer.tokenize_state.token_1.clone());
+    State::Next(StateName::DirectiveAttributesShortcutBefore)
+}
+
+/// After `#` or `.`, at the value of a shortcut.
+///
+/// ```markdown
+/// > | :a{#b .c}
+///         ^  ^
+/// ```
+pub fn shortcut_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(byte) if shortcut(byte) => {
+            tokenizer.enter(tokenizer.tokenize_state.token_2.clone());
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesShortcutInside)
+        }
+        _ => {
+            tokenizer.tokenize_state.token_1 = Name::Data;
+            tokenizer.tokenize_state.token_2 = Name::Data;
+            State::Nok
+        }
+    }
+}
+
+/// In the value of a shortcut.
+///
+/// ```markdown
+/// > | :a{#bc}
+///          ^
+/// ```
+pub fn shortcut_inside(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(byte) if shortcut(byte) => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesShortcutInside)
+        }
+        _ => {
+            tokenizer.exit(tokenizer.tokenize_state.token_2.clone());
+            tokenizer.exit(Name::DirectiveAttribute);
+            tokenizer.tokenize_state.token_1 = Name::Data;
+            tokenizer.tokenize_state.token_2 = Name::Data;
+            State::Retry(StateName::DirectiveAttributesBetween)
+        }
+    }
+}
+
+/// In an attribute name.
+///
+/// ```markdown
+/// > | :a{bc=d}
+///         ^
+/// ```
+pub fn name_inside(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'-' | b'.' | b'0'..=b'9' | b':' | b'A'..=b'Z' | b'_' | b'a'..=b'z') => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesNameInside)
+        }
+        Some(b'=') => {
+            tokenizer.exit(Name::DirectiveAttributeName);
+            tokenizer.enter(Name::DirectiveAttributeInitializerMarker);
+            tokenizer.consume();
+            tokenizer.exit(Name::DirectiveAttributeInitializerMarker);
+            State::Next(StateName::DirectiveAttributesValueBefore)
+        }
+        _ => {
+            tokenizer.exit(Name::DirectiveAttributeName);
+            tokenizer.exit(Name::DirectiveAttribute);
+            State::Retry(StateName::DirectiveAttributesBetween)
+        }
+    }
+}
+
+/// After `=`, at an attribute value.
+///
+/// ```markdown
+/// > | :a{b="c"}
+///          ^
+/// ```
+pub fn value_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'"' | b'\'') => {
+            tokenizer.tokenize_state.marker = tokenizer.current.unwrap();
+            tokenizer.enter(Name::DirectiveAttributeValueMarker);
+            tokenizer.consume();
+            tokenizer.exit(Name::DirectiveAttributeValueMarker);
+            State::Next(StateName::DirectiveAttributesValueQuotedStart)
+        }
+        Some(byte) if unquoted(byte) => {
+            tokenizer.enter(Name::DirectiveAttributeValue);
+            tokenizer.enter_link(
+                Name::Data,
+                Link {
+                    previous: None,
+                    next: None,
+                    content: Content::String,
+                },
+            );
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesValueUnquoted)
+        }
+        _ => State::Nok,
+    }
+}
+
+/// In an unquoted attribute value.
+///
+/// ```markdown
+/// > | :a{b=cd}
+///           ^
+/// ```
+pub fn value_unquoted(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(byte) if unquoted(byte) => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesValueUnquoted)
+        }
+        _ => {
+            tokenizer.exit(Name::Data);
+            tokenizer.exit(Name::DirectiveAttributeValue);
+            tokenizer.exit(Name::DirectiveAttribute);
+            State::Retry(StateName::DirectiveAttributesBetween)
+        }
+    }
+}
+
+/// After an opening quote, at the value or the closing quote.
+///
+/// ```markdown
+/// > | :a{b="c"}
+///           ^
+/// ```
+pub fn value_quoted_start(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.tokenize_state.marker = 0;
+            State::Nok
+        }
+        Some(byte) if byte == tokenizer.tokenize_state.marker => {
+            State::Retry(StateName::DirectiveAttributesValueQuotedEnd)
+        }
+        _ => {
+            tokenizer.enter(Name::DirectiveAttributeValue);
+            tokenizer.enter_link(
+                Name::Data,
+                Link {
+                    previous: None,
+                    next: None,
+                    content: Content::String,
+                },
+            );
+            State::Retry(StateName::DirectiveAttributesValueQuoted)
+        }
+    }
+}
+
+/// In a quoted attribute value.
+///
+/// ```markdown
+/// > | :a{b="cd"}
+///            ^
+/// ```
+pub fn value_quoted(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.tokenize_state.marker = 0;
+            State::Nok
+        }
+        Some(byte) if byte == tokenizer.tokenize_state.marker => {
+            tokenizer.exit(Name::Data);
+            tokenizer.exit(Name::DirectiveAttributeValue);
+            State::Retry(StateName::DirectiveAttributesValueQuotedEnd)
+        }
+        _ => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesValueQuoted)
+        }
+    }
+}
+
+/// At the closing quote.
+///
+/// ```markdown
+/// > | :a{b="c"}
+///            ^
+/// ```
+pub fn value_quoted_end(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.marker = 0;
+    tokenizer.enter(Name::DirectiveAttributeValueMarker);
+    tokenizer.consume();
+    tokenizer.exit(Name::DirectiveAttributeValueMarker);
+    tokenizer.exit(Name::DirectiveAttribute);
+    State::Next(StateName::DirectiveAttributesBetween)
+}
+
+/// Check whether `byte` can occur in an unquoted value.
+fn unquoted(byte: u8) -> bool {
+    !matches!(
+        byte,
+        b'\t' | b'\n' | b' ' | b'"' | b'\'' | b'<' | b'=' | b'>' | b'`' | b'}'
+    )
+}
+
+/// Check whether `byte` can occur in a shortcut (`#a`, `.b`).
+fn shortcut(byte: u8) -> bool {
+    unquoted(byte) && !matches!(byte, b'#' | b'.')
+}
@@ -0,0 +1,144 @@
+//! Directive label occurs in [directive (container)][directive_container],
+//! [directive (leaf)][directive_leaf], and [directive (text)][directive_text].
+//!
+//! ## Grammar
+//!
+//! Directive label forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: unescaped brackets must be balanced.
+//! directive_label ::= '[' *(line - '\\' | '\\' byte) ']'
+//! ```
+//!
+//! The label is interpreted as the [text][] content type.
+//! That means that it can include, for example, emphasis and links.
+//!
+//! Unlike labels of links, directive labels can contain balanced brackets,
+//! and they cannot contain line endings.
+//!
+//! ## Tokens
+//!
+//! *   [`DirectiveLabel`][Name::DirectiveLabel]
+//! *   [`DirectiveLabelMarker`][Name::DirectiveLabelMarker]
+//! *   [`DirectiveLabelString`][Name::DirectiveLabelString]
+//!
+//! ## References
+//!
+//! *   [`factory-label.js` in `micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive/blob/main/dev/lib/factory-label.js)
+//!
+//! [directive_container]: crate::construct::directive_container
+//! [directive_leaf]: crate::construct::directive_leaf
+//! [directive_text]: crate::construct::directive_text
+//! [text]: crate::construct::text
+
+use crate::event::{Content, Link, Name};
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+
+/// Start of directive label.
+///
+/// ```markdown
+/// > | :a[b]
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    debug_assert_eq!(tokenizer.current, Some(b'['), "expected `[`");
+    tokenizer.enter(Name::DirectiveLabel);
+    tokenizer.enter(Name::DirectiveLabelMarker);
+    tokenizer.consume();
+    tokenizer.exit(Name::DirectiveLabelMarker);
+    State::Next(StateName::DirectiveLabelBefore)
+}
+
+/// After `[`, at label content or `]`.
+///
+/// ```markdown
+/// > | :a[b]
+///        ^
+/// ```
+pub fn before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => State::Nok,
+        Some(b']') => State::Retry(StateName::DirectiveLabelEnd),
+        _ => {
+            tokenizer.enter(Name::DirectiveLabelString);
+            tokenizer.enter_link(
+                Name::Data,
+                Link {
+                    previous: None,
+                    next: None,
+                    content: Content::Text,
+                },
+            );
+            State::Retry(StateName::DirectiveLabelInside)
+        }
+    }
+}
+
+/// In label content.
+///
+/// ```markdown
+/// > | :a[b]
+///        ^
+/// ```
+pub fn inside(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.tokenize_state.size = 0;
+            State::Nok
+        }
+        Some(b']') if tokenizer.tokenize_state.size == 0 => {
+            tokenizer.exit(Name::Data);
+            tokenizer.exit(Name::DirectiveLabelString);
+            State::Retry(StateName::DirectiveLabelEnd)
+        }
+        Some(byte) => {
+            let next = match byte {
+                b'[' => {
+                    tokenizer.tokenize_state.size += 1;
+                    StateName::DirectiveLabelInside
+                }
+                b']' => {
+                    tokenizer.tokenize_state.size -= 1;
+                    StateName::DirectiveLabelInside
+                }
+                b'\\' => StateName::DirectiveLabelEscape,
+                _ => StateName::DirectiveLabelInside,
+            };
+            tokenizer.consume();
+            State::Next(next)
+        }
+    }
+}
+
+/// After `\`, at a special character.
+///
+/// ```markdown
+/// > | :a[b\]c]
+///          ^
+/// ```
+pub fn escape(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'[' | b'\\' | b']') => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveLabelInside)
+        }
+        _ => State::Retry(StateName::DirectiveLabelInside),
+    }
+}
+
+/// At `]`.
+///
+/// ```markdown
+/// > | :a[b]
+///         ^
+/// ```
+pub fn end(tokenizer: &mut Tokenizer) -> State {
+    debug_assert_eq!(tokenizer.current, Some(b']'), "expected `]`");
+    tokenizer.enter(Name::DirectiveLabelMarker);
+    tokenizer.consume();
+    tokenizer.exit(Name::DirectiveLabelMarker);
+    tokenizer.exit(Name::DirectiveLabel);
+    State::Ok
+}
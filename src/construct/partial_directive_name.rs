@@ -0,0 +1,71 @@
+//! Directive name occurs in [directive (container)][directive_container],
+//! [directive (leaf)][directive_leaf], and [directive (text)][directive_text].
+//!
+//! ## Grammar
+//!
+//! Directive name forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: cannot end in `-` or `_`.
+//! directive_name ::= ascii_alphabetic *(ascii_alphanumeric | '-' | '_')
+//! ```
+//!
+//! The name is what a downstream consumer uses to decide what a directive
+//! means.
+//! Names are case-sensitive.
+//!
+//! ## Tokens
+//!
+//! *   [`DirectiveName`][Name::DirectiveName]
+//!
+//! ## References
+//!
+//! *   [`factory-name.js` in `micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive/blob/main/dev/lib/factory-name.js)
+//!
+//! [directive_container]: crate::construct::directive_container
+//! [directive_leaf]: crate::construct::directive_leaf
+//! [directive_text]: crate::construct::directive_text
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+
+/// Start of directive name.
+///
+/// ```markdown
+/// > | :a
+///      ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'A'..=b'Z' | b'a'..=b'z')) {
+        tokenizer.enter(Name::DirectiveName);
+        tokenizer.consume();
+        State::Next(StateName::DirectiveNameInside)
+    } else {
+        State::Nok
+    }
+}
+
+/// In directive name.
+///
+/// ```markdown
+/// > | :ab
+///       ^
+/// ```
+pub fn inside(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'-' | b'0'..=b'9' | b'A'..=b'Z' | b'_' | b'a'..=b'z') => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveNameInside)
+        }
+        _ => {
+            if matches!(tokenizer.previous, Some(b'-' | b'_')) {
+                State::Nok
+            } else {
+                tokenizer.exit(Name::DirectiveName);
+                State::Ok
+            }
+        }
+    }
+}
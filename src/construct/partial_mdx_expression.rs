@@ -61,7 +61,7 @@ use crate::event::Name;
 use crate::message;
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
-use crate::util::{constant::TAB_SIZE, mdx_collect::collect};
+use crate::util::mdx_collect::collect;
 use crate::{MdxExpressionKind, MdxExpressionParse, MdxSignal};
 use alloc::boxed::Box;
 
@@ -200,7 +200,11 @@ pub fn eol_after(tokenizer: &mut Tokenizer) -> State {
         // the start of the expression and move past whitespace.
         // For future lines, we’d move at most to
         // `line_start_shifted.column + 4`.
-        State::Retry(space_or_tab_min_max(tokenizer, 0, TAB_SIZE))
+        State::Retry(space_or_tab_min_max(
+            tokenizer,
+            0,
+            tokenizer.parse_state.options.tab_size,
+        ))
     } else {
         State::Retry(StateName::MdxExpressionBefore)
     }
@@ -215,6 +219,7 @@ fn parse_expression(tokenizer: &mut Tokenizer, parse: &MdxExpressionParse) -> St
         tokenizer.tokenize_state.start,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[],
+        tokenizer.parse_state.options.tab_size,
     );
 
     // Turn the name of the expression into a kind.
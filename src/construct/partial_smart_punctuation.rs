@@ -0,0 +1,196 @@
+//! Smart punctuation occurs in [text][].
+//!
+//! ## Grammar
+//!
+//! Smart punctuation forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! smart_punctuation ::= quote | dash | ellipsis
+//!
+//! quote ::= '"' | '\''
+//! dash ::= 2*'-'
+//! ellipsis ::= '...'
+//! ```
+//!
+//! Smart punctuation is not a construct that is parsed: instead, it is found
+//! in [data][partial_data] after the rest of text is parsed.
+//! That means that it never occurs in code, autolinks, HTML, or other
+//! constructs.
+//! Escape a character to prevent it from turning into smart punctuation
+//! (such as `\"` or `\-\-`).
+//!
+//! Whether a quote is opening or closing is decided by the characters around
+//! it, similar to whether [attention][] can open or close.
+//! If the quote is followed by something and preceded by whitespace or
+//! punctuation, it opens; if it is preceded by something, it closes.
+//! That makes an apostrophe in a contraction, such as in `don't`, a closing
+//! quote.
+//!
+//! A sequence of dashes is turned into em dashes and en dashes.
+//! Em dashes are preferred: three dashes make an em dash, two an en dash.
+//! Longer sequences are split in a way that uses as few different dashes as
+//! possible (such as `----`, which makes two en dashes).
+//!
+//! ## HTML
+//!
+//! Smart punctuation turns into the typographic characters
+//! `“`, `”`, `‘`, `’`, `–`, `—`, and `…` in HTML.
+//!
+//! ## Tokens
+//!
+//! *   [`SmartPunctuationDash`][Name::SmartPunctuationDash]
+//! *   [`SmartPunctuationEllipsis`][Name::SmartPunctuationEllipsis]
+//! *   [`SmartPunctuationQuoteClosing`][Name::SmartPunctuationQuoteClosing]
+//! *   [`SmartPunctuationQuoteOpening`][Name::SmartPunctuationQuoteOpening]
+//!
+//! ## References
+//!
+//! *   [`smart` in `commonmark.js`](https://github.com/commonmark/commonmark.js/blob/20b52b0/lib/inlines.js#L272)
+//! *   [`SmartyPants`](https://daringfireball.net/projects/smartypants/)
+//!
+//! [text]: crate::construct::text
+//! [partial_data]: crate::construct::partial_data
+//! [attention]: crate::construct::attention
+
+use crate::event::{Event, Kind, Name};
+use crate::tokenizer::Tokenizer;
+use crate::util::{
+    char::{after_index, before_index, classify_opt, Kind as CharacterKind},
+    slice::{Position, Slice},
+};
+use alloc::vec::Vec;
+
+/// Resolve smart punctuation.
+pub fn resolve(tokenizer: &mut Tokenizer) {
+    tokenizer.map.consume(&mut tokenizer.events);
+
+    let tab_size = tokenizer.parse_state.options.tab_size;
+    let column_unit = tokenizer.parse_state.options.column_unit;
+    let mut index = 0;
+
+    while index < tokenizer.events.len() {
+        let event = &tokenizer.events[index];
+
+        if event.kind == Kind::Exit
+            && event.name == Name::Data
+            && tokenizer.events[index - 1].link.is_none()
+        {
+            let all = tokenizer.parse_state.bytes;
+            let slice = Slice::from_position(
+                all,
+                &Position::from_exit_event(&tokenizer.events, index),
+                tab_size,
+            );
+            let bytes = slice.bytes;
+            let mut point = tokenizer.events[index - 1].point.clone();
+            let start_index = point.index;
+            let mut replace = Vec::new();
+            let mut byte_index = 0;
+            let mut min = 0;
+
+            while byte_index < bytes.len() {
+                let (size, name) = match bytes[byte_index] {
+                    b'"' | b'\'' => {
+                        let before = classify_opt(before_index(all, start_index + byte_index));
+                        let after = classify_opt(after_index(all, start_index + byte_index + 1));
+                        let open = after != CharacterKind::Whitespace
+                            && (after == CharacterKind::Other || before != CharacterKind::Other);
+                        let close = before != CharacterKind::Whitespace
+                            && (before == CharacterKind::Other || after != CharacterKind::Other);
+
+                        // Quotes that can neither open nor close, such as
+                        // ones surrounded by whitespace, are still turned
+                        // into something: left double and right single.
+                        if close || (!open && bytes[byte_index] == b'\'') {
+                            (1, Some(Name::SmartPunctuationQuoteClosing))
+                        } else {
+                            (1, Some(Name::SmartPunctuationQuoteOpening))
+                        }
+                    }
+                    b'-' => {
+                        let mut end = byte_index + 1;
+                        while end < bytes.len() && bytes[end] == b'-' {
+                            end += 1;
+                        }
+                        let size = end - byte_index;
+                        (
+                            size,
+                            if size > 1 {
+                                Some(Name::SmartPunctuationDash)
+                            } else {
+                                None
+                            },
+                        )
+                    }
+                    b'.' if bytes[byte_index..].starts_with(b"...") => {
+                        (3, Some(Name::SmartPunctuationEllipsis))
+                    }
+                    _ => (1, None),
+                };
+
+                if let Some(name) = name {
+                    // If there is something between the last replacement
+                    // (or the start) and this one.
+                    if min != byte_index {
+                        replace.push(Event {
+                            kind: Kind::Enter,
+                            name: Name::Data,
+                            point: point.clone(),
+                            link: None,
+                        });
+                        point =
+                            point.shift_to(all, start_index + byte_index, tab_size, column_unit);
+                        replace.push(Event {
+                            kind: Kind::Exit,
+                            name: Name::Data,
+                            point: point.clone(),
+                            link: None,
+                        });
+                    }
+
+                    replace.push(Event {
+                        kind: Kind::Enter,
+                        name: name.clone(),
+                        point: point.clone(),
+                        link: None,
+                    });
+                    point =
+                        point.shift_to(all, start_index + byte_index + size, tab_size, column_unit);
+                    replace.push(Event {
+                        kind: Kind::Exit,
+                        name,
+                        point: point.clone(),
+                        link: None,
+                    });
+                    min = byte_index + size;
+                }
+
+                byte_index += size;
+            }
+
+            // If there were replacements.
+            if !replace.is_empty() {
+                // If there are more bytes left.
+                if min < bytes.len() {
+                    replace.push(Event {
+                        kind: Kind::Enter,
+                        name: Name::Data,
+                        point: point.clone(),
+                        link: None,
+                    });
+                    replace.push(Event {
+                        kind: Kind::Exit,
+                        name: Name::Data,
+                        point: tokenizer.events[index].point.clone(),
+                        link: None,
+                    });
+                }
+
+                tokenizer.map.add(index - 1, 2, replace);
+            }
+        }
+
+        index += 1;
+    }
+}
@@ -8,15 +8,21 @@
 //! ```bnf
 //! ; Restriction: no blank lines.
 //! ; Restriction: markers must match (in case of `(` with `)`).
+//! ; Restriction: in case of `(`, unescaped `(` and `)` in the title must be
+//! ; balanced.
 //! title ::= marker *(title_byte | title_escape) marker
 //! title_byte ::= code - '\\' - marker
-//! title_escape ::= '\\' ['\\' | marker]
+//! title_escape ::= '\\' ['\\' | '(' | marker]
 //! marker ::= '"' | '\'' | '('
 //! ```
 //!
 //! Titles can be double quoted (`"a"`), single quoted (`'a'`), or
 //! parenthesized (`(a)`).
 //!
+//! Parenthesized titles can contain balanced parens (`(a (b) c)`).
+//! When they are not balanced (`(a (b)`), there is no title.
+//! Escaped parens (`(a \( b)`) do not count.
+//!
 //! Titles can contain line endings and whitespace, but they are not allowed to
 //! contain blank lines.
 //! They are allowed to be blank themselves.
@@ -77,6 +83,7 @@ pub fn begin(tokenizer: &mut Tokenizer) -> State {
         tokenizer.exit(tokenizer.tokenize_state.token_2.clone());
         tokenizer.exit(tokenizer.tokenize_state.token_1.clone());
         tokenizer.tokenize_state.marker = 0;
+        tokenizer.tokenize_state.size = 0;
         tokenizer.tokenize_state.connect = false;
         State::Ok
     } else {
@@ -93,7 +100,8 @@ pub fn begin(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn at_break(tokenizer: &mut Tokenizer) -> State {
     if let Some(byte) = tokenizer.current {
-        if byte == tokenizer.tokenize_state.marker {
+        // Balanced parens are not the end (`size` is only used for parens).
+        if byte == tokenizer.tokenize_state.marker && tokenizer.tokenize_state.size == 0 {
             tokenizer.exit(tokenizer.tokenize_state.token_3.clone());
             State::Retry(StateName::TitleBegin)
         } else if byte == b'\n' {
@@ -152,6 +160,7 @@ pub fn after_eol(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn nok(tokenizer: &mut Tokenizer) -> State {
     tokenizer.tokenize_state.marker = 0;
+    tokenizer.tokenize_state.size = 0;
     tokenizer.tokenize_state.connect = false;
     State::Nok
 }
@@ -163,16 +172,24 @@ pub fn nok(tokenizer: &mut Tokenizer) -> State {
 ///      ^
 /// ```
 pub fn inside(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer.current == Some(tokenizer.tokenize_state.marker)
+    if (tokenizer.current == Some(tokenizer.tokenize_state.marker)
+        && tokenizer.tokenize_state.size == 0)
         || matches!(tokenizer.current, None | Some(b'\n'))
     {
         tokenizer.exit(Name::Data);
         State::Retry(StateName::TitleAtBreak)
     } else {
-        let name = if tokenizer.current == Some(b'\\') {
-            StateName::TitleEscape
-        } else {
-            StateName::TitleInside
+        let name = match tokenizer.current {
+            Some(b'\\') => StateName::TitleEscape,
+            Some(b'(') if tokenizer.tokenize_state.marker == b')' => {
+                tokenizer.tokenize_state.size += 1;
+                StateName::TitleInside
+            }
+            Some(b')') if tokenizer.tokenize_state.marker == b')' => {
+                tokenizer.tokenize_state.size -= 1;
+                StateName::TitleInside
+            }
+            _ => StateName::TitleInside,
         };
         tokenizer.consume();
         State::Next(name)
@@ -187,7 +204,7 @@ pub fn inside(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn escape(tokenizer: &mut Tokenizer) -> State {
     match tokenizer.current {
-        Some(b'"' | b'\'' | b')' | b'\\') => {
+        Some(b'"' | b'\'' | b'(' | b')' | b'\\') => {
             tokenizer.consume();
             State::Next(StateName::TitleInside)
         }
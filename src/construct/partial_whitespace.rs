@@ -99,6 +99,7 @@ fn trim_data(
     let mut slice = Slice::from_position(
         tokenizer.parse_state.bytes,
         &Position::from_exit_event(&tokenizer.events, exit_index),
+        tokenizer.parse_state.options.tab_size,
     );
 
     if trim_end {
@@ -184,6 +185,12 @@ fn trim_data(
         if index > 0 || slice.before > 0 {
             let enter_point = tokenizer.events[exit_index - 1].point.clone();
             let mut exit_point = enter_point.clone();
+            // Move past the rest of a tab that is partially used by a
+            // container (such as a list item), which is not in the bytes.
+            if slice.before > 0 {
+                exit_point.index += 1;
+                exit_point.column += slice.before;
+            }
             exit_point.index += index;
             exit_point.column += index;
             exit_point.vs = 0;
@@ -90,6 +90,11 @@
 //! </code></pre>
 //! ```
 //!
+//! When [`attributes`][attributes] are turned on, an attribute list (such as
+//! `{#a .b c=d}`) as the `meta` (`~~~css {#a}`), or as both the `info` and
+//! `meta` (`~~~ {.css #a}`), is used for the attributes of the `<code>`
+//! element.
+//!
 //! ## Recommendation
 //!
 //! It is recommended to use code (fenced) instead of code (indented).
@@ -134,6 +139,7 @@
 //! [character_escape]: crate::construct::character_escape
 //! [character_reference]: crate::construct::character_reference
 //! [code_indented]: crate::construct::code_indented
+//! [attributes]: crate::Constructs::attributes
 //! [raw_text]: crate::construct::raw_text
 //! [html_code]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-code-element
 //! [html_pre]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-pre-element
@@ -143,7 +149,7 @@ use crate::event::{Content, Link, Name};
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
 use crate::util::{
-    constant::{CODE_FENCED_SEQUENCE_SIZE_MIN, MATH_FLOW_SEQUENCE_SIZE_MIN, TAB_SIZE},
+    constant::{CODE_FENCED_SEQUENCE_SIZE_MIN, MATH_FLOW_SEQUENCE_SIZE_MIN},
     slice::{Position, Slice},
 };
 
@@ -168,7 +174,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 tokenizer,
                 0,
                 if tokenizer.parse_state.options.constructs.code_indented {
-                    TAB_SIZE - 1
+                    tokenizer.parse_state.options.tab_size - 1
                 } else {
                     usize::MAX
                 },
@@ -200,6 +206,7 @@ pub fn before_sequence_open(tokenizer: &mut Tokenizer) -> State {
             prefix = Slice::from_position(
                 tokenizer.parse_state.bytes,
                 &Position::from_exit_event(&tokenizer.events, tokenizer.events.len() - 1),
+                tokenizer.parse_state.options.tab_size,
             )
             .len();
         }
@@ -475,7 +482,7 @@ pub fn close_start(tokenizer: &mut Tokenizer) -> State {
             tokenizer,
             0,
             if tokenizer.parse_state.options.constructs.code_indented {
-                TAB_SIZE - 1
+                tokenizer.parse_state.options.tab_size - 1
             } else {
                 usize::MAX
             },
@@ -609,7 +616,14 @@ pub fn before_content_chunk(tokenizer: &mut Tokenizer) -> State {
             State::Retry(StateName::NonLazyContinuationStart)
         }
         _ => {
-            tokenizer.enter(tokenizer.tokenize_state.token_6.clone());
+            tokenizer.enter_link(
+                tokenizer.tokenize_state.token_6.clone(),
+                Link {
+                    previous: None,
+                    next: None,
+                    content: Content::Raw,
+                },
+            );
             State::Retry(StateName::RawFlowContentChunk)
         }
     }
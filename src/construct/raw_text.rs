@@ -115,7 +115,7 @@
 //! [html_code]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-code-element
 //! [parse_options]: crate::ParseOptions
 
-use crate::event::Name;
+use crate::event::{Content, Link, Name};
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
 
@@ -212,7 +212,14 @@ pub fn between(tokenizer: &mut Tokenizer) -> State {
                 tokenizer.enter(tokenizer.tokenize_state.token_2.clone());
                 State::Retry(StateName::RawTextSequenceClose)
             } else {
-                tokenizer.enter(tokenizer.tokenize_state.token_3.clone());
+                tokenizer.enter_link(
+                    tokenizer.tokenize_state.token_3.clone(),
+                    Link {
+                        previous: None,
+                        next: None,
+                        content: Content::Raw,
+                    },
+                );
                 State::Retry(StateName::RawTextData)
             }
         }
@@ -264,6 +271,11 @@ pub fn sequence_close(tokenizer: &mut Tokenizer) -> State {
             let len = tokenizer.events.len();
             tokenizer.events[len - 2].name = tokenizer.tokenize_state.token_3.clone();
             tokenizer.events[len - 1].name = tokenizer.tokenize_state.token_3.clone();
+            tokenizer.events[len - 2].link = Some(Link {
+                previous: None,
+                next: None,
+                content: Content::Raw,
+            });
             tokenizer.tokenize_state.size_b = 0;
             State::Retry(StateName::RawTextBetween)
         }
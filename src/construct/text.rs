@@ -6,25 +6,31 @@
 //!
 //! The constructs found in text are:
 //!
-//! *   [Attention][crate::construct::attention] (emphasis, gfm strikethrough, strong)
+//! *   [Attention][crate::construct::attention] (emphasis, gfm strikethrough, mark, strong)
 //! *   [Autolink][crate::construct::autolink]
 //! *   [Character escape][crate::construct::character_escape]
 //! *   [Character reference][crate::construct::character_reference]
+//! *   [Directive (text)][crate::construct::directive_text]
 //! *   [Raw (text)][crate::construct::raw_text] (code (text), math (text))
 //! *   [GFM: Label start (footnote)][crate::construct::gfm_label_start_footnote]
 //! *   [GFM: Task list item check][crate::construct::gfm_task_list_item_check]
 //! *   [Hard break (escape)][crate::construct::hard_break_escape]
+//! *   [Hashtag][crate::construct::hashtag]
 //! *   [HTML (text)][crate::construct::html_text]
 //! *   [Label start (image)][crate::construct::label_start_image]
+//! *   [Label start (inline footnote)][crate::construct::label_start_inline_footnote]
 //! *   [Label start (link)][crate::construct::label_start_link]
 //! *   [Label end][crate::construct::label_end]
 //! *   [MDX: expression (text)][crate::construct::mdx_expression_text]
 //! *   [MDX: JSX (text)][crate::construct::mdx_jsx_text]
+//! *   [Mention][crate::construct::mention]
+//! *   [Wikilink][crate::construct::wikilink]
 //!
 //! > 👉 **Note**: for performance reasons, hard break (trailing) is formed by
 //! > [whitespace][crate::construct::partial_whitespace].
 
 use crate::construct::gfm_autolink_literal::resolve as resolve_gfm_autolink_literal;
+use crate::construct::partial_smart_punctuation::resolve as resolve_smart_punctuation;
 use crate::construct::partial_whitespace::resolve_whitespace;
 use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
@@ -32,17 +38,22 @@ use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
 
 /// Characters that can start something in text.
-const MARKERS: [u8; 16] = [
+const MARKERS: [u8; 21] = [
     b'!',  // `label_start_image`
+    b'#',  // `hashtag`
     b'$',  // `raw_text` (math (text))
     b'&',  // `character_reference`
     b'*',  // `attention` (emphasis, strong)
+    b':',  // `directive_text`
     b'<',  // `autolink`, `html_text`, `mdx_jsx_text`
+    b'=',  // `attention` (mark)
+    b'@',  // `mention`
     b'H',  // `gfm_autolink_literal` (`protocol` kind)
     b'W',  // `gfm_autolink_literal` (`www.` kind)
-    b'[',  // `label_start_link`
+    b'[',  // `label_start_link`, `gfm_label_start_footnote`, `wikilink`
     b'\\', // `character_escape`, `hard_break_escape`
     b']',  // `label_end`, `gfm_label_start_footnote`
+    b'^',  // `label_start_inline_footnote`
     b'_',  // `attention` (emphasis, strong)
     b'`',  // `raw_text` (code (text))
     b'h',  // `gfm_autolink_literal` (`protocol` kind)
@@ -90,6 +101,13 @@ pub fn before(tokenizer: &mut Tokenizer) -> State {
             );
             State::Retry(StateName::LabelStartImageStart)
         }
+        Some(b'#') => {
+            tokenizer.attempt(
+                State::Next(StateName::TextBefore),
+                State::Next(StateName::TextBeforeData),
+            );
+            State::Retry(StateName::HashtagStart)
+        }
         // raw (text) (code (text), math (text))
         Some(b'$' | b'`') => {
             tokenizer.attempt(
@@ -105,14 +123,21 @@ pub fn before(tokenizer: &mut Tokenizer) -> State {
             );
             State::Retry(StateName::CharacterReferenceStart)
         }
-        // attention (emphasis, gfm strikethrough, strong)
-        Some(b'*' | b'_' | b'~') => {
+        // attention (emphasis, gfm strikethrough, mark, strong)
+        Some(b'*' | b'=' | b'_' | b'~') => {
             tokenizer.attempt(
                 State::Next(StateName::TextBefore),
                 State::Next(StateName::TextBeforeData),
             );
             State::Retry(StateName::AttentionStart)
         }
+        Some(b':') => {
+            tokenizer.attempt(
+                State::Next(StateName::TextBefore),
+                State::Next(StateName::TextBeforeData),
+            );
+            State::Retry(StateName::DirectiveTextStart)
+        }
         // `autolink`, `html_text` (order does not matter), `mdx_jsx_text` (order matters).
         Some(b'<') => {
             tokenizer.attempt(
@@ -121,6 +146,13 @@ pub fn before(tokenizer: &mut Tokenizer) -> State {
             );
             State::Retry(StateName::AutolinkStart)
         }
+        Some(b'@') => {
+            tokenizer.attempt(
+                State::Next(StateName::TextBefore),
+                State::Next(StateName::TextBeforeData),
+            );
+            State::Retry(StateName::MentionStart)
+        }
         Some(b'H' | b'h') => {
             tokenizer.attempt(
                 State::Next(StateName::TextBefore),
@@ -138,9 +170,9 @@ pub fn before(tokenizer: &mut Tokenizer) -> State {
         Some(b'[') => {
             tokenizer.attempt(
                 State::Next(StateName::TextBefore),
-                State::Next(StateName::TextBeforeLabelStartLink),
+                State::Next(StateName::TextBeforeGfmLabelStartFootnote),
             );
-            State::Retry(StateName::GfmLabelStartFootnoteStart)
+            State::Retry(StateName::WikiLinkStart)
         }
         Some(b'\\') => {
             tokenizer.attempt(
@@ -156,6 +188,13 @@ pub fn before(tokenizer: &mut Tokenizer) -> State {
             );
             State::Retry(StateName::LabelEndStart)
         }
+        Some(b'^') => {
+            tokenizer.attempt(
+                State::Next(StateName::TextBefore),
+                State::Next(StateName::TextBeforeData),
+            );
+            State::Retry(StateName::LabelStartInlineFootnoteStart)
+        }
         Some(b'{') => {
             tokenizer.attempt(
                 State::Next(StateName::TextBefore),
@@ -215,9 +254,25 @@ pub fn before_hard_break_escape(tokenizer: &mut Tokenizer) -> State {
     State::Retry(StateName::HardBreakEscapeStart)
 }
 
+/// Before GFM label start (footnote).
+///
+/// At `[`, which wasn’t a wikilink.
+///
+/// ```markdown
+/// > | [^a]
+///     ^
+/// ```
+pub fn before_gfm_label_start_footnote(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::TextBefore),
+        State::Next(StateName::TextBeforeLabelStartLink),
+    );
+    State::Retry(StateName::GfmLabelStartFootnoteStart)
+}
+
 /// Before label start (link).
 ///
-/// At `[`, which wasn’t a GFM label start (footnote).
+/// At `[`, which wasn’t a wikilink or GFM label start (footnote).
 ///
 /// ```markdown
 /// > | [a](b)
@@ -259,6 +314,10 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
         resolve_gfm_autolink_literal(tokenizer);
     }
 
+    if tokenizer.parse_state.options.smart_punctuation {
+        resolve_smart_punctuation(tokenizer);
+    }
+
     tokenizer.map.consume(&mut tokenizer.events);
     None
 }
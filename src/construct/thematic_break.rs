@@ -7,7 +7,7 @@
 //!
 //! ```bnf
 //! ; Restriction: all markers must be identical.
-//! ; Restriction: at least 3 markers must be used.
+//! ; Restriction: at least `thematic_break_min` (default: 3) markers must be used.
 //! thematic_break ::= *space_or_tab 1*(1*marker *space_or_tab)
 //!
 //! marker ::= '*' | '-' | '_'
@@ -16,6 +16,9 @@
 //! As this construct occurs in flow, like all flow constructs, it must be
 //! followed by an eol (line ending) or eof (end of file).
 //!
+//! The number of markers needed can be configured with
+//! [`thematic_break_min`][thematic_break_min].
+//!
 //! ## HTML
 //!
 //! Thematic breaks in markdown typically relate to the HTML element `<hr>`.
@@ -54,13 +57,13 @@
 //! [flow]: crate::construct::flow
 //! [heading_setext]: crate::construct::heading_setext
 //! [list-item]: crate::construct::list_item
+//! [thematic_break_min]: crate::ParseOptions::thematic_break_min
 //! [html]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-hr-element
 
 use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
 use crate::event::Name;
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
-use crate::util::constant::{TAB_SIZE, THEMATIC_BREAK_MARKER_COUNT_MIN};
 
 /// Start of thematic break.
 ///
@@ -78,7 +81,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 tokenizer,
                 0,
                 if tokenizer.parse_state.options.constructs.code_indented {
-                    TAB_SIZE - 1
+                    tokenizer.parse_state.options.tab_size - 1
                 } else {
                     usize::MAX
                 },
@@ -117,7 +120,7 @@ pub fn at_break(tokenizer: &mut Tokenizer) -> State {
     if tokenizer.current == Some(tokenizer.tokenize_state.marker) {
         tokenizer.enter(Name::ThematicBreakSequence);
         State::Retry(StateName::ThematicBreakSequence)
-    } else if tokenizer.tokenize_state.size >= THEMATIC_BREAK_MARKER_COUNT_MIN
+    } else if tokenizer.tokenize_state.size >= tokenizer.parse_state.options.thematic_break_min
         && matches!(tokenizer.current, None | Some(b'\n'))
     {
         tokenizer.tokenize_state.marker = 0;
@@ -0,0 +1,309 @@
+//! Wikilink occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Wikilink forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! wikilink ::= '[[' target [ '|' label ] ']]'
+//!
+//! ; Restriction: the first `]]` closes the wikilink.
+//! target ::= 1*( line - '|' )
+//! label ::= 1*line
+//! ```
+//!
+//! The target and label cannot contain line endings, and they cannot contain
+//! markdown: they are plain text.
+//! Only the first `|` splits the target from the label, so the label can
+//! contain more `|`s.
+//! When there is no label, the target is used as the label.
+//! The target and the label can each be at most `999` bytes
+//! ([`WIKILINK_SIZE_MAX`][wikilink_size_max]), to safeguard performance.
+//!
+//! Like links, wikilinks cannot contain links: when a wikilink is formed
+//! inside the label of a link, that link cannot be formed anymore.
+//!
+//! Wikilinks are tried before [label start (link)][label_start_link], so
+//! `[[a]]` is a wikilink when this construct is turned on.
+//! When a wikilink cannot be formed, such as for `[[]]`, the brackets are
+//! parsed as they would be otherwise.
+//!
+//! ## HTML
+//!
+//! Wikilinks relate to the `<a>` element in HTML.
+//! See [*§ 4.5.1 The `a` element*][html_a] in the HTML spec for more info.
+//! The `href` is made from the target: by default, with GitHub-style slugs
+//! (`[[Page Name]]` links to `page-name`).
+//! Pass [`wikilink_href_fn`][crate::CompileOptions::wikilink_href_fn] to
+//! change that.
+//!
+//! ## Tokens
+//!
+//! *   [`WikiLink`][Name::WikiLink]
+//! *   [`WikiLinkLabel`][Name::WikiLinkLabel]
+//! *   [`WikiLinkMarker`][Name::WikiLinkMarker]
+//! *   [`WikiLinkTarget`][Name::WikiLinkTarget]
+//!
+//! ## References
+//!
+//! *   [`micromark-extension-wiki-link`](https://github.com/landakram/micromark-extension-wiki-link)
+//!
+//! [text]: crate::construct::text
+//! [label_start_link]: crate::construct::label_start_link
+//! [wikilink_size_max]: crate::util::constant::WIKILINK_SIZE_MAX
+//! [html_a]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-a-element
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::{LabelKind, Tokenizer};
+use crate::util::constant::WIKILINK_SIZE_MAX;
+
+/// Start of wikilink.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.wikilink && tokenizer.current == Some(b'[') {
+        tokenizer.enter(Name::WikiLink);
+        tokenizer.enter(Name::WikiLinkMarker);
+        tokenizer.consume();
+        State::Next(StateName::WikiLinkOpen)
+    } else {
+        State::Nok
+    }
+}
+
+/// After first `[`, at second.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///        ^
+/// ```
+pub fn open(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'[') {
+        tokenizer.consume();
+        tokenizer.exit(Name::WikiLinkMarker);
+        State::Next(StateName::WikiLinkTargetStart)
+    } else {
+        State::Nok
+    }
+}
+
+/// After opening, at target.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///         ^
+/// ```
+pub fn target_start(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.size = 0;
+
+    match tokenizer.current {
+        None | Some(b'\n' | b'|' | b']') => State::Nok,
+        _ => {
+            tokenizer.enter(Name::WikiLinkTarget);
+            State::Retry(StateName::WikiLinkTargetInside)
+        }
+    }
+}
+
+/// In target.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///         ^
+/// ```
+pub fn target_inside(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.tokenize_state.size > WIKILINK_SIZE_MAX {
+        tokenizer.tokenize_state.size = 0;
+        return State::Nok;
+    }
+
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.tokenize_state.size = 0;
+            State::Nok
+        }
+        Some(b'|') => {
+            tokenizer.tokenize_state.size = 0;
+            tokenizer.exit(Name::WikiLinkTarget);
+            tokenizer.enter(Name::WikiLinkMarker);
+            tokenizer.consume();
+            tokenizer.exit(Name::WikiLinkMarker);
+            State::Next(StateName::WikiLinkLabelStart)
+        }
+        Some(b']') => {
+            tokenizer.check(
+                State::Next(StateName::WikiLinkTargetClose),
+                State::Next(StateName::WikiLinkTargetBracket),
+            );
+            State::Retry(StateName::WikiLinkCloseCheck)
+        }
+        _ => {
+            tokenizer.tokenize_state.size += 1;
+            tokenizer.consume();
+            State::Next(StateName::WikiLinkTargetInside)
+        }
+    }
+}
+
+/// In target, at a `]` that does not close.
+///
+/// ```markdown
+/// > | a [[b]c]] d
+///          ^
+/// ```
+pub fn target_bracket(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.size += 1;
+    tokenizer.consume();
+    State::Next(StateName::WikiLinkTargetInside)
+}
+
+/// After target, at `]]`.
+///
+/// ```markdown
+/// > | a [[b]] c
+///          ^
+/// ```
+pub fn target_close(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.size = 0;
+    tokenizer.exit(Name::WikiLinkTarget);
+    State::Retry(StateName::WikiLinkCloseStart)
+}
+
+/// After `|`, at label.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///           ^
+/// ```
+pub fn label_start(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n' | b']') => State::Nok,
+        _ => {
+            tokenizer.enter(Name::WikiLinkLabel);
+            State::Retry(StateName::WikiLinkLabelInside)
+        }
+    }
+}
+
+/// In label.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///           ^
+/// ```
+pub fn label_inside(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.tokenize_state.size > WIKILINK_SIZE_MAX {
+        tokenizer.tokenize_state.size = 0;
+        return State::Nok;
+    }
+
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.tokenize_state.size = 0;
+            State::Nok
+        }
+        Some(b']') => {
+            tokenizer.check(
+                State::Next(StateName::WikiLinkLabelClose),
+                State::Next(StateName::WikiLinkLabelBracket),
+            );
+            State::Retry(StateName::WikiLinkCloseCheck)
+        }
+        _ => {
+            tokenizer.tokenize_state.size += 1;
+            tokenizer.consume();
+            State::Next(StateName::WikiLinkLabelInside)
+        }
+    }
+}
+
+/// In label, at a `]` that does not close.
+///
+/// ```markdown
+/// > | a [[b|c]d]] e
+///            ^
+/// ```
+pub fn label_bracket(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.size += 1;
+    tokenizer.consume();
+    State::Next(StateName::WikiLinkLabelInside)
+}
+
+/// After label, at `]]`.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///            ^
+/// ```
+pub fn label_close(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.size = 0;
+    tokenizer.exit(Name::WikiLinkLabel);
+    State::Retry(StateName::WikiLinkCloseStart)
+}
+
+/// At `]`, which might close.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///            ^
+/// ```
+pub fn close_check(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.consume();
+    State::Next(StateName::WikiLinkCloseCheckAfter)
+}
+
+/// After `]`, at `]` if it closes.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///             ^
+/// ```
+pub fn close_check_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b']') {
+        State::Ok
+    } else {
+        State::Nok
+    }
+}
+
+/// At first `]` of closing.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///            ^
+/// ```
+pub fn close_start(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter(Name::WikiLinkMarker);
+    tokenizer.consume();
+    State::Next(StateName::WikiLinkCloseEnd)
+}
+
+/// At second `]` of closing.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///             ^
+/// ```
+pub fn close_end(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.consume();
+    tokenizer.exit(Name::WikiLinkMarker);
+    tokenizer.exit(Name::WikiLink);
+
+    // Like links, mark earlier link starts as no longer viable, as they would
+    // otherwise contain a link.
+    let mut index = 0;
+    while index < tokenizer.tokenize_state.label_starts.len() {
+        let label_start = &mut tokenizer.tokenize_state.label_starts[index];
+        if label_start.kind != LabelKind::Image && label_start.kind != LabelKind::InlineFootnote {
+            label_start.inactive = true;
+        }
+        index += 1;
+    }
+
+    State::Ok
+}
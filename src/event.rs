@@ -1,7 +1,18 @@
 //! Semantic labels of things happening.
+//!
+//! Markdown is parsed into a flat list of [`Event`][]s: each thing (such as
+//! a heading or emphasis) starts with an [`Enter`][Kind::Enter] event and
+//! ends with an [`Exit`][Kind::Exit] event, and the things inside it are
+//! between them.
+//! These events are what the compilers (to HTML, to mdast) walk.
+//! They can be accessed through [`ParseResult::events`][crate::ParseResult::events],
+//! walked with [`visit()`][], and the markdown they come from can be taken with
+//! [`ParseResult::slice()`][crate::ParseResult::slice].
 
+use crate::configuration::ColumnUnit;
 use crate::unist;
-use crate::util::constant::TAB_SIZE;
+use crate::util::char::column_size;
+use alloc::vec;
 
 /// Semantic label of a span.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -847,6 +858,444 @@ pub enum Name {
     ///             ^
     /// ```
     DefinitionTitleString,
+    /// Directive (container).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [document content][crate::construct::document]
+    /// *   **Content model**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [`LineEnding`][Name::LineEnding],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab],
+    ///     [flow content][crate::construct::flow]
+    /// *   **Construct**:
+    ///     [`directive_container`][crate::construct::directive_container]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :::a
+    ///     ^^^^
+    /// > | b
+    ///     ^
+    /// > | :::
+    ///     ^^^
+    /// ```
+    DirectiveContainer,
+    /// Directive (container) fence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainer`][Name::DirectiveContainer]
+    /// *   **Content model**:
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes],
+    ///     [`DirectiveContainerSequence`][Name::DirectiveContainerSequence],
+    ///     [`DirectiveLabel`][Name::DirectiveLabel],
+    ///     [`DirectiveName`][Name::DirectiveName],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`directive_container`][crate::construct::directive_container]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :::a
+    ///     ^^^^
+    ///   | b
+    /// > | :::
+    ///     ^^^
+    /// ```
+    DirectiveContainerFence,
+    /// Directive (container) sequence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_container`][crate::construct::directive_container]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :::a
+    ///     ^^^
+    ///   | b
+    /// > | :::
+    ///     ^^^
+    /// ```
+    DirectiveContainerSequence,
+    /// Directive (leaf).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [flow content][crate::construct::flow]
+    /// *   **Content model**:
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes],
+    ///     [`DirectiveLabel`][Name::DirectiveLabel],
+    ///     [`DirectiveLeafSequence`][Name::DirectiveLeafSequence],
+    ///     [`DirectiveName`][Name::DirectiveName],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`directive_leaf`][crate::construct::directive_leaf]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ::a[b]{c}
+    ///     ^^^^^^^^^
+    /// ```
+    DirectiveLeaf,
+    /// Directive (leaf) sequence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveLeaf`][Name::DirectiveLeaf]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_leaf`][crate::construct::directive_leaf]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ::a
+    ///     ^^
+    /// ```
+    DirectiveLeafSequence,
+    /// Directive (text).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes],
+    ///     [`DirectiveLabel`][Name::DirectiveLabel],
+    ///     [`DirectiveName`][Name::DirectiveName],
+    ///     [`DirectiveTextMarker`][Name::DirectiveTextMarker]
+    /// *   **Construct**:
+    ///     [`directive_text`][crate::construct::directive_text]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b[c]{d} e
+    ///       ^^^^^^^^
+    /// ```
+    DirectiveText,
+    /// Directive (text) marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveText`][Name::DirectiveText]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_text`][crate::construct::directive_text]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b c
+    ///       ^
+    /// ```
+    DirectiveTextMarker,
+    /// Directive name.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [`DirectiveLeaf`][Name::DirectiveLeaf],
+    ///     [`DirectiveText`][Name::DirectiveText]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_name`][crate::construct::partial_directive_name]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b c
+    ///        ^
+    /// ```
+    #[allow(clippy::enum_variant_names)]
+    DirectiveName,
+    /// Directive label.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [`DirectiveLeaf`][Name::DirectiveLeaf],
+    ///     [`DirectiveText`][Name::DirectiveText]
+    /// *   **Content model**:
+    ///     [`DirectiveLabelMarker`][Name::DirectiveLabelMarker],
+    ///     [`DirectiveLabelString`][Name::DirectiveLabelString]
+    /// *   **Construct**:
+    ///     [`directive_label`][crate::construct::partial_directive_label]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b[c] d
+    ///         ^^^
+    /// ```
+    DirectiveLabel,
+    /// Directive label marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveLabel`][Name::DirectiveLabel]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_label`][crate::construct::partial_directive_label]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b[c] d
+    ///         ^ ^
+    /// ```
+    DirectiveLabelMarker,
+    /// Directive label string.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveLabel`][Name::DirectiveLabel]
+    /// *   **Content model**:
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`directive_label`][crate::construct::partial_directive_label]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b[c] d
+    ///          ^
+    /// ```
+    DirectiveLabelString,
+    /// Directive attributes.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [`DirectiveLeaf`][Name::DirectiveLeaf],
+    ///     [`DirectiveText`][Name::DirectiveText]
+    /// *   **Content model**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute],
+    ///     [`DirectiveAttributesMarker`][Name::DirectiveAttributesMarker],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`directive_attributes`][crate::construct::partial_directive_attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b{#c .d e="f"} g
+    ///         ^^^^^^^^^^^^^
+    /// ```
+    DirectiveAttributes,
+    /// Directive attributes marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_attributes`][crate::construct::partial_directive_attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b{c} d
+    ///         ^ ^
+    /// ```
+    DirectiveAttributesMarker,
+    /// Directive attribute.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes]
+    /// *   **Content model**:
+    ///     [`DirectiveAttributeClass`][Name::DirectiveAttributeClass],
+    ///     [`DirectiveAttributeClassMarker`][Name::DirectiveAttributeClassMarker],
+    ///     [`DirectiveAttributeId`][Name::DirectiveAttributeId],
+    ///     [`DirectiveAttributeIdMarker`][Name::DirectiveAttributeIdMarker],
+    ///     [`DirectiveAttributeInitializerMarker`][Name::DirectiveAttributeInitializerMarker],
+    ///     [`DirectiveAttributeName`][Name::DirectiveAttributeName],
+    ///     [`DirectiveAttributeValue`][Name::DirectiveAttributeValue],
+    ///     [`DirectiveAttributeValueMarker`][Name::DirectiveAttributeValueMarker]
+    /// *   **Construct**:
+    ///     [`directive_attributes`][crate::construct::partial_directive_attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b{#c .d e="f"} g
+    ///          ^^ ^^ ^^^^^
+    /// ```
+    DirectiveAttribute,
+    /// Directive attribute id marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_attributes`][crate::construct::partial_directive_attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b{#c} d
+    ///          ^
+    /// ```
+    DirectiveAttributeIdMarker,
+    /// Directive attribute id.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_attributes`][crate::construct::partial_directive_attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b{#c} d
+    ///           ^
+    /// ```
+    DirectiveAttributeId,
+    /// Directive attribute class marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_attributes`][crate::construct::partial_directive_attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b{.c} d
+    ///          ^
+    /// ```
+    DirectiveAttributeClassMarker,
+    /// Directive attribute class.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_attributes`][crate::construct::partial_directive_attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b{.c} d
+    ///           ^
+    /// ```
+    DirectiveAttributeClass,
+    /// Directive attribute name.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_attributes`][crate::construct::partial_directive_attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b{c="d"} e
+    ///          ^
+    /// ```
+    #[allow(clippy::enum_variant_names)]
+    DirectiveAttributeName,
+    /// Directive attribute initializer marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_attributes`][crate::construct::partial_directive_attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b{c="d"} e
+    ///           ^
+    /// ```
+    DirectiveAttributeInitializerMarker,
+    /// Directive attribute value marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_attributes`][crate::construct::partial_directive_attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b{c="d"} e
+    ///            ^ ^
+    /// ```
+    DirectiveAttributeValueMarker,
+    /// Directive attribute value.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     [string content][crate::construct::string]
+    /// *   **Construct**:
+    ///     [`directive_attributes`][crate::construct::partial_directive_attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b{c="d"} e
+    ///             ^
+    /// ```
+    DirectiveAttributeValue,
     /// Emphasis.
     ///
     /// ## Info
@@ -1650,8 +2099,27 @@ pub enum Name {
     ///      ^
     /// > | b
     /// ```
-    HardBreakEscape,
-    /// Whole hard break (trailing).
+    HardBreakEscape,
+    /// Whole hard break (trailing).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`whitespace`][crate::construct::partial_whitespace]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a␠␠␊
+    ///      ^^
+    /// > | b
+    /// ```
+    HardBreakTrailing,
+    /// Hashtag.
     ///
     /// ## Info
     ///
@@ -1660,16 +2128,15 @@ pub enum Name {
     /// *   **Content model**:
     ///     void
     /// *   **Construct**:
-    ///     [`whitespace`][crate::construct::partial_whitespace]
+    ///     [`hashtag`][crate::construct::hashtag]
     ///
     /// ## Example
     ///
     /// ```markdown
-    /// > | a␠␠␊
-    ///      ^^
-    /// > | b
+    /// > | a #b c
+    ///       ^^
     /// ```
-    HardBreakTrailing,
+    Hashtag,
     /// Whole heading (atx).
     ///
     /// ## Info
@@ -1677,6 +2144,7 @@ pub enum Name {
     /// *   **Context**:
     ///     [flow content][crate::construct::flow]
     /// *   **Content model**:
+    ///     [`HeadingAtxAttributes`][Name::HeadingAtxAttributes],
     ///     [`HeadingAtxSequence`][Name::HeadingAtxSequence],
     ///     [`HeadingAtxText`][Name::HeadingAtxText],
     ///     [`SpaceOrTab`][Name::SpaceOrTab]
@@ -1690,6 +2158,24 @@ pub enum Name {
     ///     ^^^^^^^
     /// ```
     HeadingAtx,
+    /// Heading (atx) attribute list.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`HeadingAtx`][Name::HeadingAtx]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`heading_atx`][crate::construct::heading_atx]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | # alpha {#b .c}
+    ///             ^^^^^^^
+    /// ```
+    HeadingAtxAttributes,
     /// Heading (atx) sequence.
     ///
     /// ## Info
@@ -1907,15 +2393,35 @@ pub enum Name {
     ///       ^^^^^^^
     /// ```
     Image,
+    /// Inline footnote.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`Label`][Name::Label]
+    /// *   **Construct**:
+    ///     [`label_end`][crate::construct::label_end]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^^^^
+    /// ```
+    InlineFootnote,
     /// Label.
     ///
     /// ## Info
     ///
     /// *   **Context**:
     ///     [`Image`][Name::Image],
+    ///     [`InlineFootnote`][Name::InlineFootnote],
     ///     [`Link`][Name::Link]
     /// *   **Content model**:
     ///     [`LabelImage`][Name::LabelImage],
+    ///     [`LabelInlineFootnote`][Name::LabelInlineFootnote],
     ///     [`LabelLink`][Name::LabelLink],
     ///     [`LabelEnd`][Name::LabelEnd],
     ///     [`LabelText`][Name::LabelText]
@@ -1990,6 +2496,43 @@ pub enum Name {
     ///       ^
     /// ```
     LabelImageMarker,
+    /// Label start (inline footnote).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Label`][Name::Label]
+    /// *   **Content model**:
+    ///     [`LabelInlineFootnoteMarker`][Name::LabelInlineFootnoteMarker],
+    ///     [`LabelMarker`][Name::LabelMarker]
+    /// *   **Construct**:
+    ///     [`label_start_inline_footnote`][crate::construct::label_start_inline_footnote]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^^
+    /// ```
+    LabelInlineFootnote,
+    /// Label start (inline footnote) marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`LabelInlineFootnote`][Name::LabelInlineFootnote]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`label_start_inline_footnote`][crate::construct::label_start_inline_footnote]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^
+    /// ```
+    LabelInlineFootnoteMarker,
     /// Label start (link).
     ///
     /// ## Info
@@ -2014,12 +2557,14 @@ pub enum Name {
     ///
     /// *   **Context**:
     ///     [`LabelImage`][Name::LabelImage],
+    ///     [`LabelInlineFootnote`][Name::LabelInlineFootnote],
     ///     [`LabelLink`][Name::LabelLink],
     ///     [`LabelEnd`][Name::LabelEnd]
     /// *   **Content model**:
     ///     void
     /// *   **Construct**:
     ///     [`label_start_image`][crate::construct::label_start_image],
+    ///     [`label_start_inline_footnote`][crate::construct::label_start_inline_footnote],
     ///     [`label_start_link`][crate::construct::label_start_link],
     ///     [`label_end`][crate::construct::label_end]
     ///
@@ -2227,6 +2772,61 @@ pub enum Name {
     ///     ^^^
     /// ```
     ListUnordered,
+    /// Mark (highlight).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`MarkSequence`][Name::MarkSequence],
+    ///     [`MarkText`][Name::MarkText]
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ==a==
+    ///     ^^^^^
+    /// ```
+    Mark,
+    /// Mark (highlight) sequence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Mark`][Name::Mark]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ==a==
+    ///     ^^ ^^
+    /// ```
+    MarkSequence,
+    /// Mark (highlight) text.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Mark`][Name::Mark]
+    /// *   **Content model**:
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ==a==
+    ///       ^
+    /// ```
+    MarkText,
     /// Whole math (flow).
     ///
     /// ## Info
@@ -2997,6 +3597,24 @@ pub enum Name {
     ///          ^
     /// ```
     MdxJsxTagSelfClosingMarker,
+    /// Mention.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`mention`][crate::construct::mention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a @b c
+    ///       ^^
+    /// ```
+    Mention,
     /// Paragraph.
     ///
     /// ## Info
@@ -3266,6 +3884,89 @@ pub enum Name {
     ///                 ^
     /// ```
     ResourceTitleString,
+    /// Smart punctuation: dash.
+    ///
+    /// Represents two or more dashes (`-`), which turn into en dashes (`–`)
+    /// and em dashes (`—`).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_smart_punctuation`][crate::construct::partial_smart_punctuation]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a--b
+    ///      ^^
+    /// ```
+    SmartPunctuationDash,
+    /// Smart punctuation: ellipsis.
+    ///
+    /// Represents three dots (`...`), which turn into an ellipsis (`…`).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_smart_punctuation`][crate::construct::partial_smart_punctuation]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a...
+    ///      ^^^
+    /// ```
+    SmartPunctuationEllipsis,
+    /// Smart punctuation: closing quote.
+    ///
+    /// Represents a straight quote (`"` or `'`), which turns into a right
+    /// quote (`”` or `’`).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_smart_punctuation`][crate::construct::partial_smart_punctuation]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | "a" b's
+    ///       ^  ^
+    /// ```
+    SmartPunctuationQuoteClosing,
+    /// Smart punctuation: opening quote.
+    ///
+    /// Represents a straight quote (`"` or `'`), which turns into a left
+    /// quote (`“` or `‘`).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_smart_punctuation`][crate::construct::partial_smart_punctuation]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | "a"
+    ///     ^
+    /// ```
+    SmartPunctuationQuoteOpening,
     /// Space or tab.
     ///
     /// ## Info
@@ -3376,10 +4077,537 @@ pub enum Name {
     ///     ^ ^ ^
     /// ```
     ThematicBreakSequence,
+    /// Whole wikilink.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`WikiLinkLabel`][Name::WikiLinkLabel],
+    ///     [`WikiLinkMarker`][Name::WikiLinkMarker],
+    ///     [`WikiLinkTarget`][Name::WikiLinkTarget]
+    /// *   **Construct**:
+    ///     [`wikilink`][crate::construct::wikilink]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a [[b|c]] d
+    ///       ^^^^^^^
+    /// ```
+    WikiLink,
+    /// Wikilink label.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`WikiLink`][Name::WikiLink]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`wikilink`][crate::construct::wikilink]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a [[b|c]] d
+    ///           ^
+    /// ```
+    WikiLinkLabel,
+    /// Wikilink marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`WikiLink`][Name::WikiLink]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`wikilink`][crate::construct::wikilink]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a [[b|c]] d
+    ///       ^^ ^ ^^
+    /// ```
+    WikiLinkMarker,
+    /// Wikilink target.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`WikiLink`][Name::WikiLink]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`wikilink`][crate::construct::wikilink]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a [[b|c]] d
+    ///         ^
+    /// ```
+    WikiLinkTarget,
+}
+
+impl Name {
+    /// List of all names, in the order they are defined in.
+    ///
+    /// This can be used to check that all names are handled, such as by a
+    /// compiler.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::event::Name;
+    ///
+    /// assert!(Name::ALL.contains(&Name::Paragraph));
+    /// ```
+    pub const ALL: [Name; 209] = [
+        Name::AttentionSequence,
+        Name::Autolink,
+        Name::AutolinkEmail,
+        Name::AutolinkMarker,
+        Name::AutolinkProtocol,
+        Name::BlankLineEnding,
+        Name::BlockQuote,
+        Name::BlockQuoteMarker,
+        Name::BlockQuotePrefix,
+        Name::ByteOrderMark,
+        Name::CharacterEscape,
+        Name::CharacterEscapeMarker,
+        Name::CharacterEscapeValue,
+        Name::CharacterReference,
+        Name::CharacterReferenceMarker,
+        Name::CharacterReferenceMarkerHexadecimal,
+        Name::CharacterReferenceMarkerNumeric,
+        Name::CharacterReferenceMarkerSemi,
+        Name::CharacterReferenceValue,
+        Name::CodeFenced,
+        Name::CodeFencedFence,
+        Name::CodeFencedFenceInfo,
+        Name::CodeFencedFenceMeta,
+        Name::CodeFencedFenceSequence,
+        Name::CodeFlowChunk,
+        Name::CodeIndented,
+        Name::CodeText,
+        Name::CodeTextData,
+        Name::CodeTextSequence,
+        Name::Content,
+        Name::Data,
+        Name::Definition,
+        Name::DefinitionDestination,
+        Name::DefinitionDestinationLiteral,
+        Name::DefinitionDestinationLiteralMarker,
+        Name::DefinitionDestinationRaw,
+        Name::DefinitionDestinationString,
+        Name::DefinitionLabel,
+        Name::DefinitionLabelMarker,
+        Name::DefinitionLabelString,
+        Name::DefinitionMarker,
+        Name::DefinitionTitle,
+        Name::DefinitionTitleMarker,
+        Name::DefinitionTitleString,
+        Name::DirectiveContainer,
+        Name::DirectiveContainerFence,
+        Name::DirectiveContainerSequence,
+        Name::DirectiveLeaf,
+        Name::DirectiveLeafSequence,
+        Name::DirectiveText,
+        Name::DirectiveTextMarker,
+        Name::DirectiveName,
+        Name::DirectiveLabel,
+        Name::DirectiveLabelMarker,
+        Name::DirectiveLabelString,
+        Name::DirectiveAttributes,
+        Name::DirectiveAttributesMarker,
+        Name::DirectiveAttribute,
+        Name::DirectiveAttributeIdMarker,
+        Name::DirectiveAttributeId,
+        Name::DirectiveAttributeClassMarker,
+        Name::DirectiveAttributeClass,
+        Name::DirectiveAttributeName,
+        Name::DirectiveAttributeInitializerMarker,
+        Name::DirectiveAttributeValueMarker,
+        Name::DirectiveAttributeValue,
+        Name::Emphasis,
+        Name::EmphasisSequence,
+        Name::EmphasisText,
+        Name::Frontmatter,
+        Name::FrontmatterChunk,
+        Name::FrontmatterFence,
+        Name::FrontmatterSequence,
+        Name::GfmAutolinkLiteralEmail,
+        Name::GfmAutolinkLiteralMailto,
+        Name::GfmAutolinkLiteralProtocol,
+        Name::GfmAutolinkLiteralWww,
+        Name::GfmAutolinkLiteralXmpp,
+        Name::GfmFootnoteCall,
+        Name::GfmFootnoteCallLabel,
+        Name::GfmFootnoteCallMarker,
+        Name::GfmFootnoteDefinition,
+        Name::GfmFootnoteDefinitionPrefix,
+        Name::GfmFootnoteDefinitionLabel,
+        Name::GfmFootnoteDefinitionLabelMarker,
+        Name::GfmFootnoteDefinitionLabelString,
+        Name::GfmFootnoteDefinitionMarker,
+        Name::GfmStrikethrough,
+        Name::GfmStrikethroughSequence,
+        Name::GfmStrikethroughText,
+        Name::GfmTable,
+        Name::GfmTableBody,
+        Name::GfmTableCell,
+        Name::GfmTableCellText,
+        Name::GfmTableCellDivider,
+        Name::GfmTableDelimiterRow,
+        Name::GfmTableDelimiterMarker,
+        Name::GfmTableDelimiterCell,
+        Name::GfmTableDelimiterCellValue,
+        Name::GfmTableDelimiterFiller,
+        Name::GfmTableHead,
+        Name::GfmTableRow,
+        Name::GfmTaskListItemCheck,
+        Name::GfmTaskListItemMarker,
+        Name::GfmTaskListItemValueChecked,
+        Name::GfmTaskListItemValueUnchecked,
+        Name::HardBreakEscape,
+        Name::HardBreakTrailing,
+        Name::Hashtag,
+        Name::HeadingAtx,
+        Name::HeadingAtxAttributes,
+        Name::HeadingAtxSequence,
+        Name::HeadingAtxText,
+        Name::HeadingSetext,
+        Name::HeadingSetextText,
+        Name::HeadingSetextUnderline,
+        Name::HeadingSetextUnderlineSequence,
+        Name::HtmlFlow,
+        Name::HtmlFlowData,
+        Name::HtmlText,
+        Name::HtmlTextData,
+        Name::Image,
+        Name::InlineFootnote,
+        Name::Label,
+        Name::LabelEnd,
+        Name::LabelImage,
+        Name::LabelImageMarker,
+        Name::LabelInlineFootnote,
+        Name::LabelInlineFootnoteMarker,
+        Name::LabelLink,
+        Name::LabelMarker,
+        Name::LabelText,
+        Name::LineEnding,
+        Name::Link,
+        Name::ListItem,
+        Name::ListItemMarker,
+        Name::ListItemPrefix,
+        Name::ListItemValue,
+        Name::ListOrdered,
+        Name::ListUnordered,
+        Name::Mark,
+        Name::MarkSequence,
+        Name::MarkText,
+        Name::MathFlow,
+        Name::MathFlowFence,
+        Name::MathFlowFenceMeta,
+        Name::MathFlowFenceSequence,
+        Name::MathFlowChunk,
+        Name::MathText,
+        Name::MathTextData,
+        Name::MathTextSequence,
+        Name::MdxEsm,
+        Name::MdxEsmData,
+        Name::MdxExpressionMarker,
+        Name::MdxExpressionData,
+        Name::MdxFlowExpression,
+        Name::MdxTextExpression,
+        Name::MdxJsxFlowTag,
+        Name::MdxJsxTextTag,
+        Name::MdxJsxEsWhitespace,
+        Name::MdxJsxTagMarker,
+        Name::MdxJsxTagClosingMarker,
+        Name::MdxJsxTagName,
+        Name::MdxJsxTagNamePrimary,
+        Name::MdxJsxTagNameMemberMarker,
+        Name::MdxJsxTagNamePrefixMarker,
+        Name::MdxJsxTagNameMember,
+        Name::MdxJsxTagNameLocal,
+        Name::MdxJsxTagAttribute,
+        Name::MdxJsxTagAttributeExpression,
+        Name::MdxJsxTagAttributeName,
+        Name::MdxJsxTagAttributePrimaryName,
+        Name::MdxJsxTagAttributeNamePrefixMarker,
+        Name::MdxJsxTagAttributeNameLocal,
+        Name::MdxJsxTagAttributeInitializerMarker,
+        Name::MdxJsxTagAttributeValueExpression,
+        Name::MdxJsxTagAttributeValueLiteral,
+        Name::MdxJsxTagAttributeValueLiteralMarker,
+        Name::MdxJsxTagAttributeValueLiteralValue,
+        Name::MdxJsxTagSelfClosingMarker,
+        Name::Mention,
+        Name::Paragraph,
+        Name::Reference,
+        Name::ReferenceMarker,
+        Name::ReferenceString,
+        Name::Resource,
+        Name::ResourceDestination,
+        Name::ResourceDestinationLiteral,
+        Name::ResourceDestinationLiteralMarker,
+        Name::ResourceDestinationRaw,
+        Name::ResourceDestinationString,
+        Name::ResourceMarker,
+        Name::ResourceTitle,
+        Name::ResourceTitleMarker,
+        Name::ResourceTitleString,
+        Name::SmartPunctuationDash,
+        Name::SmartPunctuationEllipsis,
+        Name::SmartPunctuationQuoteClosing,
+        Name::SmartPunctuationQuoteOpening,
+        Name::SpaceOrTab,
+        Name::Strong,
+        Name::StrongSequence,
+        Name::StrongText,
+        Name::ThematicBreak,
+        Name::ThematicBreakSequence,
+        Name::WikiLink,
+        Name::WikiLinkLabel,
+        Name::WikiLinkMarker,
+        Name::WikiLinkTarget,
+    ];
+
+    /// Get the index of this name in [`Name::ALL`].
+    ///
+    /// The match has no wildcard, so adding a name without adding it here
+    /// (and in [`Name::ALL`]) fails to compile.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::event::Name;
+    ///
+    /// assert_eq!(Name::ALL[Name::Paragraph.index()], Name::Paragraph);
+    /// ```
+    #[must_use]
+    pub const fn index(&self) -> usize {
+        match self {
+            Name::AttentionSequence => 0,
+            Name::Autolink => 1,
+            Name::AutolinkEmail => 2,
+            Name::AutolinkMarker => 3,
+            Name::AutolinkProtocol => 4,
+            Name::BlankLineEnding => 5,
+            Name::BlockQuote => 6,
+            Name::BlockQuoteMarker => 7,
+            Name::BlockQuotePrefix => 8,
+            Name::ByteOrderMark => 9,
+            Name::CharacterEscape => 10,
+            Name::CharacterEscapeMarker => 11,
+            Name::CharacterEscapeValue => 12,
+            Name::CharacterReference => 13,
+            Name::CharacterReferenceMarker => 14,
+            Name::CharacterReferenceMarkerHexadecimal => 15,
+            Name::CharacterReferenceMarkerNumeric => 16,
+            Name::CharacterReferenceMarkerSemi => 17,
+            Name::CharacterReferenceValue => 18,
+            Name::CodeFenced => 19,
+            Name::CodeFencedFence => 20,
+            Name::CodeFencedFenceInfo => 21,
+            Name::CodeFencedFenceMeta => 22,
+            Name::CodeFencedFenceSequence => 23,
+            Name::CodeFlowChunk => 24,
+            Name::CodeIndented => 25,
+            Name::CodeText => 26,
+            Name::CodeTextData => 27,
+            Name::CodeTextSequence => 28,
+            Name::Content => 29,
+            Name::Data => 30,
+            Name::Definition => 31,
+            Name::DefinitionDestination => 32,
+            Name::DefinitionDestinationLiteral => 33,
+            Name::DefinitionDestinationLiteralMarker => 34,
+            Name::DefinitionDestinationRaw => 35,
+            Name::DefinitionDestinationString => 36,
+            Name::DefinitionLabel => 37,
+            Name::DefinitionLabelMarker => 38,
+            Name::DefinitionLabelString => 39,
+            Name::DefinitionMarker => 40,
+            Name::DefinitionTitle => 41,
+            Name::DefinitionTitleMarker => 42,
+            Name::DefinitionTitleString => 43,
+            Name::DirectiveContainer => 44,
+            Name::DirectiveContainerFence => 45,
+            Name::DirectiveContainerSequence => 46,
+            Name::DirectiveLeaf => 47,
+            Name::DirectiveLeafSequence => 48,
+            Name::DirectiveText => 49,
+            Name::DirectiveTextMarker => 50,
+            Name::DirectiveName => 51,
+            Name::DirectiveLabel => 52,
+            Name::DirectiveLabelMarker => 53,
+            Name::DirectiveLabelString => 54,
+            Name::DirectiveAttributes => 55,
+            Name::DirectiveAttributesMarker => 56,
+            Name::DirectiveAttribute => 57,
+            Name::DirectiveAttributeIdMarker => 58,
+            Name::DirectiveAttributeId => 59,
+            Name::DirectiveAttributeClassMarker => 60,
+            Name::DirectiveAttributeClass => 61,
+            Name::DirectiveAttributeName => 62,
+            Name::DirectiveAttributeInitializerMarker => 63,
+            Name::DirectiveAttributeValueMarker => 64,
+            Name::DirectiveAttributeValue => 65,
+            Name::Emphasis => 66,
+            Name::EmphasisSequence => 67,
+            Name::EmphasisText => 68,
+            Name::Frontmatter => 69,
+            Name::FrontmatterChunk => 70,
+            Name::FrontmatterFence => 71,
+            Name::FrontmatterSequence => 72,
+            Name::GfmAutolinkLiteralEmail => 73,
+            Name::GfmAutolinkLiteralMailto => 74,
+            Name::GfmAutolinkLiteralProtocol => 75,
+            Name::GfmAutolinkLiteralWww => 76,
+            Name::GfmAutolinkLiteralXmpp => 77,
+            Name::GfmFootnoteCall => 78,
+            Name::GfmFootnoteCallLabel => 79,
+            Name::GfmFootnoteCallMarker => 80,
+            Name::GfmFootnoteDefinition => 81,
+            Name::GfmFootnoteDefinitionPrefix => 82,
+            Name::GfmFootnoteDefinitionLabel => 83,
+            Name::GfmFootnoteDefinitionLabelMarker => 84,
+            Name::GfmFootnoteDefinitionLabelString => 85,
+            Name::GfmFootnoteDefinitionMarker => 86,
+            Name::GfmStrikethrough => 87,
+            Name::GfmStrikethroughSequence => 88,
+            Name::GfmStrikethroughText => 89,
+            Name::GfmTable => 90,
+            Name::GfmTableBody => 91,
+            Name::GfmTableCell => 92,
+            Name::GfmTableCellText => 93,
+            Name::GfmTableCellDivider => 94,
+            Name::GfmTableDelimiterRow => 95,
+            Name::GfmTableDelimiterMarker => 96,
+            Name::GfmTableDelimiterCell => 97,
+            Name::GfmTableDelimiterCellValue => 98,
+            Name::GfmTableDelimiterFiller => 99,
+            Name::GfmTableHead => 100,
+            Name::GfmTableRow => 101,
+            Name::GfmTaskListItemCheck => 102,
+            Name::GfmTaskListItemMarker => 103,
+            Name::GfmTaskListItemValueChecked => 104,
+            Name::GfmTaskListItemValueUnchecked => 105,
+            Name::HardBreakEscape => 106,
+            Name::HardBreakTrailing => 107,
+            Name::Hashtag => 108,
+            Name::HeadingAtx => 109,
+            Name::HeadingAtxAttributes => 110,
+            Name::HeadingAtxSequence => 111,
+            Name::HeadingAtxText => 112,
+            Name::HeadingSetext => 113,
+            Name::HeadingSetextText => 114,
+            Name::HeadingSetextUnderline => 115,
+            Name::HeadingSetextUnderlineSequence => 116,
+            Name::HtmlFlow => 117,
+            Name::HtmlFlowData => 118,
+            Name::HtmlText => 119,
+            Name::HtmlTextData => 120,
+            Name::Image => 121,
+            Name::InlineFootnote => 122,
+            Name::Label => 123,
+            Name::LabelEnd => 124,
+            Name::LabelImage => 125,
+            Name::LabelImageMarker => 126,
+            Name::LabelInlineFootnote => 127,
+            Name::LabelInlineFootnoteMarker => 128,
+            Name::LabelLink => 129,
+            Name::LabelMarker => 130,
+            Name::LabelText => 131,
+            Name::LineEnding => 132,
+            Name::Link => 133,
+            Name::ListItem => 134,
+            Name::ListItemMarker => 135,
+            Name::ListItemPrefix => 136,
+            Name::ListItemValue => 137,
+            Name::ListOrdered => 138,
+            Name::ListUnordered => 139,
+            Name::Mark => 140,
+            Name::MarkSequence => 141,
+            Name::MarkText => 142,
+            Name::MathFlow => 143,
+            Name::MathFlowFence => 144,
+            Name::MathFlowFenceMeta => 145,
+            Name::MathFlowFenceSequence => 146,
+            Name::MathFlowChunk => 147,
+            Name::MathText => 148,
+            Name::MathTextData => 149,
+            Name::MathTextSequence => 150,
+            Name::MdxEsm => 151,
+            Name::MdxEsmData => 152,
+            Name::MdxExpressionMarker => 153,
+            Name::MdxExpressionData => 154,
+            Name::MdxFlowExpression => 155,
+            Name::MdxTextExpression => 156,
+            Name::MdxJsxFlowTag => 157,
+            Name::MdxJsxTextTag => 158,
+            Name::MdxJsxEsWhitespace => 159,
+            Name::MdxJsxTagMarker => 160,
+            Name::MdxJsxTagClosingMarker => 161,
+            Name::MdxJsxTagName => 162,
+            Name::MdxJsxTagNamePrimary => 163,
+            Name::MdxJsxTagNameMemberMarker => 164,
+            Name::MdxJsxTagNamePrefixMarker => 165,
+            Name::MdxJsxTagNameMember => 166,
+            Name::MdxJsxTagNameLocal => 167,
+            Name::MdxJsxTagAttribute => 168,
+            Name::MdxJsxTagAttributeExpression => 169,
+            Name::MdxJsxTagAttributeName => 170,
+            Name::MdxJsxTagAttributePrimaryName => 171,
+            Name::MdxJsxTagAttributeNamePrefixMarker => 172,
+            Name::MdxJsxTagAttributeNameLocal => 173,
+            Name::MdxJsxTagAttributeInitializerMarker => 174,
+            Name::MdxJsxTagAttributeValueExpression => 175,
+            Name::MdxJsxTagAttributeValueLiteral => 176,
+            Name::MdxJsxTagAttributeValueLiteralMarker => 177,
+            Name::MdxJsxTagAttributeValueLiteralValue => 178,
+            Name::MdxJsxTagSelfClosingMarker => 179,
+            Name::Mention => 180,
+            Name::Paragraph => 181,
+            Name::Reference => 182,
+            Name::ReferenceMarker => 183,
+            Name::ReferenceString => 184,
+            Name::Resource => 185,
+            Name::ResourceDestination => 186,
+            Name::ResourceDestinationLiteral => 187,
+            Name::ResourceDestinationLiteralMarker => 188,
+            Name::ResourceDestinationRaw => 189,
+            Name::ResourceDestinationString => 190,
+            Name::ResourceMarker => 191,
+            Name::ResourceTitle => 192,
+            Name::ResourceTitleMarker => 193,
+            Name::ResourceTitleString => 194,
+            Name::SmartPunctuationDash => 195,
+            Name::SmartPunctuationEllipsis => 196,
+            Name::SmartPunctuationQuoteClosing => 197,
+            Name::SmartPunctuationQuoteOpening => 198,
+            Name::SpaceOrTab => 199,
+            Name::Strong => 200,
+            Name::StrongSequence => 201,
+            Name::StrongText => 202,
+            Name::ThematicBreak => 203,
+            Name::ThematicBreakSequence => 204,
+            Name::WikiLink => 205,
+            Name::WikiLinkLabel => 206,
+            Name::WikiLinkMarker => 207,
+            Name::WikiLinkTarget => 208,
+        }
+    }
 }
 
 /// List of void events, used to make sure everything is working well.
-pub const VOID_EVENTS: [Name; 76] = [
+pub(crate) const VOID_EVENTS: [Name; 100] = [
     Name::AttentionSequence,
     Name::AutolinkEmail,
     Name::AutolinkMarker,
@@ -3403,6 +4631,19 @@ pub const VOID_EVENTS: [Name; 76] = [
     Name::DefinitionLabelMarker,
     Name::DefinitionMarker,
     Name::DefinitionTitleMarker,
+    Name::DirectiveAttributeClass,
+    Name::DirectiveAttributeClassMarker,
+    Name::DirectiveAttributeId,
+    Name::DirectiveAttributeIdMarker,
+    Name::DirectiveAttributeInitializerMarker,
+    Name::DirectiveAttributeName,
+    Name::DirectiveAttributeValueMarker,
+    Name::DirectiveAttributesMarker,
+    Name::DirectiveContainerSequence,
+    Name::DirectiveLabelMarker,
+    Name::DirectiveLeafSequence,
+    Name::DirectiveName,
+    Name::DirectiveTextMarker,
     Name::EmphasisSequence,
     Name::FrontmatterChunk,
     Name::GfmAutolinkLiteralEmail,
@@ -3421,15 +4662,18 @@ pub const VOID_EVENTS: [Name; 76] = [
     Name::FrontmatterSequence,
     Name::HardBreakEscape,
     Name::HardBreakTrailing,
+    Name::Hashtag,
     Name::HeadingAtxSequence,
     Name::HeadingSetextUnderlineSequence,
     Name::HtmlFlowData,
     Name::HtmlTextData,
     Name::LabelImageMarker,
+    Name::LabelInlineFootnoteMarker,
     Name::LabelMarker,
     Name::LineEnding,
     Name::ListItemMarker,
     Name::ListItemValue,
+    Name::MarkSequence,
     Name::MathFlowFenceSequence,
     Name::MathFlowChunk,
     Name::MathTextData,
@@ -3450,12 +4694,20 @@ pub const VOID_EVENTS: [Name; 76] = [
     Name::MdxJsxTagAttributeNameLocal,
     Name::MdxJsxTagAttributeValueLiteralMarker,
     Name::MdxJsxEsWhitespace,
+    Name::Mention,
     Name::ReferenceMarker,
     Name::ResourceMarker,
     Name::ResourceTitleMarker,
+    Name::SmartPunctuationDash,
+    Name::SmartPunctuationEllipsis,
+    Name::SmartPunctuationQuoteClosing,
+    Name::SmartPunctuationQuoteOpening,
     Name::SpaceOrTab,
     Name::StrongSequence,
     Name::ThematicBreakSequence,
+    Name::WikiLinkLabel,
+    Name::WikiLinkMarker,
+    Name::WikiLinkTarget,
 ];
 
 /// Embedded content type.
@@ -3470,10 +4722,13 @@ pub enum Content {
     String,
     /// Represents [text content][crate::construct::text].
     Text,
+    /// Represents raw content, such as the contents of code and math, which
+    /// is verbatim and not tokenized further.
+    Raw,
 }
 
 /// Link to another event.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Link {
     /// Previous event.
     pub previous: Option<usize>,
@@ -3487,7 +4742,7 @@ pub struct Link {
 ///
 /// The interface for the location in the document comes from unist
 /// [`Point`](https://github.com/syntax-tree/unist#point).
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Point {
     /// 1-indexed line number.
     pub line: usize,
@@ -3516,8 +4771,15 @@ impl Point {
     }
 
     /// Create a new point, that is shifted from the close earlier current
-    /// point, to `index`.
-    pub fn shift_to(&self, bytes: &[u8], index: usize) -> Point {
+    /// point, to `index`, with tab stops every `tab_size` columns, counting
+    /// other columns in `column_unit`.
+    pub(crate) fn shift_to(
+        &self,
+        bytes: &[u8],
+        index: usize,
+        tab_size: usize,
+        column_unit: ColumnUnit,
+    ) -> Point {
         let mut next = self.clone();
         debug_assert!(index > next.index, "expected to shift forward");
 
@@ -3525,18 +4787,18 @@ impl Point {
             match bytes[next.index] {
                 b'\n' | b'\r' => unreachable!("cannot move past line endings"),
                 b'\t' => {
-                    let remainder = next.column % TAB_SIZE;
+                    let remainder = next.column % tab_size;
                     let vs = if remainder == 0 {
                         0
                     } else {
-                        TAB_SIZE - remainder
+                        tab_size - remainder
                     };
                     next.index += 1;
                     next.column += 1 + vs;
                 }
                 _ => {
+                    next.column += column_size(bytes, next.index, column_unit);
                     next.index += 1;
-                    next.column += 1;
                 }
             }
         }
@@ -3555,7 +4817,7 @@ pub enum Kind {
 }
 
 /// Something semantic happening somewhere.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Event {
     /// Kind of event.
     pub kind: Kind,
@@ -3566,3 +4828,66 @@ pub struct Event {
     /// Link to another event.
     pub link: Option<Link>,
 }
+
+/// Something that walks events, with [`visit()`][].
+///
+/// Both methods do nothing by default, so implement the ones you need.
+pub trait Visitor {
+    /// Called for each [`Enter`][Kind::Enter] event.
+    fn enter(&mut self, _event: &Event) {}
+    /// Called for each [`Exit`][Kind::Exit] event.
+    fn exit(&mut self, _event: &Event) {}
+}
+
+/// Walk `events` with `visitor`.
+///
+/// Calls [`Visitor::enter`][] and [`Visitor::exit`][] for each event, in
+/// order.
+/// Every enter is paired with an exit of the same name, and everything
+/// between them is inside that thing.
+///
+/// Embedded content (such as the text in a paragraph) is already placed
+/// between the enter and exit of its parent when parsing, so the
+/// [`link`][Event::link]s do not have to be followed.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{event::{visit, Event, Name, Visitor}, parse, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// #[derive(Default)]
+/// struct Headings(usize);
+///
+/// impl Visitor for Headings {
+///     fn enter(&mut self, event: &Event) {
+///         if matches!(event.name, Name::HeadingAtx | Name::HeadingSetext) {
+///             self.0 += 1;
+///         }
+///     }
+/// }
+///
+/// let result = parse("# a\n\nb\n=", &ParseOptions::default())?;
+/// let mut headings = Headings::default();
+/// visit(&result.events, &mut headings);
+///
+/// assert_eq!(headings.0, 2);
+/// # Ok(())
+/// # }
+/// ```
+pub fn visit(events: &[Event], visitor: &mut impl Visitor) {
+    let mut stack = vec![];
+
+    for event in events {
+        if event.kind == Kind::Enter {
+            stack.push(&event.name);
+            visitor.enter(event);
+        } else {
+            let name = stack.pop();
+            debug_assert_eq!(name, Some(&event.name), "expected matching exit");
+            visitor.exit(event);
+        }
+    }
+
+    debug_assert!(stack.is_empty(), "expected all events to be exited");
+}
@@ -9,8 +9,29 @@
 //!     — like `to_html` but lets you configure how markdown is turned into
 //!     HTML, such as allowing dangerous HTML or turning on/off different
 //!     constructs (GFM, MDX, and the like)
+//! *   [`to_html_with_diagnostics()`][]
+//!     — like `to_html_with_options` but also returns warnings about the
+//!     markdown
+//! *   [`to_html_with_map()`][]
+//!     — like `to_html_with_options` but also returns where things in the
+//!     HTML come from
+//! *   [`to_html_inline()`][]
+//!     — like `to_html_with_options` but only for inline (phrasing)
+//!     markdown, without paragraphs around it
+//! *   [`to_html_blocks()`][]
+//!     — like `to_html_with_options` but split into top-level blocks,
+//!     such as to render a document progressively
 //! *   [`to_mdast()`][]
 //!     — turn markdown into a syntax tree
+//! *   [`parse()`][]
+//!     — get info about markdown, such as the definitions in it, and the
+//!     events it is made of (which can be walked with [`event::visit()`][])
+//! *   [`to_markdown()`][]
+//!     — turn markdown into normalized markdown
+//! *   [`to_plain_text()`][]
+//!     — get the text in markdown, without markup
+//! *   [`sanitize_url()`][]
+//!     — make a URL safe, the same way that links and images are
 //!
 //! ## Features
 //!
@@ -20,7 +41,10 @@
 //!     — enable serde to serialize the AST (includes `dep:serde`)
 //! *   **`log`**
 //!     — enable logging (includes `dep:log`);
-//!     you can show logs with `RUST_LOG=debug`
+//!     you can show logs with `RUST_LOG=debug`;
+//!     without it, no logging code is compiled in
+//! *   **`std`**
+//!     — enable `try_to_html()`, which needs `std` to catch panics
 
 #![no_std]
 #![deny(clippy::pedantic)]
@@ -34,15 +58,23 @@
 )]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 mod configuration;
 mod construct;
-mod event;
+// The docs of events link to constructs, which are documented when
+// documenting private items.
+#[allow(rustdoc::private_intra_doc_links)]
+pub mod event;
+mod parse_result;
 mod parser;
 mod resolve;
 mod state;
 mod subtokenize;
 mod to_html;
+mod to_markdown;
 mod to_mdast;
+mod to_plain_text;
 mod tokenizer;
 mod util;
 
@@ -62,16 +94,32 @@ pub use util::sanitize_uri::sanitize;
 #[doc(hidden)]
 pub use util::location::Location;
 
+pub use util::highlight::CodeHighlighterFn;
+
 pub use util::line_ending::LineEnding;
 
+pub use util::slug::{HeadingIdFn, WikilinkHrefFn};
+
 pub use util::mdx::{
     EsmParse as MdxEsmParse, ExpressionKind as MdxExpressionKind,
     ExpressionParse as MdxExpressionParse, Signal as MdxSignal,
 };
 
-pub use configuration::{CompileOptions, Constructs, Options, ParseOptions};
+pub use configuration::{
+    CharacterReferences, ColumnUnit, CompileOptions, Constructs, DroppedLinkBehavior,
+    GfmTableCellAlign, HtmlSanitization, HtmlSyntax, Options, OptionsBuilder, ParseOptions,
+};
+
+pub use parse_result::{CodeFence, Definition, ParseResult};
+
+pub use resolve::Resolver;
+
+pub use to_html::BlockKind;
 
-use alloc::string::String;
+use alloc::{
+    string::String,
+    vec::{IntoIter, Vec},
+};
 
 /// Turn markdown into HTML.
 ///
@@ -92,6 +140,9 @@ pub fn to_html(value: &str) -> String {
 
 /// Turn markdown into HTML, with configuration.
 ///
+/// The whole document is needed: markdown cannot be compiled in chunks, as
+/// a definition at its end can turn a reference at its start into a link.
+///
 /// ## Errors
 ///
 /// `to_html_with_options()` never errors with normal markdown because markdown
@@ -99,6 +150,21 @@ pub fn to_html(value: &str) -> String {
 /// However, MDX does have syntax errors.
 /// When MDX is turned on, there are several errors that can occur with how
 /// expressions, ESM, and JSX are written.
+/// When [`max_output_size`][CompileOptions::max_output_size] is set, it is
+/// also an error when the HTML gets bigger than it.
+/// Errors are [`Message`][message::Message]s, which include where
+/// (`place`) and why (`reason`) something went wrong.
+///
+/// ## Panics
+///
+/// `to_html_with_options()` should never panic, for any input.
+/// Internal checks (such as that tokens are closed in the order they were
+/// opened) can panic, but if that happens, that is a bug: please report it
+/// with the input that caused it.
+/// With the `std` feature, `try_to_html()` catches those panics, such as
+/// for use at an FFI boundary.
+/// When compiling to WebAssembly, panics abort instead, so they cannot be
+/// caught there: set a panic hook to at least see the message.
 ///
 /// ## Examples
 ///
@@ -126,12 +192,250 @@ pub fn to_html(value: &str) -> String {
 /// # }
 /// ```
 pub fn to_html_with_options(value: &str, options: &Options) -> Result<String, message::Message> {
-    let (events, parse_state) = parser::parse(value, &options.parse)?;
-    Ok(to_html::compile(
+    let (events, parse_state) = parser::parse(value, &options.parse, false)?;
+    let (html, _) = to_html::compile(
+        &events,
+        parse_state.bytes,
+        &options.parse,
+        &options.compile,
+        false,
+    )?;
+    Ok(html)
+}
+
+/// Turn markdown into HTML, with configuration, catching panics.
+///
+/// Like [`to_html_with_options()`][], but when one of its internal checks
+/// fails (which is a bug), the panic is caught and turned into an error,
+/// instead of unwinding into the caller.
+/// Panics in functions passed in options are caught too.
+/// The panic hook still runs, so the panic is still printed by default.
+///
+/// This is only available with the `std` feature.
+/// Panics can only be caught when they unwind: when they abort, such as
+/// with `panic = "abort"` or on `wasm32-unknown-unknown`, they cannot be.
+///
+/// ## Errors
+///
+/// Errors with the [`Message`][message::Message] of
+/// [`to_html_with_options()`][], turned into a string, or the message of a
+/// panic.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{try_to_html, Options};
+///
+/// assert_eq!(
+///     try_to_html("# Hi!", &Options::default()),
+///     Ok("<h1>Hi!</h1>".into())
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub fn try_to_html(value: &str, options: &Options) -> Result<String, String> {
+    use alloc::string::ToString;
+
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        to_html_with_options(value, options)
+    }))
+    .map_err(|payload| {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).into()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Unexpected panic".into()
+        }
+    })?
+    .map_err(|message| message.to_string())
+}
+
+/// Turn markdown into HTML, with warnings about the markdown.
+///
+/// This is like [`to_html_with_options()`][], but also returns a list of
+/// warnings, such as to show to authors.
+/// Markdown does not have syntax errors, but some things are likely not what
+/// the author meant.
+/// Currently, there is a warning for each reference (such as `[a]` or
+/// `[a][]`) that does not match a definition, and is thus not a link.
+///
+/// ## Errors
+///
+/// `to_html_with_diagnostics()` never errors with normal markdown because
+/// markdown does not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// See [`to_html_with_options()`][] for more info.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_html_with_diagnostics, Options};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let (html, diagnostics) = to_html_with_diagnostics("[a] [b]\n\n[b]: c", &Options::default())?;
+///
+/// assert_eq!(html, "<p>[a] <a href=\"c\">b</a></p>\n");
+/// assert_eq!(
+///     diagnostics[0].to_string(),
+///     "1:1-1:4: Unexpected reference to undefined definition `a` (markdown-rs:undefined-reference)"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_html_with_diagnostics(
+    value: &str,
+    options: &Options,
+) -> Result<(String, Vec<message::Message>), message::Message> {
+    let (events, parse_state) = parser::parse(value, &options.parse, true)?;
+    let (html, _) = to_html::compile(
         &events,
         parse_state.bytes,
+        &options.parse,
         &options.compile,
-    ))
+        false,
+    )?;
+    Ok((html, parse_state.diagnostics.unwrap_or_default()))
+}
+
+/// Turn markdown into HTML, with a map of where things in the HTML come from
+/// in the markdown.
+///
+/// This is like [`to_html_with_options()`][], but also returns a list of
+/// offsets in the HTML (in bytes) with the points in the markdown they
+/// come from, such as to find what markdown some HTML was made from.
+/// Offsets are where elements and text start and end, sorted from first
+/// to last, and each offset is listed once.
+/// To find the point for some offset in the HTML, take the last entry with
+/// an offset that is less than or equal to it.
+///
+/// Things that are not made right where they are found in markdown are not
+/// mapped, such as the contents of links, images, and headings, and the
+/// footnotes section.
+///
+/// ## Errors
+///
+/// `to_html_with_map()` never errors with normal markdown because markdown
+/// does not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// See [`to_html_with_options()`][] for more info.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_html_with_map, unist::Point, Options};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let (html, map) = to_html_with_map("# a\n\nb", &Options::default())?;
+///
+/// assert_eq!(html, "<h1>a</h1>\n<p>b</p>");
+/// // `<p>` comes from the paragraph at 3:1:
+/// assert_eq!(map[map.len() - 3], (11, Point::new(3, 1, 5)));
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_html_with_map(
+    value: &str,
+    options: &Options,
+) -> Result<(String, Vec<(usize, unist::Point)>), message::Message> {
+    let (events, parse_state) = parser::parse(value, &options.parse, false)?;
+    to_html::compile(
+        &events,
+        parse_state.bytes,
+        &options.parse,
+        &options.compile,
+        true,
+    )
+}
+
+/// Turn inline markdown into HTML.
+///
+/// This is like [`to_html_with_options()`][], but `value` is parsed as text
+/// (phrasing) content: only things such as emphasis, code (text), links, and
+/// autolinks are supported, and the result is not wrapped in a paragraph.
+/// Flow (block) constructs, such as headings and lists, are not supported,
+/// so their markers are kept as text.
+/// There are no definitions either, so references (such as `[a]`) are not
+/// links.
+/// This is useful for short snippets, such as the contents of a table cell
+/// that something else renders.
+///
+/// ## Errors
+///
+/// `to_html_inline()` never errors with normal markdown because markdown
+/// does not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// See [`to_html_with_options()`][] for more info.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_html_inline, Options};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// assert_eq!(to_html_inline("*hi*", &Options::default())?, "<em>hi</em>");
+/// assert_eq!(to_html_inline("# hi", &Options::default())?, "# hi");
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_html_inline(value: &str, options: &Options) -> Result<String, message::Message> {
+    let (events, parse_state) = parser::parse_inline(value, &options.parse)?;
+    let (html, _) = to_html::compile(
+        &events,
+        parse_state.bytes,
+        &options.parse,
+        &options.compile,
+        false,
+    )?;
+    Ok(html)
+}
+
+/// Turn markdown into HTML, split into top-level blocks.
+///
+/// This is like [`to_html_with_options()`][], but yields each top-level
+/// block (such as a paragraph, heading, or list) separately, with its kind
+/// and its HTML.
+/// Joining the HTML of all blocks together gives the same result as
+/// [`to_html_with_options()`][]: the line ending between two blocks is at
+/// the end of the first.
+///
+/// Some blocks produce no HTML, such as definitions and GFM footnote
+/// definitions.
+/// When there are GFM footnote calls, the footnote section is yielded last,
+/// as [`BlockKind::GfmFootnoteSection`][].
+///
+/// The whole document is parsed and compiled first, because references can
+/// come before definitions.
+///
+/// ## Errors
+///
+/// `to_html_blocks()` never errors with normal markdown because markdown
+/// does not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// See [`to_html_with_options()`][] for more info.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_html_blocks, BlockKind, Options};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut blocks = to_html_blocks("# a\n\nb", &Options::default())?;
+///
+/// assert_eq!(blocks.next(), Some((BlockKind::HeadingAtx, "<h1>a</h1>\n".into())));
+/// assert_eq!(blocks.next(), Some((BlockKind::Paragraph, "<p>b</p>".into())));
+/// assert_eq!(blocks.next(), None);
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_html_blocks(
+    value: &str,
+    options: &Options,
+) -> Result<IntoIter<(BlockKind, String)>, message::Message> {
+    let (events, parse_state) = parser::parse(value, &options.parse, false)?;
+    Ok(
+        to_html::compile_blocks(&events, parse_state.bytes, &options.parse, &options.compile)?
+            .into_iter(),
+    )
 }
 
 /// Turn markdown into a syntax tree.
@@ -144,6 +448,11 @@ pub fn to_html_with_options(value: &str, options: &Options) -> Result<String, me
 /// When MDX is turned on, there are several errors that can occur with how
 /// JSX, expressions, or ESM are written.
 ///
+/// ## Panics
+///
+/// `to_mdast()` should never panic, for any input.
+/// See [`to_html_with_options()`][] for more info.
+///
 /// ## Examples
 ///
 /// ```
@@ -158,7 +467,210 @@ pub fn to_html_with_options(value: &str, options: &Options) -> Result<String, me
 /// # }
 /// ```
 pub fn to_mdast(value: &str, options: &ParseOptions) -> Result<mdast::Node, message::Message> {
-    let (events, parse_state) = parser::parse(value, options)?;
-    let node = to_mdast::compile(&events, parse_state.bytes)?;
+    let (events, parse_state) = parser::parse(value, options, false)?;
+    let node = to_mdast::compile(&events, parse_state.bytes, options)?;
     Ok(node)
 }
+
+/// Parse markdown, and get info about it.
+///
+/// Currently, this exposes the definitions (`[a]: b "c"`) in the document,
+/// which can be used to check that references (`[a]`) resolve.
+///
+/// ## Errors
+///
+/// `parse()` never errors with normal markdown because markdown does not
+/// have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// See [`to_mdast()`][] for more info.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{parse, Definition, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let result = parse("[A]: /x \"t\"", &ParseOptions::default())?;
+///
+/// assert_eq!(
+///     result.definitions.get("a"),
+///     Some(&Definition {
+///         destination: "/x".into(),
+///         title: Some("t".into())
+///     })
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse(value: &str, options: &ParseOptions) -> Result<ParseResult, message::Message> {
+    let (events, parse_state) = parser::parse(value, options, false)?;
+    let tree = to_mdast::compile(&events, parse_state.bytes, options)?;
+    Ok(ParseResult::new(&tree, events, value))
+}
+
+/// Turn events into HTML.
+///
+/// This is like [`to_html_with_options()`][], but compiles events that were
+/// already parsed, such as the [`events`][ParseResult::events] of a
+/// [`parse()`][] result, instead of parsing `value` again.
+/// Events can be changed before they are compiled.
+/// Given the unchanged events of `value`, parsed with `options.parse`, the
+/// result is the same as that of [`to_html_with_options()`][].
+///
+/// `value` must be the markdown the events were parsed from, as events point
+/// into it.
+///
+/// ## Errors
+///
+/// `events_to_html()` never errors with normal markdown because markdown
+/// does not have syntax errors, so feel free to `unwrap()`.
+/// When [`max_output_size`][CompileOptions::max_output_size] is set, it is
+/// an error when the HTML gets bigger than it.
+///
+/// ## Panics
+///
+/// `events_to_html()` panics when the events are not valid, such as when
+/// enter and exit events do not match, or when they point outside of
+/// `value`.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{events_to_html, parse, Options};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let options = Options::default();
+/// let value = "# Hello, *world*!";
+/// let result = parse(value, &options.parse)?;
+///
+/// assert_eq!(
+///     events_to_html(&result.events, value, &options)?,
+///     "<h1>Hello, <em>world</em>!</h1>"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn events_to_html(
+    events: &[event::Event],
+    value: &str,
+    options: &Options,
+) -> Result<String, message::Message> {
+    let (html, _) = to_html::compile(
+        events,
+        value.as_bytes(),
+        &options.parse,
+        &options.compile,
+        false,
+    )?;
+    Ok(html)
+}
+
+/// Turn markdown into normalized markdown.
+///
+/// The markdown is parsed and then serialized again: block constructs are
+/// separated by blank lines, headings use `#`s (unless they span several
+/// lines), code is fenced with fences long enough for the code in them,
+/// list items use `-` (or `1.`, counting up), thematic breaks use `***`,
+/// emphasis and strong use `*`, and characters that would otherwise form
+/// syntax are escaped.
+/// The result turns into the same HTML as `value`, and turning the result
+/// into markdown again does not change it.
+///
+/// Constructs that are not supported yet (such as MDX) are kept as they are
+/// in `value`.
+///
+/// ## Errors
+///
+/// `to_markdown()` never errors with normal markdown because markdown does
+/// not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// See [`to_mdast()`][] for more info.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_markdown, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// assert_eq!(
+///     to_markdown("Hey\n===\n+ *you* __!__", &ParseOptions::default())?,
+///     "# Hey\n\n- *you* **!**\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_markdown(value: &str, options: &ParseOptions) -> Result<String, message::Message> {
+    let tree = to_mdast(value, options)?;
+    Ok(to_markdown::serialize(&tree, value, options))
+}
+
+/// Turn markdown into plain text.
+///
+/// This gets the text content of a document, without markup, such as for
+/// search indexes or descriptions.
+/// Blocks are separated by a line ending (hard breaks also turn into line
+/// endings), and line endings in paragraphs turn into spaces.
+/// Images turn into their alternative text, links into their text, and code
+/// into its content.
+/// HTML, definitions, thematic breaks, frontmatter, and MDX expressions and
+/// ESM are dropped.
+///
+/// ## Errors
+///
+/// `to_plain_text()` never errors with normal markdown because markdown does
+/// not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// See [`to_mdast()`][] for more info.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_plain_text, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// assert_eq!(
+///     to_plain_text("# Hi **there**\n\n![a](b) [c](d) `e`", &ParseOptions::default())?,
+///     "Hi there\na c e"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_plain_text(value: &str, options: &ParseOptions) -> Result<String, message::Message> {
+    let tree = to_mdast(value, options)?;
+    Ok(to_plain_text::serialize(&tree))
+}
+
+/// Make a URL safe, in the same way as the URLs of links and images are
+/// when turning markdown into HTML.
+///
+/// Unsafe characters are percent-encoded (already encoded sequences are
+/// kept) and the result is safe to use in an HTML attribute.
+/// URLs with a protocol that is not in `allowed` (compared
+/// case-insensitively), such as `javascript:`, result in an empty string.
+/// Relative URLs (no protocol) are kept.
+///
+/// By default, [`to_html()`][] allows
+/// `&["http", "https", "irc", "ircs", "mailto", "xmpp"]` for links and
+/// `&["http", "https"]` for images
+/// (see [`allowed_protocols`][CompileOptions::allowed_protocols] and
+/// [`allow_dangerous_protocol`][CompileOptions::allow_dangerous_protocol]).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::sanitize_url;
+///
+/// assert_eq!(sanitize_url("javascript:alert(1)", &["http", "https"]), "");
+/// assert_eq!(sanitize_url("HTTPS://a.b", &["http", "https"]), "HTTPS://a.b");
+/// assert_eq!(sanitize_url("./a b\"c", &["http", "https"]), "./a%20b%22c");
+/// ```
+pub fn sanitize_url(url: &str, allowed: &[&str]) -> String {
+    let allowed = allowed
+        .iter()
+        .map(|protocol| protocol.to_lowercase())
+        .collect::<Vec<_>>();
+    util::sanitize_uri::sanitize_with_protocols(
+        url,
+        &allowed.iter().map(String::as_str).collect::<Vec<_>>(),
+    )
+}
@@ -177,6 +177,8 @@ pub enum Node {
     FootnoteDefinition(FootnoteDefinition),
     /// MDX: JSX element (container).
     MdxJsxFlowElement(MdxJsxFlowElement),
+    /// Directive (container).
+    ContainerDirective(ContainerDirective),
     /// List.
     List(List),
 
@@ -203,6 +205,8 @@ pub enum Node {
     MdxTextExpression(MdxTextExpression),
     /// Footnote reference.
     FootnoteReference(FootnoteReference),
+    /// Inline footnote.
+    InlineFootnote(InlineFootnote),
     /// Html (phrasing).
     Html(Html),
     /// Image.
@@ -211,10 +215,14 @@ pub enum Node {
     ImageReference(ImageReference),
     // MDX: JSX element (text).
     MdxJsxTextElement(MdxJsxTextElement),
+    /// Directive (text).
+    TextDirective(TextDirective),
     /// Link.
     Link(Link),
     /// Link reference.
     LinkReference(LinkReference),
+    /// Mark.
+    Mark(Mark),
     /// Strong
     Strong(Strong),
     /// Text.
@@ -227,6 +235,8 @@ pub enum Node {
     Math(Math),
     // MDX: expression (flow).
     MdxFlowExpression(MdxFlowExpression),
+    /// Directive (leaf).
+    LeafDirective(LeafDirective),
     /// Heading.
     Heading(Heading),
     /// Html (flow).
@@ -263,6 +273,7 @@ impl fmt::Debug for Node {
             Node::Blockquote(x) => x.fmt(f),
             Node::FootnoteDefinition(x) => x.fmt(f),
             Node::MdxJsxFlowElement(x) => x.fmt(f),
+            Node::ContainerDirective(x) => x.fmt(f),
             Node::List(x) => x.fmt(f),
             Node::MdxjsEsm(x) => x.fmt(f),
             Node::Toml(x) => x.fmt(f),
@@ -274,17 +285,21 @@ impl fmt::Debug for Node {
             Node::Emphasis(x) => x.fmt(f),
             Node::MdxTextExpression(x) => x.fmt(f),
             Node::FootnoteReference(x) => x.fmt(f),
+            Node::InlineFootnote(x) => x.fmt(f),
             Node::Html(x) => x.fmt(f),
             Node::Image(x) => x.fmt(f),
             Node::ImageReference(x) => x.fmt(f),
             Node::MdxJsxTextElement(x) => x.fmt(f),
+            Node::TextDirective(x) => x.fmt(f),
             Node::Link(x) => x.fmt(f),
             Node::LinkReference(x) => x.fmt(f),
+            Node::Mark(x) => x.fmt(f),
             Node::Strong(x) => x.fmt(f),
             Node::Text(x) => x.fmt(f),
             Node::Code(x) => x.fmt(f),
             Node::Math(x) => x.fmt(f),
             Node::MdxFlowExpression(x) => x.fmt(f),
+            Node::LeafDirective(x) => x.fmt(f),
             Node::Heading(x) => x.fmt(f),
             Node::Table(x) => x.fmt(f),
             Node::ThematicBreak(x) => x.fmt(f),
@@ -311,12 +326,16 @@ impl ToString for Node {
             Node::Blockquote(x) => children_to_string(&x.children),
             Node::FootnoteDefinition(x) => children_to_string(&x.children),
             Node::MdxJsxFlowElement(x) => children_to_string(&x.children),
+            Node::ContainerDirective(x) => children_to_string(&x.children),
             Node::List(x) => children_to_string(&x.children),
             Node::Delete(x) => children_to_string(&x.children),
             Node::Emphasis(x) => children_to_string(&x.children),
             Node::MdxJsxTextElement(x) => children_to_string(&x.children),
+            Node::TextDirective(x) => children_to_string(&x.children),
+            Node::LeafDirective(x) => children_to_string(&x.children),
             Node::Link(x) => children_to_string(&x.children),
             Node::LinkReference(x) => children_to_string(&x.children),
+            Node::Mark(x) => children_to_string(&x.children),
             Node::Strong(x) => children_to_string(&x.children),
             Node::Heading(x) => children_to_string(&x.children),
             Node::Table(x) => children_to_string(&x.children),
@@ -338,10 +357,11 @@ impl ToString for Node {
             Node::Math(x) => x.value.clone(),
             Node::MdxFlowExpression(x) => x.value.clone(),
 
-            // Voids.
+            // Voids, and notes, which are not part of the surrounding text.
             Node::Break(_)
             | Node::FootnoteReference(_)
             | Node::Image(_)
+            | Node::InlineFootnote(_)
             | Node::ImageReference(_)
             | Node::ThematicBreak(_)
             | Node::Definition(_) => String::new(),
@@ -364,13 +384,18 @@ impl Node {
             Node::Strong(x) => Some(&x.children),
             Node::Link(x) => Some(&x.children),
             Node::LinkReference(x) => Some(&x.children),
+            Node::Mark(x) => Some(&x.children),
+            Node::InlineFootnote(x) => Some(&x.children),
             Node::FootnoteDefinition(x) => Some(&x.children),
             Node::Table(x) => Some(&x.children),
             Node::TableRow(x) => Some(&x.children),
             Node::TableCell(x) => Some(&x.children),
             Node::Delete(x) => Some(&x.children),
             Node::MdxJsxFlowElement(x) => Some(&x.children),
+            Node::ContainerDirective(x) => Some(&x.children),
             Node::MdxJsxTextElement(x) => Some(&x.children),
+            Node::TextDirective(x) => Some(&x.children),
+            Node::LeafDirective(x) => Some(&x.children),
             // Non-parent.
             _ => None,
         }
@@ -389,13 +414,18 @@ impl Node {
             Node::Strong(x) => Some(&mut x.children),
             Node::Link(x) => Some(&mut x.children),
             Node::LinkReference(x) => Some(&mut x.children),
+            Node::Mark(x) => Some(&mut x.children),
+            Node::InlineFootnote(x) => Some(&mut x.children),
             Node::FootnoteDefinition(x) => Some(&mut x.children),
             Node::Table(x) => Some(&mut x.children),
             Node::TableRow(x) => Some(&mut x.children),
             Node::TableCell(x) => Some(&mut x.children),
             Node::Delete(x) => Some(&mut x.children),
             Node::MdxJsxFlowElement(x) => Some(&mut x.children),
+            Node::ContainerDirective(x) => Some(&mut x.children),
             Node::MdxJsxTextElement(x) => Some(&mut x.children),
+            Node::TextDirective(x) => Some(&mut x.children),
+            Node::LeafDirective(x) => Some(&mut x.children),
             // Non-parent.
             _ => None,
         }
@@ -408,6 +438,7 @@ impl Node {
             Node::Blockquote(x) => x.position.as_ref(),
             Node::FootnoteDefinition(x) => x.position.as_ref(),
             Node::MdxJsxFlowElement(x) => x.position.as_ref(),
+            Node::ContainerDirective(x) => x.position.as_ref(),
             Node::List(x) => x.position.as_ref(),
             Node::MdxjsEsm(x) => x.position.as_ref(),
             Node::Toml(x) => x.position.as_ref(),
@@ -419,17 +450,21 @@ impl Node {
             Node::Emphasis(x) => x.position.as_ref(),
             Node::MdxTextExpression(x) => x.position.as_ref(),
             Node::FootnoteReference(x) => x.position.as_ref(),
+            Node::InlineFootnote(x) => x.position.as_ref(),
             Node::Html(x) => x.position.as_ref(),
             Node::Image(x) => x.position.as_ref(),
             Node::ImageReference(x) => x.position.as_ref(),
             Node::MdxJsxTextElement(x) => x.position.as_ref(),
+            Node::TextDirective(x) => x.position.as_ref(),
             Node::Link(x) => x.position.as_ref(),
             Node::LinkReference(x) => x.position.as_ref(),
+            Node::Mark(x) => x.position.as_ref(),
             Node::Strong(x) => x.position.as_ref(),
             Node::Text(x) => x.position.as_ref(),
             Node::Code(x) => x.position.as_ref(),
             Node::Math(x) => x.position.as_ref(),
             Node::MdxFlowExpression(x) => x.position.as_ref(),
+            Node::LeafDirective(x) => x.position.as_ref(),
             Node::Heading(x) => x.position.as_ref(),
             Node::Table(x) => x.position.as_ref(),
             Node::ThematicBreak(x) => x.position.as_ref(),
@@ -447,6 +482,7 @@ impl Node {
             Node::Blockquote(x) => x.position.as_mut(),
             Node::FootnoteDefinition(x) => x.position.as_mut(),
             Node::MdxJsxFlowElement(x) => x.position.as_mut(),
+            Node::ContainerDirective(x) => x.position.as_mut(),
             Node::List(x) => x.position.as_mut(),
             Node::MdxjsEsm(x) => x.position.as_mut(),
             Node::Toml(x) => x.position.as_mut(),
@@ -458,17 +494,21 @@ impl Node {
             Node::Emphasis(x) => x.position.as_mut(),
             Node::MdxTextExpression(x) => x.position.as_mut(),
             Node::FootnoteReference(x) => x.position.as_mut(),
+            Node::InlineFootnote(x) => x.position.as_mut(),
             Node::Html(x) => x.position.as_mut(),
             Node::Image(x) => x.position.as_mut(),
             Node::ImageReference(x) => x.position.as_mut(),
             Node::MdxJsxTextElement(x) => x.position.as_mut(),
+            Node::TextDirective(x) => x.position.as_mut(),
             Node::Link(x) => x.position.as_mut(),
             Node::LinkReference(x) => x.position.as_mut(),
+            Node::Mark(x) => x.position.as_mut(),
             Node::Strong(x) => x.position.as_mut(),
             Node::Text(x) => x.position.as_mut(),
             Node::Code(x) => x.position.as_mut(),
             Node::Math(x) => x.position.as_mut(),
             Node::MdxFlowExpression(x) => x.position.as_mut(),
+            Node::LeafDirective(x) => x.position.as_mut(),
             Node::Heading(x) => x.position.as_mut(),
             Node::Table(x) => x.position.as_mut(),
             Node::ThematicBreak(x) => x.position.as_mut(),
@@ -486,6 +526,7 @@ impl Node {
             Node::Blockquote(x) => x.position = position,
             Node::FootnoteDefinition(x) => x.position = position,
             Node::MdxJsxFlowElement(x) => x.position = position,
+            Node::ContainerDirective(x) => x.position = position,
             Node::List(x) => x.position = position,
             Node::MdxjsEsm(x) => x.position = position,
             Node::Toml(x) => x.position = position,
@@ -497,17 +538,21 @@ impl Node {
             Node::Emphasis(x) => x.position = position,
             Node::MdxTextExpression(x) => x.position = position,
             Node::FootnoteReference(x) => x.position = position,
+            Node::InlineFootnote(x) => x.position = position,
             Node::Html(x) => x.position = position,
             Node::Image(x) => x.position = position,
             Node::ImageReference(x) => x.position = position,
             Node::MdxJsxTextElement(x) => x.position = position,
+            Node::TextDirective(x) => x.position = position,
             Node::Link(x) => x.position = position,
             Node::LinkReference(x) => x.position = position,
+            Node::Mark(x) => x.position = position,
             Node::Strong(x) => x.position = position,
             Node::Text(x) => x.position = position,
             Node::Code(x) => x.position = position,
             Node::Math(x) => x.position = position,
             Node::MdxFlowExpression(x) => x.position = position,
+            Node::LeafDirective(x) => x.position = position,
             Node::Heading(x) => x.position = position,
             Node::Table(x) => x.position = position,
             Node::ThematicBreak(x) => x.position = position,
@@ -1180,6 +1225,40 @@ pub struct Delete {
     pub position: Option<Position>,
 }
 
+/// Mark.
+///
+/// ```markdown
+/// > | ==a==
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mark {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub position: Option<Position>,
+}
+
+/// Inline footnote.
+///
+/// ```markdown
+/// > | ^[a]
+///     ^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InlineFootnote {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub position: Option<Position>,
+}
+
 /// Frontmatter: yaml.
 ///
 /// ```markdown
@@ -1386,6 +1465,101 @@ pub struct MdxJsxExpressionAttribute {
     pub stops: Vec<Stop>,
 }
 
+/// Directive (container).
+///
+/// The label, if any, is the first child: a paragraph.
+///
+/// ```markdown
+/// > | :::a[b]{c}
+///     ^^^^^^^^^^
+/// > | d
+///     ^
+/// > | :::
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ContainerDirective {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub position: Option<Position>,
+    // Directive.
+    /// Name.
+    pub name: String,
+    /// Attributes, as key/value pairs.
+    ///
+    /// `#a` is turned into `("id", "a")`, `.b` and `.c` into
+    /// `("class", "b c")`.
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Directive (leaf).
+///
+/// The label, if any, is the content.
+///
+/// ```markdown
+/// > | ::a[b]{c}
+///     ^^^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct LeafDirective {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub position: Option<Position>,
+    // Directive.
+    /// Name.
+    pub name: String,
+    /// Attributes, as key/value pairs.
+    ///
+    /// See [`ContainerDirective`][] for more info.
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Directive (text).
+///
+/// The label, if any, is the content.
+///
+/// ```markdown
+/// > | a :b[c]{d} e
+///       ^^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TextDirective {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub position: Option<Position>,
+    // Directive.
+    /// Name.
+    pub name: String,
+    /// Attributes, as key/value pairs.
+    ///
+    /// See [`ContainerDirective`][] for more info.
+    pub attributes: Vec<(String, String)>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1919,6 +2093,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mark() {
+        let mut node = Node::Mark(Mark {
+            position: None,
+            children: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Mark { children: [], position: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Mark { children: [], position: Some(1:1-1:2 (0-1)) }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn inline_footnote() {
+        let mut node = Node::InlineFootnote(InlineFootnote {
+            position: None,
+            children: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "InlineFootnote { children: [], position: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "InlineFootnote { children: [], position: Some(1:1-1:2 (0-1)) }",
+            "should support `position_set`"
+        );
+    }
+
     #[test]
     fn emphasis() {
         let mut node = Node::Emphasis(Emphasis {
@@ -2342,4 +2574,35 @@ mod tests {
             "should support `position_set`"
         );
     }
+
+    #[test]
+    fn container_directive() {
+        let mut node = Node::ContainerDirective(ContainerDirective {
+            position: None,
+            name: "a".into(),
+            attributes: vec![],
+            children: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "ContainerDirective { children: [], position: None, name: \"a\", attributes: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "ContainerDirective { children: [], position: Some(1:1-1:2 (0-1)), name: \"a\", attributes: [] }",
+            "should support `position_set`"
+        );
+    }
 }
@@ -0,0 +1,181 @@
+//! Info found when parsing markdown.
+
+use crate::event::{Event, Kind, Name};
+use crate::mdast::Node;
+use crate::unist::Position;
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+/// Definition, as found in a document.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Definition {
+    /// URL to the referenced resource.
+    ///
+    /// Character escapes and character references are parsed.
+    pub destination: String,
+    /// Advisory info for the resource, such as something that would be
+    /// appropriate for a tooltip.
+    ///
+    /// Character escapes and character references are parsed.
+    pub title: Option<String>,
+}
+
+/// Opening fence of code (fenced), as found in a document.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CodeFence {
+    /// Marker of the fence: a grave accent (`` ` ``) or a tilde (`~`).
+    pub marker: char,
+    /// Number of markers in the fence (at least `3`).
+    ///
+    /// The closing fence, if any, has at least this many markers.
+    pub size: usize,
+    /// Place of the markers in the document.
+    pub position: Position,
+}
+
+/// Result of [`parse()`][crate::parse()].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ParseResult {
+    /// Definitions, by normalized identifier.
+    ///
+    /// Identifiers are normalized like how references are matched to
+    /// definitions: whitespace is collapsed, initial and final whitespace is
+    /// dropped, and case is folded (the key for `[A  b]: c` is `a b`).
+    /// The key is the same as `identifier` on a
+    /// [`Definition`][crate::mdast::Definition] in mdast.
+    ///
+    /// When something is defined several times, the first definition is
+    /// used, just like when references are resolved.
+    pub definitions: BTreeMap<String, Definition>,
+    /// Events that the document is made of.
+    ///
+    /// These are what the compilers walk.
+    /// Use [`visit()`][crate::event::visit] to walk them yourself.
+    pub events: Vec<Event>,
+    /// Markdown the events come from.
+    value: String,
+}
+
+impl ParseResult {
+    /// Collect info from a syntax tree, and keep the events and markdown it
+    /// came from.
+    pub(crate) fn new(tree: &Node, events: Vec<Event>, value: &str) -> Self {
+        let mut result = ParseResult {
+            events,
+            value: value.into(),
+            ..ParseResult::default()
+        };
+        collect(&mut result, tree);
+        result
+    }
+
+    /// Get the markdown between an enter event and its exit event.
+    ///
+    /// This is the source text of what the events represent, such as the
+    /// info string of fenced code, as it was written: character escapes and
+    /// character references are not decoded.
+    ///
+    /// When a tab is only partially part of the events (because its first
+    /// columns belong to something else, such as the indent of a list item),
+    /// the whole tab is included, as points cannot represent part of a
+    /// character.
+    ///
+    /// ## Panics
+    ///
+    /// This function panics if `enter` is not before `exit`, or if they do
+    /// not come from this result.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{event::{Kind, Name}, parse, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let result = parse("# *a*", &ParseOptions::default())?;
+    /// let enter = result.events.iter().find(|d| d.name == Name::Emphasis).unwrap();
+    /// let exit = result.events.iter().rfind(|d| d.name == Name::Emphasis).unwrap();
+    ///
+    /// assert_eq!(result.slice(enter, exit), "*a*");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn slice(&self, enter: &Event, exit: &Event) -> String {
+        debug_assert_eq!(enter.kind, Kind::Enter, "expected `enter` event");
+        debug_assert_eq!(exit.kind, Kind::Exit, "expected `exit` event");
+        let mut end = exit.point.index;
+
+        // Virtual spaces after mean the tab at `end` is partially included.
+        if exit.point.vs > 0 {
+            end += 1;
+        }
+
+        self.value[enter.point.index..end].into()
+    }
+
+    /// Get the opening fences of code (fenced), in the order they occur in.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{parse, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let result = parse("~~~~js\na\n~~~~", &ParseOptions::default())?;
+    /// let fence = &result.code_fences()[0];
+    ///
+    /// assert_eq!(fence.marker, '~');
+    /// assert_eq!(fence.size, 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn code_fences(&self) -> Vec<CodeFence> {
+        let mut fences = Vec::new();
+        let mut index = 0;
+        let mut in_code = false;
+
+        while index < self.events.len() {
+            let event = &self.events[index];
+
+            if event.name == Name::CodeFenced {
+                in_code = event.kind == Kind::Enter;
+            } else if in_code
+                && event.kind == Kind::Enter
+                && event.name == Name::CodeFencedFenceSequence
+            {
+                // Only the opening fence.
+                in_code = false;
+                let exit = &self.events[index + 1];
+                fences.push(CodeFence {
+                    marker: self.value.as_bytes()[event.point.index] as char,
+                    size: exit.point.index - event.point.index,
+                    position: Position {
+                        start: event.point.to_unist(),
+                        end: exit.point.to_unist(),
+                    },
+                });
+            }
+
+            index += 1;
+        }
+
+        fences
+    }
+}
+
+/// Collect info from `node`, and its descendants, into `result`.
+fn collect(result: &mut ParseResult, node: &Node) {
+    if let Node::Definition(definition) = node {
+        result
+            .definitions
+            .entry(definition.identifier.clone())
+            .or_insert_with(|| Definition {
+                destination: definition.url.clone(),
+                title: definition.title.clone(),
+            });
+    }
+
+    if let Some(children) = node.children() {
+        for child in children {
+            collect(result, child);
+        }
+    }
+}
@@ -7,7 +7,7 @@ use crate::subtokenize::subtokenize;
 use crate::tokenizer::Tokenizer;
 use crate::util::location::Location;
 use crate::ParseOptions;
-use alloc::{string::String, vec, vec::Vec};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
 
 /// Info needed, in all content types, when parsing markdown.
 ///
@@ -25,15 +25,50 @@ pub struct ParseState<'a> {
     pub definitions: Vec<String>,
     /// Set of defined GFM footnote definition identifiers.
     pub gfm_footnote_definitions: Vec<String>,
+    /// List of diagnostics, if they are collected.
+    pub diagnostics: Option<Vec<message::Message>>,
 }
 
 /// Turn a string of markdown into events.
 ///
 /// Passes the bytes back so the compiler can access the source.
+/// When `diagnostics`, also collects warnings about the markdown (such as
+/// references to undefined definitions) in `ParseState`.
 pub fn parse<'a>(
     value: &'a str,
     options: &'a ParseOptions,
+    diagnostics: bool,
 ) -> Result<(Vec<Event>, ParseState<'a>), message::Message> {
+    tokenize(value, options, diagnostics, StateName::DocumentStart)
+}
+
+/// Turn a string of markdown into events, as text (phrasing) content.
+///
+/// This is like [`parse`][], but without flow (block) constructs, so there
+/// are no paragraphs, headings, and such.
+pub fn parse_inline<'a>(
+    value: &'a str,
+    options: &'a ParseOptions,
+) -> Result<(Vec<Event>, ParseState<'a>), message::Message> {
+    tokenize(value, options, false, StateName::TextStart)
+}
+
+/// Turn a string of markdown into events, starting in `name`.
+fn tokenize<'a>(
+    value: &'a str,
+    options: &'a ParseOptions,
+    diagnostics: bool,
+    name: StateName,
+) -> Result<(Vec<Event>, ParseState<'a>), message::Message> {
+    if options.tab_size == 0 {
+        return Err(message::Message {
+            place: None,
+            reason: "Unexpected `tab_size` of `0`, expected a tab size of at least `1`".into(),
+            rule_id: Box::new("unexpected-tab-size".into()),
+            source: Box::new("markdown-rs".into()),
+        });
+    }
+
     let bytes = value.as_bytes();
 
     let mut parse_state = ParseState {
@@ -46,6 +81,7 @@ pub fn parse<'a>(
         },
         definitions: vec![],
         gfm_footnote_definitions: vec![],
+        diagnostics: if diagnostics { Some(vec![]) } else { None },
     };
 
     let start = Point {
@@ -56,11 +92,7 @@ pub fn parse<'a>(
     };
     let mut tokenizer = Tokenizer::new(start, &parse_state);
 
-    let state = tokenizer.push(
-        (0, 0),
-        (parse_state.bytes.len(), 0),
-        State::Next(StateName::DocumentStart),
-    );
+    let state = tokenizer.push((0, 0), (parse_state.bytes.len(), 0), State::Next(name));
     let mut result = tokenizer.flush(state, true)?;
     let mut events = tokenizer.events;
 
@@ -70,10 +102,27 @@ pub fn parse<'a>(
         fn_defs.append(&mut result.gfm_footnote_definitions);
         defs.append(&mut result.definitions);
 
+        if let Some(diagnostics) = &mut parse_state.diagnostics {
+            diagnostics.append(&mut result.diagnostics);
+        }
+
         if result.done {
+            resolve(&mut events, value, options);
             return Ok((events, parse_state));
         }
 
         result = subtokenize(&mut events, &parse_state, &None)?;
     }
 }
+
+/// Call the custom resolvers in `options`, skipping duplicate IDs.
+fn resolve(events: &mut Vec<Event>, value: &str, options: &ParseOptions) {
+    let mut ids: Vec<&str> = vec![];
+
+    for (id, resolver) in &options.resolvers {
+        if !ids.contains(&id.as_str()) {
+            ids.push(id);
+            resolver(events, value);
+        }
+    }
+}
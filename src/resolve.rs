@@ -1,9 +1,20 @@
 //! Resolve events.
 
 use crate::construct;
+use crate::event::Event;
 use crate::message;
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
+use alloc::vec::Vec;
+
+/// Signature of a function that changes events after parsing.
+///
+/// Can be passed in `resolvers` in
+/// [`ParseOptions`][crate::configuration::ParseOptions] to adjust the events
+/// of a whole document before it is compiled.
+/// The function gets the events and the markdown they come from (points in
+/// events are byte offsets into it).
+pub type Resolver = dyn Fn(&mut Vec<Event>, &str);
 
 /// Names of resolvers.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -114,6 +114,56 @@ pub enum Name {
     DestinationRaw,
     DestinationRawEscape,
 
+    DirectiveAttributesStart,
+    DirectiveAttributesBetween,
+    DirectiveAttributesBefore,
+    DirectiveAttributesShortcutStart,
+    DirectiveAttributesShortcutBefore,
+    DirectiveAttributesShortcutInside,
+    DirectiveAttributesNameInside,
+    DirectiveAttributesValueBefore,
+    DirectiveAttributesValueUnquoted,
+    DirectiveAttributesValueQuotedStart,
+    DirectiveAttributesValueQuoted,
+    DirectiveAttributesValueQuotedEnd,
+
+    DirectiveContainerStart,
+    DirectiveContainerBefore,
+    DirectiveContainerSequence,
+    DirectiveContainerNameAfter,
+    DirectiveContainerLabelAfter,
+    DirectiveContainerAttributesAfter,
+    DirectiveContainerAfter,
+    DirectiveContainerContStart,
+    DirectiveContainerContOpen,
+    DirectiveContainerContClosed,
+    DirectiveContainerCloseStart,
+    DirectiveContainerCloseBefore,
+    DirectiveContainerCloseSequence,
+    DirectiveContainerCloseAfter,
+
+    DirectiveLabelStart,
+    DirectiveLabelBefore,
+    DirectiveLabelInside,
+    DirectiveLabelEscape,
+    DirectiveLabelEnd,
+
+    DirectiveLeafStart,
+    DirectiveLeafBefore,
+    DirectiveLeafSequence,
+    DirectiveLeafNameAfter,
+    DirectiveLeafLabelAfter,
+    DirectiveLeafAttributesAfter,
+    DirectiveLeafAfter,
+
+    DirectiveNameStart,
+    DirectiveNameInside,
+
+    DirectiveTextStart,
+    DirectiveTextNameAfter,
+    DirectiveTextLabelAfter,
+    DirectiveTextAfter,
+
     DocumentStart,
     DocumentBeforeFrontmatter,
     DocumentContainerExistingBefore,
@@ -122,6 +172,7 @@ pub enum Name {
     DocumentContainerNewBeforeNotBlockQuote,
     DocumentContainerNewBeforeNotList,
     DocumentContainerNewBeforeNotGfmFootnoteDefinition,
+    DocumentContainerNewBeforeNotDirectiveContainer,
     DocumentContainerNewAfter,
     DocumentContainersAfter,
     DocumentFlowInside,
@@ -132,6 +183,7 @@ pub enum Name {
     FlowBeforeCodeIndented,
     FlowBeforeRaw,
     FlowBeforeHtml,
+    FlowBeforeDirectiveLeaf,
     FlowBeforeMdxExpression,
     FlowBeforeMdxJsx,
     FlowBeforeHeadingAtx,
@@ -202,6 +254,7 @@ pub enum Name {
     GfmTableHeadRowBreak,
     GfmTableHeadRowData,
     GfmTableHeadRowEscape,
+    GfmTableHeadRowCode,
     GfmTableHeadDelimiterStart,
     GfmTableHeadDelimiterBefore,
     GfmTableHeadDelimiterCellBefore,
@@ -216,10 +269,14 @@ pub enum Name {
     GfmTableBodyRowBreak,
     GfmTableBodyRowData,
     GfmTableBodyRowEscape,
+    GfmTableBodyRowCode,
 
     HardBreakEscapeStart,
     HardBreakEscapeAfter,
 
+    HashtagStart,
+    HashtagInside,
+
     HeadingAtxStart,
     HeadingAtxBefore,
     HeadingAtxSequenceOpen,
@@ -324,6 +381,9 @@ pub enum Name {
     LabelStartImageOpen,
     LabelStartImageAfter,
 
+    LabelStartInlineFootnoteStart,
+    LabelStartInlineFootnoteOpen,
+
     LabelStartLinkStart,
 
     ListItemStart,
@@ -400,6 +460,9 @@ pub enum Name {
     MdxJsxAttributeValueQuoted,
     MdxJsxAttributeValueExpressionAfter,
 
+    MentionStart,
+    MentionInside,
+
     NonLazyContinuationStart,
     NonLazyContinuationAfter,
 
@@ -450,6 +513,7 @@ pub enum Name {
     TextBeforeHtml,
     TextBeforeMdxJsx,
     TextBeforeHardBreakEscape,
+    TextBeforeGfmLabelStartFootnote,
     TextBeforeLabelStartLink,
     TextBeforeData,
 
@@ -458,6 +522,21 @@ pub enum Name {
     ThematicBreakSequence,
     ThematicBreakAtBreak,
 
+    WikiLinkStart,
+    WikiLinkOpen,
+    WikiLinkTargetStart,
+    WikiLinkTargetInside,
+    WikiLinkTargetBracket,
+    WikiLinkTargetClose,
+    WikiLinkLabelStart,
+    WikiLinkLabelInside,
+    WikiLinkLabelBracket,
+    WikiLinkLabelClose,
+    WikiLinkCloseCheck,
+    WikiLinkCloseCheckAfter,
+    WikiLinkCloseStart,
+    WikiLinkCloseEnd,
+
     TitleStart,
     TitleBegin,
     TitleAfterEol,
@@ -544,6 +623,72 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::DestinationRaw => construct::partial_destination::raw,
         Name::DestinationRawEscape => construct::partial_destination::raw_escape,
 
+        Name::DirectiveAttributesStart => construct::partial_directive_attributes::start,
+        Name::DirectiveAttributesBetween => construct::partial_directive_attributes::between,
+        Name::DirectiveAttributesBefore => construct::partial_directive_attributes::before,
+        Name::DirectiveAttributesShortcutStart => {
+            construct::partial_directive_attributes::shortcut_start
+        }
+        Name::DirectiveAttributesShortcutBefore => {
+            construct::partial_directive_attributes::shortcut_before
+        }
+        Name::DirectiveAttributesShortcutInside => {
+            construct::partial_directive_attributes::shortcut_inside
+        }
+        Name::DirectiveAttributesNameInside => construct::partial_directive_attributes::name_inside,
+        Name::DirectiveAttributesValueBefore => {
+            construct::partial_directive_attributes::value_before
+        }
+        Name::DirectiveAttributesValueUnquoted => {
+            construct::partial_directive_attributes::value_unquoted
+        }
+        Name::DirectiveAttributesValueQuotedStart => {
+            construct::partial_directive_attributes::value_quoted_start
+        }
+        Name::DirectiveAttributesValueQuoted => {
+            construct::partial_directive_attributes::value_quoted
+        }
+        Name::DirectiveAttributesValueQuotedEnd => {
+            construct::partial_directive_attributes::value_quoted_end
+        }
+
+        Name::DirectiveContainerStart => construct::directive_container::start,
+        Name::DirectiveContainerBefore => construct::directive_container::before,
+        Name::DirectiveContainerSequence => construct::directive_container::sequence,
+        Name::DirectiveContainerNameAfter => construct::directive_container::name_after,
+        Name::DirectiveContainerLabelAfter => construct::directive_container::label_after,
+        Name::DirectiveContainerAttributesAfter => construct::directive_container::attributes_after,
+        Name::DirectiveContainerAfter => construct::directive_container::after,
+        Name::DirectiveContainerContStart => construct::directive_container::cont_start,
+        Name::DirectiveContainerContOpen => construct::directive_container::cont_open,
+        Name::DirectiveContainerContClosed => construct::directive_container::cont_closed,
+        Name::DirectiveContainerCloseStart => construct::directive_container::close_start,
+        Name::DirectiveContainerCloseBefore => construct::directive_container::close_before,
+        Name::DirectiveContainerCloseSequence => construct::directive_container::close_sequence,
+        Name::DirectiveContainerCloseAfter => construct::directive_container::close_after,
+
+        Name::DirectiveLabelStart => construct::partial_directive_label::start,
+        Name::DirectiveLabelBefore => construct::partial_directive_label::before,
+        Name::DirectiveLabelInside => construct::partial_directive_label::inside,
+        Name::DirectiveLabelEscape => construct::partial_directive_label::escape,
+        Name::DirectiveLabelEnd => construct::partial_directive_label::end,
+
+        Name::DirectiveLeafStart => construct::directive_leaf::start,
+        Name::DirectiveLeafBefore => construct::directive_leaf::before,
+        Name::DirectiveLeafSequence => construct::directive_leaf::sequence,
+        Name::DirectiveLeafNameAfter => construct::directive_leaf::name_after,
+        Name::DirectiveLeafLabelAfter => construct::directive_leaf::label_after,
+        Name::DirectiveLeafAttributesAfter => construct::directive_leaf::attributes_after,
+        Name::DirectiveLeafAfter => construct::directive_leaf::after,
+
+        Name::DirectiveNameStart => construct::partial_directive_name::start,
+        Name::DirectiveNameInside => construct::partial_directive_name::inside,
+
+        Name::DirectiveTextStart => construct::directive_text::start,
+        Name::DirectiveTextNameAfter => construct::directive_text::name_after,
+        Name::DirectiveTextLabelAfter => construct::directive_text::label_after,
+        Name::DirectiveTextAfter => construct::directive_text::after,
+
         Name::DocumentStart => construct::document::start,
         Name::DocumentBeforeFrontmatter => construct::document::before_frontmatter,
         Name::DocumentContainerExistingBefore => construct::document::container_existing_before,
@@ -558,6 +703,9 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::DocumentContainerNewBeforeNotGfmFootnoteDefinition => {
             construct::document::container_new_before_not_footnote_definition
         }
+        Name::DocumentContainerNewBeforeNotDirectiveContainer => {
+            construct::document::container_new_before_not_directive_container
+        }
         Name::DocumentContainerNewAfter => construct::document::container_new_after,
         Name::DocumentContainersAfter => construct::document::containers_after,
         Name::DocumentFlowEnd => construct::document::flow_end,
@@ -568,6 +716,7 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::FlowBeforeCodeIndented => construct::flow::before_code_indented,
         Name::FlowBeforeRaw => construct::flow::before_raw,
         Name::FlowBeforeHtml => construct::flow::before_html,
+        Name::FlowBeforeDirectiveLeaf => construct::flow::before_directive_leaf,
         Name::FlowBeforeMdxExpression => construct::flow::before_mdx_expression,
         Name::FlowBeforeMdxJsx => construct::flow::before_mdx_jsx,
         Name::FlowBeforeHeadingAtx => construct::flow::before_heading_atx,
@@ -648,6 +797,7 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::GfmTableHeadRowBreak => construct::gfm_table::head_row_break,
         Name::GfmTableHeadRowData => construct::gfm_table::head_row_data,
         Name::GfmTableHeadRowEscape => construct::gfm_table::head_row_escape,
+        Name::GfmTableHeadRowCode => construct::gfm_table::head_row_code,
         Name::GfmTableHeadDelimiterStart => construct::gfm_table::head_delimiter_start,
         Name::GfmTableHeadDelimiterBefore => construct::gfm_table::head_delimiter_before,
         Name::GfmTableHeadDelimiterCellBefore => construct::gfm_table::head_delimiter_cell_before,
@@ -665,6 +815,7 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::GfmTableBodyRowBreak => construct::gfm_table::body_row_break,
         Name::GfmTableBodyRowData => construct::gfm_table::body_row_data,
         Name::GfmTableBodyRowEscape => construct::gfm_table::body_row_escape,
+        Name::GfmTableBodyRowCode => construct::gfm_table::body_row_code,
 
         Name::GfmTaskListItemCheckStart => construct::gfm_task_list_item_check::start,
         Name::GfmTaskListItemCheckInside => construct::gfm_task_list_item_check::inside,
@@ -677,6 +828,9 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::HardBreakEscapeStart => construct::hard_break_escape::start,
         Name::HardBreakEscapeAfter => construct::hard_break_escape::after,
 
+        Name::HashtagStart => construct::hashtag::start,
+        Name::HashtagInside => construct::hashtag::inside,
+
         Name::HeadingAtxStart => construct::heading_atx::start,
         Name::HeadingAtxBefore => construct::heading_atx::before,
         Name::HeadingAtxSequenceOpen => construct::heading_atx::sequence_open,
@@ -808,6 +962,8 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::LabelStartImageStart => construct::label_start_image::start,
         Name::LabelStartImageOpen => construct::label_start_image::open,
         Name::LabelStartImageAfter => construct::label_start_image::after,
+        Name::LabelStartInlineFootnoteStart => construct::label_start_inline_footnote::start,
+        Name::LabelStartInlineFootnoteOpen => construct::label_start_inline_footnote::open,
         Name::LabelStartLinkStart => construct::label_start_link::start,
 
         Name::ListItemStart => construct::list_item::start,
@@ -898,6 +1054,9 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::MdxJsxEsWhitespaceInside => construct::partial_mdx_jsx::es_whitespace_inside,
         Name::MdxJsxEsWhitespaceEolAfter => construct::partial_mdx_jsx::es_whitespace_eol_after,
 
+        Name::MentionStart => construct::mention::start,
+        Name::MentionInside => construct::mention::inside,
+
         Name::NonLazyContinuationStart => construct::partial_non_lazy_continuation::start,
         Name::NonLazyContinuationAfter => construct::partial_non_lazy_continuation::after,
 
@@ -948,6 +1107,7 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::TextBeforeHtml => construct::text::before_html,
         Name::TextBeforeMdxJsx => construct::text::before_mdx_jsx,
         Name::TextBeforeHardBreakEscape => construct::text::before_hard_break_escape,
+        Name::TextBeforeGfmLabelStartFootnote => construct::text::before_gfm_label_start_footnote,
         Name::TextBeforeLabelStartLink => construct::text::before_label_start_link,
         Name::TextBeforeData => construct::text::before_data,
 
@@ -956,6 +1116,21 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::ThematicBreakSequence => construct::thematic_break::sequence,
         Name::ThematicBreakAtBreak => construct::thematic_break::at_break,
 
+        Name::WikiLinkStart => construct::wikilink::start,
+        Name::WikiLinkOpen => construct::wikilink::open,
+        Name::WikiLinkTargetStart => construct::wikilink::target_start,
+        Name::WikiLinkTargetInside => construct::wikilink::target_inside,
+        Name::WikiLinkTargetBracket => construct::wikilink::target_bracket,
+        Name::WikiLinkTargetClose => construct::wikilink::target_close,
+        Name::WikiLinkLabelStart => construct::wikilink::label_start,
+        Name::WikiLinkLabelInside => construct::wikilink::label_inside,
+        Name::WikiLinkLabelBracket => construct::wikilink::label_bracket,
+        Name::WikiLinkLabelClose => construct::wikilink::label_close,
+        Name::WikiLinkCloseCheck => construct::wikilink::close_check,
+        Name::WikiLinkCloseCheckAfter => construct::wikilink::close_check_after,
+        Name::WikiLinkCloseStart => construct::wikilink::close_start,
+        Name::WikiLinkCloseEnd => construct::wikilink::close_end,
+
         Name::TitleStart => construct::partial_title::start,
         Name::TitleBegin => construct::partial_title::begin,
         Name::TitleAfterEol => construct::partial_title::after_eol,
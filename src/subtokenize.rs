@@ -30,6 +30,7 @@ pub struct Subresult {
     pub done: bool,
     pub gfm_footnote_definitions: Vec<String>,
     pub definitions: Vec<String>,
+    pub diagnostics: Vec<message::Message>,
 }
 
 /// Link two [`Event`][]s.
@@ -86,6 +87,7 @@ pub fn subtokenize(
         done: true,
         gfm_footnote_definitions: vec![],
         definitions: vec![],
+        diagnostics: vec![],
     };
     let mut acc = (0, 0);
 
@@ -96,8 +98,10 @@ pub fn subtokenize(
         if let Some(ref link) = event.link {
             debug_assert_eq!(event.kind, Kind::Enter);
 
-            // No need to enter linked events again.
+            // No need to enter linked events again, and raw content is never
+            // tokenized.
             if link.previous.is_none()
+                && link.content != Content::Raw
                 && (filter.is_none() || &link.content == filter.as_ref().unwrap())
             {
                 // Index into `events` pointing to a chunk.
@@ -105,8 +109,8 @@ pub fn subtokenize(
                 // Subtokenizer.
                 let mut tokenizer = Tokenizer::new(event.point.clone(), parse_state);
                 debug_assert!(
-                    !matches!(link.content, Content::Flow),
-                    "cannot use flow as subcontent yet"
+                    !matches!(link.content, Content::Flow | Content::Raw),
+                    "cannot use flow or raw as subcontent"
                 );
                 // Substate.
                 let mut state = State::Next(match link.content {
@@ -169,6 +173,7 @@ pub fn subtokenize(
                     .gfm_footnote_definitions
                     .append(&mut result.gfm_footnote_definitions);
                 value.definitions.append(&mut result.definitions);
+                value.diagnostics.append(&mut result.diagnostics);
                 value.done = false;
 
                 acc = divide_events(&mut map, events, index, &mut tokenizer.events, acc);
@@ -1,19 +1,30 @@
 //! Turn events into a string of HTML.
 use crate::event::{Event, Kind, Name};
 use crate::mdast::AlignKind;
+use crate::message;
+use crate::unist::Point;
 use crate::util::{
+    attributes::{parse as parse_attributes, safe as safe_attribute},
     character_reference::decode as decode_character_reference,
-    constant::{SAFE_PROTOCOL_HREF, SAFE_PROTOCOL_SRC},
+    constant::{HTML_RAW_NAMES, SAFE_PROTOCOL_HREF, SAFE_PROTOCOL_SRC},
     encode::encode,
     gfm_tagfilter::gfm_tagfilter,
     infer::{gfm_table_align, list_loose},
+    normalize_html::normalize_html_tags,
     normalize_identifier::normalize_identifier,
+    sanitize_html::sanitize_html,
     sanitize_uri::{sanitize, sanitize_with_protocols},
     skip,
     slice::{Position, Slice},
+    slug::{slug, unique as unique_slug},
+    smart_punctuation::replacement as smart_punctuation,
+};
+use crate::{
+    CompileOptions, DroppedLinkBehavior, GfmTableCellAlign, HtmlSyntax, LineEnding, ParseOptions,
 };
-use crate::{CompileOptions, LineEnding};
 use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
     format,
     string::{String, ToString},
     vec,
@@ -29,6 +40,11 @@ struct Media {
     /// Whether this represents an image (`true`) or a link or definition
     /// (`false`).
     image: bool,
+    /// Whether this represents an inline footnote (`true`).
+    ///
+    /// Links can be used in inline footnotes, as their content is moved to
+    /// the footnote section.
+    inline_footnote: bool,
     /// The text between the brackets (`x` in `![x]()` and `[x]()`).
     ///
     /// Not interpreted.
@@ -71,6 +87,17 @@ struct Definition {
     title: Option<String>,
 }
 
+/// Result of compiling.
+#[derive(Debug)]
+struct Compiled {
+    /// HTML.
+    html: String,
+    /// Offsets in the HTML, with the points in markdown they come from.
+    source_map: Vec<(usize, Point)>,
+    /// Offsets in the HTML where top-level blocks start, with their kind.
+    block_starts: Vec<(usize, BlockKind)>,
+}
+
 /// Context used to compile markdown.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug)]
@@ -80,6 +107,10 @@ struct CompileContext<'a> {
     events: &'a [Event],
     /// List of bytes.
     bytes: &'a [u8],
+    /// Size of a tab stop.
+    tab_size: usize,
+    /// Configuration of how markdown was parsed.
+    parse_options: &'a ParseOptions,
     /// Configuration.
     options: &'a CompileOptions,
     // Fields used by handlers to track the things they need to track to
@@ -88,14 +119,29 @@ struct CompileContext<'a> {
     heading_atx_rank: Option<usize>,
     /// Buffer of heading (setext) text.
     heading_setext_buffer: Option<String>,
+    /// Slugs used as heading IDs so far, and IDs in attribute lists.
+    heading_slugs: BTreeMap<String, usize>,
     /// Whether raw (flow) (code (fenced), math (flow)) or code (indented) contains data.
     raw_flow_seen_data: Option<bool>,
     /// Number of raw (flow) fences.
     raw_flow_fences_count: Option<usize>,
+    /// Info (language) of code (fenced), compiled, but not encoded.
+    raw_flow_fence_info: Option<String>,
+    /// Language of code (fenced), when it is highlighted.
+    raw_flow_fence_lang: Option<String>,
+    /// Meta of code (fenced), compiled.
+    raw_flow_fence_meta: Option<String>,
     /// Whether we are in code (text).
     raw_text_inside: bool,
+    /// Whether we are in preformatted text (code (flow), math (flow), raw
+    /// HTML (flow)).
+    pre_inside: bool,
     /// Whether we are in image text.
     image_alt_inside: bool,
+    /// Whether we are in a paragraph.
+    paragraph_inside: bool,
+    /// Whether we are in a paragraph rendered as a figure.
+    figure_inside: bool,
     /// Marker of character reference.
     character_reference_marker: Option<u8>,
     /// Whether we are expecting the first list item marker.
@@ -108,6 +154,8 @@ struct CompileContext<'a> {
     definitions: Vec<Definition>,
     /// List of definitions.
     gfm_footnote_definitions: Vec<(String, String)>,
+    /// Size of the compiled footnote definitions so far.
+    gfm_footnote_definitions_size: usize,
     gfm_footnote_definition_calls: Vec<(String, usize)>,
     gfm_footnote_definition_stack: Vec<(usize, usize)>,
     /// Whether we are in a GFM table head.
@@ -127,6 +175,12 @@ struct CompileContext<'a> {
     // Intermediate results.
     /// Stack of buffers.
     buffers: Vec<String>,
+    /// Offsets in the HTML, with the points in markdown they come from, if
+    /// they are being collected.
+    source_map: Option<Vec<(usize, Point)>>,
+    /// Offsets in the HTML where top-level blocks start, with their kind, if
+    /// they are being collected.
+    block_starts: Option<Vec<(usize, BlockKind)>>,
     /// Current event index.
     index: usize,
 }
@@ -136,22 +190,31 @@ impl<'a> CompileContext<'a> {
     fn new(
         events: &'a [Event],
         bytes: &'a [u8],
+        parse_options: &'a ParseOptions,
         options: &'a CompileOptions,
         line_ending: LineEnding,
     ) -> CompileContext<'a> {
         CompileContext {
             events,
             bytes,
+            tab_size: parse_options.tab_size,
+            parse_options,
             heading_atx_rank: None,
             heading_setext_buffer: None,
+            heading_slugs: BTreeMap::new(),
             raw_flow_seen_data: None,
             raw_flow_fences_count: None,
+            raw_flow_fence_info: None,
+            raw_flow_fence_lang: None,
+            raw_flow_fence_meta: None,
             raw_text_inside: false,
+            pre_inside: false,
             character_reference_marker: None,
             list_expect_first_marker: None,
             media_stack: vec![],
             definitions: vec![],
             gfm_footnote_definitions: vec![],
+            gfm_footnote_definitions_size: 0,
             gfm_footnote_definition_calls: vec![],
             gfm_footnote_definition_stack: vec![],
             gfm_table_in_head: false,
@@ -160,9 +223,13 @@ impl<'a> CompileContext<'a> {
             tight_stack: vec![],
             slurp_one_line_ending: false,
             image_alt_inside: false,
+            paragraph_inside: false,
+            figure_inside: false,
             encode_html: true,
             line_ending_default: line_ending,
             buffers: vec![String::new()],
+            source_map: None,
+            block_starts: None,
             index: 0,
             options,
         }
@@ -188,9 +255,47 @@ impl<'a> CompileContext<'a> {
     /// Add a line ending.
     fn line_ending(&mut self) {
         let eol = self.line_ending_default.as_str().to_string();
+        self.trim_trailing_whitespace();
         self.push(&eol);
     }
 
+    /// Remove spaces and tabs at the end of the last buffer, if
+    /// `trim_trailing_whitespace` is on and we are not in preformatted text.
+    fn trim_trailing_whitespace(&mut self) {
+        if !self.options.trim_trailing_whitespace || self.pre_inside {
+            return;
+        }
+
+        let last_buf_opt = self.buffers.last_mut();
+        let last_buf = last_buf_opt.expect("at least one buffer should exist");
+        let len = last_buf.trim_end_matches(|d| d == ' ' || d == '\t').len();
+
+        if len == last_buf.len() {
+            return;
+        }
+
+        last_buf.truncate(len);
+
+        // Offsets into the main buffer must stay in it.
+        if self.buffers.len() == 1 {
+            if let Some(source_map) = &mut self.source_map {
+                for (offset, _) in source_map.iter_mut() {
+                    if *offset > len {
+                        *offset = len;
+                    }
+                }
+            }
+
+            if let Some(block_starts) = &mut self.block_starts {
+                for (offset, _) in block_starts.iter_mut() {
+                    if *offset > len {
+                        *offset = len;
+                    }
+                }
+            }
+        }
+    }
+
     /// Add a line ending if needed (as in, there’s no eol/eof already).
     fn line_ending_if_needed(&mut self) {
         let last_buf_opt = self.buffers.last();
@@ -201,22 +306,211 @@ impl<'a> CompileContext<'a> {
             self.line_ending();
         }
     }
+
+    /// Close a void element (such as `<br />`), as configured with
+    /// `html_syntax`.
+    fn push_void_end(&mut self) {
+        self.push(if self.options.html_syntax == HtmlSyntax::Html {
+            ">"
+        } else {
+            " />"
+        });
+    }
+
+    /// Add a boolean attribute (such as ` disabled=""`), as configured with
+    /// `html_syntax`.
+    fn push_boolean_attribute(&mut self, name: &str) {
+        self.push(" ");
+        self.push(name);
+
+        match self.options.html_syntax {
+            HtmlSyntax::Polyglot => self.push("=\"\""),
+            HtmlSyntax::Xhtml => {
+                self.push("=\"");
+                self.push(name);
+                self.push("\"");
+            }
+            HtmlSyntax::Html => {}
+        }
+    }
+}
+
+/// Kind of top-level block, as yielded by
+/// [`to_html_blocks()`][crate::to_html_blocks()].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlockKind {
+    /// Block quote (`> a`).
+    BlockQuote,
+    /// Code (fenced) (`~~~`).
+    CodeFenced,
+    /// Code (indented) (`    a`).
+    CodeIndented,
+    /// Definition (`[a]: b`).
+    ///
+    /// Definitions produce no HTML, unless
+    /// [`debug_definitions`][CompileOptions::debug_definitions] is on.
+    Definition,
+    /// Directive (container) (`:::a`).
+    DirectiveContainer,
+    /// Directive (leaf) (`::a`).
+    DirectiveLeaf,
+    /// Frontmatter (`---`).
+    Frontmatter,
+    /// GFM: footnote definition (`[^a]: b`).
+    ///
+    /// Footnote definitions produce no HTML where they are: they are
+    /// compiled into the footnote section.
+    GfmFootnoteDefinition,
+    /// GFM: section with footnotes, generated at the end of the document.
+    GfmFootnoteSection,
+    /// GFM: table (`| a |`).
+    GfmTable,
+    /// Heading (atx) (`# a`).
+    HeadingAtx,
+    /// Heading (setext) (`a` followed by `===` on the next line).
+    HeadingSetext,
+    /// HTML (flow) (`<div>`).
+    HtmlFlow,
+    /// Ordered list (`1. a`).
+    ListOrdered,
+    /// Unordered list (`* a`).
+    ListUnordered,
+    /// Math (flow) (`$$`).
+    MathFlow,
+    /// MDX: ESM (`import a from 'b'`).
+    MdxEsm,
+    /// MDX: expression (flow) (`{a}`).
+    MdxFlowExpression,
+    /// MDX: JSX (flow) (`<A />`).
+    MdxJsxFlowTag,
+    /// Paragraph.
+    Paragraph,
+    /// Thematic break (`***`).
+    ThematicBreak,
+}
+
+impl BlockKind {
+    /// Get the kind of block that starts with an event named `name`.
+    ///
+    /// Returns `None` for things that are not blocks, such as line endings
+    /// between them.
+    fn from_name(name: &Name) -> Option<BlockKind> {
+        match name {
+            Name::BlockQuote => Some(BlockKind::BlockQuote),
+            Name::CodeFenced => Some(BlockKind::CodeFenced),
+            Name::CodeIndented => Some(BlockKind::CodeIndented),
+            Name::Definition => Some(BlockKind::Definition),
+            Name::DirectiveContainer => Some(BlockKind::DirectiveContainer),
+            Name::DirectiveLeaf => Some(BlockKind::DirectiveLeaf),
+            Name::Frontmatter => Some(BlockKind::Frontmatter),
+            Name::GfmFootnoteDefinition => Some(BlockKind::GfmFootnoteDefinition),
+            Name::GfmTable => Some(BlockKind::GfmTable),
+            Name::HeadingAtx => Some(BlockKind::HeadingAtx),
+            Name::HeadingSetext => Some(BlockKind::HeadingSetext),
+            Name::HtmlFlow => Some(BlockKind::HtmlFlow),
+            Name::ListOrdered => Some(BlockKind::ListOrdered),
+            Name::ListUnordered => Some(BlockKind::ListUnordered),
+            Name::MathFlow => Some(BlockKind::MathFlow),
+            Name::MdxEsm => Some(BlockKind::MdxEsm),
+            Name::MdxFlowExpression => Some(BlockKind::MdxFlowExpression),
+            Name::MdxJsxFlowTag => Some(BlockKind::MdxJsxFlowTag),
+            Name::Paragraph => Some(BlockKind::Paragraph),
+            Name::ThematicBreak => Some(BlockKind::ThematicBreak),
+            _ => None,
+        }
+    }
 }
 
 /// Turn events and bytes into a string of HTML.
-pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> String {
+///
+/// When `source_map`, also returns offsets in the HTML (where elements and
+/// text start and end) with the points in markdown that they come from.
+///
+/// Errors if the HTML gets bigger than
+/// [`max_output_size`][CompileOptions::max_output_size].
+pub fn compile(
+    events: &[Event],
+    bytes: &[u8],
+    parse_options: &ParseOptions,
+    options: &CompileOptions,
+    source_map: bool,
+) -> Result<(String, Vec<(usize, Point)>), message::Message> {
+    let compiled = compile_impl(events, bytes, parse_options, options, source_map, false)?;
+    Ok((compiled.html, compiled.source_map))
+}
+
+/// Turn events and bytes into HTML, split into top-level blocks.
+///
+/// The line ending between two blocks belongs to the first one, so that the
+/// blocks, joined together, are the same as the result of [`compile()`][].
+pub fn compile_blocks(
+    events: &[Event],
+    bytes: &[u8],
+    parse_options: &ParseOptions,
+    options: &CompileOptions,
+) -> Result<Vec<(BlockKind, String)>, message::Message> {
+    let Compiled {
+        html, block_starts, ..
+    } = compile_impl(events, bytes, parse_options, options, false, true)?;
+    let html_bytes = html.as_bytes();
+    let mut blocks = vec![];
+    let mut index = 0;
+
+    while index < block_starts.len() {
+        let start = if index == 0 {
+            0
+        } else {
+            skip_line_ending(html_bytes, block_starts[index].0)
+        };
+        let end = if index + 1 < block_starts.len() {
+            skip_line_ending(html_bytes, block_starts[index + 1].0)
+        } else {
+            html.len()
+        };
+
+        blocks.push((block_starts[index].1, html[start..end].to_string()));
+        index += 1;
+    }
+
+    Ok(blocks)
+}
+
+/// Move past one line ending at `index` in `bytes`, if there is one.
+fn skip_line_ending(bytes: &[u8], index: usize) -> usize {
+    match (bytes.get(index), bytes.get(index + 1)) {
+        (Some(b'\r'), Some(b'\n')) => index + 2,
+        (Some(b'\n' | b'\r'), _) => index + 1,
+        _ => index,
+    }
+}
+
+/// Turn events and bytes into a string of HTML, optionally also collecting
+/// a source map and where top-level blocks start.
+fn compile_impl(
+    events: &[Event],
+    bytes: &[u8],
+    parse_options: &ParseOptions,
+    options: &CompileOptions,
+    source_map: bool,
+    block_starts: bool,
+) -> Result<Compiled, message::Message> {
     let mut index = 0;
     let mut line_ending_inferred = None;
 
     // First, we figure out what the used line ending style is.
     // Stop when we find a line ending.
-    while index < events.len() {
+    // A configured line ending overrides the document.
+    while options.default_line_ending.is_none() && index < events.len() {
         let event = &events[index];
 
         if event.kind == Kind::Exit
             && (event.name == Name::BlankLineEnding || event.name == Name::LineEnding)
         {
-            let slice = Slice::from_position(bytes, &Position::from_exit_event(events, index));
+            let slice = Slice::from_position(
+                bytes,
+                &Position::from_exit_event(events, index),
+                parse_options.tab_size,
+            );
             line_ending_inferred = Some(slice.as_str().parse().unwrap());
             break;
         }
@@ -225,10 +519,14 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
     }
 
     // Figure out which line ending style we’ll use.
-    let line_ending_default =
-        line_ending_inferred.unwrap_or_else(|| options.default_line_ending.clone());
-
-    let mut context = CompileContext::new(events, bytes, options, line_ending_default);
+    let line_ending_default = options
+        .default_line_ending
+        .clone()
+        .or(line_ending_inferred)
+        .unwrap_or_default();
+
+    let mut context =
+        CompileContext::new(events, bytes, parse_options, options, line_ending_default);
     let mut definition_indices = vec![];
     let mut index = 0;
     let mut definition_inside = false;
@@ -265,7 +563,22 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
         index += 1;
     }
 
+    if source_map {
+        context.source_map = Some(vec![]);
+    }
+
+    if block_starts {
+        context.block_starts = Some(vec![]);
+    }
+
+    // Reserve the IDs in attribute lists, so that heading IDs made from text
+    // do not clash with them, even when they come later.
+    if options.heading_ids && parse_options.constructs.attributes {
+        context.heading_slugs = attribute_ids(&context);
+    }
+
     let mut index = 0;
+    let mut depth = 0;
     let jump_default = (events.len(), events.len());
     let mut definition_index = 0;
     let mut jump = definition_indices
@@ -273,50 +586,135 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
         .unwrap_or(&jump_default);
 
     while index < events.len() {
+        if depth == 0 && events[index].kind == Kind::Enter {
+            if let (Some(block_starts), Some(kind)) = (
+                &mut context.block_starts,
+                BlockKind::from_name(&events[index].name),
+            ) {
+                block_starts.push((context.buffers[0].len(), kind));
+            }
+        }
+
         if index == jump.0 {
+            if options.debug_definitions {
+                on_definition_debug(&mut context, *jump, definition_index);
+            }
+
             index = jump.1 + 1;
             definition_index += 1;
             jump = definition_indices
                 .get(definition_index)
                 .unwrap_or(&jump_default);
         } else {
+            if events[index].kind == Kind::Enter {
+                depth += 1;
+            } else {
+                depth -= 1;
+            }
+
             handle(&mut context, index);
+            check_output_size(&context)?;
             index += 1;
         }
     }
 
     // No section to generate.
     if !context.gfm_footnote_definition_calls.is_empty() {
+        if let Some(block_starts) = &mut context.block_starts {
+            block_starts.push((context.buffers[0].len(), BlockKind::GfmFootnoteSection));
+        }
+
         generate_footnote_section(&mut context);
+        // The definitions are now in the main buffer, or unused.
+        context.gfm_footnote_definitions_size = 0;
+        check_output_size(&context)?;
     }
 
+    context.trim_trailing_whitespace();
+
     debug_assert_eq!(context.buffers.len(), 1, "expected 1 final buffer");
-    context
-        .buffers
-        .first()
-        .expect("expected 1 final buffer")
-        .into()
+    Ok(Compiled {
+        html: context.buffers.pop().expect("expected 1 final buffer"),
+        source_map: context.source_map.unwrap_or_default(),
+        block_starts: context.block_starts.unwrap_or_default(),
+    })
+}
+
+/// Error if the HTML is bigger than `max_output_size`.
+///
+/// All buffers are counted, as well as footnote definitions, which are only
+/// added to the main buffer at the end.
+fn check_output_size(context: &CompileContext) -> Result<(), message::Message> {
+    if let Some(max) = context.options.max_output_size {
+        let size = context.buffers.iter().map(String::len).sum::<usize>()
+            + context.gfm_footnote_definitions_size;
+
+        if size > max {
+            return Err(message::Message {
+                place: Some(Box::new(message::Place::Point(
+                    context.events[context.index].point.to_unist(),
+                ))),
+                reason: format!(
+                    "Unexpected HTML bigger than `max_output_size` (`{}` bytes)",
+                    max
+                ),
+                rule_id: Box::new("max-output-size".into()),
+                source: Box::new("markdown-rs".into()),
+            });
+        }
+    }
+
+    Ok(())
 }
 
 /// Handle the event at `index`.
 fn handle(context: &mut CompileContext, index: usize) {
     context.index = index;
 
+    let start = if context.source_map.is_some() && context.buffers.len() == 1 {
+        Some(context.buffers[0].len())
+    } else {
+        None
+    };
+
     if context.events[index].kind == Kind::Enter {
         enter(context);
     } else {
         exit(context);
     }
+
+    // Map where the event generated something, if it was not buffered, as
+    // buffers are moved around.
+    if let (Some(source_map), Some(start), [buffer]) =
+        (&mut context.source_map, start, context.buffers.as_slice())
+    {
+        if buffer.len() > start && source_map.last().map_or(true, |d| d.0 < start) {
+            let mut point = &context.events[index].point;
+
+            // Things such as data are generated when exiting them, but they
+            // start where they are entered.
+            if context.events[index].kind == Kind::Exit
+                && context.events[index - 1].kind == Kind::Enter
+                && context.events[index - 1].name == context.events[index].name
+            {
+                point = &context.events[index - 1].point;
+            }
+
+            source_map.push((start, Point::new(point.line, point.column, point.index)));
+        }
+    }
 }
 
 /// Handle [`Enter`][Kind::Enter].
 fn enter(context: &mut CompileContext) {
     match context.events[context.index].name {
-        Name::CodeFencedFenceInfo
-        | Name::CodeFencedFenceMeta
+        Name::CodeFencedFenceMeta
         | Name::MathFlowFenceMeta
         | Name::DefinitionLabelString
         | Name::DefinitionTitleString
+        | Name::DirectiveContainer
+        | Name::DirectiveLeaf
+        | Name::DirectiveText
         | Name::GfmFootnoteDefinitionPrefix
         | Name::HeadingAtxText
         | Name::HeadingSetextText
@@ -332,6 +730,7 @@ fn enter(context: &mut CompileContext) {
         Name::BlockQuote => on_enter_block_quote(context),
         Name::CodeIndented => on_enter_code_indented(context),
         Name::CodeFenced | Name::MathFlow => on_enter_raw_flow(context),
+        Name::CodeFencedFenceInfo => on_enter_raw_flow_fence_info(context),
         Name::CodeText | Name::MathText => on_enter_raw_text(context),
         Name::Definition => on_enter_definition(context),
         Name::DefinitionDestinationString => on_enter_definition_destination_string(context),
@@ -349,9 +748,11 @@ fn enter(context: &mut CompileContext) {
         Name::HtmlFlow => on_enter_html_flow(context),
         Name::HtmlText => on_enter_html_text(context),
         Name::Image => on_enter_image(context),
+        Name::InlineFootnote => on_enter_inline_footnote(context),
         Name::Link => on_enter_link(context),
         Name::ListItemMarker => on_enter_list_item_marker(context),
         Name::ListOrdered | Name::ListUnordered => on_enter_list(context),
+        Name::Mark => on_enter_mark(context),
         Name::Paragraph => on_enter_paragraph(context),
         Name::Resource => on_enter_resource(context),
         Name::ResourceDestinationString => on_enter_resource_destination_string(context),
@@ -363,17 +764,25 @@ fn enter(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit].
 fn exit(context: &mut CompileContext) {
     match context.events[context.index].name {
-        Name::CodeFencedFenceMeta
+        Name::DirectiveText
         | Name::MathFlowFenceMeta
         | Name::MdxJsxTextTag
         | Name::MdxTextExpression
         | Name::Resource => {
             on_exit_drop(context);
         }
-        Name::MdxEsm | Name::MdxFlowExpression | Name::MdxJsxFlowTag => on_exit_drop_slurp(context),
+        // Container directives include their closing line ending already.
+        Name::DirectiveContainer => on_exit_drop(context),
+        Name::DirectiveLeaf | Name::MdxEsm | Name::MdxFlowExpression | Name::MdxJsxFlowTag => {
+            on_exit_drop_slurp(context);
+        }
         Name::CharacterEscapeValue | Name::CodeTextData | Name::Data | Name::MathTextData => {
             on_exit_data(context);
         }
+        Name::SmartPunctuationDash
+        | Name::SmartPunctuationEllipsis
+        | Name::SmartPunctuationQuoteClosing
+        | Name::SmartPunctuationQuoteOpening => on_exit_smart_punctuation(context),
         Name::AutolinkEmail => on_exit_autolink_email(context),
         Name::AutolinkProtocol => on_exit_autolink_protocol(context),
         Name::BlankLineEnding => on_exit_blank_line_ending(context),
@@ -389,6 +798,7 @@ fn exit(context: &mut CompileContext) {
         Name::CodeFenced | Name::CodeIndented | Name::MathFlow => on_exit_raw_flow(context),
         Name::CodeFencedFence | Name::MathFlowFence => on_exit_raw_flow_fence(context),
         Name::CodeFencedFenceInfo => on_exit_raw_flow_fence_info(context),
+        Name::CodeFencedFenceMeta => on_exit_raw_flow_fence_meta(context),
         Name::CodeFlowChunk | Name::MathFlowChunk => on_exit_raw_flow_chunk(context),
         Name::CodeText | Name::MathText => on_exit_raw_text(context),
         Name::Definition => on_exit_definition(context),
@@ -417,6 +827,7 @@ fn exit(context: &mut CompileContext) {
         Name::GfmTaskListItemCheck => on_exit_gfm_task_list_item_check(context),
         Name::GfmTaskListItemValueChecked => on_exit_gfm_task_list_item_value_checked(context),
         Name::HardBreakEscape | Name::HardBreakTrailing => on_exit_break(context),
+        Name::Hashtag | Name::Mention => on_exit_hashtag_or_mention(context),
         Name::HeadingAtx => on_exit_heading_atx(context),
         Name::HeadingAtxSequence => on_exit_heading_atx_sequence(context),
         Name::HeadingAtxText => on_exit_heading_atx_text(context),
@@ -425,18 +836,21 @@ fn exit(context: &mut CompileContext) {
         Name::HtmlFlow | Name::HtmlText => on_exit_html(context),
         Name::HtmlFlowData | Name::HtmlTextData => on_exit_html_data(context),
         Name::Image | Name::Link => on_exit_media(context),
+        Name::InlineFootnote => on_exit_inline_footnote(context),
         Name::Label => on_exit_label(context),
         Name::LabelText => on_exit_label_text(context),
         Name::LineEnding => on_exit_line_ending(context),
         Name::ListOrdered | Name::ListUnordered => on_exit_list(context),
         Name::ListItem => on_exit_list_item(context),
         Name::ListItemValue => on_exit_list_item_value(context),
+        Name::Mark => on_exit_mark(context),
         Name::Paragraph => on_exit_paragraph(context),
         Name::ReferenceString => on_exit_reference_string(context),
         Name::ResourceDestinationString => on_exit_resource_destination_string(context),
         Name::ResourceTitleString => on_exit_resource_title_string(context),
         Name::Strong => on_exit_strong(context),
         Name::ThematicBreak => on_exit_thematic_break(context),
+        Name::WikiLink => on_exit_wikilink(context),
         _ => {}
     }
 }
@@ -452,22 +866,34 @@ fn on_enter_buffer(context: &mut CompileContext) {
 fn on_enter_block_quote(context: &mut CompileContext) {
     context.tight_stack.push(false);
     context.line_ending_if_needed();
-    context.push("<blockquote>");
+    context.push("<blockquote");
+    source_line(context, context.index);
+    context.push(">");
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`CodeIndented`][Name::CodeIndented].
 fn on_enter_code_indented(context: &mut CompileContext) {
     context.raw_flow_seen_data = Some(false);
     context.line_ending_if_needed();
-    context.push("<pre><code>");
+    context.pre_inside = true;
+    context.push("<pre");
+    source_line(context, context.index);
+    context.push("><code>");
+
+    if context.options.code_line_numbers {
+        context.buffer();
+    }
 }
 
 /// Handle [`Enter`][Kind::Enter]:{[`CodeFenced`][Name::CodeFenced],[`MathFlow`][Name::MathFlow]}.
 fn on_enter_raw_flow(context: &mut CompileContext) {
     context.raw_flow_seen_data = Some(false);
     context.line_ending_if_needed();
+    context.pre_inside = true;
+    context.push("<pre");
+    source_line(context, context.index);
     // Note that no `>` is used, which is added later (due to info)
-    context.push("<pre><code");
+    context.push("><code");
     context.raw_flow_fences_count = Some(0);
 
     if context.events[context.index].name == Name::MathFlow {
@@ -475,6 +901,13 @@ fn on_enter_raw_flow(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`CodeFencedFenceInfo`][Name::CodeFencedFenceInfo].
+fn on_enter_raw_flow_fence_info(context: &mut CompileContext) {
+    // Encoded when used, as the highlighter gets it as-is.
+    context.buffer();
+    context.encode_html = false;
+}
+
 /// Handle [`Enter`][Kind::Enter]:{[`CodeText`][Name::CodeText],[`MathText`][Name::MathText]}.
 fn on_enter_raw_text(context: &mut CompileContext) {
     context.raw_text_inside = true;
@@ -493,6 +926,7 @@ fn on_enter_definition(context: &mut CompileContext) {
     context.buffer();
     context.media_stack.push(Media {
         image: false,
+        inline_footnote: false,
         label: None,
         label_id: None,
         reference_id: None,
@@ -528,6 +962,7 @@ fn on_enter_gfm_footnote_definition(context: &mut CompileContext) {
 fn on_enter_gfm_footnote_call(context: &mut CompileContext) {
     context.media_stack.push(Media {
         image: false,
+        inline_footnote: false,
         label_id: None,
         label: None,
         reference_id: None,
@@ -574,11 +1009,21 @@ fn on_enter_gfm_table_cell(context: &mut CompileContext) {
             context.push("<td");
         }
 
-        match value {
-            AlignKind::Left => context.push(" align=\"left\""),
-            AlignKind::Right => context.push(" align=\"right\""),
-            AlignKind::Center => context.push(" align=\"center\""),
-            AlignKind::None => {}
+        let value = match value {
+            AlignKind::Left => Some("left"),
+            AlignKind::Right => Some("right"),
+            AlignKind::Center => Some("center"),
+            AlignKind::None => None,
+        };
+
+        if let Some(value) = value {
+            match context.options.gfm_table_cell_align {
+                GfmTableCellAlign::Attribute => context.push(" align=\""),
+                GfmTableCellAlign::Style => context.push(" style=\"text-align:"),
+            }
+
+            context.push(value);
+            context.push("\"");
         }
 
         context.push(">");
@@ -601,9 +1046,9 @@ fn on_enter_gfm_table_row(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`GfmTaskListItemCheck`][Name::GfmTaskListItemCheck].
 fn on_enter_gfm_task_list_item_check(context: &mut CompileContext) {
     if !context.image_alt_inside {
-        context.push("<input type=\"checkbox\" ");
+        context.push("<input type=\"checkbox\"");
         if !context.options.gfm_task_list_item_checkable {
-            context.push("disabled=\"\" ");
+            context.push_boolean_attribute("disabled");
         }
     }
 }
@@ -611,14 +1056,40 @@ fn on_enter_gfm_task_list_item_check(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`HtmlFlow`][Name::HtmlFlow].
 fn on_enter_html_flow(context: &mut CompileContext) {
     context.line_ending_if_needed();
-    if context.options.allow_dangerous_html {
-        context.encode_html = false;
-    }
+    context.pre_inside = is_html_flow_raw(context);
+    on_enter_html(context);
+}
+
+/// Check whether the HTML (flow) at the current event starts with a tag
+/// whose content is kept as-is (such as `<pre>`).
+fn is_html_flow_raw(context: &CompileContext) -> bool {
+    let bytes = &context.bytes[context.events[context.index].point.index..];
+
+    HTML_RAW_NAMES.iter().any(|name| {
+        bytes.len() > name.len() + 1
+            && bytes[0] == b'<'
+            && bytes[1..=name.len()].eq_ignore_ascii_case(name.as_bytes())
+            && matches!(bytes[name.len() + 1], b'\t' | b'\n' | b'\r' | b' ' | b'>')
+    })
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`HtmlText`][Name::HtmlText].
 fn on_enter_html_text(context: &mut CompileContext) {
+    on_enter_html(context);
+}
+
+/// Handle [`Enter`][Kind::Enter]:{[`HtmlFlow`][Name::HtmlFlow],[`HtmlText`][Name::HtmlText]}.
+fn on_enter_html(context: &mut CompileContext) {
     if context.options.allow_dangerous_html {
+        // Normalize the whole thing on exit, as tags can span several lines.
+        if context.options.normalize_html_tags {
+            context.buffer();
+        }
+
+        context.encode_html = false;
+    } else if context.options.html_sanitization.is_some() {
+        // Sanitize the whole thing on exit, as tags can span several lines.
+        context.buffer();
         context.encode_html = false;
     }
 }
@@ -627,6 +1098,7 @@ fn on_enter_html_text(context: &mut CompileContext) {
 fn on_enter_image(context: &mut CompileContext) {
     context.media_stack.push(Media {
         image: true,
+        inline_footnote: false,
         label_id: None,
         label: None,
         reference_id: None,
@@ -636,10 +1108,24 @@ fn on_enter_image(context: &mut CompileContext) {
     context.image_alt_inside = true; // Disallow tags.
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`InlineFootnote`][Name::InlineFootnote].
+fn on_enter_inline_footnote(context: &mut CompileContext) {
+    context.media_stack.push(Media {
+        image: false,
+        inline_footnote: true,
+        label_id: None,
+        label: None,
+        reference_id: None,
+        destination: None,
+        title: None,
+    });
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Link`][Name::Link].
 fn on_enter_link(context: &mut CompileContext) {
     context.media_stack.push(Media {
         image: false,
+        inline_footnote: false,
         label_id: None,
         label: None,
         reference_id: None,
@@ -666,22 +1152,47 @@ fn on_enter_list(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`ListItemMarker`][Name::ListItemMarker].
 fn on_enter_list_item_marker(context: &mut CompileContext) {
     if context.list_expect_first_marker.take().unwrap() {
+        if context.options.debug_list_markers {
+            let marker = context.bytes[context.events[context.index].point.index];
+            context.push(" data-marker=\"");
+            context.push(str::from_utf8(&[marker]).unwrap());
+            context.push("\"");
+        }
+
         context.push(">");
     }
 
     context.line_ending_if_needed();
 
-    context.push("<li>");
+    context.push("<li");
+    source_line(context, context.index);
+    context.push(">");
     context.list_expect_first_marker = Some(false);
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`Mark`][Name::Mark].
+fn on_enter_mark(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push("<mark>");
+    }
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Paragraph`][Name::Paragraph].
 fn on_enter_paragraph(context: &mut CompileContext) {
     let tight = context.tight_stack.last().unwrap_or(&false);
+    context.paragraph_inside = true;
+    context.figure_inside = context.options.image_figure && is_lone_image(context, context.index);
 
-    if !tight {
+    if context.figure_inside {
+        context.line_ending_if_needed();
+        context.push("<figure");
+        source_line(context, context.index);
+        context.push(">");
+    } else if !tight {
         context.line_ending_if_needed();
-        context.push("<p>");
+        context.push("<p");
+        source_line(context, context.index);
+        context.push(">");
     }
 }
 
@@ -714,6 +1225,7 @@ fn on_exit_autolink_email(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         false,
@@ -728,6 +1240,7 @@ fn on_exit_autolink_protocol(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         false,
@@ -737,7 +1250,8 @@ fn on_exit_autolink_protocol(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:{[`HardBreakEscape`][Name::HardBreakEscape],[`HardBreakTrailing`][Name::HardBreakTrailing]}.
 fn on_exit_break(context: &mut CompileContext) {
     if !context.image_alt_inside {
-        context.push("<br />");
+        context.push("<br");
+        context.push_void_end();
     }
 }
 
@@ -780,24 +1294,33 @@ fn on_exit_character_reference_value(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
-    let value = decode_character_reference(slice.as_str(), marker, true)
-        .expect("expected to parse only valid named references");
+    let value = decode_character_reference(
+        slice.as_str(),
+        marker,
+        &context.parse_options.character_references,
+    )
+    .expect("expected to parse only valid named references");
 
     context.push(&encode(&value, context.encode_html));
 }
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFlowChunk`][Name::CodeFlowChunk],[`MathFlowChunk`][Name::MathFlowChunk]}.
 fn on_exit_raw_flow_chunk(context: &mut CompileContext) {
+    let encode_html = context.encode_html
+        && !(context.options.unsafe_raw_code
+            && context.events[context.index].name == Name::CodeFlowChunk);
     context.raw_flow_seen_data = Some(true);
     context.push(&encode(
         &Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         // Must serialize to get virtual spaces.
         .serialize(),
-        context.encode_html,
+        encode_html,
     ));
 }
 
@@ -808,8 +1331,66 @@ fn on_exit_raw_flow_fence(context: &mut CompileContext) {
         .expect("expected `raw_flow_fences_count`");
 
     if count == 0 {
+        if context.events[context.index].name == Name::CodeFencedFence {
+            let mut info = context.raw_flow_fence_info.take();
+            let mut meta = context.raw_flow_fence_meta.take();
+            let mut attributes = vec![];
+
+            if context.parse_options.constructs.attributes {
+                if let Some((in_info, list)) = code_fenced_attributes(context, context.index) {
+                    // Attributes instead of an info string (`~~~ {.a}`).
+                    if in_info {
+                        info = None;
+                    }
+
+                    // The meta is (part of) the attribute list.
+                    meta = None;
+                    attributes = list;
+                }
+            }
+
+            let info = info.or_else(|| context.options.default_code_language.clone());
+
+            if context.options.code_highlighter.is_some() {
+                context.raw_flow_fence_lang = Some(info.clone().unwrap_or_default());
+            }
+
+            let class = info.map(|d| {
+                let mut value = encode(
+                    context
+                        .options
+                        .code_block_class_prefix
+                        .as_deref()
+                        .unwrap_or("language-"),
+                    context.encode_html,
+                );
+                value.push_str(&encode(&d, context.encode_html));
+                value
+            });
+
+            push_attributes(context, &attributes, class);
+
+            if let (true, Some(meta)) = (context.options.code_meta_attribute, meta) {
+                context.push(" data-meta=\"");
+                context.push(&meta);
+                context.push("\"");
+            }
+        }
+
         context.push(">");
         context.slurp_one_line_ending = true;
+
+        if context.options.code_line_numbers
+            && context.events[context.index].name == Name::CodeFencedFence
+        {
+            context.buffer();
+        }
+
+        // Collect the code as-is for the highlighter.
+        if context.raw_flow_fence_lang.is_some() {
+            context.buffer();
+            context.encode_html = false;
+        }
     }
 
     context.raw_flow_fences_count = Some(count + 1);
@@ -820,9 +1401,108 @@ fn on_exit_raw_flow_fence(context: &mut CompileContext) {
 /// Note: math (flow) does not support `info`.
 fn on_exit_raw_flow_fence_info(context: &mut CompileContext) {
     let value = context.resume();
-    context.push(" class=\"language-");
-    context.push(&value);
-    context.push("\"");
+    context.encode_html = true;
+    context.raw_flow_fence_info = Some(value);
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`CodeFencedFenceMeta`][Name::CodeFencedFenceMeta].
+fn on_exit_raw_flow_fence_meta(context: &mut CompileContext) {
+    let value = context.resume();
+    context.raw_flow_fence_meta = Some(value);
+}
+
+/// Find an attribute list in the opening fence of code (fenced), which ends
+/// at `index`.
+///
+/// The attribute list is either the meta (`~~~a {#b}`), or the info and
+/// meta together (`~~~ {#b .c}`), in which case the first value is `true`.
+fn code_fenced_attributes(
+    context: &CompileContext,
+    index: usize,
+) -> Option<(bool, Vec<(String, String)>)> {
+    let mut index = index - 1;
+    let mut info = None;
+    let mut meta = None;
+
+    while context.events[index].name != Name::CodeFencedFence {
+        let event = &context.events[index];
+
+        if event.kind == Kind::Exit {
+            if event.name == Name::CodeFencedFenceInfo {
+                info = Some(Position::from_exit_event(context.events, index));
+            } else if event.name == Name::CodeFencedFenceMeta {
+                meta = Some(Position::from_exit_event(context.events, index));
+            }
+        }
+
+        index -= 1;
+    }
+
+    let info = info?;
+
+    if context.bytes[info.start.index] == b'{' {
+        let end = meta.as_ref().map_or(info.end.index, |d| d.end.index);
+        let value = str::from_utf8(&context.bytes[info.start.index..end]).unwrap();
+
+        if let Some(attributes) = parse_attributes(value) {
+            return Some((true, attributes));
+        }
+    }
+
+    let meta = meta?;
+    let value = str::from_utf8(&context.bytes[meta.start.index..meta.end.index]).unwrap();
+    parse_attributes(value).map(|d| (false, d))
+}
+
+/// Add attributes from an attribute list.
+///
+/// The `id` comes first, then `class`, with `class` (such as the language
+/// of code) before the classes in the list, then other attributes.
+/// Unsafe attributes are dropped, unless dangerous HTML is allowed.
+fn push_attributes(
+    context: &mut CompileContext,
+    attributes: &[(String, String)],
+    class: Option<String>,
+) {
+    let mut id = None;
+    let mut classes = vec![];
+
+    if let Some(class) = class {
+        classes.push(class);
+    }
+
+    for (name, value) in attributes {
+        if name == "id" {
+            id = Some(value);
+        } else if name == "class" {
+            classes.push(encode(value, true));
+        }
+    }
+
+    if let Some(id) = id {
+        context.push(" id=\"");
+        context.push(&encode(id, true));
+        context.push("\"");
+    }
+
+    if !classes.is_empty() {
+        context.push(" class=\"");
+        context.push(&classes.join(" "));
+        context.push("\"");
+    }
+
+    for (name, value) in attributes {
+        if name != "id"
+            && name != "class"
+            && (context.options.allow_dangerous_html || safe_attribute(name, value))
+        {
+            context.push(" ");
+            context.push(name);
+            context.push("=\"");
+            context.push(&encode(value, true));
+            context.push("\"");
+        }
+    }
 }
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFenced`][Name::CodeFenced],[`CodeIndented`][Name::CodeIndented],[`MathFlow`][Name::MathFlow]}.
@@ -854,7 +1534,25 @@ fn on_exit_raw_flow(context: &mut CompileContext) {
         context.line_ending_if_needed();
     }
 
+    if let Some(lang) = context.raw_flow_fence_lang.take() {
+        let code = context.resume();
+        context.encode_html = true;
+        let highlighter = context
+            .options
+            .code_highlighter
+            .as_ref()
+            .expect("expected `code_highlighter` if there is a language");
+        let html = highlighter(&lang, &code);
+        context.push(&html);
+    }
+
+    if context.options.code_line_numbers && context.events[context.index].name != Name::MathFlow {
+        let value = context.resume();
+        push_code_lines(context, &value);
+    }
+
     context.push("</code></pre>");
+    context.pre_inside = false;
 
     if let Some(count) = context.raw_flow_fences_count.take() {
         if count < 2 {
@@ -865,6 +1563,23 @@ fn on_exit_raw_flow(context: &mut CompileContext) {
     context.slurp_one_line_ending = false;
 }
 
+/// Wrap each line of compiled code in a span.
+///
+/// The line ending after the last line does not start another line.
+fn push_code_lines(context: &mut CompileContext, value: &str) {
+    let mut rest = value;
+
+    while !rest.is_empty() {
+        let (line, after) = rest.split_at(rest.find('\n').map_or(rest.len(), |index| index + 1));
+        let text = line.trim_end_matches('\n').trim_end_matches('\r');
+        context.push("<span class=\"line\">");
+        context.push(text);
+        context.push("</span>");
+        context.push(&line[text.len()..]);
+        rest = after;
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:{[`CodeText`][Name::CodeText],[`MathText`][Name::MathText]}.
 fn on_exit_raw_text(context: &mut CompileContext) {
     let result = context.resume();
@@ -933,13 +1648,17 @@ fn on_exit_drop_slurp(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeTextData`][Name::CodeTextData],[`Data`][Name::Data],[`CharacterEscapeValue`][Name::CharacterEscapeValue]}.
 fn on_exit_data(context: &mut CompileContext) {
+    let encode_html = context.encode_html
+        && !(context.options.unsafe_raw_code
+            && context.events[context.index].name == Name::CodeTextData);
     context.push(&encode(
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
-        context.encode_html,
+        encode_html,
     ));
 }
 
@@ -958,6 +1677,39 @@ fn on_exit_definition(context: &mut CompileContext) {
     });
 }
 
+/// Show the definition at `indices` (in events), handled as the definition
+/// at `definition_index`, as a comment.
+fn on_definition_debug(
+    context: &mut CompileContext,
+    indices: (usize, usize),
+    definition_index: usize,
+) {
+    let label_index = (indices.0..indices.1)
+        .find(|d| {
+            context.events[*d].kind == Kind::Exit
+                && context.events[*d].name == Name::DefinitionLabelString
+        })
+        .expect("expected label in definition");
+    let label = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, label_index),
+        context.tab_size,
+    )
+    .serialize();
+    let destination = context.definitions[definition_index]
+        .destination
+        .clone()
+        .unwrap_or_default();
+
+    context.line_ending_if_needed();
+    context.push("<!-- definition: ");
+    // Encoding `>` makes sure the comment cannot be closed early.
+    context.push(&encode(&label, true));
+    context.push(" -> ");
+    context.push(&encode(&destination, true));
+    context.push(" -->");
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`DefinitionDestinationString`][Name::DefinitionDestinationString].
 fn on_exit_definition_destination_string(context: &mut CompileContext) {
     let buf = context.resume();
@@ -1000,6 +1752,7 @@ fn on_exit_gfm_autolink_literal_email(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1014,6 +1767,7 @@ fn on_exit_gfm_autolink_literal_mailto(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1028,6 +1782,7 @@ fn on_exit_gfm_autolink_literal_protocol(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1042,6 +1797,7 @@ fn on_exit_gfm_autolink_literal_www(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1056,6 +1812,7 @@ fn on_exit_gfm_autolink_literal_xmpp(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1088,10 +1845,33 @@ fn on_exit_gfm_footnote_call(context: &mut CompileContext) {
 
     // No call is output in an image alt, though the definition and
     // backreferences are generated as if it was the case.
-    if context.image_alt_inside {
-        return;
+    if !context.image_alt_inside {
+        generate_footnote_call(context, call_index, &safe_id);
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`InlineFootnote`][Name::InlineFootnote].
+fn on_exit_inline_footnote(context: &mut CompileContext) {
+    let label = context.media_stack.pop().unwrap().label.unwrap();
+    let call_index = context.gfm_footnote_definition_calls.len();
+    // Normalized identifiers are uppercase, so this never matches a GFM
+    // footnote definition.
+    let id = format!("inline-{}", call_index + 1);
+    let safe_id = sanitize(&id);
+
+    context
+        .gfm_footnote_definitions
+        .push((id.clone(), format!("<p>{}</p>", label)));
+    context.gfm_footnote_definition_calls.push((id, 1));
+
+    // Same as GFM footnote calls: no call in an image alt.
+    if !context.image_alt_inside {
+        generate_footnote_call(context, call_index, &safe_id);
     }
+}
 
+/// Generate a footnote call (`<sup>`) for the call at `call_index`.
+fn generate_footnote_call(context: &mut CompileContext, call_index: usize, safe_id: &str) {
     context.push("<sup><a href=\"#");
     if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
         context.push(&encode(value, context.encode_html));
@@ -1099,7 +1879,7 @@ fn on_exit_gfm_footnote_call(context: &mut CompileContext) {
         context.push("user-content-");
     }
     context.push("fn-");
-    context.push(&safe_id);
+    context.push(safe_id);
     context.push("\" id=\"");
     if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
         context.push(&encode(value, context.encode_html));
@@ -1107,7 +1887,7 @@ fn on_exit_gfm_footnote_call(context: &mut CompileContext) {
         context.push("user-content-");
     }
     context.push("fnref-");
-    context.push(&safe_id);
+    context.push(safe_id);
     if context.gfm_footnote_definition_calls[call_index].1 > 1 {
         context.push("-");
         context.push(
@@ -1142,6 +1922,7 @@ fn on_exit_gfm_footnote_definition(context: &mut CompileContext) {
     let value = context.resume();
     let indices = context.gfm_footnote_definition_stack.pop().unwrap();
     context.tight_stack.pop();
+    context.gfm_footnote_definitions_size += value.len();
     context.gfm_footnote_definitions.push((
         normalize_identifier(Slice::from_indices(context.bytes, indices.0, indices.1).as_str()),
         value,
@@ -1214,14 +1995,51 @@ fn on_exit_gfm_table_row(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:[`GfmTaskListItemCheck`][Name::GfmTaskListItemCheck].
 fn on_exit_gfm_task_list_item_check(context: &mut CompileContext) {
     if !context.image_alt_inside {
-        context.push("/>");
+        context.push_void_end();
     }
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`GfmTaskListItemValueChecked`][Name::GfmTaskListItemValueChecked].
 fn on_exit_gfm_task_list_item_value_checked(context: &mut CompileContext) {
     if !context.image_alt_inside {
-        context.push("checked=\"\" ");
+        context.push_boolean_attribute("checked");
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:{[`Hashtag`][Name::Hashtag],[`Mention`][Name::Mention]}.
+fn on_exit_hashtag_or_mention(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    let value = slice.as_str();
+    let (template, placeholder) = if context.events[context.index].name == Name::Hashtag {
+        (context.options.hashtag_url.as_ref(), "{tag}")
+    } else {
+        (context.options.mention_url.as_ref(), "{name}")
+    };
+    // Drop the marker (`#` or `@`).
+    let url = template.map(|template| template.replace(placeholder, &value[1..]));
+    let link = !context.image_alt_inside && !is_in_link(context);
+
+    if let (Some(url), true) = (&url, link) {
+        let sanitized = sanitize_url(context, url, false);
+        context.push("<a");
+        push_url_attribute(
+            context,
+            "href",
+            &sanitized,
+            sanitized.is_empty() && !url.is_empty(),
+        );
+        push_link_attributes(context, is_external(context, &sanitized));
+        context.push(">");
+    }
+
+    context.push(&encode(value, context.encode_html));
+
+    if url.is_some() && link {
+        context.push("</a>");
     }
 }
 
@@ -1244,16 +2062,231 @@ fn on_exit_heading_atx_sequence(context: &mut CompileContext) {
         let rank = Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .len();
         context.line_ending_if_needed();
         context.heading_atx_rank = Some(rank);
         context.push("<h");
         context.push(&rank.to_string());
+        source_line(context, context.index);
+
+        let mut end = context.index;
+        let mut attributes = vec![];
+
+        while !(context.events[end].kind == Kind::Exit
+            && context.events[end].name == Name::HeadingAtx)
+        {
+            if context.events[end].kind == Kind::Exit
+                && context.events[end].name == Name::HeadingAtxAttributes
+            {
+                attributes = heading_atx_attributes(context, end);
+            }
+
+            end += 1;
+        }
+
+        // An `id` in the attribute list wins.
+        if context.options.heading_ids && !attributes.iter().any(|d| d.0 == "id") {
+            heading_id(context, context.index, end);
+        }
+
+        push_attributes(context, &attributes, None);
+
         context.push(">");
     }
 }
 
+/// Get the attribute list of a heading (atx), which ends at `index`.
+fn heading_atx_attributes(context: &CompileContext, index: usize) -> Vec<(String, String)> {
+    let position = Position::from_exit_event(context.events, index);
+    let value = str::from_utf8(&context.bytes[position.start.index..position.end.index]).unwrap();
+    parse_attributes(value).expect("expected valid attributes")
+}
+
+/// Collect the `id`s in the attribute lists of headings (atx) and code
+/// (fenced) in the whole document.
+fn attribute_ids(context: &CompileContext) -> BTreeMap<String, usize> {
+    let mut ids = BTreeMap::new();
+    let mut index = 0;
+
+    while index < context.events.len() {
+        let event = &context.events[index];
+
+        if event.kind == Kind::Exit {
+            let attributes = match event.name {
+                Name::HeadingAtxAttributes => Some(heading_atx_attributes(context, index)),
+                Name::CodeFencedFence => code_fenced_attributes(context, index).map(|d| d.1),
+                _ => None,
+            };
+
+            for (name, value) in attributes.unwrap_or_default() {
+                if name == "id" {
+                    ids.insert(value, 0);
+                }
+            }
+        }
+
+        index += 1;
+    }
+
+    ids
+}
+
+/// Add a `data-line` attribute with the line of the event at `index`, if
+/// turned on.
+fn source_line(context: &mut CompileContext, index: usize) {
+    if context.options.source_lines {
+        context.push(" data-line=\"");
+        context.push(&context.events[index].point.line.to_string());
+        context.push("\"");
+    }
+}
+
+/// Check whether the paragraph entered at `index` contains exactly one image,
+/// and nothing else except whitespace.
+fn is_lone_image(context: &CompileContext, index: usize) -> bool {
+    let mut index = index + 1;
+
+    while context.events[index].name == Name::SpaceOrTab {
+        index += 1;
+    }
+
+    if context.events[index].name != Name::Image {
+        return false;
+    }
+
+    let mut depth = 0;
+
+    loop {
+        let event = &context.events[index];
+
+        if event.name == Name::Image {
+            if event.kind == Kind::Enter {
+                depth += 1;
+            } else {
+                depth -= 1;
+            }
+        }
+
+        index += 1;
+
+        if depth == 0 {
+            break;
+        }
+    }
+
+    while context.events[index].name == Name::SpaceOrTab {
+        index += 1;
+    }
+
+    context.events[index].name == Name::Paragraph
+}
+
+/// Add a unique `id` attribute for a heading, made from the text of the
+/// events from `start` to `end`.
+fn heading_id(context: &mut CompileContext, start: usize, end: usize) {
+    let mut value = String::new();
+    let mut ignore = 0;
+    let mut marker = b'&';
+    let mut index = start;
+
+    while index < end {
+        let event = &context.events[index];
+
+        match event.name {
+            // Things that do not contribute to the text of a heading.
+            Name::DirectiveText
+            | Name::GfmFootnoteCall
+            | Name::HtmlText
+            | Name::InlineFootnote
+            | Name::MdxJsxTextTag
+            | Name::MdxTextExpression
+            | Name::ReferenceString
+            | Name::ResourceDestinationString
+            | Name::ResourceTitleString => {
+                if event.kind == Kind::Enter {
+                    ignore += 1;
+                } else {
+                    ignore -= 1;
+                }
+            }
+            _ if ignore > 0 || event.kind == Kind::Enter => {}
+            Name::AutolinkEmail
+            | Name::AutolinkProtocol
+            | Name::CharacterEscapeValue
+            | Name::CodeTextData
+            | Name::Data
+            | Name::GfmAutolinkLiteralEmail
+            | Name::GfmAutolinkLiteralMailto
+            | Name::GfmAutolinkLiteralProtocol
+            | Name::GfmAutolinkLiteralWww
+            | Name::GfmAutolinkLiteralXmpp
+            | Name::Hashtag
+            | Name::MathTextData
+            | Name::Mention => {
+                value.push_str(
+                    Slice::from_position(
+                        context.bytes,
+                        &Position::from_exit_event(context.events, index),
+                        context.tab_size,
+                    )
+                    .as_str(),
+                );
+            }
+            Name::CharacterReferenceMarkerHexadecimal => marker = b'x',
+            Name::CharacterReferenceMarkerNumeric => marker = b'#',
+            Name::CharacterReferenceValue => {
+                let slice = Slice::from_position(
+                    context.bytes,
+                    &Position::from_exit_event(context.events, index),
+                    context.tab_size,
+                );
+                if let Some(decoded) = decode_character_reference(
+                    slice.as_str(),
+                    marker,
+                    &context.parse_options.character_references,
+                ) {
+                    value.push_str(&decoded);
+                }
+                marker = b'&';
+            }
+            Name::LineEnding => value.push(' '),
+            Name::SmartPunctuationDash
+            | Name::SmartPunctuationEllipsis
+            | Name::SmartPunctuationQuoteClosing
+            | Name::SmartPunctuationQuoteOpening => {
+                let slice = Slice::from_position(
+                    context.bytes,
+                    &Position::from_exit_event(context.events, index),
+                    context.tab_size,
+                );
+                value.push_str(&smart_punctuation(&event.name, slice.bytes));
+            }
+            Name::WikiLink => {
+                let (target, label) = wikilink_parts(context, index);
+                value.push_str(&label.unwrap_or(target));
+            }
+            _ => {}
+        }
+
+        index += 1;
+    }
+
+    let value = if let Some(heading_id_fn) = &context.options.heading_id_fn {
+        heading_id_fn(value.trim())
+    } else {
+        slug(value.trim())
+    };
+
+    if !value.is_empty() {
+        let id = unique_slug(&value, &mut context.heading_slugs);
+        context.push(" id=\"");
+        context.push(&encode(&id, context.encode_html));
+        context.push("\"");
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`HeadingAtxText`][Name::HeadingAtxText].
 fn on_exit_heading_atx_text(context: &mut CompileContext) {
     let value = context.resume();
@@ -1277,9 +2310,22 @@ fn on_exit_heading_setext_underline_sequence(context: &mut CompileContext) {
     let head = context.bytes[position.start.index];
     let rank = if head == b'-' { "2" } else { "1" };
 
+    let mut start = context.index;
+    while !(context.events[start].kind == Kind::Enter
+        && context.events[start].name == Name::HeadingSetext)
+    {
+        start -= 1;
+    }
+
     context.line_ending_if_needed();
     context.push("<h");
     context.push(rank);
+    source_line(context, start);
+
+    if context.options.heading_ids {
+        heading_id(context, start, context.index);
+    }
+
     context.push(">");
     context.push(&text);
     context.push("</h");
@@ -1289,6 +2335,26 @@ fn on_exit_heading_setext_underline_sequence(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:{[`HtmlFlow`][Name::HtmlFlow],[`HtmlText`][Name::HtmlText]}.
 fn on_exit_html(context: &mut CompileContext) {
+    context.pre_inside = false;
+
+    if context.options.allow_dangerous_html {
+        if context.options.normalize_html_tags {
+            let value = context.resume();
+            context.push(&normalize_html_tags(&value));
+        }
+    } else if let Some(sanitization) = &context.options.html_sanitization {
+        let mut value = context.resume();
+
+        if context.options.normalize_html_tags {
+            value = normalize_html_tags(&value);
+        }
+
+        let result = sanitize_html(&value, sanitization, &|url, image| {
+            sanitize_url(context, url, image)
+        });
+        context.push(&result);
+    }
+
     context.encode_html = true;
 }
 
@@ -1297,6 +2363,7 @@ fn on_exit_html_data(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let value = slice.as_str();
 
@@ -1333,14 +2400,38 @@ fn on_exit_line_ending(context: &mut CompileContext) {
     {
         context.slurp_one_line_ending = false;
     } else {
-        context.push(&encode(
-            Slice::from_position(
-                context.bytes,
-                &Position::from_exit_event(context.events, context.index),
+        // Turn soft breaks into hard breaks, but not in image text, titles,
+        // HTML, or right after a hard break.
+        if context.options.hard_line_breaks
+            && context.paragraph_inside
+            && !context.image_alt_inside
+            && context
+                .media_stack
+                .last()
+                .map_or(true, |media| media.destination.is_none())
+            && !matches!(
+                context.events[context.index - 2].name,
+                Name::HardBreakEscape | Name::HardBreakTrailing | Name::HtmlTextData
             )
-            .as_str(),
-            context.encode_html,
-        ));
+        {
+            context.push("<br");
+            context.push_void_end();
+        }
+
+        if context.options.default_line_ending.is_some() {
+            context.line_ending();
+        } else {
+            context.trim_trailing_whitespace();
+            context.push(&encode(
+                Slice::from_position(
+                    context.bytes,
+                    &Position::from_exit_event(context.events, context.index),
+                    context.tab_size,
+                )
+                .as_str(),
+                context.encode_html,
+            ));
+        }
     }
 }
 
@@ -1372,9 +2463,20 @@ fn on_exit_list_item(context: &mut CompileContext) {
         ],
     );
     let previous = &context.events[before_item];
-    let tight_paragraph = *tight && previous.name == Name::Paragraph;
+    let mut tight_paragraph = *tight && previous.name == Name::Paragraph;
     let empty_item = previous.name == Name::ListItemPrefix;
 
+    // A paragraph rendered as a figure is a block, also in tight lists.
+    if tight_paragraph && context.options.image_figure {
+        let mut enter = before_item;
+        while context.events[enter].kind != Kind::Enter
+            || context.events[enter].name != Name::Paragraph
+        {
+            enter -= 1;
+        }
+        tight_paragraph = !is_lone_image(context, enter);
+    }
+
     context.slurp_one_line_ending = false;
 
     if !tight_paragraph && !empty_item {
@@ -1390,6 +2492,7 @@ fn on_exit_list_item_value(context: &mut CompileContext) {
         let slice = Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         );
         let value = slice.as_str().parse::<u32>().ok().unwrap();
 
@@ -1401,6 +2504,13 @@ fn on_exit_list_item_value(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`Mark`][Name::Mark].
+fn on_exit_mark(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push("</mark>");
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:{[`Image`][Name::Image],[`Link`][Name::Link]}.
 fn on_exit_media(context: &mut CompileContext) {
     let mut is_in_image = false;
@@ -1442,12 +2552,11 @@ fn on_exit_media(context: &mut CompileContext) {
         None
     };
 
+    let mut external = false;
+
     if !is_in_image {
-        if media.image {
-            context.push("<img src=\"");
-        } else {
-            context.push("<a href=\"");
-        };
+        let image = media.image;
+        context.push(if image { "<img" } else { "<a" });
 
         let destination = if let Some(index) = definition_index {
             context.definitions[index].destination.as_ref()
@@ -1455,24 +2564,19 @@ fn on_exit_media(context: &mut CompileContext) {
             media.destination.as_ref()
         };
 
-        if let Some(destination) = destination {
-            let url = if context.options.allow_dangerous_protocol {
-                sanitize(destination)
-            } else {
-                sanitize_with_protocols(
-                    destination,
-                    if media.image {
-                        &SAFE_PROTOCOL_SRC
-                    } else {
-                        &SAFE_PROTOCOL_HREF
-                    },
-                )
-            };
-            context.push(&url);
-        }
+        let url = destination.map_or_else(String::new, |destination| {
+            sanitize_url(context, destination, image)
+        });
+        external = !media.image && is_external(context, &url);
+        push_url_attribute(
+            context,
+            if media.image { "src" } else { "href" },
+            &url,
+            url.is_empty() && destination.map_or(false, |d| !d.is_empty()),
+        );
 
         if media.image {
-            context.push("\" alt=\"");
+            context.push(" alt=\"");
         };
     }
 
@@ -1481,7 +2585,9 @@ fn on_exit_media(context: &mut CompileContext) {
     }
 
     if !is_in_image {
-        context.push("\"");
+        if media.image {
+            context.push("\"");
+        }
 
         let title = if let Some(index) = definition_index {
             context.definitions[index].title.clone()
@@ -1489,17 +2595,38 @@ fn on_exit_media(context: &mut CompileContext) {
             media.title
         };
 
-        if let Some(title) = title {
+        if let Some(title) = &title {
             context.push(" title=\"");
-            context.push(&title);
+            context.push(title);
             context.push("\"");
         };
 
+        if !media.image {
+            push_link_attributes(context, external);
+        }
+
+        if media.image && context.options.image_lazy_loading {
+            context.push(" loading=\"lazy\"");
+        }
+
         if media.image {
-            context.push(" /");
+            context.push_void_end();
+        } else {
+            context.push(">");
         }
 
-        context.push(">");
+        if media.image && context.figure_inside {
+            let caption = title
+                .as_ref()
+                .filter(|title| !title.is_empty())
+                .unwrap_or(&label);
+
+            if !caption.is_empty() {
+                context.push("<figcaption>");
+                context.push(caption);
+                context.push("</figcaption>");
+            }
+        }
     }
 
     if !media.image {
@@ -1513,9 +2640,14 @@ fn on_exit_media(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:[`Paragraph`][Name::Paragraph].
 fn on_exit_paragraph(context: &mut CompileContext) {
-    let tight = context.tight_stack.last().unwrap_or(&false);
-
-    if *tight {
+    let tight = *context.tight_stack.last().unwrap_or(&false);
+    context.paragraph_inside = false;
+
+    if context.figure_inside {
+        // A figure is a block, also in tight lists.
+        context.figure_inside = false;
+        context.push("</figure>");
+    } else if tight {
         context.slurp_one_line_ending = true;
     } else {
         context.push("</p>");
@@ -1544,6 +2676,19 @@ fn on_exit_resource_title_string(context: &mut CompileContext) {
     context.media_stack.last_mut().unwrap().title = Some(buf);
 }
 
+/// Handle [`Exit`][Kind::Exit]:{[`SmartPunctuationDash`][Name::SmartPunctuationDash],[`SmartPunctuationEllipsis`][Name::SmartPunctuationEllipsis],[`SmartPunctuationQuoteClosing`][Name::SmartPunctuationQuoteClosing],[`SmartPunctuationQuoteOpening`][Name::SmartPunctuationQuoteOpening]}.
+fn on_exit_smart_punctuation(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    context.push(&smart_punctuation(
+        &context.events[context.index].name,
+        slice.bytes,
+    ));
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`Strong`][Name::Strong].
 fn on_exit_strong(context: &mut CompileContext) {
     if !context.image_alt_inside {
@@ -1554,7 +2699,40 @@ fn on_exit_strong(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:[`ThematicBreak`][Name::ThematicBreak].
 fn on_exit_thematic_break(context: &mut CompileContext) {
     context.line_ending_if_needed();
-    context.push("<hr />");
+    context.push("<hr");
+    context.push_void_end();
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`WikiLink`][Name::WikiLink].
+fn on_exit_wikilink(context: &mut CompileContext) {
+    let (target, label) = wikilink_parts(context, context.index);
+
+    if !context.image_alt_inside {
+        let url = if let Some(wikilink_href_fn) = &context.options.wikilink_href_fn {
+            wikilink_href_fn(&target)
+        } else {
+            slug(&target)
+        };
+        let sanitized = sanitize_url(context, &url, false);
+        context.push("<a");
+        push_url_attribute(
+            context,
+            "href",
+            &sanitized,
+            sanitized.is_empty() && !url.is_empty(),
+        );
+        push_link_attributes(context, is_external(context, &sanitized));
+        context.push(">");
+    }
+
+    context.push(&encode(
+        label.as_ref().unwrap_or(&target),
+        context.encode_html,
+    ));
+
+    if !context.image_alt_inside {
+        context.push("</a>");
+    }
 }
 
 /// Generate a footnote section.
@@ -1710,33 +2888,26 @@ fn generate_autolink(
     value: &str,
     is_gfm_literal: bool,
 ) {
-    let mut is_in_link = false;
-    let mut index = 0;
-
-    while index < context.media_stack.len() {
-        if !context.media_stack[index].image {
-            is_in_link = true;
-            break;
-        }
-        index += 1;
-    }
+    let is_in_link = is_in_link(context);
 
     if !context.image_alt_inside && (!is_in_link || !is_gfm_literal) {
-        context.push("<a href=\"");
+        context.push("<a");
         let url = if let Some(protocol) = protocol {
             format!("{}{}", protocol, value)
         } else {
             value.into()
         };
 
-        let url = if context.options.allow_dangerous_protocol {
-            sanitize(&url)
-        } else {
-            sanitize_with_protocols(&url, &SAFE_PROTOCOL_HREF)
-        };
+        let sanitized = sanitize_url(context, &url, false);
+        push_url_attribute(
+            context,
+            "href",
+            &sanitized,
+            sanitized.is_empty() && !url.is_empty(),
+        );
+        push_link_attributes(context, is_external(context, &sanitized));
 
-        context.push(&url);
-        context.push("\">");
+        context.push(">");
     }
 
     context.push(&encode(value, context.encode_html));
@@ -1745,3 +2916,133 @@ fn generate_autolink(
         context.push("</a>");
     }
 }
+
+/// Check whether we are in a link, which cannot contain other links.
+fn is_in_link(context: &CompileContext) -> bool {
+    let mut index = context.media_stack.len();
+
+    // Inline footnotes end up in the footnote section, so links outside
+    // them do not matter.
+    while index > 0 {
+        index -= 1;
+        let media = &context.media_stack[index];
+        if media.inline_footnote {
+            break;
+        }
+        if !media.image {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Get the target and label of the wikilink that exits at `index`.
+fn wikilink_parts(context: &CompileContext, index: usize) -> (String, Option<String>) {
+    let mut target = String::new();
+    let mut label = None;
+    let mut index = index;
+
+    while index > 0 {
+        index -= 1;
+        let event = &context.events[index];
+
+        if event.name == Name::WikiLink {
+            break;
+        }
+
+        if event.kind == Kind::Exit {
+            let slice = Slice::from_position(
+                context.bytes,
+                &Position::from_exit_event(context.events, index),
+                context.tab_size,
+            );
+
+            if event.name == Name::WikiLinkLabel {
+                label = Some(slice.serialize());
+            } else if event.name == Name::WikiLinkTarget {
+                target = slice.serialize();
+            }
+        }
+    }
+
+    (target, label)
+}
+
+/// Sanitize a URL (used by links, images, and autolinks).
+///
+/// Checks its protocol against the allowed protocols, unless dangerous
+/// protocols are allowed.
+fn sanitize_url(context: &CompileContext, url: &str, image: bool) -> String {
+    if context.options.allow_dangerous_protocol {
+        sanitize(url)
+    } else if let Some(allowed) = &context.options.allowed_protocols {
+        crate::sanitize_url(url, &allowed.iter().map(String::as_str).collect::<Vec<_>>())
+    } else if image {
+        sanitize_with_protocols(url, &SAFE_PROTOCOL_SRC)
+    } else {
+        sanitize_with_protocols(url, &SAFE_PROTOCOL_HREF)
+    }
+}
+
+/// Add a URL attribute (`href` or `src`) with a (sanitized) URL.
+///
+/// When `dropped`, because the protocol of the URL is not allowed, the
+/// attribute is empty, left out, or `#`, depending on
+/// [`dropped_link_behavior`][CompileOptions::dropped_link_behavior].
+fn push_url_attribute(context: &mut CompileContext, name: &str, url: &str, dropped: bool) {
+    let value = if dropped {
+        match context.options.dropped_link_behavior {
+            DroppedLinkBehavior::Empty => Some(""),
+            DroppedLinkBehavior::Omit => None,
+            DroppedLinkBehavior::Hash => Some("#"),
+        }
+    } else {
+        Some(url)
+    };
+
+    if let Some(value) = value {
+        context.push(" ");
+        context.push(name);
+        context.push("=\"");
+        context.push(value);
+        context.push("\"");
+    }
+}
+
+/// Add `target` and `rel` attributes to a link, if needed.
+///
+/// External links get `target="_blank"` and `rel="noopener"`, with
+/// [`link_rel`][CompileOptions::link_rel] merged into `rel`.
+fn push_link_attributes(context: &mut CompileContext, external: bool) {
+    let options = context.options;
+    let mut rel = vec![];
+
+    if external {
+        context.push(" target=\"_blank\"");
+        rel.push("noopener");
+    }
+
+    if let Some(link_rel) = &options.link_rel {
+        for value in link_rel.split_ascii_whitespace() {
+            if !rel.contains(&value) {
+                rel.push(value);
+            }
+        }
+    }
+
+    if !rel.is_empty() {
+        context.push(" rel=\"");
+        context.push(&encode(&rel.join(" "), context.encode_html));
+        context.push("\"");
+    }
+}
+
+/// Check if a (sanitized) URL is external and should open in a new tab, if
+/// turned on.
+fn is_external(context: &CompileContext, url: &str) -> bool {
+    context.options.open_external_links_in_new_tab && {
+        let url = url.to_ascii_lowercase();
+        url.starts_with("http:") || url.starts_with("https:")
+    }
+}
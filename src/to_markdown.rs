@@ -0,0 +1,952 @@
+//! Turn a syntax tree into markdown.
+//!
+//! The result is normalized: block constructs are separated by blank lines,
+//! ATX headings are used where possible, code is always fenced, lists use
+//! `-` (or `1.`), emphasis uses `*`, and characters that would otherwise
+//! form syntax are escaped.
+//! Serializing the markdown that results from this again yields the same
+//! markdown.
+
+use crate::configuration::{Constructs, ParseOptions};
+use crate::mdast::{
+    AlignKind, Code, Definition, FootnoteDefinition, Heading, Image, ImageReference, InlineCode,
+    InlineMath, Link, LinkReference, List, Math, Node, ReferenceKind, Table,
+};
+use crate::util::normalize_identifier::normalize_identifier;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// State needed to serialize.
+struct State<'a> {
+    /// Markdown that the tree was made from, used for nodes that are not
+    /// supported.
+    value: &'a str,
+    /// Configuration, used to know which characters to escape.
+    options: &'a ParseOptions,
+    /// Whether we are at the start of a line in phrasing content.
+    at_line_start: bool,
+    /// Whether the current node is the first or last child of emphasis.
+    emphasis_edge: bool,
+    /// Whether we are in a link.
+    link_inside: bool,
+}
+
+/// Turn a syntax tree (made from `value`) into markdown.
+pub fn serialize(tree: &Node, value: &str, options: &ParseOptions) -> String {
+    let mut state = State {
+        value,
+        options,
+        at_line_start: false,
+        emphasis_edge: false,
+        link_inside: false,
+    };
+
+    let mut result = match tree {
+        Node::Root(root) => flow(&mut state, &root.children, true, false),
+        _ => block(&mut state, tree, false),
+    };
+
+    if !result.is_empty() {
+        result.push('\n');
+    }
+
+    result
+}
+
+/// Serialize flow (block) content.
+///
+/// When `spread`, blocks are separated by blank lines.
+/// When `alternate`, a list as the first child uses the other marker.
+fn flow(state: &mut State, children: &[Node], spread: bool, alternate: bool) -> String {
+    let mut result = String::new();
+    let mut previous: Option<&Node> = None;
+    let mut alternate = alternate;
+
+    for child in children {
+        // Two lists of the same kind next to each other would merge, unless
+        // they use different markers.
+        if let Some(previous) = previous {
+            alternate = matches!((previous, child), (Node::List(a), Node::List(b)) if a.ordered == b.ordered)
+                && !alternate;
+        }
+
+        let value = block(state, child, alternate);
+
+        if previous.is_some() {
+            result.push_str(if spread { "\n\n" } else { "\n" });
+        }
+
+        result.push_str(&value);
+        previous = Some(child);
+    }
+
+    result
+}
+
+/// Serialize a block.
+fn block(state: &mut State, node: &Node, alternate: bool) -> String {
+    match node {
+        Node::Blockquote(node) => {
+            let value = flow(state, &node.children, true, false);
+            indent(&value, "> ", "> ")
+        }
+        Node::Code(node) => code(node),
+        Node::Definition(node) => definition(node),
+        Node::FootnoteDefinition(node) => footnote_definition(state, node),
+        Node::Heading(node) => heading(state, node),
+        // Trailing blank lines would be added to the HTML each time.
+        Node::Html(node) => node
+            .value
+            .trim_end_matches(|d| d == '\r' || d == '\n')
+            .into(),
+        Node::List(node) => list(state, node, alternate),
+        Node::Math(node) => math(node),
+        Node::Paragraph(node) => phrasing(state, &node.children, true),
+        Node::Table(node) => table(state, node),
+        Node::ThematicBreak(_) => "***".into(),
+        Node::Toml(node) => format!("+++\n{}\n+++", node.value),
+        Node::Yaml(node) => format!("---\n{}\n---", node.value),
+        _ => source(state, node).into(),
+    }
+}
+
+/// Serialize a heading.
+///
+/// Uses a setext heading if there are line endings in it (and that’s
+/// possible), and an ATX heading otherwise.
+fn heading(state: &mut State, node: &Heading) -> String {
+    let multiline = node.depth < 3 && has_line_ending(&node.children);
+    let mut value = phrasing(state, &node.children, multiline);
+
+    if multiline {
+        value.push('\n');
+        value.push_str(if node.depth == 1 { "===" } else { "---" });
+        return value;
+    }
+
+    // A final sequence of `#`s would otherwise be seen as the closing
+    // sequence.
+    let bytes = value.as_bytes();
+    let mut index = bytes.len();
+
+    while index > 0 && bytes[index - 1] == b'#' {
+        index -= 1;
+    }
+
+    if index < bytes.len() && (index == 0 || bytes[index - 1] == b' ') {
+        value.insert(index, '\\');
+    }
+
+    let mut result = "#".repeat(node.depth.into());
+
+    if !value.is_empty() {
+        result.push(' ');
+        result.push_str(&value);
+    }
+
+    result
+}
+
+/// Serialize a list.
+fn list(state: &mut State, node: &List, alternate: bool) -> String {
+    let mut result = String::new();
+    let mut number = node.start.unwrap_or(1);
+    let spread = node.spread
+        || node
+            .children
+            .iter()
+            .any(|d| matches!(d, Node::ListItem(item) if item.spread));
+
+    for (index, child) in node.children.iter().enumerate() {
+        let value = if let Node::ListItem(item) = child {
+            // A list directly in a list item uses the other marker, as
+            // `- - -` would otherwise be a thematic break.
+            let mut value = flow(state, &item.children, item.spread, !alternate);
+
+            if let Some(checked) = item.checked {
+                value.insert_str(0, if checked { "[x] " } else { "[ ] " });
+            }
+
+            value
+        } else {
+            block(state, child, false)
+        };
+
+        let marker = if node.ordered {
+            let marker = format!("{}{}", number, if alternate { ')' } else { '.' });
+            number += 1;
+            marker
+        } else if alternate {
+            "*".into()
+        } else {
+            "-".into()
+        };
+
+        if index > 0 {
+            result.push_str(if spread { "\n\n" } else { "\n" });
+        }
+
+        if value.is_empty() {
+            result.push_str(&marker);
+        } else {
+            let rest = " ".repeat(marker.len() + 1);
+            result.push_str(&indent(&value, &format!("{} ", marker), &rest));
+        }
+    }
+
+    result
+}
+
+/// Serialize (fenced) code.
+fn code(node: &Code) -> String {
+    let mut info = String::new();
+
+    if let Some(lang) = &node.lang {
+        info.push_str(&escape_raw(lang));
+
+        if let Some(meta) = &node.meta {
+            info.push(' ');
+            info.push_str(&escape_raw(meta));
+        }
+    }
+
+    // Grave accents are not allowed in the info of a fence of grave accents.
+    let marker = if info.contains('`') { '~' } else { '`' };
+    fence(&node.value, &info, marker, 3)
+}
+
+/// Serialize math (flow).
+fn math(node: &Math) -> String {
+    let meta = node.meta.as_deref().map_or_else(String::new, escape_raw);
+    fence(&node.value, &meta, '$', 2)
+}
+
+/// Serialize a fenced construct.
+///
+/// The fence is made longer than any sequence of `marker` in `value`.
+fn fence(value: &str, info: &str, marker: char, min: usize) -> String {
+    let value = value.replace("\r\n", "\n").replace('\r', "\n");
+    let size = (longest_run(&value, marker) + 1).max(min);
+    let fence = marker.to_string().repeat(size);
+    let mut result = format!("{}{}\n", fence, info);
+
+    if !value.is_empty() {
+        result.push_str(&value);
+        result.push('\n');
+    }
+
+    result.push_str(&fence);
+    result
+}
+
+/// Serialize a definition.
+fn definition(node: &Definition) -> String {
+    format!(
+        "[{}]: {}{}",
+        label(node.label.as_deref(), &node.identifier),
+        destination(&node.url),
+        title(node.title.as_deref())
+    )
+}
+
+/// Serialize a footnote definition.
+fn footnote_definition(state: &mut State, node: &FootnoteDefinition) -> String {
+    let label = format!("[^{}]:", label(node.label.as_deref(), &node.identifier));
+    let value = flow(state, &node.children, true, false);
+
+    if value.is_empty() {
+        label
+    } else {
+        indent(&value, &format!("{} ", label), "    ")
+    }
+}
+
+/// Serialize a table.
+fn table(state: &mut State, node: &Table) -> String {
+    let mut rows = Vec::new();
+
+    for (index, row) in node.children.iter().enumerate() {
+        let mut cells = Vec::new();
+
+        if let Some(children) = row.children() {
+            for cell in children {
+                cells.push(match cell.children() {
+                    Some(children) => phrasing(state, children, false),
+                    None => String::new(),
+                });
+            }
+        }
+
+        rows.push(format!("| {} |", cells.join(" | ")));
+
+        if index == 0 {
+            let delimiters = node
+                .align
+                .iter()
+                .map(|align| match align {
+                    AlignKind::Left => ":--",
+                    AlignKind::Right => "--:",
+                    AlignKind::Center => ":-:",
+                    AlignKind::None => "---",
+                })
+                .collect::<Vec<_>>();
+            rows.push(format!("| {} |", delimiters.join(" | ")));
+        }
+    }
+
+    rows.join("\n")
+}
+
+/// Serialize phrasing (inline) content.
+///
+/// When not `multiline`, line endings are turned into spaces.
+fn phrasing(state: &mut State, children: &[Node], multiline: bool) -> String {
+    let mut result = String::new();
+    state.at_line_start = true;
+    inline_children(state, children, multiline, &mut result);
+    result
+}
+
+/// Serialize the children of a phrasing node.
+fn inline_children(state: &mut State, children: &[Node], multiline: bool, result: &mut String) {
+    for (index, child) in children.iter().enumerate() {
+        inline(state, child, children.get(index + 1), multiline, result);
+    }
+}
+
+/// Serialize a phrasing node.
+fn inline(
+    state: &mut State,
+    node: &Node,
+    next: Option<&Node>,
+    multiline: bool,
+    result: &mut String,
+) {
+    let edge = state.emphasis_edge;
+    state.emphasis_edge = false;
+
+    match node {
+        Node::Text(node) => {
+            text(state, &node.value, next.is_some(), multiline, result);
+            return;
+        }
+        Node::Break(_) => {
+            if multiline {
+                result.push_str("\\\n");
+                state.at_line_start = true;
+            } else {
+                result.push(' ');
+            }
+
+            return;
+        }
+        _ => {}
+    }
+
+    state.at_line_start = false;
+
+    match node {
+        Node::Delete(node) => wrap(state, &node.children, "~~", multiline, result),
+        Node::Emphasis(node) => {
+            // Emphasis directly in emphasis would otherwise form strong.
+            let marker = if edge { "_" } else { "*" };
+            result.push_str(marker);
+
+            for (index, child) in node.children.iter().enumerate() {
+                state.emphasis_edge = !edge && (index == 0 || index == node.children.len() - 1);
+                inline(
+                    state,
+                    child,
+                    node.children.get(index + 1),
+                    multiline,
+                    result,
+                );
+            }
+
+            result.push_str(marker);
+        }
+        Node::Strong(node) => wrap(state, &node.children, "**", multiline, result),
+        Node::FootnoteReference(node) => {
+            result.push_str("[^");
+            result.push_str(&label(node.label.as_deref(), &node.identifier));
+            result.push(']');
+        }
+        Node::Html(node) => result.push_str(&node.value),
+        Node::Image(node) => image(state, node, result),
+        Node::ImageReference(node) => image_reference(state, node, next, result),
+        Node::InlineCode(node) => inline_code(node, result),
+        Node::InlineFootnote(node) => {
+            result.push_str("^[");
+            inline_children(state, &node.children, multiline, result);
+            result.push(']');
+        }
+        Node::InlineMath(node) => inline_math(state, node, result),
+        Node::Link(node) => link(state, node, next, multiline, result),
+        Node::LinkReference(node) => link_reference(state, node, next, multiline, result),
+        Node::Mark(node) => wrap(state, &node.children, "==", multiline, result),
+        _ => result.push_str(source(state, node)),
+    }
+
+    state.at_line_start = false;
+}
+
+/// Serialize phrasing children between two sequences.
+fn wrap(state: &mut State, children: &[Node], marker: &str, multiline: bool, result: &mut String) {
+    result.push_str(marker);
+    inline_children(state, children, multiline, result);
+    result.push_str(marker);
+}
+
+/// Serialize a link.
+///
+/// Uses an autolink if the link looks like one, and keeps literal autolinks
+/// (GFM) as they are.
+fn link(state: &mut State, node: &Link, next: Option<&Node>, multiline: bool, result: &mut String) {
+    if let (None, [Node::Text(text)]) = (&node.title, node.children.as_slice()) {
+        if is_literal(state, node, &text.value, next) {
+            result.push_str(&text.value);
+            return;
+        }
+
+        let autolink = (text.value == node.url && is_absolute_url(&node.url))
+            || (node
+                .url
+                .get(..7)
+                .map_or(false, |d| d.eq_ignore_ascii_case("mailto:"))
+                && node.url[7..] == text.value
+                && is_email(&text.value));
+
+        if autolink && !state.link_inside {
+            result.push('<');
+            result.push_str(&text.value);
+            result.push('>');
+            return;
+        }
+    }
+
+    // Links in links (literal autolinks, GFM) are not links.
+    if state.link_inside {
+        inline_children(state, &node.children, multiline, result);
+        return;
+    }
+
+    result.push('[');
+    state.link_inside = true;
+    inline_children(state, &node.children, multiline, result);
+    state.link_inside = false;
+    result.push_str("](");
+    result.push_str(&destination(&node.url));
+    result.push_str(&title(node.title.as_deref()));
+    result.push(')');
+}
+
+/// Check if a link is a literal autolink (GFM) that can be kept as it is.
+fn is_literal(state: &State, node: &Link, value: &str, next: Option<&Node>) -> bool {
+    let literal = node.position.as_ref().map_or(false, |position| {
+        state.value.get(position.start.offset..position.end.offset) == Some(value)
+    });
+
+    if !literal {
+        return false;
+    }
+
+    // An escape right after a literal autolink would be seen as part of it.
+    if let Some(Node::Text(next)) = next {
+        let chars = next.value.chars().collect::<Vec<_>>();
+        let mut index = 0;
+
+        while index < chars.len() && !chars[index].is_whitespace() {
+            let previous = if index == 0 {
+                None
+            } else {
+                Some(chars[index - 1])
+            };
+
+            if escape(
+                &state.options.constructs,
+                chars[index],
+                previous,
+                chars.get(index + 1).copied(),
+                false,
+            ) {
+                return false;
+            }
+
+            index += 1;
+        }
+    }
+
+    true
+}
+
+/// Serialize a link reference.
+fn link_reference(
+    state: &mut State,
+    node: &LinkReference,
+    next: Option<&Node>,
+    multiline: bool,
+    result: &mut String,
+) {
+    let mut content = String::new();
+    state.link_inside = true;
+    inline_children(state, &node.children, multiline, &mut content);
+    state.link_inside = false;
+    result.push('[');
+    result.push_str(&content);
+    result.push(']');
+    reference(
+        node.reference_kind,
+        &content,
+        &label(node.label.as_deref(), &node.identifier),
+        &node.identifier,
+        next,
+        result,
+    );
+}
+
+/// Serialize an image.
+fn image(state: &mut State, node: &Image, result: &mut String) {
+    result.push_str("![");
+    text(state, &node.alt, false, false, result);
+    result.push_str("](");
+    result.push_str(&destination(&node.url));
+    result.push_str(&title(node.title.as_deref()));
+    result.push(')');
+}
+
+/// Serialize an image reference.
+fn image_reference(
+    state: &mut State,
+    node: &ImageReference,
+    next: Option<&Node>,
+    result: &mut String,
+) {
+    let mut content = String::new();
+    text(state, &node.alt, false, false, &mut content);
+    result.push_str("![");
+    result.push_str(&content);
+    result.push(']');
+    reference(
+        node.reference_kind,
+        &content,
+        &label(node.label.as_deref(), &node.identifier),
+        &node.identifier,
+        next,
+        result,
+    );
+}
+
+/// Serialize the end of a reference.
+///
+/// `content` is what is between the brackets: when it does not match the
+/// label, a full reference is used.
+fn reference(
+    kind: ReferenceKind,
+    content: &str,
+    label: &str,
+    identifier: &str,
+    next: Option<&Node>,
+    result: &mut String,
+) {
+    if kind == ReferenceKind::Full || normalize_identifier(content).to_lowercase() != identifier {
+        result.push('[');
+        result.push_str(label);
+        result.push(']');
+    } else if kind == ReferenceKind::Collapsed
+        // A following `(` would otherwise start a resource.
+        || matches!(next, Some(Node::Text(text)) if text.value.starts_with('('))
+    {
+        result.push_str("[]");
+    }
+}
+
+/// Serialize inline code.
+fn inline_code(node: &InlineCode, result: &mut String) {
+    code_text(&node.value, '`', 1, result);
+}
+
+/// Serialize inline math.
+fn inline_math(state: &State, node: &InlineMath, result: &mut String) {
+    let min = if state.options.math_text_single_dollar {
+        1
+    } else {
+        2
+    };
+    code_text(&node.value, '$', min, result);
+}
+
+/// Serialize code (text) or math (text).
+///
+/// Uses the smallest sequence that does not occur in `value`.
+fn code_text(value: &str, marker: char, min: usize, result: &mut String) {
+    // Padding that includes line endings is not removed from the value when
+    // parsing.
+    let is_padding = |d: char| d == ' ' || d == '\r' || d == '\n';
+    let mut value = value;
+
+    if (value.starts_with(|d| d == '\r' || d == '\n')
+        || value.ends_with(|d| d == '\r' || d == '\n'))
+        && value.starts_with(is_padding)
+        && value.ends_with(is_padding)
+        && !value.chars().all(is_padding)
+    {
+        value = value.strip_prefix("\r\n").unwrap_or(&value[1..]);
+        value = value
+            .strip_suffix("\r\n")
+            .unwrap_or(&value[..value.len() - 1]);
+    }
+
+    // Line endings are turned into spaces when parsing anyway.
+    let value = value
+        .replace("\r\n", " ")
+        .replace(|d| d == '\r' || d == '\n', " ");
+    let mut size = min;
+
+    while has_run(&value, marker, size) {
+        size += 1;
+    }
+
+    let sequence = marker.to_string().repeat(size);
+    // Padding is stripped, once, when parsing.
+    let pad = value.starts_with(marker)
+        || value.ends_with(marker)
+        || (value.starts_with(' ') && value.ends_with(' ') && value.bytes().any(|d| d != b' '));
+
+    result.push_str(&sequence);
+
+    if pad {
+        result.push(' ');
+    }
+
+    result.push_str(&value);
+
+    if pad {
+        result.push(' ');
+    }
+
+    result.push_str(&sequence);
+}
+
+/// Serialize text, escaping what would otherwise be syntax.
+///
+/// `before` is whether something follows this text.
+fn text(state: &mut State, value: &str, before: bool, multiline: bool, result: &mut String) {
+    let constructs = &state.options.constructs;
+    let chars = value.chars().collect::<Vec<_>>();
+    let mut index = 0;
+    // Index of the `.` or `)` after a number at the start of a line.
+    let mut list_marker = None;
+
+    while index < chars.len() {
+        let char = chars[index];
+        let previous = if index == 0 {
+            None
+        } else {
+            Some(chars[index - 1])
+        };
+        let next = chars.get(index + 1).copied();
+        index += 1;
+
+        if char == '\r' || char == '\n' {
+            if char == '\r' && next == Some('\n') {
+                index += 1;
+            }
+
+            let blank = state.at_line_start
+                || (index == chars.len() && !before)
+                || chars.get(index).map_or(false, |d| *d == '\r' || *d == '\n');
+
+            // Character references, which would otherwise form blank lines.
+            if blank {
+                result.push_str(if char == '\r' { "&#xD;" } else { "&#xA;" });
+            } else if multiline {
+                result.push('\n');
+                state.at_line_start = true;
+            } else {
+                result.push(' ');
+            }
+
+            continue;
+        }
+
+        if state.at_line_start {
+            state.at_line_start = false;
+
+            // Initial whitespace would be dropped.
+            if char == ' ' || char == '\t' {
+                result.push_str(if char == ' ' { "&#x20;" } else { "&#x9;" });
+                continue;
+            }
+
+            if matches!(char, '#' | '>' | '-' | '+' | '=' | '~')
+                || (char == '$' && constructs.math_flow)
+                || (char == ':' && (constructs.directive_leaf || constructs.directive_container))
+            {
+                result.push('\\');
+                result.push(char);
+                continue;
+            }
+
+            if char.is_ascii_digit() {
+                let mut end = index;
+
+                while end < chars.len() && chars[end].is_ascii_digit() {
+                    end += 1;
+                }
+
+                if end < chars.len() && matches!(chars[end], '.' | ')') {
+                    list_marker = Some(end);
+                }
+            }
+        }
+
+        if list_marker == Some(index - 1) || escape(constructs, char, previous, next, before) {
+            result.push('\\');
+        }
+
+        result.push(char);
+    }
+}
+
+/// Check if `char` in text has to be escaped.
+fn escape(
+    constructs: &Constructs,
+    char: char,
+    previous: Option<char>,
+    next: Option<char>,
+    before: bool,
+) -> bool {
+    match char {
+        '\\' | '*' | '`' | '[' | ']' => true,
+        // Could form a character reference or HTML (or an autolink) with
+        // what follows.
+        '&' => next.map_or(before, |d| d == '#' || d.is_ascii_alphanumeric()),
+        '<' => next.map_or(before, |d| {
+            matches!(d, '/' | '!' | '?') || d.is_ascii_alphabetic()
+        }),
+        '_' => {
+            !(previous.map_or(false, char::is_alphanumeric)
+                && next.map_or(false, char::is_alphanumeric))
+        }
+        '~' => constructs.gfm_strikethrough,
+        '=' => constructs.mark,
+        '|' => constructs.gfm_table,
+        '$' => constructs.math_text,
+        ':' => constructs.directive_text,
+        // Would otherwise form an image with a following link.
+        '!' => before && next.is_none(),
+        _ => false,
+    }
+}
+
+/// Serialize a destination.
+fn destination(url: &str) -> String {
+    let enclosed = url.is_empty()
+        || url
+            .chars()
+            .any(|d| matches!(d, '<' | '>' | '(' | ')' | ' ') || d.is_control());
+
+    if enclosed {
+        let mut result = String::from("<");
+
+        for char in url.chars() {
+            if matches!(char, '<' | '>' | '\\' | '&') {
+                result.push('\\');
+            }
+
+            // Line endings are not allowed.
+            if char == '\n' || char == '\r' {
+                result.push_str(if char == '\n' { "%0A" } else { "%0D" });
+            } else {
+                result.push(char);
+            }
+        }
+
+        result.push('>');
+        result
+    } else {
+        escape_raw(url)
+    }
+}
+
+/// Serialize a title, if there is one, with a space before it.
+fn title(title: Option<&str>) -> String {
+    match title {
+        Some(title) => {
+            let mut result = String::from(" \"");
+
+            for char in title.chars() {
+                if matches!(char, '"' | '\\' | '&') {
+                    result.push('\\');
+                }
+
+                result.push(char);
+            }
+
+            result.push('"');
+            result
+        }
+        None => String::new(),
+    }
+}
+
+/// Serialize a label.
+///
+/// Uses `label` (with brackets and backslashes escaped) if it matches
+/// `identifier`, and `identifier` otherwise.
+fn label(label: Option<&str>, identifier: &str) -> String {
+    if let Some(label) = label {
+        let mut result = String::with_capacity(label.len());
+
+        for char in label.chars() {
+            if matches!(char, '[' | ']' | '\\') {
+                result.push('\\');
+            }
+
+            result.push(char);
+        }
+
+        if normalize_identifier(&result).to_lowercase() == identifier {
+            return result;
+        }
+    }
+
+    identifier.into()
+}
+
+/// Escape backslashes and ampersands, which have a meaning in destinations
+/// and infos.
+fn escape_raw(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for char in value.chars() {
+        if char == '\\' || char == '&' {
+            result.push('\\');
+        }
+
+        result.push(char);
+    }
+
+    result
+}
+
+/// Prefix lines: the first with `first`, the rest with `rest`.
+///
+/// Blank lines are not padded with trailing whitespace.
+fn indent(value: &str, first: &str, rest: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for (index, line) in value.split('\n').enumerate() {
+        let prefix = if index == 0 { first } else { rest };
+
+        if index > 0 {
+            result.push('\n');
+        }
+
+        if line.is_empty() {
+            result.push_str(prefix.trim_end());
+        } else {
+            result.push_str(prefix);
+            result.push_str(line);
+        }
+    }
+
+    result
+}
+
+/// Check if there are line endings in phrasing content.
+fn has_line_ending(children: &[Node]) -> bool {
+    children.iter().any(|child| match child {
+        Node::Break(_) => true,
+        Node::Text(node) => node.value.contains(|d| d == '\r' || d == '\n'),
+        _ => child.children().map_or(false, |d| has_line_ending(d)),
+    })
+}
+
+/// Get the markdown that `node` was made from.
+fn source<'a>(state: &State<'a>, node: &Node) -> &'a str {
+    match node.position() {
+        Some(position) => state
+            .value
+            .get(position.start.offset..position.end.offset)
+            .unwrap_or_default(),
+        None => "",
+    }
+}
+
+/// Get the size of the longest sequence of `marker` in `value`.
+fn longest_run(value: &str, marker: char) -> usize {
+    let mut longest = 0;
+    let mut size = 0;
+
+    for char in value.chars() {
+        if char == marker {
+            size += 1;
+            longest = longest.max(size);
+        } else {
+            size = 0;
+        }
+    }
+
+    longest
+}
+
+/// Check if there is a sequence of exactly `size` times `marker` in `value`.
+fn has_run(value: &str, marker: char, size: usize) -> bool {
+    let mut current = 0;
+
+    for char in value.chars() {
+        if char == marker {
+            current += 1;
+        } else {
+            if current == size {
+                return true;
+            }
+
+            current = 0;
+        }
+    }
+
+    current == size
+}
+
+/// Check if `value` can be an autolink to a URL.
+fn is_absolute_url(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    let mut index = 0;
+
+    while index < bytes.len()
+        && index < 32
+        && (bytes[index].is_ascii_alphanumeric() || matches!(bytes[index], b'+' | b'-' | b'.'))
+    {
+        index += 1;
+    }
+
+    index > 1
+        && bytes[0].is_ascii_alphabetic()
+        && index < bytes.len()
+        && bytes[index] == b':'
+        && !value
+            .chars()
+            .any(|d| matches!(d, '<' | '>' | ' ') || d.is_control())
+}
+
+/// Check if `value` can be an autolink to an email address.
+fn is_email(value: &str) -> bool {
+    match value.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.is_empty()
+                && local
+                    .bytes()
+                    .all(|d| d.is_ascii_alphanumeric() || b".!#$%&'*+/=?^_`{|}~-".contains(&d))
+                && domain.split('.').all(|d| {
+                    !d.is_empty() && d.bytes().all(|d| d.is_ascii_alphanumeric() || d == b'-')
+                })
+        }
+        None => false,
+    }
+}
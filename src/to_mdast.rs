@@ -3,11 +3,12 @@
 use crate::event::{Event, Kind, Name};
 use crate::mdast::{
     AttributeContent, AttributeValue, AttributeValueExpression, Blockquote, Break, Code,
-    Definition, Delete, Emphasis, FootnoteDefinition, FootnoteReference, Heading, Html, Image,
-    ImageReference, InlineCode, InlineMath, Link, LinkReference, List, ListItem, Math,
-    MdxFlowExpression, MdxJsxAttribute, MdxJsxExpressionAttribute, MdxJsxFlowElement,
-    MdxJsxTextElement, MdxTextExpression, MdxjsEsm, Node, Paragraph, ReferenceKind, Root, Strong,
-    Table, TableCell, TableRow, Text, ThematicBreak, Toml, Yaml,
+    ContainerDirective, Definition, Delete, Emphasis, FootnoteDefinition, FootnoteReference,
+    Heading, Html, Image, ImageReference, InlineCode, InlineFootnote, InlineMath, LeafDirective,
+    Link, LinkReference, List, ListItem, Mark, Math, MdxFlowExpression, MdxJsxAttribute,
+    MdxJsxExpressionAttribute, MdxJsxFlowElement, MdxJsxTextElement, MdxTextExpression, MdxjsEsm,
+    Node, Paragraph, ReferenceKind, Root, Strong, Table, TableCell, TableRow, Text, TextDirective,
+    ThematicBreak, Toml, Yaml,
 };
 use crate::message;
 use crate::unist::{Point, Position};
@@ -19,7 +20,9 @@ use crate::util::{
     mdx_collect::{collect, Result as CollectResult},
     normalize_identifier::normalize_identifier,
     slice::{Position as SlicePosition, Slice},
+    smart_punctuation::replacement as smart_punctuation,
 };
+use crate::{CharacterReferences, ParseOptions};
 use alloc::{
     boxed::Box,
     format,
@@ -90,6 +93,10 @@ struct CompileContext<'a> {
     events: &'a [Event],
     /// List of bytes.
     bytes: &'a [u8],
+    /// Size of a tab stop.
+    tab_size: usize,
+    /// Supported named character references.
+    character_references: &'a CharacterReferences,
     // Fields used by handlers to track the things they need to track to
     // compile markdown.
     character_reference_marker: u8,
@@ -109,7 +116,7 @@ struct CompileContext<'a> {
 
 impl<'a> CompileContext<'a> {
     /// Create a new compile context.
-    fn new(events: &'a [Event], bytes: &'a [u8]) -> CompileContext<'a> {
+    fn new(events: &'a [Event], bytes: &'a [u8], options: &'a ParseOptions) -> CompileContext<'a> {
         let tree = Node::Root(Root {
             children: vec![],
             position: Some(Position {
@@ -129,6 +136,8 @@ impl<'a> CompileContext<'a> {
         CompileContext {
             events,
             bytes,
+            tab_size: options.tab_size,
+            character_references: &options.character_references,
             character_reference_marker: 0,
             gfm_table_inside: false,
             hard_break_after: false,
@@ -225,8 +234,12 @@ impl<'a> CompileContext<'a> {
 }
 
 /// Turn events and bytes into a syntax tree.
-pub fn compile(events: &[Event], bytes: &[u8]) -> Result<Node, message::Message> {
-    let mut context = CompileContext::new(events, bytes);
+pub fn compile(
+    events: &[Event],
+    bytes: &[u8],
+    options: &ParseOptions,
+) -> Result<Node, message::Message> {
+    let mut context = CompileContext::new(events, bytes, options);
 
     let mut index = 0;
     while index < events.len() {
@@ -269,16 +282,23 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
         | Name::CodeTextData
         | Name::Data
         | Name::FrontmatterChunk
+        | Name::Hashtag
         | Name::HtmlFlowData
         | Name::HtmlTextData
         | Name::MathFlowChunk
         | Name::MathTextData
-        | Name::MdxJsxTagAttributeValueLiteralValue => on_enter_data(context),
+        | Name::MdxJsxTagAttributeValueLiteralValue
+        | Name::Mention
+        | Name::SmartPunctuationDash
+        | Name::SmartPunctuationEllipsis
+        | Name::SmartPunctuationQuoteClosing
+        | Name::SmartPunctuationQuoteOpening => on_enter_data(context),
         Name::CodeFencedFenceInfo
         | Name::CodeFencedFenceMeta
         | Name::DefinitionDestinationString
         | Name::DefinitionLabelString
         | Name::DefinitionTitleString
+        | Name::DirectiveAttributeValue
         | Name::GfmFootnoteDefinitionLabelString
         | Name::LabelText
         | Name::MathFlowFenceMeta
@@ -292,6 +312,10 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::CodeIndented => on_enter_code_indented(context),
         Name::CodeText => on_enter_code_text(context),
         Name::Definition => on_enter_definition(context),
+        Name::DirectiveContainer => on_enter_directive_container(context),
+        Name::DirectiveLabel => on_enter_directive_label(context),
+        Name::DirectiveLeaf => on_enter_directive_leaf(context),
+        Name::DirectiveText => on_enter_directive_text(context),
         Name::Emphasis => on_enter_emphasis(context),
         Name::Frontmatter => on_enter_frontmatter(context),
         Name::GfmAutolinkLiteralEmail
@@ -309,9 +333,11 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::HeadingAtx | Name::HeadingSetext => on_enter_heading(context),
         Name::HtmlFlow | Name::HtmlText => on_enter_html(context),
         Name::Image => on_enter_image(context),
+        Name::InlineFootnote => on_enter_inline_footnote(context),
         Name::Link => on_enter_link(context),
         Name::ListItem => on_enter_list_item(context),
         Name::ListOrdered | Name::ListUnordered => on_enter_list(context),
+        Name::Mark => on_enter_mark(context),
         Name::MathFlow => on_enter_math_flow(context),
         Name::MathText => on_enter_math_text(context),
         Name::MdxEsm => on_enter_mdx_esm(context),
@@ -330,6 +356,8 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::Resource => on_enter_resource(context),
         Name::Strong => on_enter_strong(context),
         Name::ThematicBreak => on_enter_thematic_break(context),
+        Name::WikiLink => on_enter_wikilink(context),
+        Name::WikiLinkLabel => on_enter_wikilink_label(context),
         _ => {}
     }
 
@@ -343,6 +371,9 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
         | Name::BlockQuote
         | Name::CharacterReference
         | Name::Definition
+        | Name::DirectiveContainer
+        | Name::DirectiveLeaf
+        | Name::DirectiveText
         | Name::Emphasis
         | Name::GfmFootnoteDefinition
         | Name::GfmStrikethrough
@@ -351,9 +382,11 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
         | Name::HeadingAtx
         | Name::ListOrdered
         | Name::ListUnordered
+        | Name::Mark
         | Name::Paragraph
         | Name::Strong
-        | Name::ThematicBreak => {
+        | Name::ThematicBreak
+        | Name::WikiLink => {
             on_exit(context)?;
         }
         Name::CharacterEscapeValue
@@ -361,13 +394,20 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
         | Name::CodeTextData
         | Name::Data
         | Name::FrontmatterChunk
+        | Name::Hashtag
         | Name::HtmlFlowData
         | Name::HtmlTextData
         | Name::MathFlowChunk
         | Name::MathTextData
-        | Name::MdxJsxTagAttributeValueLiteralValue => {
+        | Name::MdxJsxTagAttributeValueLiteralValue
+        | Name::Mention
+        | Name::WikiLinkLabel => {
             on_exit_data(context)?;
         }
+        Name::SmartPunctuationDash
+        | Name::SmartPunctuationEllipsis
+        | Name::SmartPunctuationQuoteClosing
+        | Name::SmartPunctuationQuoteOpening => on_exit_smart_punctuation(context)?,
         Name::MdxJsxTagAttributeExpression | Name::MdxJsxTagAttributeValueExpression => {
             on_exit_drop(context);
         }
@@ -392,13 +432,21 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
             on_exit_definition_id(context);
         }
         Name::DefinitionTitleString => on_exit_definition_title_string(context),
+        Name::DirectiveAttributeClass => on_exit_directive_attribute_class(context),
+        Name::DirectiveAttributeId => on_exit_directive_attribute_id(context),
+        Name::DirectiveAttributeName => on_exit_directive_attribute_name(context),
+        Name::DirectiveAttributeValue => on_exit_directive_attribute_value(context),
+        Name::DirectiveLabel => on_exit_directive_label(context)?,
+        Name::DirectiveName => on_exit_directive_name(context),
         Name::Frontmatter => on_exit_frontmatter(context)?,
         Name::GfmAutolinkLiteralEmail
         | Name::GfmAutolinkLiteralMailto
         | Name::GfmAutolinkLiteralProtocol
         | Name::GfmAutolinkLiteralWww
         | Name::GfmAutolinkLiteralXmpp => on_exit_gfm_autolink_literal(context)?,
-        Name::GfmFootnoteCall | Name::Image | Name::Link => on_exit_media(context)?,
+        Name::GfmFootnoteCall | Name::Image | Name::InlineFootnote | Name::Link => {
+            on_exit_media(context)?;
+        }
         Name::GfmTable => on_exit_gfm_table(context)?,
         Name::GfmTaskListItemValueUnchecked | Name::GfmTaskListItemValueChecked => {
             on_exit_gfm_task_list_item_value(context);
@@ -431,6 +479,7 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::ReferenceString => on_exit_reference_string(context),
         Name::ResourceDestinationString => on_exit_resource_destination_string(context),
         Name::ResourceTitleString => on_exit_resource_title_string(context),
+        Name::WikiLinkTarget => on_exit_wikilink_target(context),
         _ => {}
     }
 
@@ -518,6 +567,7 @@ fn on_enter_mdx_esm(context: &mut CompileContext) {
         context.index,
         &[Name::MdxEsmData, Name::LineEnding],
         &[Name::MdxEsm],
+        context.tab_size,
     );
     context.tail_push(Node::MdxjsEsm(MdxjsEsm {
         value: result.value,
@@ -535,6 +585,7 @@ fn on_enter_mdx_flow_expression(context: &mut CompileContext) {
         context.index,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[Name::MdxFlowExpression],
+        context.tab_size,
     );
     context.tail_push(Node::MdxFlowExpression(MdxFlowExpression {
         value: result.value,
@@ -552,6 +603,7 @@ fn on_enter_mdx_text_expression(context: &mut CompileContext) {
         context.index,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[Name::MdxTextExpression],
+        context.tab_size,
     );
     context.tail_push(Node::MdxTextExpression(MdxTextExpression {
         value: result.value,
@@ -572,6 +624,48 @@ fn on_enter_definition(context: &mut CompileContext) {
     }));
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`DirectiveContainer`][Name::DirectiveContainer].
+fn on_enter_directive_container(context: &mut CompileContext) {
+    context.tail_push(Node::ContainerDirective(ContainerDirective {
+        name: String::new(),
+        attributes: vec![],
+        children: vec![],
+        position: None,
+    }));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DirectiveLabel`][Name::DirectiveLabel].
+fn on_enter_directive_label(context: &mut CompileContext) {
+    // The label of a container directive is a paragraph, because its
+    // children are flow; the label of other directives is their content.
+    if let Node::ContainerDirective(_) = context.tail_mut() {
+        context.tail_push(Node::Paragraph(Paragraph {
+            children: vec![],
+            position: None,
+        }));
+    }
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DirectiveLeaf`][Name::DirectiveLeaf].
+fn on_enter_directive_leaf(context: &mut CompileContext) {
+    context.tail_push(Node::LeafDirective(LeafDirective {
+        name: String::new(),
+        attributes: vec![],
+        children: vec![],
+        position: None,
+    }));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DirectiveText`][Name::DirectiveText].
+fn on_enter_directive_text(context: &mut CompileContext) {
+    context.tail_push(Node::TextDirective(TextDirective {
+        name: String::new(),
+        attributes: vec![],
+        children: vec![],
+        position: None,
+    }));
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Emphasis`][Name::Emphasis].
 fn on_enter_emphasis(context: &mut CompileContext) {
     context.tail_push(Node::Emphasis(Emphasis {
@@ -729,6 +823,18 @@ fn on_enter_image(context: &mut CompileContext) {
     context.media_reference_stack.push(Reference::new());
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`InlineFootnote`][Name::InlineFootnote].
+fn on_enter_inline_footnote(context: &mut CompileContext) {
+    context.tail_push(Node::InlineFootnote(InlineFootnote {
+        children: vec![],
+        position: None,
+    }));
+    // Inline footnotes are never references.
+    let mut reference = Reference::new();
+    reference.reference_kind = None;
+    context.media_reference_stack.push(reference);
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Link`][Name::Link].
 fn on_enter_link(context: &mut CompileContext) {
     context.tail_push(Node::Link(Link {
@@ -740,6 +846,28 @@ fn on_enter_link(context: &mut CompileContext) {
     context.media_reference_stack.push(Reference::new());
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`WikiLink`][Name::WikiLink].
+fn on_enter_wikilink(context: &mut CompileContext) {
+    context.tail_push(Node::Link(Link {
+        url: String::new(),
+        title: None,
+        children: vec![],
+        position: None,
+    }));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`WikiLinkLabel`][Name::WikiLinkLabel].
+fn on_enter_wikilink_label(context: &mut CompileContext) {
+    // A label replaces the target as the content of the link.
+    if let Node::Link(link) = context.tail_mut() {
+        link.children.clear();
+    } else {
+        unreachable!("expected link on stack");
+    }
+
+    on_enter_data(context);
+}
+
 /// Handle [`Enter`][Kind::Enter]:{[`ListOrdered`][Name::ListOrdered],[`ListUnordered`][Name::ListUnordered]}.
 fn on_enter_list(context: &mut CompileContext) {
     let ordered = context.events[context.index].name == Name::ListOrdered;
@@ -766,6 +894,14 @@ fn on_enter_list_item(context: &mut CompileContext) {
     }));
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`Mark`][Name::Mark].
+fn on_enter_mark(context: &mut CompileContext) {
+    context.tail_push(Node::Mark(Mark {
+        children: vec![],
+        position: None,
+    }));
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`MathFlow`][Name::MathFlow].
 fn on_enter_math_flow(context: &mut CompileContext) {
     context.tail_push(Node::Math(Math {
@@ -852,6 +988,7 @@ fn on_enter_mdx_jsx_tag_attribute_expression(
         context.index,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[Name::MdxJsxTagAttributeExpression],
+        context.tab_size,
     );
     context
         .jsx_tag
@@ -876,6 +1013,7 @@ fn on_enter_mdx_jsx_tag_attribute_value_expression(context: &mut CompileContext)
         context.index,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[Name::MdxJsxTagAttributeValueExpression],
+        context.tab_size,
     );
 
     if let Some(AttributeContent::Property(node)) = context
@@ -935,6 +1073,7 @@ fn on_exit_autolink_protocol(context: &mut CompileContext) -> Result<(), message
     let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     if let Node::Link(link) = context.tail_mut() {
         link.url.push_str(value.as_str());
@@ -950,6 +1089,7 @@ fn on_exit_autolink_email(context: &mut CompileContext) -> Result<(), message::M
     let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     if let Node::Link(link) = context.tail_mut() {
         link.url.push_str("mailto:");
@@ -980,10 +1120,14 @@ fn on_exit_character_reference_value(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
-    let value =
-        decode_character_reference(slice.as_str(), context.character_reference_marker, true)
-            .expect("expected to parse only valid named references");
+    let value = decode_character_reference(
+        slice.as_str(),
+        context.character_reference_marker,
+        context.character_references,
+    )
+    .expect("expected to parse only valid named references");
 
     if let Node::Text(node) = context.tail_mut() {
         node.value.push_str(value.as_str());
@@ -1108,9 +1252,11 @@ fn on_exit_data(context: &mut CompileContext) -> Result<(), message::Message> {
     let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     if let Node::Text(text) = context.tail_mut() {
-        text.value.push_str(value.as_str());
+        // Must serialize to get virtual spaces.
+        text.value.push_str(&value.serialize());
     } else {
         unreachable!("expected text on stack");
     }
@@ -1134,6 +1280,7 @@ fn on_exit_definition_id(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let identifier = normalize_identifier(slice.as_str()).to_lowercase();
 
@@ -1165,6 +1312,81 @@ fn on_exit_drop(context: &mut CompileContext) {
     context.resume();
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveAttributeClass`][Name::DirectiveAttributeClass].
+fn on_exit_directive_attribute_class(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    let value = slice.as_str();
+    let attributes = directive_attributes_mut(context.tail_mut());
+
+    // Classes are joined together.
+    if let Some(attribute) = attributes.iter_mut().find(|d| d.0 == "class") {
+        if !attribute.1.is_empty() {
+            attribute.1.push(' ');
+        }
+        attribute.1.push_str(value);
+    } else {
+        attributes.push(("class".into(), value.into()));
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveAttributeId`][Name::DirectiveAttributeId].
+fn on_exit_directive_attribute_id(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    let value = slice.as_str().into();
+    directive_attribute_set(context.tail_mut(), "id".into(), value);
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveAttributeName`][Name::DirectiveAttributeName].
+fn on_exit_directive_attribute_name(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    let name = slice.as_str().into();
+    directive_attribute_set(context.tail_mut(), name, String::new());
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveAttributeValue`][Name::DirectiveAttributeValue].
+fn on_exit_directive_attribute_value(context: &mut CompileContext) {
+    let value = context.resume().to_string();
+    let attributes = directive_attributes_mut(context.tail_mut());
+    attributes.last_mut().expect("expected attribute").1 = value;
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveLabel`][Name::DirectiveLabel].
+fn on_exit_directive_label(context: &mut CompileContext) -> Result<(), message::Message> {
+    if let Node::Paragraph(_) = context.tail_mut() {
+        on_exit(context)?;
+    }
+    Ok(())
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveName`][Name::DirectiveName].
+fn on_exit_directive_name(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    let value = slice.as_str().into();
+
+    match context.tail_mut() {
+        Node::ContainerDirective(node) => node.name = value,
+        Node::LeafDirective(node) => node.name = value,
+        Node::TextDirective(node) => node.name = value,
+        _ => unreachable!("expected directive on stack"),
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`Frontmatter`][Name::Frontmatter].
 fn on_exit_frontmatter(context: &mut CompileContext) -> Result<(), message::Message> {
     let value = trim_eol(context.resume().to_string(), true, true);
@@ -1186,6 +1408,7 @@ fn on_exit_gfm_autolink_literal(context: &mut CompileContext) -> Result<(), mess
     let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
 
     let prefix = match &context.events[context.index].name {
@@ -1240,6 +1463,7 @@ fn on_exit_heading_atx_sequence(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
 
     if let Node::Heading(node) = context.tail_mut() {
@@ -1286,6 +1510,7 @@ fn on_exit_label_text(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let identifier = normalize_identifier(slice.as_str()).to_lowercase();
 
@@ -1298,9 +1523,10 @@ fn on_exit_label_text(context: &mut CompileContext) {
 
     match context.tail_mut() {
         Node::Link(node) => node.children = children,
-        Node::Image(node) => node.alt = label,
+        Node::Image(node) => node.alt = alt(&children),
+        Node::InlineFootnote(node) => node.children = children,
         Node::FootnoteReference(_) => {}
-        _ => unreachable!("expected footnote refereence, image, or link on stack"),
+        _ => unreachable!("expected footnote refereence, image, inline footnote, or link on stack"),
     }
 }
 
@@ -1329,6 +1555,7 @@ fn on_exit_line_ending(context: &mut CompileContext) -> Result<(), message::Mess
             | Node::Paragraph(_)
             | Node::Strong(_)
             | Node::Delete(_)
+            | Node::Mark(_)
     ) {
         context.index -= 1;
         on_enter_data(context);
@@ -1352,7 +1579,7 @@ fn on_exit_html(context: &mut CompileContext) -> Result<(), message::Message> {
     Ok(())
 }
 
-/// Handle [`Exit`][Kind::Exit]:{[`GfmFootnoteCall`][Name::GfmFootnoteCall],[`Image`][Name::Image],[`Link`][Name::Link]}.
+/// Handle [`Exit`][Kind::Exit]:{[`GfmFootnoteCall`][Name::GfmFootnoteCall],[`Image`][Name::Image],[`InlineFootnote`][Name::InlineFootnote],[`Link`][Name::Link]}.
 fn on_exit_media(context: &mut CompileContext) -> Result<(), message::Message> {
     let reference = context
         .media_reference_stack
@@ -1455,6 +1682,7 @@ fn on_exit_list_item_value(context: &mut CompileContext) {
     let start = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     )
     .as_str()
     .parse()
@@ -1555,6 +1783,7 @@ fn on_exit_mdx_jsx_tag_name_primary(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let value = slice.serialize();
     context.jsx_tag.as_mut().expect("expected tag").name = Some(value);
@@ -1565,6 +1794,7 @@ fn on_exit_mdx_jsx_tag_name_member(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let name = context
         .jsx_tag
@@ -1582,6 +1812,7 @@ fn on_exit_mdx_jsx_tag_name_local(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let name = context
         .jsx_tag
@@ -1606,6 +1837,7 @@ fn on_exit_mdx_jsx_tag_attribute_primary_name(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let value = slice.serialize();
 
@@ -1627,6 +1859,7 @@ fn on_exit_mdx_jsx_tag_attribute_name_local(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     if let Some(AttributeContent::Property(attribute)) = context
         .jsx_tag
@@ -1672,6 +1905,7 @@ fn on_exit_reference_string(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let identifier = normalize_identifier(slice.as_str()).to_lowercase();
     let reference = context
@@ -1705,6 +1939,48 @@ fn on_exit_resource_title_string(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Exit`][Kind::Exit]:{[`SmartPunctuationDash`][Name::SmartPunctuationDash],[`SmartPunctuationEllipsis`][Name::SmartPunctuationEllipsis],[`SmartPunctuationQuoteClosing`][Name::SmartPunctuationQuoteClosing],[`SmartPunctuationQuoteOpening`][Name::SmartPunctuationQuoteOpening]}.
+fn on_exit_smart_punctuation(context: &mut CompileContext) -> Result<(), message::Message> {
+    let slice = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    let value = smart_punctuation(&context.events[context.index].name, slice.bytes);
+    if let Node::Text(text) = context.tail_mut() {
+        text.value.push_str(&value);
+    } else {
+        unreachable!("expected text on stack");
+    }
+    on_exit(context)?;
+    Ok(())
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`WikiLinkTarget`][Name::WikiLinkTarget].
+fn on_exit_wikilink_target(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    let value = slice.serialize();
+    // The target is also the content of the link, unless there is a label.
+    let text = Node::Text(Text {
+        value: value.clone(),
+        position: Some(Position {
+            start: context.events[context.index - 1].point.to_unist(),
+            end: context.events[context.index].point.to_unist(),
+        }),
+    });
+
+    if let Node::Link(link) = context.tail_mut() {
+        link.url = value;
+        link.children.push(text);
+    } else {
+        unreachable!("expected link on stack");
+    }
+}
+
 /// Create a position from an event.
 fn position_from_event(event: &Event) -> Position {
     let end = Point::new(event.point.line, event.point.column, event.point.index);
@@ -1725,6 +2001,23 @@ fn delve_mut<'tree>(mut node: &'tree mut Node, stack: &'tree [usize]) -> &'tree
     node
 }
 
+/// Get the plain text of the label of an image, including the alts of images
+/// in it, like the `alt` attribute in HTML.
+fn alt(children: &[Node]) -> String {
+    children
+        .iter()
+        .map(|child| match child {
+            Node::Image(x) => x.alt.clone(),
+            Node::ImageReference(x) => x.alt.clone(),
+            // Notes are not part of the surrounding text.
+            Node::InlineFootnote(_) => String::new(),
+            _ => child
+                .children()
+                .map_or_else(|| child.to_string(), |d| alt(d)),
+        })
+        .collect()
+}
+
 /// Remove initial/final EOLs.
 fn trim_eol(value: String, at_start: bool, at_end: bool) -> String {
     let bytes = value.as_bytes();
@@ -1828,3 +2121,20 @@ fn serialize_abbreviated_tag(tag: &JsxTag) -> String {
         if let Some(name) = &tag.name { name } else { "" },
     )
 }
+
+/// Get the attributes of a directive.
+fn directive_attributes_mut(node: &mut Node) -> &mut Vec<(String, String)> {
+    match node {
+        Node::ContainerDirective(node) => &mut node.attributes,
+        Node::LeafDirective(node) => &mut node.attributes,
+        Node::TextDirective(node) => &mut node.attributes,
+        _ => unreachable!("expected directive on stack"),
+    }
+}
+
+/// Set an attribute on a directive, replacing an earlier one with that name.
+fn directive_attribute_set(node: &mut Node, name: String, value: String) {
+    let attributes = directive_attributes_mut(node);
+    attributes.retain(|d| d.0 != name);
+    attributes.push((name, value));
+}
@@ -0,0 +1,112 @@
+//! Turn a syntax tree into plain text.
+
+use crate::mdast::Node;
+use alloc::{string::String, vec::Vec};
+
+/// Turn a syntax tree into plain text.
+pub fn serialize(tree: &Node) -> String {
+    block(tree)
+}
+
+/// Serialize flow (block) content.
+///
+/// Blocks are separated by a line ending.
+/// Blocks without text, such as thematic breaks, are dropped.
+fn flow(children: &[Node]) -> String {
+    children
+        .iter()
+        .map(block)
+        .filter(|d| !d.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Serialize a block.
+fn block(node: &Node) -> String {
+    match node {
+        Node::Code(node) => node.value.clone(),
+        Node::Math(node) => node.value.clone(),
+        Node::Heading(node) => phrasing(&node.children),
+        Node::LeafDirective(node) => phrasing(&node.children),
+        Node::Paragraph(node) => phrasing(&node.children),
+        // Cells are separated by a space, rows by a line ending.
+        Node::Table(node) => node
+            .children
+            .iter()
+            .map(|row| match row.children() {
+                Some(cells) => cells
+                    .iter()
+                    .map(|cell| cell.children().map_or_else(String::new, |d| phrasing(d)))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                None => String::new(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        // Markup and metadata.
+        Node::Definition(_)
+        | Node::Html(_)
+        | Node::MdxFlowExpression(_)
+        | Node::MdxjsEsm(_)
+        | Node::ThematicBreak(_)
+        | Node::Toml(_)
+        | Node::Yaml(_) => String::new(),
+        _ => node.children().map_or_else(String::new, |d| flow(d)),
+    }
+}
+
+/// Serialize phrasing (inline) content.
+fn phrasing(children: &[Node]) -> String {
+    let mut result = String::new();
+
+    for child in children {
+        inline(child, &mut result);
+    }
+
+    result
+}
+
+/// Serialize a phrasing node.
+fn inline(node: &Node, result: &mut String) {
+    match node {
+        Node::Text(node) => push_line(result, &node.value),
+        Node::InlineCode(node) => push_line(result, &node.value),
+        Node::InlineMath(node) => push_line(result, &node.value),
+        Node::Image(node) => push_line(result, &node.alt),
+        Node::ImageReference(node) => push_line(result, &node.alt),
+        Node::Break(_) => result.push('\n'),
+        // Notes are not part of the surrounding text.
+        Node::InlineFootnote(_) => {}
+        _ => {
+            if let Some(children) = node.children() {
+                for child in children {
+                    inline(child, result);
+                }
+            }
+        }
+    }
+}
+
+/// Push `value`, with its line endings turned into spaces.
+fn push_line(result: &mut String, value: &str) {
+    let bytes = value.as_bytes();
+    let mut index = 0;
+    let mut start = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'\r' || bytes[index] == b'\n' {
+            result.push_str(&value[start..index]);
+            result.push(' ');
+
+            if bytes[index] == b'\r' && index + 1 < bytes.len() && bytes[index + 1] == b'\n' {
+                index += 1;
+            }
+
+            start = index + 1;
+        }
+
+        index += 1;
+    }
+
+    result.push_str(&value[start..]);
+}
@@ -18,7 +18,8 @@ use crate::subtokenize::Subresult;
 #[cfg(feature = "log")]
 use crate::util::char::format_byte_opt;
 
-use crate::util::{constant::TAB_SIZE, edit_map::EditMap};
+use crate::util::char::column_size;
+use crate::util::edit_map::EditMap;
 use alloc::{boxed::Box, string::String, vec, vec::Vec};
 
 /// Containers.
@@ -36,6 +37,9 @@ pub enum Container {
     ListItem,
     /// [GFM: Footnote definition][crate::construct::gfm_footnote_definition].
     GfmFootnoteDefinition,
+    /// [Directive (container)][crate::construct::directive_container].
+    #[allow(clippy::enum_variant_names)]
+    DirectiveContainer,
 }
 
 /// Info used to tokenize a container.
@@ -76,6 +80,15 @@ pub enum LabelKind {
     ///
     /// Construct: [Label start (image)][crate::construct::label_start_image].
     Image,
+    /// Label (inline footnote) start.
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^^
+    /// ```
+    ///
+    /// Construct: [Label start (inline footnote)][crate::construct::label_start_inline_footnote].
+    InlineFootnote,
     /// Label (image) link.
     ///
     /// ```markdown
@@ -231,6 +244,8 @@ pub struct TokenizeState<'a> {
     pub definitions: Vec<String>,
     /// List of defined GFM footnote definition identifiers.
     pub gfm_footnote_definitions: Vec<String>,
+    /// List of diagnostics, if they are collected.
+    pub diagnostics: Vec<message::Message>,
 
     // Last error message provided at an EOF of an expression.
     pub mdx_last_parse_error: Option<(String, String, String)>,
@@ -351,6 +366,7 @@ impl<'a> Tokenizer<'a> {
                 document_at_first_paragraph_of_list_item: false,
                 definitions: vec![],
                 gfm_footnote_definitions: vec![],
+                diagnostics: vec![],
                 mdx_last_parse_error: None,
                 end: 0,
                 label_starts: vec![],
@@ -425,6 +441,18 @@ impl<'a> Tokenizer<'a> {
         self.account_for_potential_skip();
     }
 
+    /// Check whether we are at the start of a line: nothing on it was
+    /// consumed yet, other than what was skipped.
+    pub fn at_line_start(&self) -> bool {
+        let at = self.point.line - self.first_line;
+
+        self.column_start
+            .get(at)
+            .map_or(self.point.column == 1, |&(index, vs)| {
+                self.point.index == index && self.point.vs == vs
+            })
+    }
+
     /// Increment the current positional info if we’re right after a line
     /// ending, which has a skip defined.
     fn account_for_potential_skip(&mut self) {
@@ -459,7 +487,11 @@ impl<'a> Tokenizer<'a> {
 
     /// Move to the next (virtual) byte.
     fn move_one(&mut self) {
-        match byte_action(self.parse_state.bytes, &self.point) {
+        match byte_action(
+            self.parse_state.bytes,
+            &self.point,
+            self.parse_state.options.tab_size,
+        ) {
             ByteAction::Ignore => {
                 self.point.index += 1;
             }
@@ -488,7 +520,11 @@ impl<'a> Tokenizer<'a> {
                     #[cfg(feature = "log")]
                     log::trace!("position: after eol: `{:?}`", self.point);
                 } else {
-                    self.point.column += 1;
+                    self.point.column += column_size(
+                        self.parse_state.bytes,
+                        self.point.index - 1,
+                        self.parse_state.options.column_unit,
+                    );
                 }
             }
         }
@@ -521,6 +557,10 @@ impl<'a> Tokenizer<'a> {
         let previous = self.events.last().expect("cannot close w/o open event");
         let mut point = self.point.clone();
 
+        // Constructs must consume something in the events they enter and
+        // exit.
+        // Events that resolvers add, such as the container exits added in
+        // `document.rs`, are created directly, so they can be empty.
         debug_assert!(
             current != previous.name
                 || previous.point.index != point.index
@@ -636,6 +676,7 @@ impl<'a> Tokenizer<'a> {
             done: false,
             gfm_footnote_definitions: self.tokenize_state.gfm_footnote_definitions.split_off(0),
             definitions: self.tokenize_state.definitions.split_off(0),
+            diagnostics: self.tokenize_state.diagnostics.split_off(0),
         };
 
         if resolve {
@@ -647,6 +688,7 @@ impl<'a> Tokenizer<'a> {
                 if let Some(mut result) = call_resolve(self, resolvers[index])? {
                     fn_defs.append(&mut result.gfm_footnote_definitions);
                     defs.append(&mut result.definitions);
+                    value.diagnostics.append(&mut result.diagnostics);
                 }
                 index += 1;
             }
@@ -662,7 +704,11 @@ impl<'a> Tokenizer<'a> {
 fn move_point_back(tokenizer: &mut Tokenizer, point: &mut Point) {
     while point.index > 0 {
         point.index -= 1;
-        let action = byte_action(tokenizer.parse_state.bytes, point);
+        let action = byte_action(
+            tokenizer.parse_state.bytes,
+            point,
+            tokenizer.parse_state.options.tab_size,
+        );
         if !matches!(action, ByteAction::Ignore) {
             point.index += 1;
             break;
@@ -734,7 +780,11 @@ fn push_impl(
                 let action = if tokenizer.point.index < to.0
                     || (tokenizer.point.index == to.0 && tokenizer.point.vs < to.1)
                 {
-                    Some(byte_action(tokenizer.parse_state.bytes, &tokenizer.point))
+                    Some(byte_action(
+                        tokenizer.parse_state.bytes,
+                        &tokenizer.point,
+                        tokenizer.parse_state.options.tab_size,
+                    ))
                 } else if flush {
                     None
                 } else {
@@ -782,7 +832,7 @@ fn push_impl(
 }
 
 /// Figure out how to handle a byte.
-fn byte_action(bytes: &[u8], point: &Point) -> ByteAction {
+fn byte_action(bytes: &[u8], point: &Point, tab_size: usize) -> ByteAction {
     if point.index < bytes.len() {
         let byte = bytes[point.index];
 
@@ -796,11 +846,11 @@ fn byte_action(bytes: &[u8], point: &Point) -> ByteAction {
                 ByteAction::Normal(b'\n')
             }
         } else if byte == b'\t' {
-            let remainder = point.column % TAB_SIZE;
+            let remainder = point.column % tab_size;
             let vs = if remainder == 0 {
                 0
             } else {
-                TAB_SIZE - remainder
+                tab_size - remainder
             };
 
             // On the tab itself, first send it.
@@ -11,8 +11,13 @@ pub struct Point {
     /// 1-indexed integer representing a line in a source file.
     pub line: usize,
     /// 1-indexed integer representing a column in a source file.
+    ///
+    /// Columns count bytes, and tabs increase them up to the next tab stop.
     pub column: usize,
-    /// 0-indexed integer representing a character in a source file.
+    /// 0-indexed integer representing a byte in a source file.
+    ///
+    /// This is an index into the original value, so a byte order mark at
+    /// its start (which is ignored otherwise) counts as 3 bytes.
     pub offset: usize,
 }
 
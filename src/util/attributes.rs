@@ -0,0 +1,166 @@
+//! Deal with attribute lists (`{#id .class key=value}`).
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Parse an attribute list.
+///
+/// `value` must be a whole attribute list: it must start with `{` and end
+/// with `}`.
+/// Inside it, attributes are separated by spaces or tabs, and can be:
+///
+/// *   `#a`, which sets `id` to `a`
+/// *   `.a`, which adds `a` to `class`
+/// *   `a=b`, `a="b"`, or `a='b'`, which sets `a` to `b`
+///
+/// Returns `None` if `value` is not a valid attribute list, or if it is
+/// empty.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::attributes::parse;
+///
+/// assert_eq!(
+///     parse("{#a .b c=\"d e\"}"),
+///     Some(vec![
+///         ("id".into(), "a".into()),
+///         ("class".into(), "b".into()),
+///         ("c".into(), "d e".into()),
+///     ])
+/// );
+/// assert_eq!(parse("{a}"), None);
+/// ```
+pub fn parse(value: &str) -> Option<Vec<(String, String)>> {
+    let bytes = value.as_bytes();
+
+    if bytes.len() < 2 || bytes[0] != b'{' || bytes[bytes.len() - 1] != b'}' {
+        return None;
+    }
+
+    let end = bytes.len() - 1;
+    let mut index = 1;
+    let mut attributes = vec![];
+
+    loop {
+        let start = index;
+
+        while index < end && matches!(bytes[index], b'\t' | b' ') {
+            index += 1;
+        }
+
+        if index == end {
+            return if attributes.is_empty() {
+                None
+            } else {
+                Some(attributes)
+            };
+        }
+
+        // Attributes must be separated by whitespace.
+        if !attributes.is_empty() && index == start {
+            return None;
+        }
+
+        match bytes[index] {
+            b'#' | b'.' => {
+                let name = if bytes[index] == b'#' { "id" } else { "class" };
+                index += 1;
+                let value_start = index;
+
+                while index < end && !matches!(bytes[index], b'\t' | b' ') {
+                    if matches!(bytes[index], b'"' | b'\'' | b'{' | b'}' | b'=') {
+                        return None;
+                    }
+
+                    index += 1;
+                }
+
+                if index == value_start {
+                    return None;
+                }
+
+                attributes.push((name.into(), value[value_start..index].to_string()));
+            }
+            _ => {
+                let name_start = index;
+
+                if !(bytes[index].is_ascii_alphabetic() || matches!(bytes[index], b'_' | b':')) {
+                    return None;
+                }
+
+                while index < end
+                    && (bytes[index].is_ascii_alphanumeric()
+                        || matches!(bytes[index], b'-' | b'.' | b':' | b'_'))
+                {
+                    index += 1;
+                }
+
+                let name_end = index;
+
+                if index == end || bytes[index] != b'=' {
+                    return None;
+                }
+
+                index += 1;
+
+                if index == end {
+                    return None;
+                }
+
+                let marker = bytes[index];
+                let value_start;
+                let value_end;
+
+                if marker == b'"' || marker == b'\'' {
+                    index += 1;
+                    value_start = index;
+
+                    while index < end && bytes[index] != marker {
+                        index += 1;
+                    }
+
+                    if index == end {
+                        return None;
+                    }
+
+                    value_end = index;
+                    index += 1;
+                } else {
+                    value_start = index;
+
+                    while index < end && !matches!(bytes[index], b'\t' | b' ') {
+                        if matches!(bytes[index], b'"' | b'\'' | b'{' | b'}' | b'=') {
+                            return None;
+                        }
+
+                        index += 1;
+                    }
+
+                    if index == value_start {
+                        return None;
+                    }
+
+                    value_end = index;
+                }
+
+                attributes.push((
+                    value[name_start..name_end].to_string(),
+                    value[value_start..value_end].to_string(),
+                ));
+            }
+        }
+    }
+}
+
+/// Check if an attribute is safe.
+///
+/// Event handlers (such as `onclick`), and styles that load things (with
+/// `url(`), are not safe.
+pub fn safe(name: &str, value: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    !(name.starts_with("on") || (name == "style" && value.to_ascii_lowercase().contains("url(")))
+}
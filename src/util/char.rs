@@ -1,8 +1,9 @@
 //! Deal with bytes, chars, and kinds.
 
-use crate::util::unicode::PUNCTUATION;
+use crate::configuration::ColumnUnit;
+use crate::util::unicode::{GRAPHEME_EXTEND, PUNCTUATION};
 use alloc::{format, string::String};
-use core::str;
+use core::{cmp::Ordering, str};
 
 /// Character kinds.
 #[derive(Debug, PartialEq, Eq)]
@@ -93,7 +94,8 @@ pub fn classify(char: char) -> Kind {
         Kind::Whitespace
     }
     // Unicode punctuation.
-    else if char.is_ascii_punctuation() || PUNCTUATION.contains(&char) {
+    // The list is sorted by code point, so we can search it quickly.
+    else if char.is_ascii_punctuation() || PUNCTUATION.binary_search(&char).is_ok() {
         Kind::Punctuation
     }
     // Everything else.
@@ -107,6 +109,83 @@ pub fn classify_opt(char_opt: Option<char>) -> Kind {
     char_opt.map_or(Kind::Whitespace, classify)
 }
 
+/// Get the number of columns that the byte at `index` in bytes (`&[u8]`)
+/// takes up, when counting in `unit`.
+///
+/// Only the first byte of a character can take up columns, unless counting
+/// bytes.
+pub fn column_size(bytes: &[u8], index: usize, unit: ColumnUnit) -> usize {
+    let byte = bytes[index];
+
+    match unit {
+        ColumnUnit::Bytes => 1,
+        // Continuation byte.
+        _ if byte & 0xC0 == 0x80 => 0,
+        ColumnUnit::CodePoints => 1,
+        // Four byte characters are encoded as a surrogate pair.
+        ColumnUnit::Utf16 => {
+            if byte >= 0xF0 {
+                2
+            } else {
+                1
+            }
+        }
+        ColumnUnit::Graphemes => usize::from(byte < 0x80 || !extends_grapheme(bytes, index)),
+    }
+}
+
+/// Check whether the character at `index` in bytes (`&[u8]`) extends the
+/// grapheme before it.
+fn extends_grapheme(bytes: &[u8], index: usize) -> bool {
+    let char = if let Some(char) = after_index(bytes, index) {
+        char
+    } else {
+        return false;
+    };
+
+    if is_regional_indicator(char) {
+        // The second of each pair of regional indicators forms a flag with
+        // the first.
+        let mut before = index;
+        let mut count = 0;
+
+        while let Some(char) = before_index(bytes, before) {
+            if !is_regional_indicator(char) {
+                break;
+            }
+
+            before -= char.len_utf8();
+            count += 1;
+        }
+
+        count % 2 == 1
+    } else {
+        // Emoji (and other non-ASCII characters) joined by a zero-width joiner.
+        before_index(bytes, index) == Some('\u{200D}')
+            // The zero-width joiner itself, and emoji modifiers (skin tones),
+            // also extend graphemes, but are not `Grapheme_Extend`.
+            || char == '\u{200D}'
+            || ('\u{1F3FB}'..='\u{1F3FF}').contains(&char)
+            // The list is sorted by code point, so we can search it quickly.
+            || GRAPHEME_EXTEND
+                .binary_search_by(|(start, end)| {
+                    if *end < char {
+                        Ordering::Less
+                    } else if *start > char {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Equal
+                    }
+                })
+                .is_ok()
+    }
+}
+
+/// Check whether a `char` is a regional indicator (used in pairs for flags).
+fn is_regional_indicator(char: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&char)
+}
+
 /// Format an optional `char` (`none` means eof).
 pub fn format_opt(char: Option<char>) -> String {
     char.map_or("end of file".into(), |char| {
@@ -1,5 +1,6 @@
 //! Helpers for character references.
 
+use crate::configuration::CharacterReferences;
 use crate::util::constant::{
     CHARACTER_REFERENCES, CHARACTER_REFERENCES_HTML_4, CHARACTER_REFERENCE_DECIMAL_SIZE_MAX,
     CHARACTER_REFERENCE_HEXADECIMAL_SIZE_MAX, CHARACTER_REFERENCE_NAMED_SIZE_MAX,
@@ -45,6 +46,23 @@ pub fn decode_named(value: &str, html5: bool) -> Option<String> {
     iter.find(|d| d.0 == value).map(|d| d.1.into())
 }
 
+/// Decode named character references, as supported by `references`.
+///
+/// Like [`decode_named`][], but looks the given string up in the names
+/// that are chosen with [`CharacterReferences`][].
+pub fn decode_named_with(value: &str, references: &CharacterReferences) -> Option<String> {
+    match references {
+        CharacterReferences::Html5 => decode_named(value, true),
+        CharacterReferences::XmlMinimal => match value {
+            "amp" | "apos" | "gt" | "lt" | "quot" => decode_named(value, true),
+            _ => None,
+        },
+        CharacterReferences::Custom(names) => {
+            names.iter().find(|d| d.0 == value).map(|d| d.1.clone())
+        }
+    }
+}
+
 /// Decode numeric character references.
 ///
 /// Turn the number (in string form as either hexadecimal or decimal) coming
@@ -56,6 +74,8 @@ pub fn decode_named(value: &str, html5: bool) -> Option<String> {
 /// character for C0 control characters (except for ASCII whitespace), C1
 /// control characters, lone surrogates, noncharacters, and out of range
 /// characters.
+/// Like HTML, C1 control characters that are assigned in Windows-1252 (such
+/// as `0x80`) turn into the character they represent there (such as `€`).
 ///
 /// ## Examples
 ///
@@ -64,6 +84,7 @@ pub fn decode_named(value: &str, html5: bool) -> Option<String> {
 ///
 /// assert_eq!(decode_numeric("123", 10), "{");
 /// assert_eq!(decode_numeric("9", 16), "\t");
+/// assert_eq!(decode_numeric("80", 16), "€"); // Windows-1252.
 /// assert_eq!(decode_numeric("0", 10), "�"); // Not allowed.
 /// ```
 ///
@@ -79,8 +100,13 @@ pub fn decode_named(value: &str, html5: bool) -> Option<String> {
 ///
 /// *   [`micromark-util-decode-numeric-character-reference` in `micromark`](https://github.com/micromark/micromark/tree/main/packages/micromark-util-decode-numeric-character-reference)
 /// *   [*§ 2.5 Entity and numeric character references* in `CommonMark`](https://spec.commonmark.org/0.31/#entity-and-numeric-character-references)
+/// *   [*§ 13.2.5.80 Numeric character reference end state* in the HTML spec](https://html.spec.whatwg.org/multipage/parsing.html#numeric-character-reference-end-state)
 pub fn decode_numeric(value: &str, radix: u32) -> String {
     if let Some(char) = char::from_u32(u32::from_str_radix(value, radix).unwrap()) {
+        if let Some(replacement) = decode_windows_1252(char) {
+            return replacement.into();
+        }
+
         if !matches!(char,
             // C0 except for HT, LF, FF, CR, space.
             '\0'..='\u{08}' | '\u{0B}' | '\u{0E}'..='\u{1F}' |
@@ -96,6 +122,43 @@ pub fn decode_numeric(value: &str, radix: u32) -> String {
     char::REPLACEMENT_CHARACTER.into()
 }
 
+/// Get the character that a C1 control character represents in
+/// Windows-1252, if it is assigned there.
+fn decode_windows_1252(char: char) -> Option<char> {
+    let replacement = match char {
+        '\u{80}' => '\u{20AC}',
+        '\u{82}' => '\u{201A}',
+        '\u{83}' => '\u{0192}',
+        '\u{84}' => '\u{201E}',
+        '\u{85}' => '\u{2026}',
+        '\u{86}' => '\u{2020}',
+        '\u{87}' => '\u{2021}',
+        '\u{88}' => '\u{02C6}',
+        '\u{89}' => '\u{2030}',
+        '\u{8A}' => '\u{0160}',
+        '\u{8B}' => '\u{2039}',
+        '\u{8C}' => '\u{0152}',
+        '\u{8E}' => '\u{017D}',
+        '\u{91}' => '\u{2018}',
+        '\u{92}' => '\u{2019}',
+        '\u{93}' => '\u{201C}',
+        '\u{94}' => '\u{201D}',
+        '\u{95}' => '\u{2022}',
+        '\u{96}' => '\u{2013}',
+        '\u{97}' => '\u{2014}',
+        '\u{98}' => '\u{02DC}',
+        '\u{99}' => '\u{2122}',
+        '\u{9A}' => '\u{0161}',
+        '\u{9B}' => '\u{203A}',
+        '\u{9C}' => '\u{0153}',
+        '\u{9E}' => '\u{017E}',
+        '\u{9F}' => '\u{0178}',
+        _ => return None,
+    };
+
+    Some(replacement)
+}
+
 /// Decode a character reference.
 ///
 /// This turns the number (in string form as either hexadecimal or decimal) or
@@ -104,18 +167,17 @@ pub fn decode_numeric(value: &str, radix: u32) -> String {
 /// The marker specifies the format: `#` for hexadecimal, `x` for decimal, and
 /// `&` for named.
 ///
-/// The `html5` boolean is used for named character references, and specifier
-/// whether the 2125 names from HTML 5 or the 252 names from HTML 4 are
-/// supported.
+/// The `references` are used for named character references, and specify
+/// which names are supported.
 ///
 /// ## Panics
 ///
 /// Panics if `marker` is not `b'&'`, `b'x'`, or `b'#'`.
-pub fn decode(value: &str, marker: u8, html5: bool) -> Option<String> {
+pub fn decode(value: &str, marker: u8, references: &CharacterReferences) -> Option<String> {
     match marker {
         b'#' => Some(decode_numeric(value, 10)),
         b'x' => Some(decode_numeric(value, 16)),
-        b'&' => decode_named(value, html5),
+        b'&' => decode_named_with(value, references),
         _ => unreachable!("Unexpected marker `{}`", marker),
     }
 }
@@ -194,11 +256,14 @@ pub fn parse(value: &str) -> String {
 
             // Non empty and terminated.
             if value_index > 0 && bytes[value_end] == b';' {
-                if let Some(decoded) = decode(
-                    str::from_utf8(&bytes[value_start..value_end]).unwrap(),
-                    marker,
-                    false,
-                ) {
+                let name = str::from_utf8(&bytes[value_start..value_end]).unwrap();
+                let decoded = match marker {
+                    b'&' => decode_named(name, false),
+                    b'x' => Some(decode_numeric(name, 16)),
+                    _ => Some(decode_numeric(name, 10)),
+                };
+
+                if let Some(decoded) = decoded {
                     result.push_str(&value[start..index]);
                     result.push_str(&decoded);
                     start = value_end + 1;
@@ -67,6 +67,23 @@ pub const CHARACTER_REFERENCE_NAMED_SIZE_MAX: usize = 31;
 /// [raw_flow]: crate::construct::raw_flow
 pub const CODE_FENCED_SEQUENCE_SIZE_MIN: usize = 3;
 
+/// The number of markers needed for [directive (container)][directive_container]
+/// to form.
+///
+/// Like many things in markdown, the number is `3`.
+///
+/// [directive_container]: crate::construct::directive_container
+pub const DIRECTIVE_CONTAINER_SEQUENCE_SIZE_MIN: usize = 3;
+
+/// The number of markers needed for [directive (leaf)][directive_leaf] to
+/// form.
+///
+/// Exactly `2`: more is a [directive (container)][directive_container].
+///
+/// [directive_leaf]: crate::construct::directive_leaf
+/// [directive_container]: crate::construct::directive_container
+pub const DIRECTIVE_LEAF_SEQUENCE_SIZE: usize = 2;
+
 /// The number of markers needed for [frontmatter][] to form.
 ///
 /// Like many things in markdown, the number is `3`.
@@ -266,6 +283,13 @@ pub const MATH_FLOW_SEQUENCE_SIZE_MIN: usize = 2;
 /// See: <https://github.com/remarkjs/react-markdown/issues/658#issuecomment-984345577>.
 pub const RESOURCE_DESTINATION_BALANCE_MAX: usize = 32;
 
+/// The maximum depth of nested containers and attention, when operating
+/// safely.
+///
+/// Used by [`Options::safe()`][crate::Options::safe].
+/// Real documents are rarely nested more than a couple of levels deep.
+pub const SAFE_NESTING_DEPTH_MAX: usize = 32;
+
 /// List of protocols allowed, when operating safely, as `href` on `a`.
 ///
 /// This list is based on what is allowed by GitHub.
@@ -276,8 +300,9 @@ pub const SAFE_PROTOCOL_HREF: [&str; 6] = ["http", "https", "irc", "ircs", "mail
 /// This list is based on what is allowed by GitHub.
 pub const SAFE_PROTOCOL_SRC: [&str; 2] = ["http", "https"];
 
-/// The number of characters that form a tab stop.
+/// The number of characters that form a tab stop, by default.
 ///
+/// Can be changed with [`tab_size`][crate::ParseOptions::tab_size].
 /// This relates to the number of whitespace characters needed to form certain
 /// constructs in markdown, most notable the whitespace required to form
 /// [code (indented)][code_indented].
@@ -285,13 +310,25 @@ pub const SAFE_PROTOCOL_SRC: [&str; 2] = ["http", "https"];
 /// [code_indented]: crate::construct::code_indented
 pub const TAB_SIZE: usize = 4;
 
-/// The number of markers needed for a [thematic break][thematic_break] to form.
+/// The number of markers needed for a [thematic break][thematic_break] to
+/// form, by default.
 ///
+/// Can be changed with
+/// [`thematic_break_min`][crate::ParseOptions::thematic_break_min].
 /// Like many things in markdown, the number is `3`.
 ///
 /// [thematic_break]: crate::construct::thematic_break
 pub const THEMATIC_BREAK_MARKER_COUNT_MIN: usize = 3;
 
+/// The maximum allowed size of the target and of the label of a
+/// [wikilink][], each.
+///
+/// To safeguard performance, they are capped at a large number: `999`, like
+/// [`LINK_REFERENCE_SIZE_MAX`][].
+///
+/// [wikilink]: crate::construct::wikilink
+pub const WIKILINK_SIZE_MAX: usize = 999;
+
 // Important: please touch the below lists as few times as possible to keep Git small.
 
 /// List of names and values that form named [character reference][character_reference]s.
@@ -9,7 +9,7 @@
 //! through another tokenizer and inject the result.
 
 use crate::event::Event;
-use alloc::{vec, vec::Vec};
+use alloc::{collections::BTreeMap, vec::Vec};
 
 /// Shift `previous` and `next` links according to `jumps`.
 ///
@@ -57,14 +57,20 @@ fn shift_links(events: &mut [Event], jumps: &[(usize, usize, usize)]) {
 /// Tracks a bunch of edits.
 #[derive(Debug)]
 pub struct EditMap {
-    /// Record of changes.
-    map: Vec<(usize, usize, Vec<Event>)>,
+    /// Record of changes, by index: how many events to remove there, and
+    /// which events to add.
+    ///
+    /// Keyed by index so that many edits (such as one for each line in a
+    /// big document) can be added quickly.
+    map: BTreeMap<usize, (usize, Vec<Event>)>,
 }
 
 impl EditMap {
     /// Create a new edit map.
     pub fn new() -> EditMap {
-        EditMap { map: vec![] }
+        EditMap {
+            map: BTreeMap::new(),
+        }
     }
     /// Create an edit: a remove and/or add at a certain place.
     pub fn add(&mut self, index: usize, remove: usize, add: Vec<Event>) {
@@ -76,20 +82,22 @@ impl EditMap {
     }
     /// Done, change the events.
     pub fn consume(&mut self, events: &mut Vec<Event>) {
-        self.map
-            .sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-
         if self.map.is_empty() {
             return;
         }
 
+        // Sorted by index.
+        let mut map = core::mem::take(&mut self.map)
+            .into_iter()
+            .collect::<Vec<_>>();
+
         // Calculate jumps: where items in the current list move to.
-        let mut jumps = Vec::with_capacity(self.map.len());
+        let mut jumps = Vec::with_capacity(map.len());
         let mut index = 0;
         let mut add_acc = 0;
         let mut remove_acc = 0;
-        while index < self.map.len() {
-            let (at, remove, add) = &self.map[index];
+        while index < map.len() {
+            let (at, (remove, add)) = &map[index];
             remove_acc += remove;
             add_acc += add.len();
             jumps.push((*at, remove_acc, add_acc));
@@ -99,13 +107,14 @@ impl EditMap {
         shift_links(events, &jumps);
 
         let len_before = events.len();
-        let mut index = self.map.len();
+        let mut index = map.len();
         let mut vecs = Vec::with_capacity(index * 2 + 1);
         while index > 0 {
             index -= 1;
-            vecs.push(events.split_off(self.map[index].0 + self.map[index].1));
-            vecs.push(self.map[index].2.split_off(0));
-            events.truncate(self.map[index].0);
+            let (at, (remove, add)) = &mut map[index];
+            vecs.push(events.split_off(*at + *remove));
+            vecs.push(add.split_off(0));
+            events.truncate(*at);
         }
         vecs.push(events.split_off(0));
 
@@ -114,35 +123,23 @@ impl EditMap {
         while let Some(mut slice) = vecs.pop() {
             events.append(&mut slice);
         }
-
-        self.map.truncate(0);
     }
 }
 
 /// Create an edit.
 fn add_impl(edit_map: &mut EditMap, at: usize, remove: usize, mut add: Vec<Event>, before: bool) {
-    let mut index = 0;
-
     if remove == 0 && add.is_empty() {
         return;
     }
 
-    while index < edit_map.map.len() {
-        if edit_map.map[index].0 == at {
-            edit_map.map[index].1 += remove;
+    let (existing_remove, existing_add) = edit_map.map.entry(at).or_default();
 
-            if before {
-                add.append(&mut edit_map.map[index].2);
-                edit_map.map[index].2 = add;
-            } else {
-                edit_map.map[index].2.append(&mut add);
-            }
+    *existing_remove += remove;
 
-            return;
-        }
-
-        index += 1;
+    if before {
+        add.append(existing_add);
+        *existing_add = add;
+    } else {
+        existing_add.append(&mut add);
     }
-
-    edit_map.map.push((at, remove, add));
 }
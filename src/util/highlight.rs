@@ -0,0 +1,13 @@
+//! Highlight code.
+
+use alloc::string::String;
+
+/// Signature of a function that turns code into HTML.
+///
+/// Can be passed as `code_highlighter` in
+/// [`CompileOptions`][crate::configuration::CompileOptions] to highlight
+/// code (fenced).
+/// It is called with the language (the first word of the info string, or an
+/// empty string) and the code, and returns the HTML to use inside the
+/// `<code>` element.
+pub type CodeHighlighterFn = dyn Fn(&str, &str) -> String;
@@ -10,8 +10,12 @@ use alloc::{vec, vec::Vec};
 ///
 /// When `include_items: true` is passed, infers whether the list as a whole
 /// is “loose”.
+///
+/// Blank lines only make a list spread when they are between items: blank
+/// lines at the end of a list do not count.
 pub fn list_loose(events: &[Event], mut index: usize, include_items: bool) -> bool {
     let mut balance = 0;
+    let mut blank = false;
     let name = &events[index].name;
     debug_assert!(
         matches!(name, Name::ListOrdered | Name::ListUnordered),
@@ -24,10 +28,9 @@ pub fn list_loose(events: &[Event], mut index: usize, include_items: bool) -> bo
         if event.kind == Kind::Enter {
             balance += 1;
 
-            if include_items
-                && balance == 2
+            if balance == 2
                 && event.name == Name::ListItem
-                && list_item_loose(events, index)
+                && (blank || (include_items && list_item_loose(events, index)))
             {
                 return true;
             }
@@ -72,7 +75,7 @@ pub fn list_loose(events: &[Event], mut index: usize, include_items: bool) -> bo
                 }
 
                 if !at_empty_list_item && !at_empty_block_quote {
-                    return true;
+                    blank = true;
                 }
             }
 
@@ -89,18 +92,35 @@ pub fn list_loose(events: &[Event], mut index: usize, include_items: bool) -> bo
 }
 
 /// Figure out if an item is spread or not.
+///
+/// Blank lines only make an item spread when they are between its children:
+/// blank lines at the end of an item do not count.
 pub fn list_item_loose(events: &[Event], mut index: usize) -> bool {
     debug_assert!(
         matches!(events[index].name, Name::ListItem),
         "expected list item"
     );
     let mut balance = 0;
+    let mut blank = false;
 
     while index < events.len() {
         let event = &events[index];
 
         if event.kind == Kind::Enter {
             balance += 1;
+
+            if blank
+                && balance == 2
+                && !matches!(
+                    event.name,
+                    Name::BlankLineEnding
+                        | Name::BlockQuotePrefix
+                        | Name::LineEnding
+                        | Name::SpaceOrTab
+                )
+            {
+                return true;
+            }
         } else {
             balance -= 1;
 
@@ -126,7 +146,7 @@ pub fn list_item_loose(events: &[Event], mut index: usize) -> bool {
                 }
 
                 if !at_prefix {
-                    return true;
+                    blank = true;
                 }
             }
 
@@ -49,6 +49,35 @@ pub enum LineEnding {
 
 // xxxxxxxxxxxxxxx
 impl LineEnding {
+    /// Find the first line ending in `value`.
+    ///
+    /// This is the line ending that is used when compiling to HTML, if line
+    /// endings have to be added (see
+    /// [`default_line_ending`][crate::CompileOptions::default_line_ending]).
+    /// Returns `None` if there are no line endings.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::LineEnding;
+    ///
+    /// assert_eq!(LineEnding::infer("a\r\nb\nc"), Some(LineEnding::CarriageReturnLineFeed));
+    /// assert_eq!(LineEnding::infer("a"), None);
+    /// ```
+    #[must_use]
+    pub fn infer(value: &str) -> Option<LineEnding> {
+        let bytes = value.as_bytes();
+        let index = bytes.iter().position(|d| *d == b'\n' || *d == b'\r')?;
+
+        Some(if bytes[index] == b'\n' {
+            LineEnding::LineFeed
+        } else if bytes.get(index + 1) == Some(&b'\n') {
+            LineEnding::CarriageReturnLineFeed
+        } else {
+            LineEnding::CarriageReturn
+        })
+    }
+
     /// Turn the line ending into a [str].
     #[must_use]
     pub fn as_str(&self) -> &str {
@@ -18,6 +18,7 @@ pub fn collect(
     from: usize,
     names: &[Name],
     stop: &[Name],
+    tab_size: usize,
 ) -> Result {
     let mut result = Result {
         value: String::new(),
@@ -35,6 +36,7 @@ pub fn collect(
                         start: &events[index].point,
                         end: &events[index + 1].point,
                     },
+                    tab_size,
                 )
                 .serialize();
                 result
@@ -0,0 +1,124 @@
+//! Lowercase tag and attribute names in HTML.
+
+use crate::util::{constant::HTML_RAW_NAMES, sanitize_html::tag};
+use alloc::{string::String, vec::Vec};
+
+/// Lowercase tag and attribute names in HTML.
+///
+/// Everything else, such as attribute values, text, and comments, is kept
+/// as-is.
+/// The content of raw tags (such as `<pre>` and `<script>`) is also kept
+/// as-is, up to their closing tag, as case can matter there.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::normalize_html::normalize_html_tags;
+///
+/// assert_eq!(
+///     normalize_html_tags("<DIV Class=\"X\">a</DIV>"),
+///     "<div class=\"X\">a</div>"
+/// );
+/// ```
+pub fn normalize_html_tags(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'<' {
+            if let Some(end) = other_end(bytes, index) {
+                result.extend_from_slice(&bytes[index..end]);
+                index = end;
+                continue;
+            }
+
+            if let Some(tag) = tag(bytes, index) {
+                let mut slice = bytes[index..tag.end].to_vec();
+                slice[(tag.name.0 - index)..(tag.name.1 - index)].make_ascii_lowercase();
+
+                for attribute in &tag.attributes {
+                    slice[(attribute.name.0 - index)..(attribute.name.1 - index)]
+                        .make_ascii_lowercase();
+                }
+
+                result.extend_from_slice(&slice);
+                index = tag.end;
+
+                // Keep the content of raw tags.
+                if !tag.closing && !tag.self_closing {
+                    let name = &bytes[tag.name.0..tag.name.1];
+
+                    if HTML_RAW_NAMES
+                        .iter()
+                        .any(|d| d.as_bytes().eq_ignore_ascii_case(name))
+                    {
+                        let end = raw_end(bytes, index, name);
+                        result.extend_from_slice(&bytes[index..end]);
+                        index = end;
+                    }
+                }
+
+                continue;
+            }
+        }
+
+        result.push(bytes[index]);
+        index += 1;
+    }
+
+    // Only ASCII bytes are changed, so this is still UTF-8.
+    String::from_utf8(result).unwrap()
+}
+
+/// Move past a comment, declaration, instruction, or CDATA at `index` (a
+/// `<`).
+///
+/// Returns `None` if there is none.
+/// When it is not closed, it runs to the end.
+fn other_end(bytes: &[u8], index: usize) -> Option<usize> {
+    let rest = &bytes[index..];
+    let close: &[u8] = if rest.starts_with(b"<!--") {
+        b"-->"
+    } else if rest.starts_with(b"<![CDATA[") {
+        b"]]>"
+    } else if rest.starts_with(b"<?") {
+        b"?>"
+    } else if rest.starts_with(b"<!") {
+        b">"
+    } else {
+        return None;
+    };
+
+    Some(
+        rest[2..]
+            .windows(close.len())
+            .position(|d| d == close)
+            .map_or(bytes.len(), |d| index + 2 + d + close.len()),
+    )
+}
+
+/// Find the closing tag of the raw tag `name`, from `index`.
+///
+/// Returns the index of its `<`, or the end if there is none.
+fn raw_end(bytes: &[u8], mut index: usize, name: &[u8]) -> usize {
+    while index < bytes.len() {
+        let end = index + 2 + name.len();
+
+        if bytes[index] == b'<'
+            && bytes.get(index + 1) == Some(&b'/')
+            && bytes.len() >= end
+            && bytes[(index + 2)..end].eq_ignore_ascii_case(name)
+            && matches!(
+                bytes.get(end),
+                None | Some(b'\t' | b'\n' | 12 /* `\f` */ | b'\r' | b' ' | b'/' | b'>')
+            )
+        {
+            return index;
+        }
+
+        index += 1;
+    }
+
+    index
+}
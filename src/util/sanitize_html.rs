@@ -0,0 +1,281 @@
+//! Make HTML safe by only allowing certain tags and attributes.
+
+use crate::configuration::HtmlSanitization;
+use crate::util::{character_reference::parse, encode::encode};
+use alloc::{string::String, vec, vec::Vec};
+
+/// Tag, as found in HTML.
+#[derive(Debug)]
+pub struct Tag {
+    /// Whether this is a closing tag (`</a>`).
+    pub closing: bool,
+    /// Whether this is a self-closing tag (`<a />`).
+    pub self_closing: bool,
+    /// Range of the tag name.
+    pub name: (usize, usize),
+    /// Attributes.
+    pub attributes: Vec<Attribute>,
+    /// Index after the tag.
+    pub end: usize,
+}
+
+/// Attribute, as found in a tag.
+#[derive(Debug)]
+pub struct Attribute {
+    /// Range of the name.
+    pub name: (usize, usize),
+    /// Range of the (raw) value, if there is one.
+    pub value: Option<(usize, usize)>,
+}
+
+/// Make HTML safe by only allowing certain tags and attributes.
+///
+/// Allowed tags are kept, but only with allowed attributes.
+/// Attributes are serialized again, with their values decoded and then
+/// encoded, and the values of `href` and `src` are passed through
+/// `sanitize_url`.
+/// Other tags (including closing tags), comments, declarations, and
+/// instructions are encoded, so that they show as text.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::sanitize_html::sanitize_html;
+/// use markdown::HtmlSanitization;
+///
+/// let sanitization = HtmlSanitization {
+///     tag_names: vec!["b".into()],
+///     attribute_names: vec!["title".into()],
+/// };
+///
+/// assert_eq!(
+///     sanitize_html("<b title=a onclick=b>c</b><i>", &sanitization, &|d, _| d.into()),
+///     "<b title=\"a\">c</b>&lt;i&gt;"
+/// );
+/// ```
+pub fn sanitize_html(
+    value: &str,
+    sanitization: &HtmlSanitization,
+    sanitize_url: &dyn Fn(&str, bool) -> String,
+) -> String {
+    let bytes = value.as_bytes();
+    // It’ll grow a bit bigger for each encoded character.
+    let mut result = String::with_capacity(bytes.len());
+    let mut index = 0;
+    let mut start = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'<' {
+            result.push_str(&value[start..index]);
+
+            if let Some(tag) = tag(bytes, index) {
+                if allowed(&sanitization.tag_names, &value[tag.name.0..tag.name.1]) {
+                    serialize(&mut result, value, &tag, sanitization, sanitize_url);
+                } else {
+                    result.push_str(&encode(&value[index..tag.end], true));
+                }
+
+                index = tag.end;
+            } else {
+                result.push_str("&lt;");
+                index += 1;
+            }
+
+            start = index;
+            continue;
+        }
+
+        index += 1;
+    }
+
+    result.push_str(&value[start..]);
+
+    result
+}
+
+/// Serialize an allowed tag, with its allowed attributes.
+fn serialize(
+    result: &mut String,
+    value: &str,
+    tag: &Tag,
+    sanitization: &HtmlSanitization,
+    sanitize_url: &dyn Fn(&str, bool) -> String,
+) {
+    result.push('<');
+
+    if tag.closing {
+        result.push('/');
+    }
+
+    result.push_str(&value[tag.name.0..tag.name.1]);
+
+    if !tag.closing {
+        for attribute in &tag.attributes {
+            let name = &value[attribute.name.0..attribute.name.1];
+
+            if !allowed(&sanitization.attribute_names, name) {
+                continue;
+            }
+
+            result.push(' ');
+            result.push_str(name);
+
+            if let Some(raw) = attribute.value {
+                let decoded = parse(&value[raw.0..raw.1]);
+                let lowercase = name.to_ascii_lowercase();
+                result.push_str("=\"");
+                result.push_str(&if lowercase == "href" || lowercase == "src" {
+                    sanitize_url(&decoded, lowercase == "src")
+                } else {
+                    encode(&decoded, true)
+                });
+                result.push('"');
+            }
+        }
+
+        if tag.self_closing {
+            result.push_str(" /");
+        }
+    }
+
+    result.push('>');
+}
+
+/// Check if `name` is in `names` (case-insensitive).
+fn allowed(names: &[String], name: &str) -> bool {
+    names.iter().any(|d| d.eq_ignore_ascii_case(name))
+}
+
+/// Parse a tag at `index` (a `<`).
+///
+/// Returns `None` if there is no (complete) opening or closing tag there,
+/// such as for comments or for a `<` followed by a space.
+pub fn tag(bytes: &[u8], index: usize) -> Option<Tag> {
+    let len = bytes.len();
+    let mut index = index + 1;
+    let closing = index < len && bytes[index] == b'/';
+
+    if closing {
+        index += 1;
+    }
+
+    let name_start = index;
+
+    if index == len || !bytes[index].is_ascii_alphabetic() {
+        return None;
+    }
+
+    while index < len && (bytes[index].is_ascii_alphanumeric() || bytes[index] == b'-') {
+        index += 1;
+    }
+
+    let name = (name_start, index);
+    let mut attributes = vec![];
+
+    loop {
+        index = skip_whitespace(bytes, index);
+
+        if index == len {
+            return None;
+        }
+
+        match bytes[index] {
+            b'>' => {
+                return Some(Tag {
+                    closing,
+                    self_closing: false,
+                    name,
+                    attributes,
+                    end: index + 1,
+                });
+            }
+            b'/' if index + 1 < len && bytes[index + 1] == b'>' => {
+                return Some(Tag {
+                    closing,
+                    self_closing: true,
+                    name,
+                    attributes,
+                    end: index + 2,
+                });
+            }
+            b'/' => {
+                index += 1;
+            }
+            _ => {
+                let attribute_name_start = index;
+
+                while index < len
+                    && !is_whitespace(bytes[index])
+                    && !matches!(bytes[index], b'/' | b'=' | b'>')
+                {
+                    index += 1;
+                }
+
+                if index == attribute_name_start {
+                    return None;
+                }
+
+                let attribute_name = (attribute_name_start, index);
+                let after_name = skip_whitespace(bytes, index);
+                let mut attribute_value = None;
+
+                if after_name < len && bytes[after_name] == b'=' {
+                    index = skip_whitespace(bytes, after_name + 1);
+
+                    if index == len {
+                        return None;
+                    }
+
+                    let marker = bytes[index];
+
+                    if marker == b'"' || marker == b'\'' {
+                        let value_start = index + 1;
+                        index = value_start;
+
+                        while index < len && bytes[index] != marker {
+                            index += 1;
+                        }
+
+                        if index == len {
+                            return None;
+                        }
+
+                        attribute_value = Some((value_start, index));
+                        index += 1;
+                    } else {
+                        let value_start = index;
+
+                        while index < len && !is_whitespace(bytes[index]) && bytes[index] != b'>' {
+                            index += 1;
+                        }
+
+                        if index == value_start {
+                            return None;
+                        }
+
+                        attribute_value = Some((value_start, index));
+                    }
+                }
+
+                attributes.push(Attribute {
+                    name: attribute_name,
+                    value: attribute_value,
+                });
+            }
+        }
+    }
+}
+
+/// Move past HTML whitespace.
+fn skip_whitespace(bytes: &[u8], mut index: usize) -> usize {
+    while index < bytes.len() && is_whitespace(bytes[index]) {
+        index += 1;
+    }
+
+    index
+}
+
+/// Check if `byte` is HTML whitespace.
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b'\t' | b'\n' | 12 /* `\f` */ | b'\r' | b' ')
+}
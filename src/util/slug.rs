@@ -0,0 +1,79 @@
+//! Make slugs for headings.
+
+use alloc::{collections::BTreeMap, format, string::String};
+
+/// Signature of a function that turns the text of a heading into an ID.
+///
+/// Can be passed as `heading_id_fn` in
+/// [`CompileOptions`][crate::configuration::CompileOptions] to use a
+/// different scheme than the default, GitHub-style, slugs.
+pub type HeadingIdFn = dyn Fn(&str) -> String;
+
+/// Signature of a function that turns the target of a wikilink into a URL.
+///
+/// Can be passed as `wikilink_href_fn` in
+/// [`CompileOptions`][crate::configuration::CompileOptions].
+/// It gets the target as written (`Page Name` in `[[Page Name|label]]`),
+/// and returns the URL to use as the `href`.
+/// That URL is then sanitized like the URLs of links: dangerous protocols
+/// are dropped, and characters are encoded.
+pub type WikilinkHrefFn = dyn Fn(&str) -> String;
+
+/// Turn text into a GitHub-style slug.
+///
+/// This lowercases the value, drops everything that is not alphanumeric,
+/// a space, a dash, or an underscore, and then turns spaces into dashes.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::slug::slug;
+///
+/// assert_eq!(slug("Hello, World!"), "hello-world");
+/// assert_eq!(slug("a_b  c"), "a_b--c");
+/// assert_eq!(slug("Привет"), "привет");
+/// ```
+///
+/// ## References
+///
+/// *   [`github-slugger`](https://github.com/Flet/github-slugger)
+pub fn slug(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for char in value.chars().flat_map(char::to_lowercase) {
+        if char == ' ' {
+            result.push('-');
+        } else if char.is_alphanumeric() || char == '-' || char == '_' {
+            result.push(char);
+        }
+    }
+
+    result
+}
+
+/// Make a slug unique, by adding `-1`, `-2`, etc. if it was already used.
+///
+/// `seen` maps each slug used so far to the last number tried for it, so
+/// that many equal slugs do not each have to count up from the start.
+/// Adds the result to `seen`.
+pub fn unique(value: &str, seen: &mut BTreeMap<String, usize>) -> String {
+    let mut result = String::from(value);
+
+    if let Some(&start) = seen.get(value) {
+        let mut count = start;
+
+        loop {
+            count += 1;
+            result = format!("{}-{}", value, count);
+
+            if !seen.contains_key(&result) {
+                break;
+            }
+        }
+
+        seen.insert(value.into(), count);
+    }
+
+    seen.insert(result.clone(), 0);
+    result
+}
@@ -0,0 +1,43 @@
+//! Turn smart punctuation into typographic characters.
+
+use crate::event::Name;
+use alloc::string::String;
+
+/// Get the typographic value of smart punctuation.
+///
+/// `name` is the name of a smart punctuation event, and `value` is what it
+/// represents in the source (such as `"`, `--`, or `...`).
+///
+/// ## Panics
+///
+/// Panics if `name` is not smart punctuation.
+pub fn replacement(name: &Name, value: &[u8]) -> String {
+    match name {
+        Name::SmartPunctuationDash => dashes(value.len()),
+        Name::SmartPunctuationEllipsis => "…".into(),
+        Name::SmartPunctuationQuoteClosing => if value == b"\"" { "”" } else { "’" }.into(),
+        Name::SmartPunctuationQuoteOpening => if value == b"\"" { "“" } else { "‘" }.into(),
+        _ => unreachable!("expected smart punctuation"),
+    }
+}
+
+/// Turn a sequence of `size` dashes into em and en dashes.
+///
+/// Em dashes are used if all dashes can be em dashes, otherwise en dashes are
+/// used if all can be en dashes, otherwise as many em dashes as possible are
+/// used, followed by one or two en dashes.
+fn dashes(size: usize) -> String {
+    let (em, en) = if size % 3 == 0 {
+        (size / 3, 0)
+    } else if size % 2 == 0 {
+        (0, size / 2)
+    } else if size % 3 == 2 {
+        ((size - 2) / 3, 1)
+    } else {
+        ((size - 4) / 3, 2)
+    };
+
+    let mut result = "—".repeat(em);
+    result.push_str(&"–".repeat(en));
+    result
+}
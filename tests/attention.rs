@@ -42,6 +42,42 @@ fn attention() -> Result<(), message::Message> {
         "should not support emphasis unicode whitespace either"
     );
 
+    assert_eq!(
+        to_html("\u{00A0}*a*\u{00A0}"),
+        "<p>\u{00A0}<em>a</em>\u{00A0}</p>",
+        "should support emphasis between unicode whitespace"
+    );
+
+    assert_eq!(
+        to_html("a\u{00A0}*\u{00A0}b*"),
+        "<p>a\u{00A0}*\u{00A0}b*</p>",
+        "should not support emphasis opening before unicode whitespace"
+    );
+
+    assert_eq!(
+        to_html("a\u{3000}_b_\u{3000}c"),
+        "<p>a\u{3000}<em>b</em>\u{3000}c</p>",
+        "should support underscore emphasis between ideographic spaces"
+    );
+
+    assert_eq!(
+        to_html("（*a*）"),
+        "<p>（<em>a</em>）</p>",
+        "should support emphasis between full-width punctuation"
+    );
+
+    assert_eq!(
+        to_html("*a*。b"),
+        "<p><em>a</em>。b</p>",
+        "should support emphasis before full-width punctuation"
+    );
+
+    assert_eq!(
+        to_html("a**「b」**c"),
+        "<p>a**「b」**c</p>",
+        "should not support strong inside words if the content is wrapped in full-width punctuation"
+    );
+
     assert_eq!(
         to_html("foo*bar*"),
         "<p>foo<em>bar</em></p>",
@@ -819,6 +855,48 @@ fn attention() -> Result<(), message::Message> {
         "should not end strong emphasis inside autolinks (2)"
     );
 
+    assert_eq!(
+        to_html("**a *b* c**"),
+        "<p><strong>a <em>b</em> c</strong></p>",
+        "should support emphasis in strong"
+    );
+
+    assert_eq!(
+        to_html("*a **b** c*"),
+        "<p><em>a <strong>b</strong> c</em></p>",
+        "should support strong in emphasis"
+    );
+
+    assert_eq!(
+        to_html("***a** b*"),
+        "<p><em><strong>a</strong> b</em></p>",
+        "should support a sequence of three opening strong in emphasis"
+    );
+
+    assert_eq!(
+        to_html("***a* b**"),
+        "<p><strong><em>a</em> b</strong></p>",
+        "should support a sequence of three opening emphasis in strong"
+    );
+
+    assert_eq!(
+        to_html("**a *b***"),
+        "<p><strong>a <em>b</em></strong></p>",
+        "should support a sequence of three closing emphasis in strong"
+    );
+
+    assert_eq!(
+        to_html("***a *b* c***"),
+        "<p><em><strong>a <em>b</em> c</strong></em></p>",
+        "should support emphasis in strong in emphasis"
+    );
+
+    assert_eq!(
+        to_html("**foo**bar**"),
+        "<p><strong>foo</strong>bar**</p>",
+        "should not match a closing sequence that was already used"
+    );
+
     assert_eq!(
         to_html_with_options(
             "*a*",
@@ -876,5 +954,26 @@ fn attention() -> Result<(), message::Message> {
         "should support attention as `Emphasis`, `Strong`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("***a***", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Emphasis(Emphasis {
+                    children: vec![Node::Strong(Strong {
+                        children: vec![Node::Text(Text {
+                            value: "a".into(),
+                            position: Some(Position::new(1, 4, 3, 1, 5, 4))
+                        }),],
+                        position: Some(Position::new(1, 2, 1, 1, 7, 6))
+                    }),],
+                    position: Some(Position::new(1, 1, 0, 1, 8, 7))
+                }),],
+                position: Some(Position::new(1, 1, 0, 1, 8, 7))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 8, 7))
+        }),
+        "should support strong in emphasis for sequences of three as `Strong` in `Emphasis` in mdast"
+    );
+
     Ok(())
 }
@@ -137,6 +137,70 @@ fn autolink() -> Result<(), message::Message> {
         "should not support autolinks w/o a colon or at sign"
     );
 
+    assert_eq!(
+        to_html("<http://exa mple> b"),
+        "<p>&lt;http://exa mple&gt; b</p>",
+        "should not support protocol autolinks w/ a space, and keep the text after it"
+    );
+
+    assert_eq!(
+        to_html("<http://a<b>"),
+        "<p>&lt;http://a&lt;b&gt;</p>",
+        "should not support protocol autolinks w/ a less than"
+    );
+
+    assert_eq!(
+        to_html("<http://a\u{1}b>"),
+        "<p>&lt;http://a\u{1}b&gt;</p>",
+        "should not support protocol autolinks w/ a control character"
+    );
+
+    assert_eq!(
+        to_html("<http://a\r\nb>"),
+        "<p>&lt;http://a\r\nb&gt;</p>",
+        "should not support protocol autolinks w/ a CRLF"
+    );
+
+    assert_eq!(
+        to_html("<a@b.c\r\nd>"),
+        "<p>&lt;a@b.c\r\nd&gt;</p>",
+        "should not support email autolinks w/ a CRLF"
+    );
+
+    assert_eq!(
+        to_html("<http://a\u{7f}b>"),
+        "<p>&lt;http://a\u{7f}b&gt;</p>",
+        "should not support protocol autolinks w/ a delete character"
+    );
+
+    assert_eq!(
+        to_html("<http://a\tb>"),
+        "<p>&lt;http://a\tb&gt;</p>",
+        "should not support protocol autolinks w/ a tab"
+    );
+
+    assert_eq!(
+        to_html_with_options(&format!("<{}:b>", "a".repeat(32)), &danger)?,
+        format!(
+            "<p><a href=\"{}:b\">{}:b</a></p>",
+            "a".repeat(32),
+            "a".repeat(32)
+        ),
+        "should support autolinks w/ a 32 character scheme"
+    );
+
+    assert_eq!(
+        to_html(&format!("<{}:b>", "a".repeat(33))),
+        format!("<p>&lt;{}:b&gt;</p>", "a".repeat(33)),
+        "should not support autolinks w/ a 33 character scheme"
+    );
+
+    assert_eq!(
+        to_html_with_options("<http://a b=\"c\">", &danger)?,
+        "<p>&lt;http://a b=&quot;c&quot;&gt;</p>",
+        "should not support autolinks or HTML w/ a space and a colon in the tag name"
+    );
+
     assert_eq!(
         to_html("http://example.com"),
         "<p>http://example.com</p>",
@@ -257,6 +321,36 @@ fn autolink() -> Result<(), message::Message> {
         "should not support an at sign at the start of email autolinks"
     );
 
+    assert_eq!(
+        to_html("<first.last+tag@mail.sub.example.co.uk>"),
+        "<p><a href=\"mailto:first.last+tag@mail.sub.example.co.uk\">first.last+tag@mail.sub.example.co.uk</a></p>",
+        "should support plus signs and dots in the local part, and several labels in the domain, in email autolinks"
+    );
+
+    assert_eq!(
+        to_html("<a-b@c-d.e-f.g>"),
+        "<p><a href=\"mailto:a-b@c-d.e-f.g\">a-b@c-d.e-f.g</a></p>",
+        "should support dashes in several domain labels in email autolinks"
+    );
+
+    assert_eq!(
+        to_html("<a@b.c->"),
+        "<p>&lt;a@b.c-&gt;</p>",
+        "should not support a dash at the end of the last domain label in email autolinks"
+    );
+
+    assert_eq!(
+        to_html("<a@b.c.>"),
+        "<p>&lt;a@b.c.&gt;</p>",
+        "should not support a dot at the end of email autolinks"
+    );
+
+    assert_eq!(
+        to_html("<.a@b.c>"),
+        "<p><a href=\"mailto:.a@b.c\">.a@b.c</a></p>",
+        "should support a dot at the start of the local part in email autolinks, like `CommonMark`"
+    );
+
     assert_eq!(
         to_html_with_options(
             "<a@b.co>",
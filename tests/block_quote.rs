@@ -1,11 +1,36 @@
 use markdown::{
-    mdast::{Blockquote, Node, Paragraph, Root, Text},
+    event::{Event, Kind, Name},
+    mdast::{Blockquote, Emphasis, List, ListItem, Node, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    Constructs, Options, ParseOptions, Resolver,
 };
 use pretty_assertions::assert_eq;
 
+/// Resolver that adds empty emphasis at the start of the first paragraph.
+fn empty_emphasis() -> Box<Resolver> {
+    Box::new(|events: &mut Vec<Event>, _value: &str| {
+        let index = events
+            .iter()
+            .position(|event| event.kind == Kind::Enter && event.name == Name::Paragraph)
+            .unwrap();
+        let point = events[index].point.clone();
+        let enter = Event {
+            kind: Kind::Enter,
+            name: Name::Emphasis,
+            point: point.clone(),
+            link: None,
+        };
+        let exit = Event {
+            kind: Kind::Exit,
+            name: Name::Emphasis,
+            point,
+            link: None,
+        };
+        events.splice((index + 1)..(index + 1), [enter, exit]);
+    })
+}
+
 #[test]
 fn block_quote() -> Result<(), message::Message> {
     assert_eq!(
@@ -200,6 +225,120 @@ fn block_quote() -> Result<(), message::Message> {
         "should support 5 spaces for indented code, not 4"
     );
 
+    assert_eq!(
+        to_html("- > a\nb"),
+        "<ul>\n<li>\n<blockquote>\n<p>a\nb</p>\n</blockquote>\n</li>\n</ul>",
+        "should support lazy lines in block quotes in list items"
+    );
+
+    assert_eq!(
+        to_html("1. > a\nb\n2. c"),
+        "<ol>\n<li>\n<blockquote>\n<p>a\nb</p>\n</blockquote>\n</li>\n<li>c</li>\n</ol>",
+        "should support lazy lines in block quotes in list items, before another item"
+    );
+
+    assert_eq!(
+        to_html("* > a\n  > b\nc\n* d"),
+        "<ul>\n<li>\n<blockquote>\n<p>a\nb\nc</p>\n</blockquote>\n</li>\n<li>d</li>\n</ul>",
+        "should support lazy lines after continued block quotes in list items"
+    );
+
+    assert_eq!(
+        to_html("- > a\n  b"),
+        "<ul>\n<li>\n<blockquote>\n<p>a\nb</p>\n</blockquote>\n</li>\n</ul>",
+        "should support lazy lines in block quotes that continue list items"
+    );
+
+    assert_eq!(
+        to_html("- a\n  > b\nc"),
+        "<ul>\n<li>a\n<blockquote>\n<p>b\nc</p>\n</blockquote>\n</li>\n</ul>",
+        "should support lazy lines in block quotes after paragraphs in list items"
+    );
+
+    assert_eq!(
+        to_html("- > a\n\n  b"),
+        "<ul>\n<li>\n<blockquote>\n<p>a</p>\n</blockquote>\n<p>b</p>\n</li>\n</ul>",
+        "should not support lazy lines after blank lines in block quotes in list items"
+    );
+
+    assert_eq!(
+        to_html("- > a\n---"),
+        "<ul>\n<li>\n<blockquote>\n<p>a</p>\n</blockquote>\n</li>\n</ul>\n<hr />",
+        "should not support lazy thematic breaks in block quotes in list items"
+    );
+
+    assert_eq!(
+        to_html("- > a\n> b"),
+        "<ul>\n<li>\n<blockquote>\n<p>a</p>\n</blockquote>\n</li>\n</ul>\n<blockquote>\n<p>b</p>\n</blockquote>",
+        "should not continue block quotes in list items w/o the list item"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> b"),
+        "<blockquote>\n<blockquote>\n<p>a\nb</p>\n</blockquote>\n</blockquote>",
+        "should support lazy lines in nested block quotes, continuing only the outer one"
+    );
+
+    assert_eq!(
+        to_html("> > > a\n> > b\n> c"),
+        "<blockquote>\n<blockquote>\n<blockquote>\n<p>a\nb\nc</p>\n</blockquote>\n</blockquote>\n</blockquote>",
+        "should support lazy lines in nested block quotes, continuing fewer and fewer"
+    );
+
+    assert_eq!(
+        to_html("> > a\n>\n> b"),
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n<p>b</p>\n</blockquote>",
+        "should close the inner block quote after a blank line in the outer one"
+    );
+
+    assert_eq!(
+        to_html("> > # a\n> b"),
+        "<blockquote>\n<blockquote>\n<h1>a</h1>\n</blockquote>\n<p>b</p>\n</blockquote>",
+        "should close the inner block quote after a heading (atx)"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> > ---\n> b"),
+        "<blockquote>\n<blockquote>\n<h2>a</h2>\n</blockquote>\n<p>b</p>\n</blockquote>",
+        "should close the inner block quote after a heading (setext)"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> > ***\n> b"),
+        "<blockquote>\n<blockquote>\n<p>a</p>\n<hr />\n</blockquote>\n<p>b</p>\n</blockquote>",
+        "should close the inner block quote after a thematic break"
+    );
+
+    assert_eq!(
+        to_html("> > ```\n> b"),
+        "<blockquote>\n<blockquote>\n<pre><code></code></pre>\n</blockquote>\n<p>b</p>\n</blockquote>",
+        "should close the inner block quote after fenced code"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> - b"),
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n<ul>\n<li>b</li>\n</ul>\n</blockquote>",
+        "should close the inner block quote for a list in the outer one"
+    );
+
+    assert_eq!(
+        to_html("> a\n> > b\n> c"),
+        "<blockquote>\n<p>a</p>\n<blockquote>\n<p>b\nc</p>\n</blockquote>\n</blockquote>",
+        "should support lazy lines in inner block quotes after paragraphs in outer ones"
+    );
+
+    assert_eq!(
+        to_html("> a\n> ---\nb"),
+        "<blockquote>\n<h2>a</h2>\n</blockquote>\n<p>b</p>",
+        "should not support lazy lines after a heading (setext) in a block quote"
+    );
+
+    assert_eq!(
+        to_html("- a\n  ===\nb"),
+        "<ul>\n<li>\n<h1>a</h1>\n</li>\n</ul>\n<p>b</p>",
+        "should not support lazy lines after a heading (setext) in a list item"
+    );
+
     assert_eq!(
         to_html_with_options(
             "> # a\n> b\n> c",
@@ -236,5 +375,72 @@ fn block_quote() -> Result<(), message::Message> {
         "should support block quotes as `BlockQuote`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("> -\n>", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Blockquote(Blockquote {
+                children: vec![Node::List(List {
+                    children: vec![Node::ListItem(ListItem {
+                        children: vec![],
+                        spread: false,
+                        checked: None,
+                        position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                    })],
+                    ordered: false,
+                    start: None,
+                    spread: false,
+                    position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                })],
+                position: Some(Position::new(1, 1, 0, 2, 2, 5))
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 2, 5))
+        }),
+        "should support an empty list item in a block quote, exited by the document resolver, in mdast"
+    );
+
+    let empty = ParseOptions {
+        resolvers: vec![("empty".into(), empty_emphasis())],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options(
+            "> a",
+            &Options {
+                parse: ParseOptions {
+                    resolvers: vec![("empty".into(), empty_emphasis())],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<blockquote>\n<p><em></em>a</p>\n</blockquote>",
+        "should support empty events added by a resolver in a block quote"
+    );
+
+    assert_eq!(
+        to_mdast("> a", &empty)?,
+        Node::Root(Root {
+            children: vec![Node::Blockquote(Blockquote {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![
+                        Node::Emphasis(Emphasis {
+                            children: vec![],
+                            position: Some(Position::new(1, 3, 2, 1, 3, 2))
+                        }),
+                        Node::Text(Text {
+                            value: "a".into(),
+                            position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                        }),
+                    ],
+                    position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 4, 3))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 4, 3))
+        }),
+        "should support empty events added by a resolver in a block quote in mdast"
+    );
+
     Ok(())
 }
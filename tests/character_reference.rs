@@ -2,7 +2,7 @@ use markdown::{
     mdast::{Node, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    CompileOptions, Constructs, Options, ParseOptions,
+    CharacterReferences, CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -28,6 +28,36 @@ fn character_reference() -> Result<(), message::Message> {
         "should support hexadecimal character references"
     );
 
+    assert_eq!(
+        to_html("&#0; &#x0; &#0000000;"),
+        "<p>\u{FFFD} \u{FFFD} \u{FFFD}</p>",
+        "should replace a zero character reference"
+    );
+
+    assert_eq!(
+        to_html("&#xD800; &#xDFFF; &#55296;"),
+        "<p>\u{FFFD} \u{FFFD} \u{FFFD}</p>",
+        "should replace surrogate character references"
+    );
+
+    assert_eq!(
+        to_html("&#x10FFFF; &#x110000; &#1114112; &#9999999;"),
+        "<p>\u{10FFFF} \u{FFFD} \u{FFFD} \u{FFFD}</p>",
+        "should replace character references above U+10FFFF"
+    );
+
+    assert_eq!(
+        to_html("&#x80; &#128; &#x99; &#x9F;"),
+        "<p>€ € ™ Ÿ</p>",
+        "should map C1 character references to Windows-1252"
+    );
+
+    assert_eq!(
+        to_html("&#x81; &#x8D; &#x9D; &#x7F;"),
+        "<p>\u{FFFD} \u{FFFD} \u{FFFD} \u{FFFD}</p>",
+        "should replace C1 character references unassigned in Windows-1252"
+    );
+
     assert_eq!(
       to_html(
         "&nbsp &x; &#; &#x;\n&#987654321;\n&#abcdef0;\n&ThisIsNotDefined; &hi?;"),
@@ -214,6 +244,90 @@ fn character_reference() -> Result<(), message::Message> {
         "should support turning off character references"
     );
 
+    assert_eq!(
+        to_html_with_options(
+            "&copy; &amp; &#169;",
+            &Options {
+                parse: ParseOptions {
+                    character_references: CharacterReferences::Html5,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>© &amp; ©</p>",
+        "should support named character references from HTML 5 w/ `CharacterReferences::Html5`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "&copy; &amp; &apos; &gt; &lt; &quot; &#169;",
+            &Options {
+                parse: ParseOptions {
+                    character_references: CharacterReferences::XmlMinimal,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>&amp;copy; &amp; ' &gt; &lt; &quot; ©</p>",
+        "should only support the XML named character references w/ `CharacterReferences::XmlMinimal`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "&shrug; &copy; &amp;",
+            &Options {
+                parse: ParseOptions {
+                    character_references: CharacterReferences::Custom(vec![(
+                        "shrug".into(),
+                        "¯\\_(ツ)_/¯".into()
+                    )]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>¯\\_(ツ)_/¯ &amp;copy; &amp;amp;</p>",
+        "should only support custom named character references w/ `CharacterReferences::Custom`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](&copy;&amp;)",
+            &Options {
+                parse: ParseOptions {
+                    character_references: CharacterReferences::XmlMinimal,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"&amp;copy;&amp;\">a</a></p>",
+        "should use the supported named character references in strings"
+    );
+
+    assert_eq!(
+        to_mdast(
+            "&copy; &amp;",
+            &ParseOptions {
+                character_references: CharacterReferences::XmlMinimal,
+                ..Default::default()
+            }
+        )?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "&copy; &".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 13, 12))
+                }),],
+                position: Some(Position::new(1, 1, 0, 1, 13, 12))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 13, 12))
+        }),
+        "should keep unsupported named character references as text in mdast"
+    );
+
     assert_eq!(
         to_mdast("&nbsp; &amp; &copy; &AElig; &Dcaron;\n&frac34; &HilbertSpace; &DifferentialD;\n&ClockwiseContourIntegral; &ngE;\n&#35; &#1234; &#992; &#0;\n&#X22; &#XD06; &#xcab;", &Default::default())?,
         Node::Root(Root {
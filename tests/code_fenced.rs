@@ -2,9 +2,10 @@ use markdown::{
     mdast::{Code, Node, Root},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
+use std::rc::Rc;
 
 #[test]
 fn code_fenced() -> Result<(), message::Message> {
@@ -50,6 +51,18 @@ fn code_fenced() -> Result<(), message::Message> {
         "should support a closing sequence equal to, but not shorter than, the opening"
     );
 
+    assert_eq!(
+        to_html("````js\n```\na\n```\n````\nb"),
+        "<pre><code class=\"language-js\">```\na\n```\n</code></pre>\n<p>b</p>",
+        "should support shorter sequences as content in fenced code w/ four grave accents"
+    );
+
+    assert_eq!(
+        to_html("````\na\n`````\nb"),
+        "<pre><code>a\n</code></pre>\n<p>b</p>",
+        "should close fenced code w/ four grave accents w/ a longer closing sequence"
+    );
+
     assert_eq!(
         to_html("```"),
         "<pre><code></code></pre>\n",
@@ -74,6 +87,42 @@ fn code_fenced() -> Result<(), message::Message> {
         "should support blank lines in fenced code"
     );
 
+    assert_eq!(
+        to_html("```\na\n\nb\n```"),
+        "<pre><code>a\n\nb\n</code></pre>",
+        "should support an empty line between lines in fenced code"
+    );
+
+    assert_eq!(
+        to_html("```\na\n\n\n\nb\n```\nc"),
+        "<pre><code>a\n\n\n\nb\n</code></pre>\n<p>c</p>",
+        "should support several empty lines between lines in fenced code"
+    );
+
+    assert_eq!(
+        to_html("```\r\na\r\n\r\nb\r\n```"),
+        "<pre><code>a\r\n\r\nb\r\n</code></pre>",
+        "should support an empty line between lines in fenced code w/ CRLF"
+    );
+
+    assert_eq!(
+        to_html("```\na\n\n"),
+        "<pre><code>a\n\n</code></pre>\n",
+        "should support empty lines at the end of unclosed fenced code"
+    );
+
+    assert_eq!(
+        to_html("- ```\n  a\n\n\n  b\n  ```\n- c"),
+        "<ul>\n<li>\n<pre><code>a\n\n\nb\n</code></pre>\n</li>\n<li>c</li>\n</ul>",
+        "should support empty lines in fenced code in a list item"
+    );
+
+    assert_eq!(
+        to_html("> ```\n> a\n>\n> b\n> ```"),
+        "<blockquote>\n<pre><code>a\n\nb\n</code></pre>\n</blockquote>",
+        "should support empty lines in fenced code in a block quote"
+    );
+
     assert_eq!(
         to_html("```\n```"),
         "<pre><code></code></pre>",
@@ -164,6 +213,94 @@ fn code_fenced() -> Result<(), message::Message> {
         "should support the info string as a `language-` class, but not the meta string"
     );
 
+    assert_eq!(
+        to_html("```rust,ignore\na\n```\n```rust ignore\nb\n```"),
+        "<pre><code class=\"language-rust,ignore\">a\n</code></pre>\n<pre><code class=\"language-rust\">b\n</code></pre>",
+        "should use the first word of the info string as the class"
+    );
+
+    assert_eq!(
+        to_html("```a&amp;\"<b\n```"),
+        "<pre><code class=\"language-a&amp;&quot;&lt;b\"></code></pre>",
+        "should encode the info string"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```rust ignore\na\n```",
+            &Options {
+                compile: CompileOptions {
+                    code_block_class_prefix: Some("lang-".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<pre><code class=\"lang-rust\">a\n</code></pre>",
+        "should support `code_block_class_prefix`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```rust\na\n```",
+            &Options {
+                compile: CompileOptions {
+                    code_block_class_prefix: Some(String::new()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<pre><code class=\"rust\">a\n</code></pre>",
+        "should support an empty `code_block_class_prefix`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```\na\n```\n\n~~~ \nb\n~~~",
+            &Options {
+                compile: CompileOptions {
+                    default_code_language: Some("text".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<pre><code class=\"language-text\">a\n</code></pre>\n<pre><code class=\"language-text\">b\n</code></pre>",
+        "should support `default_code_language` for code w/o info"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```rust ignore\na\n```\n\n    b\n\n```",
+            &Options {
+                compile: CompileOptions {
+                    default_code_language: Some("text".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<pre><code class=\"language-rust\">a\n</code></pre>\n<pre><code>b\n</code></pre>\n<pre><code class=\"language-text\"></code></pre>\n",
+        "should not use `default_code_language` for code w/ info, or indented code"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```\na\n```",
+            &Options {
+                compile: CompileOptions {
+                    code_block_class_prefix: Some("lang-".into()),
+                    default_code_language: Some("<b>".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<pre><code class=\"lang-&lt;b&gt;\">a\n</code></pre>",
+        "should encode `default_code_language`, and use `code_block_class_prefix`"
+    );
+
     assert_eq!(
         to_html("``` aa ```\nfoo"),
         "<p><code>aa</code>\nfoo</p>",
@@ -310,6 +447,20 @@ fn code_fenced() -> Result<(), message::Message> {
         "should support code (fenced) w/o closing fence in mdast"
     );
 
+    assert_eq!(
+        to_mdast("```\na\n\nb\n```", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Code(Code {
+                lang: None,
+                meta: None,
+                value: "a\n\nb".into(),
+                position: Some(Position::new(1, 1, 0, 5, 4, 12))
+            })],
+            position: Some(Position::new(1, 1, 0, 5, 4, 12))
+        }),
+        "should support empty lines in code (fenced) in mdast"
+    );
+
     assert_eq!(
         to_mdast("```\rasd\r```", &Default::default())?,
         Node::Root(Root {
@@ -340,3 +491,301 @@ fn code_fenced() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn code_fenced_attributes() -> Result<(), message::Message> {
+    let attributes = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                attributes: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("~~~js {#a .b}\nc\n~~~"),
+        "<pre><code class=\"language-js\">c\n</code></pre>",
+        "should not support attributes by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("~~~js {#a .b c=d}\ne\n~~~", &attributes)?,
+        "<pre><code id=\"a\" class=\"language-js b\" c=\"d\">e\n</code></pre>",
+        "should support attributes in the meta"
+    );
+
+    assert_eq!(
+        to_html_with_options("~~~ {#a .b}\nc\n~~~", &attributes)?,
+        "<pre><code id=\"a\" class=\"b\">c\n</code></pre>",
+        "should support attributes as the info and meta"
+    );
+
+    assert_eq!(
+        to_html_with_options("~~~js a {#b}\nc\n~~~", &attributes)?,
+        "<pre><code class=\"language-js\">c\n</code></pre>",
+        "should not support attributes after other meta"
+    );
+
+    assert_eq!(
+        to_html_with_options("~~~js {onclick=a style=b}\nc\n~~~", &attributes)?,
+        "<pre><code class=\"language-js\" style=\"b\">c\n</code></pre>",
+        "should drop unsafe attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "~~~ {#a}\nb\n~~~",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        attributes: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    default_code_language: Some("c".into()),
+                    ..Default::default()
+                },
+            }
+        )?,
+        "<pre><code id=\"a\" class=\"language-c\">b\n</code></pre>",
+        "should support `default_code_language` w/ attributes as the info"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn code_fenced_meta() -> Result<(), message::Message> {
+    let meta = Options {
+        compile: CompileOptions {
+            code_meta_attribute: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("```js {1,3}\na\n```"),
+        "<pre><code class=\"language-js\">a\n</code></pre>",
+        "should not add the meta by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js {1,3}\na\n```", &meta)?,
+        "<pre><code class=\"language-js\" data-meta=\"{1,3}\">a\n</code></pre>",
+        "should add the meta separately from the info w/ `code_meta_attribute`"
+    );
+
+    assert_eq!(
+        to_html_with_options("~~~js  highlight=\"1,3\" b\n~~~", &meta)?,
+        "<pre><code class=\"language-js\" data-meta=\"highlight=&quot;1,3&quot; b\"></code></pre>",
+        "should encode the meta"
+    );
+
+    assert_eq!(
+        to_html_with_options("~~~js a\\*b&amp;c&lt;\n~~~", &meta)?,
+        "<pre><code class=\"language-js\" data-meta=\"a*b&amp;c&lt;\"></code></pre>",
+        "should support character escapes and character references in the meta"
+    );
+
+    assert_eq!(
+        to_html_with_options("~~~js\n~~~\n\n~~~\n~~~", &meta)?,
+        "<pre><code class=\"language-js\"></code></pre>\n<pre><code></code></pre>",
+        "should not add the meta w/o meta"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "~~~js {#a}\n~~~\n\n~~~js b\n~~~",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        attributes: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    code_meta_attribute: true,
+                    ..Default::default()
+                },
+            }
+        )?,
+        "<pre><code id=\"a\" class=\"language-js\"></code></pre>\n<pre><code class=\"language-js\" data-meta=\"b\"></code></pre>",
+        "should not add the meta if it is an attribute list"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn code_fenced_line_numbers() -> Result<(), message::Message> {
+    let line_numbers = Options {
+        compile: CompileOptions {
+            code_line_numbers: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("```\na\n```"),
+        "<pre><code>a\n</code></pre>",
+        "should not wrap lines by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na\nb\nc\n```", &line_numbers)?
+            .matches("<span class=\"line\">")
+            .count(),
+        3,
+        "should wrap each line of a three-line block w/ `code_line_numbers`"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js\n<a>\n\n&\n```", &line_numbers)?,
+        "<pre><code class=\"language-js\"><span class=\"line\">&lt;a&gt;</span>\n<span class=\"line\"></span>\n<span class=\"line\">&amp;</span>\n</code></pre>",
+        "should keep encoding and empty lines w/ `code_line_numbers`"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na\r\nb\r\n```", &line_numbers)?,
+        "<pre><code><span class=\"line\">a</span>\r\n<span class=\"line\">b</span>\r\n</code></pre>",
+        "should keep line endings outside lines w/ `code_line_numbers`"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\n```\n\n```\na", &line_numbers)?,
+        "<pre><code></code></pre>\n<pre><code><span class=\"line\">a</span>\n</code></pre>\n",
+        "should not add lines to empty code, or for the final line ending, w/ `code_line_numbers`"
+    );
+
+    assert_eq!(
+        to_html_with_options("> ```\n> a\nb", &line_numbers)?,
+        "<blockquote>\n<pre><code><span class=\"line\">a</span>\n</code></pre>\n</blockquote>\n<p>b</p>",
+        "should support code in containers w/ `code_line_numbers`"
+    );
+
+    assert_eq!(
+        to_html_with_options("    a\n\n    b", &line_numbers)?,
+        "<pre><code><span class=\"line\">a</span>\n<span class=\"line\"></span>\n<span class=\"line\">b</span>\n</code></pre>",
+        "should support code (indented) w/ `code_line_numbers`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "$$\na\n$$",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        math_flow: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    code_line_numbers: true,
+                    ..Default::default()
+                },
+            }
+        )?,
+        "<pre><code class=\"language-math math-display\">a\n</code></pre>",
+        "should not wrap lines of math (flow) w/ `code_line_numbers`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn code_fenced_highlighter() -> Result<(), message::Message> {
+    let highlight = Options {
+        compile: CompileOptions {
+            code_highlighter: Some(Rc::new(|lang, code| {
+                format!("<x-highlight lang=\"{}\">{}</x-highlight>", lang, code)
+            })),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("```js\na\n```"),
+        "<pre><code class=\"language-js\">a\n</code></pre>",
+        "should not highlight code by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js\na\n```", &highlight)?,
+        "<pre><code class=\"language-js\"><x-highlight lang=\"js\">a\n</x-highlight></code></pre>",
+        "should use the highlighter for code (fenced)"
+    );
+
+    assert_eq!(
+        to_html_with_options("```a&amp;<b meta\n<c> &amp;\n\td\n```", &highlight)?,
+        "<pre><code class=\"language-a&amp;&lt;b\"><x-highlight lang=\"a&<b\"><c> &amp;\n\td\n</x-highlight></code></pre>",
+        "should pass the language and code as-is to the highlighter"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na", &highlight)?,
+        "<pre><code><x-highlight lang=\"\">a\n</x-highlight></code></pre>\n",
+        "should pass an empty language w/o info string"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```\na\n```",
+            &Options {
+                compile: CompileOptions {
+                    code_highlighter: Some(Rc::new(|lang, _code| lang.into())),
+                    default_code_language: Some("txt".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<pre><code class=\"language-txt\">txt</code></pre>",
+        "should pass `default_code_language` to the highlighter"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\n```", &highlight)?,
+        "<pre><code><x-highlight lang=\"\"></x-highlight></code></pre>",
+        "should use the highlighter for empty code"
+    );
+
+    assert_eq!(
+        to_html_with_options("> ```js\n> a\nb", &highlight)?,
+        "<blockquote>\n<pre><code class=\"language-js\"><x-highlight lang=\"js\">a\n</x-highlight></code></pre>\n</blockquote>\n<p>b</p>",
+        "should support code in containers w/ the highlighter"
+    );
+
+    assert_eq!(
+        to_html_with_options("    a", &highlight)?,
+        "<pre><code>a\n</code></pre>",
+        "should not use the highlighter for code (indented)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```js\na\nb\n```",
+            &Options {
+                compile: CompileOptions {
+                    code_highlighter: Some(Rc::new(|_lang, code| code.to_uppercase())),
+                    code_line_numbers: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<pre><code class=\"language-js\"><span class=\"line\">A</span>\n<span class=\"line\">B</span>\n</code></pre>",
+        "should support `code_line_numbers` w/ the highlighter"
+    );
+
+    Ok(())
+}
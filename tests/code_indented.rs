@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Code, Node, Root},
+    mdast::{Blockquote, Code, Node, Root},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
@@ -202,6 +202,134 @@ fn code_indented() -> Result<(), message::Message> {
         "should support turning off code (indented, 9)"
     );
 
+    assert_eq!(
+        to_html_with_options("a\n\n    b\n    c\n\n        d", &off)?,
+        "<p>a</p>\n<p>b\nc</p>\n<p>d</p>",
+        "should support turning off code (indented, 10)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "    <b>a</b> <https://b>\n\n<div>\nc\n</div>",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        autolink: false,
+                        code_indented: false,
+                        html_flow: false,
+                        html_text: false,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>&lt;b&gt;a&lt;/b&gt; &lt;https://b&gt;</p>\n<p>&lt;div&gt;\nc\n&lt;/div&gt;</p>",
+        "should support turning off code (indented) together with autolinks and HTML"
+    );
+
+    assert_eq!(
+        to_html("\tfoo\n\t\tbar"),
+        "<pre><code>foo\n\tbar\n</code></pre>",
+        "should strip a tab as the indent, and keep further tabs"
+    );
+
+    assert_eq!(
+        to_html("  \tfoo\n   \t\tbar"),
+        "<pre><code>foo\n\tbar\n</code></pre>",
+        "should strip spaces and a tab up to the next tab stop as the indent"
+    );
+
+    assert_eq!(
+        to_html(" \t\tfoo"),
+        "<pre><code>\tfoo\n</code></pre>",
+        "should keep a tab after the indent"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n\t\tfoo\n  \t  bar"),
+        "<ul>\n<li>\n<p>a</p>\n<pre><code>  foo\nbar\n</code></pre>\n</li>\n</ul>",
+        "should count tabs to the next tab stop in list items"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n      foo"),
+        "<ul>\n<li>\n<p>a</p>\n<pre><code>foo\n</code></pre>\n</li>\n</ul>",
+        "should support code (indented) in a list item, relative to its content"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n       foo\n      bar\n\n  b"),
+        "<ul>\n<li>\n<p>a</p>\n<pre><code> foo\nbar\n</code></pre>\n<p>b</p>\n</li>\n</ul>",
+        "should strip the indent of a list item and 4 spaces from code (indented) in it"
+    );
+
+    assert_eq!(
+        to_html("1.  a\n\n        foo"),
+        "<ol>\n<li>\n<p>a</p>\n<pre><code>foo\n</code></pre>\n</li>\n</ol>",
+        "should use the content indent of an ordered list item as the base"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n     foo"),
+        "<ul>\n<li>\n<p>a</p>\n<p>foo</p>\n</li>\n</ul>",
+        "should not support code (indented) in a list item w/o enough indent"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n\tfoo"),
+        "<ul>\n<li>\n<p>a</p>\n<p>foo</p>\n</li>\n</ul>",
+        "should not support code (indented) in a list item w/ the rest of a tab"
+    );
+
+    assert_eq!(
+        to_html(">     a\n>     b"),
+        "<blockquote>\n<pre><code>a\nb\n</code></pre>\n</blockquote>",
+        "should continue code (indented) in a block quote opened on its first line"
+    );
+
+    assert_eq!(
+        to_html(">\t\tfoo\n>\t\t\tbar"),
+        "<blockquote>\n<pre><code>  foo\n  \tbar\n</code></pre>\n</blockquote>",
+        "should keep the spaces of a tab that remain after the indent"
+    );
+
+    assert_eq!(
+        to_html("-\t\tfoo\n\t\tbar"),
+        "<ul>\n<li>\n<pre><code>  foo\n  bar\n</code></pre>\n</li>\n</ul>",
+        "should continue code (indented) in a list item opened on its first line"
+    );
+
+    assert_eq!(
+        to_html("    a\n>\n>     b"),
+        "<pre><code>a\n</code></pre>\n<blockquote>\n<pre><code>b\n</code></pre>\n</blockquote>",
+        "should close code (indented) before a block quote opened on an empty line"
+    );
+
+    assert_eq!(
+        to_html("    a\n-\n      b"),
+        "<pre><code>a\n</code></pre>\n<ul>\n<li>\n<pre><code>b\n</code></pre>\n</li>\n</ul>",
+        "should close code (indented) before a list item opened on an empty line"
+    );
+
+    assert_eq!(
+        to_mdast(">\t\tfoo\n>\t\t\tbar", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Blockquote(Blockquote {
+                children: vec![Node::Code(Code {
+                    lang: None,
+                    meta: None,
+                    value: "  foo\n  \tbar".into(),
+                    position: Some(Position::new(1, 3, 1, 2, 16, 14))
+                })],
+                position: Some(Position::new(1, 1, 0, 2, 16, 14))
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 16, 14))
+        }),
+        "should keep the spaces of a tab that remain after the indent in mdast"
+    );
+
     assert_eq!(
         to_mdast(
             "\tconsole.log(1)\n    console.log(2)\n",
@@ -47,6 +47,30 @@ fn code_text() -> Result<(), message::Message> {
         "should support code w/ unbalanced padding"
     );
 
+    assert_eq!(
+        to_html("`a `"),
+        "<p><code>a </code></p>",
+        "should support code w/ unbalanced padding (trailing)"
+    );
+
+    assert_eq!(
+        to_html("`` `foo` ``"),
+        "<p><code>`foo`</code></p>",
+        "should support code w/ padding around grave accents"
+    );
+
+    assert_eq!(
+        to_html("`  a  `"),
+        "<p><code> a </code></p>",
+        "should strip only one space from each side"
+    );
+
+    assert_eq!(
+        to_html("` \na\n `"),
+        "<p><code> a </code></p>",
+        "should strip after turning line endings into spaces"
+    );
+
     assert_eq!(
         to_html("`\u{a0}b\u{a0}`"),
         "<p><code>\u{a0}b\u{a0}</code></p>",
@@ -77,6 +101,24 @@ fn code_text() -> Result<(), message::Message> {
         "should not support whitespace collapsing"
     );
 
+    assert_eq!(
+        to_html("`a\r\nb`"),
+        "<p><code>a b</code></p>",
+        "should turn a CRLF into a space"
+    );
+
+    assert_eq!(
+        to_html("`a\rb`"),
+        "<p><code>a b</code></p>",
+        "should turn a CR into a space"
+    );
+
+    assert_eq!(
+        to_html("``\r\na\r\nb\r\n``"),
+        "<p><code>a b</code></p>",
+        "should strip after turning CRLFs into spaces"
+    );
+
     assert_eq!(
         to_html("`foo\\`bar`"),
         "<p><code>foo\\</code>bar`</p>",
@@ -205,6 +247,36 @@ fn code_text() -> Result<(), message::Message> {
         "should support code (text) as `InlineCode`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("`*a* [b](c)\n&amp;`", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::InlineCode(InlineCode {
+                    value: "*a* [b](c)\n&amp;".into(),
+                    position: Some(Position::new(1, 1, 0, 2, 7, 18))
+                })],
+                position: Some(Position::new(1, 1, 0, 2, 7, 18))
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 7, 18))
+        }),
+        "should not tokenize the contents of code (text), such as emphasis"
+    );
+
+    assert_eq!(
+        to_mdast("`a\r\nb`", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::InlineCode(InlineCode {
+                    value: "a\r\nb".into(),
+                    position: Some(Position::new(1, 1, 0, 2, 3, 6))
+                })],
+                position: Some(Position::new(1, 1, 0, 2, 3, 6))
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 3, 6))
+        }),
+        "should keep CRLFs in code (text) in mdast, like other line endings"
+    );
+
     assert_eq!(
         to_mdast("`  alpha `", &Default::default())?,
         Node::Root(Root {
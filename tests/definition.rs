@@ -65,6 +65,48 @@ fn definition() -> Result<(), message::Message> {
         "should not support blank lines in titles"
     );
 
+    assert_eq!(
+        to_html("[a]: /url\n\"multi\nline title\"\nb\n\n[a]"),
+        "<p>b</p>\n<p><a href=\"/url\" title=\"multi\nline title\">a</a></p>",
+        "should support line endings in titles on their own line, w/ a paragraph after"
+    );
+
+    assert_eq!(
+        to_html("[a]: /url (multi\nline)\n\n[a]"),
+        "<p><a href=\"/url\" title=\"multi\nline\">a</a></p>",
+        "should support line endings in paren enclosed titles"
+    );
+
+    assert_eq!(
+        to_html("[a]: /url (b (c)\nd)\n\n[a]"),
+        "<p><a href=\"/url\" title=\"b (c)\nd\">a</a></p>",
+        "should support balanced parens in paren enclosed titles"
+    );
+
+    assert_eq!(
+        to_html("[a]: /url (b (c)\n\n[a]"),
+        "<p>[a]: /url (b (c)</p>\n<p>[a]</p>",
+        "should not support unbalanced parens in paren enclosed titles"
+    );
+
+    assert_eq!(
+        to_html("[a]: /url (b \\(c)\n\n[a]"),
+        "<p><a href=\"/url\" title=\"b (c\">a</a></p>",
+        "should support escaped parens in paren enclosed titles"
+    );
+
+    assert_eq!(
+        to_html("[a]: /url \"b\n\nc\"\n\n[a]"),
+        "<p>[a]: /url &quot;b</p>\n<p>c&quot;</p>\n<p>[a]</p>",
+        "should not support blank lines in double quoted titles"
+    );
+
+    assert_eq!(
+        to_html("[a]: /url (b\n\nc)\n\n[a]"),
+        "<p>[a]: /url (b</p>\n<p>c)</p>\n<p>[a]</p>",
+        "should not support blank lines in paren enclosed titles"
+    );
+
     assert_eq!(
         to_html("[foo]:\n/url\n\n[foo]"),
         "<p><a href=\"/url\">foo</a></p>",
@@ -144,6 +186,30 @@ fn definition() -> Result<(), message::Message> {
         "should *not* match w/ dotted turkish i (2)"
     );
 
+    assert_eq!(
+        to_html("[Straße]: a\n\n[STRASSE] [strasse] [STRAẞE]"),
+        "<p><a href=\"a\">STRASSE</a> <a href=\"a\">strasse</a> <a href=\"a\">STRAẞE</a></p>",
+        "should match w/ unicode case folding (`ß`, `SS`, `ẞ`)"
+    );
+
+    assert_eq!(
+        to_html("[å]: a\n\n[Å]"),
+        "<p><a href=\"a\">Å</a></p>",
+        "should match w/ unicode case folding (`å`, `Å`)"
+    );
+
+    assert_eq!(
+        to_html("[a \t\n b]: c\n\n[A\t B] [ a\n\nb ]"),
+        "<p><a href=\"c\">A\t B</a> [ a</p>\n<p>b ]</p>",
+        "should match w/ collapsed spaces, tabs, and line endings in labels"
+    );
+
+    assert_eq!(
+        to_html("[Å  b]: c\n\n[å\nB][] [x][  Å\tB ]"),
+        "<p><a href=\"c\">å\nB</a> <a href=\"c\">x</a></p>",
+        "should match w/ case folding and collapsed whitespace in collapsed and full references"
+    );
+
     assert_eq!(
         to_html("[foo]: /url"),
         "",
@@ -374,6 +440,12 @@ fn definition() -> Result<(), message::Message> {
         "should support whitespace before a destination"
     );
 
+    assert_eq!(
+        to_html("[ab] [a  b] [ a\tb ]\n\n[a b]: c"),
+        "<p>[ab] <a href=\"c\">a  b</a> <a href=\"c\"> a\tb </a></p>\n",
+        "should collapse whitespace in identifiers, but not drop it"
+    );
+
     // See: <https://github.com/commonmark/commonmark.js/issues/192>
     assert_eq!(
         to_html("[x]: <> \"\"\n[][x]"),
@@ -507,6 +579,42 @@ fn definition() -> Result<(), message::Message> {
         "should support turning off definitions"
     );
 
+    assert_eq!(
+        to_html_with_options("[foo]: /url \"title\"\n\n[foo]", &Options::default())?,
+        "<p><a href=\"/url\" title=\"title\">foo</a></p>",
+        "should not show definitions by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[Foo]: /url \"title\"\n\n[foo]",
+            &Options {
+                compile: CompileOptions {
+                    debug_definitions: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<!-- definition: Foo -> /url -->\n<p><a href=\"/url\" title=\"title\">foo</a></p>",
+        "should show definitions w/ `debug_definitions`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "> [a\\>]: b&gt;-->c\n> d",
+            &Options {
+                compile: CompileOptions {
+                    debug_definitions: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<blockquote>\n<!-- definition: a\\&gt; -> b&gt;--&gt;c -->\n<p>d</p>\n</blockquote>",
+        "should encode definitions w/ `debug_definitions`"
+    );
+
     assert_eq!(
         to_mdast("[a]: <b> 'c'", &Default::default())?,
         Node::Root(Root {
@@ -0,0 +1,214 @@
+use markdown::{
+    mdast::{ContainerDirective, Emphasis, Node, Paragraph, Root, Text, TextDirective},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn directive() -> Result<(), message::Message> {
+    let directive = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                directive_container: true,
+                directive_leaf: true,
+                directive_text: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html(":::note\na\n:::"),
+        "<p>:::note\na\n:::</p>",
+        "should not support directives by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b[c]{d} e", &directive)?,
+        "<p>a  e</p>",
+        "should drop text directives"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a[b]{c}\nd", &directive)?,
+        "<p>d</p>",
+        "should drop leaf directives"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::note[a]\n# b\n\n* c\n:::\nd", &directive)?,
+        "<p>d</p>",
+        "should drop container directives, including their content"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n:::b\nc\n:::\nd", &directive)?,
+        "<p>a</p>\n<p>d</p>",
+        "should support container directives interrupting paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::::a\n:::b\nc\n:::\n:::::\nd", &directive)?,
+        "<p>d</p>",
+        "should support nested container directives with longer fences"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\n:::\nb\nc", &directive)?,
+        "<p>b\nc</p>",
+        "should keep line endings in a paragraph after a container directive"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::::a\n:::b\nc\n:::\n:::::\nd\ne", &directive)?,
+        "<p>d\ne</p>",
+        "should keep line endings in a paragraph after nested container directives"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\nb\n:::\n:::\nc", &directive)?,
+        "<p>:::\nc</p>",
+        "should keep line endings in a paragraph after a container directive, w/o name"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\nb", &directive)?,
+        "",
+        "should support container directives without closing fence"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::\na\n:::", &directive)?,
+        "<p>:::\na\n:::</p>",
+        "should not support container directives without name"
+    );
+
+    assert_eq!(
+        to_html_with_options("    a\n:::b\n    c\n:::\n", &directive)?,
+        "<pre><code>a\n</code></pre>\n",
+        "should close indented code before a container directive"
+    );
+
+    assert_eq!(
+        to_html_with_options("    a\n:::b\n\tc\n:::\n", &directive)?,
+        "<pre><code>a\n</code></pre>\n",
+        "should close indented code before a container directive (tab)"
+    );
+
+    assert_eq!(
+        to_mdast("    a\n:::b\n    c\n:::\n", &directive.parse)?
+            .children()
+            .map(Vec::len),
+        Some(2),
+        "should close indented code before a container directive in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("    a\n:::b\n\tc\n:::\n", &directive.parse)?
+            .children()
+            .map(Vec::len),
+        Some(2),
+        "should close indented code before a container directive in mdast (tab)"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a b", &directive)?,
+        "<p>::a b</p>",
+        "should not support leaf directives followed by other content"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ::b c", &directive)?,
+        "<p>a ::b c</p>",
+        "should not support text directives after a colon"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :1 b", &directive)?,
+        "<p>a :1 b</p>",
+        "should not support text directives with a name not starting with a letter"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b- c", &directive)?,
+        "<p>a :b- c</p>",
+        "should not support text directives with a name ending in a dash"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b[c\nd] e", &directive)?,
+        "<p>a [c\nd] e</p>",
+        "should not support line endings in labels"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b{c\nd} e", &directive)?,
+        "<p>a {c\nd} e</p>",
+        "should not support line endings in attributes"
+    );
+
+    assert_eq!(
+        to_mdast("a :b[c]{#d .e .f g=\"h\" i=j k #l} m", &directive.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::TextDirective(TextDirective {
+                        name: "b".into(),
+                        attributes: vec![
+                            ("class".into(), "e f".into()),
+                            ("g".into(), "h".into()),
+                            ("i".into(), "j".into()),
+                            ("k".into(), String::new()),
+                            ("id".into(), "l".into()),
+                        ],
+                        children: vec![Node::Text(Text {
+                            value: "c".into(),
+                            position: Some(Position::new(1, 6, 5, 1, 7, 6))
+                        })],
+                        position: Some(Position::new(1, 3, 2, 1, 33, 32))
+                    }),
+                    Node::Text(Text {
+                        value: " m".into(),
+                        position: Some(Position::new(1, 33, 32, 1, 35, 34))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 35, 34))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 35, 34))
+        }),
+        "should support a text directive in a paragraph as `TextDirective`s in mdast"
+    );
+
+    assert_eq!(
+        to_mdast(":::note{.warning}\n*a*\n:::", &directive.parse)?,
+        Node::Root(Root {
+            children: vec![Node::ContainerDirective(ContainerDirective {
+                name: "note".into(),
+                attributes: vec![("class".into(), "warning".into())],
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Emphasis(Emphasis {
+                        children: vec![Node::Text(Text {
+                            value: "a".into(),
+                            position: Some(Position::new(2, 2, 19, 2, 3, 20))
+                        })],
+                        position: Some(Position::new(2, 1, 18, 2, 4, 21))
+                    })],
+                    position: Some(Position::new(2, 1, 18, 2, 4, 21))
+                })],
+                position: Some(Position::new(1, 1, 0, 3, 4, 25))
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 4, 25))
+        }),
+        "should support container directives with nested markdown as `ContainerDirective`s in mdast"
+    );
+
+    Ok(())
+}
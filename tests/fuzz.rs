@@ -129,5 +129,39 @@ fn fuzz() -> Result<(), message::Message> {
         "12: mdx: handle invalid mdx without panic (GH-26)"
     );
 
+    let everything = Options {
+        parse: markdown::ParseOptions {
+            constructs: markdown::Constructs {
+                directive_container: true,
+                directive_leaf: true,
+                directive_text: true,
+                ..markdown::Constructs::gfm()
+            },
+            smart_punctuation: true,
+            ..markdown::ParseOptions::gfm()
+        },
+        ..Default::default()
+    };
+
+    for input in [
+        ":a[",
+        ":a{b=\"",
+        "> :a[b]{c\n> d}",
+        "::a[b\n",
+        "- :::a\n  b\n:::",
+        ":::a\n> :::\n",
+        "é\"a\"é",
+        "\"\n\"",
+        "*\"*\"",
+        "a--\n--b...\n...",
+    ] {
+        assert!(
+            to_html_with_options(input, &everything).is_ok()
+                && to_mdast(input, &everything.parse).is_ok(),
+            "13: directives, smart punctuation: handle unclosed and odd input without panic ({:?})",
+            input
+        );
+    }
+
     Ok(())
 }
@@ -161,6 +161,40 @@ fn gfm_footnote() -> Result<(), message::Message> {
         "should supports multiple calls to the same definition"
     );
 
+    assert_eq!(
+        to_html_with_options("a[^2] b[^1]\n\n[^1]: c\n\n[^2]: d", &Options::gfm())?,
+        "<p>a<sup><a href=\"#user-content-fn-2\" id=\"user-content-fnref-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> b<sup><a href=\"#user-content-fn-1\" id=\"user-content-fnref-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-2\">
+<p>d <a href=\"#user-content-fnref-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+<li id=\"user-content-fn-1\">
+<p>c <a href=\"#user-content-fnref-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should number footnotes in the order they are first called, not defined"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^1]\n\n[^1]: b[^2]\n\n[^2]: c", &Options::gfm())?,
+        "<p>a<sup><a href=\"#user-content-fn-1\" id=\"user-content-fnref-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-1\">
+<p>b<sup><a href=\"#user-content-fn-2\" id=\"user-content-fnref-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup> <a href=\"#user-content-fnref-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+<li id=\"user-content-fn-2\">
+<p>c <a href=\"#user-content-fnref-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support a call in a definition"
+    );
+
     assert_eq!(
         to_html_with_options("![^a](b)", &Options::gfm())?,
         "<p>!<a href=\"b\">^a</a></p>",
@@ -2,7 +2,7 @@ use markdown::{
     mdast::{AlignKind, InlineCode, Node, Root, Table, TableCell, TableRow, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    CompileOptions, Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, GfmTableCellAlign, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -1837,6 +1837,27 @@ normal escape: <a href="https://github.com/github/cmark-gfm/issues/277">https://
         "should match loose escapes like GitHub"
     );
 
+    assert_eq!(
+        to_html_with_options("| a | b |\n| - | :-: |\n| c | d |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n<th align=\"center\">b</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>c</td>\n<td align=\"center\">d</td>\n</tr>\n</tbody>\n</table>",
+        "should use `align` attributes for alignment by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "| a | b |\n| - | :-: |\n| c |",
+            &Options {
+                parse: ParseOptions::gfm(),
+                compile: CompileOptions {
+                    gfm_table_cell_align: GfmTableCellAlign::Style,
+                    ..CompileOptions::gfm()
+                }
+            }
+        )?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n<th style=\"text-align:center\">b</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>c</td>\n<td style=\"text-align:center\"></td>\n</tr>\n</tbody>\n</table>",
+        "should support `style` attributes for alignment w/ `gfm_table_cell_align: Style`"
+    );
+
     assert_eq!(
         to_mdast(
             "| none | left | right | center |\n| - | :- | -: | :-: |\n| a |\n| b | c | d | e | f |",
@@ -1966,3 +1987,76 @@ normal escape: <a href="https://github.com/github/cmark-gfm/issues/277">https://
 
     Ok(())
 }
+
+#[test]
+fn gfm_table_pipes_in_code() -> Result<(), message::Message> {
+    let pipes = Options {
+        parse: ParseOptions {
+            gfm_table_pipes_in_code: true,
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| b\\|c |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>b|c</td>\n</tr>\n</tbody>\n</table>",
+        "should not split cells at escaped pipes"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| `b\\|c` |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td><code>b|c</code></td>\n</tr>\n</tbody>\n</table>",
+        "should not split cells at escaped pipes in code"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| `b|c` |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>`b</td>\n</tr>\n</tbody>\n</table>",
+        "should split cells at pipes in code by default (like GitHub)"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| `b|c` |", &pipes)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td><code>b|c</code></td>\n</tr>\n</tbody>\n</table>",
+        "should not split cells at pipes in code w/ `gfm_table_pipes_in_code`"
+    );
+
+    assert_eq!(
+        to_html_with_options("| `a|b` | c |\n| - | - |", &pipes)?,
+        "<table>\n<thead>\n<tr>\n<th><code>a|b</code></th>\n<th>c</th>\n</tr>\n</thead>\n</table>",
+        "should not split head cells at pipes in code w/ `gfm_table_pipes_in_code`"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| ``b|`c`` |", &pipes)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td><code>b|`c</code></td>\n</tr>\n</tbody>\n</table>",
+        "should support code w/ several grave accents w/ `gfm_table_pipes_in_code`"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| `b|c |", &pipes)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>`b</td>\n</tr>\n</tbody>\n</table>",
+        "should split cells at pipes after unclosed code w/ `gfm_table_pipes_in_code`"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| ``b|c` |", &pipes)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>``b</td>\n</tr>\n</tbody>\n</table>",
+        "should split cells at pipes after code w/ a different closing sequence w/ `gfm_table_pipes_in_code`"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a | b |\n| - | - |\n| \\`c|d` |", &pipes)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n<th>b</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>`c</td>\n<td>d`</td>\n</tr>\n</tbody>\n</table>",
+        "should split cells at pipes after escaped grave accents w/ `gfm_table_pipes_in_code`"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| `b\\|c` |", &pipes)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td><code>b|c</code></td>\n</tr>\n</tbody>\n</table>",
+        "should still decode escaped pipes in code w/ `gfm_table_pipes_in_code`"
+    );
+
+    Ok(())
+}
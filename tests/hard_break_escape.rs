@@ -44,6 +44,36 @@ fn hard_break_escape() -> Result<(), message::Message> {
         "should not support escape hard breaks at the end of a heading"
     );
 
+    assert_eq!(
+        to_html("a\\\n"),
+        "<p>a\\</p>\n",
+        "should not support escape hard breaks at the end of a paragraph, before a final line ending"
+    );
+
+    assert_eq!(
+        to_html("a\\\n\nb"),
+        "<p>a\\</p>\n<p>b</p>",
+        "should not support escape hard breaks at the end of a paragraph, before a blank line"
+    );
+
+    assert_eq!(
+        to_html("a\\\n  \nb"),
+        "<p>a\\</p>\n<p>b</p>",
+        "should not support escape hard breaks at the end of a paragraph, before a whitespace-only line"
+    );
+
+    assert_eq!(
+        to_html("a\\\r\n\r\nb"),
+        "<p>a\\</p>\r\n<p>b</p>",
+        "should not support escape hard breaks at the end of a paragraph, before a CRLF blank line"
+    );
+
+    assert_eq!(
+        to_html("> a\\\n\n- b\\"),
+        "<blockquote>\n<p>a\\</p>\n</blockquote>\n<ul>\n<li>b\\</li>\n</ul>",
+        "should not support escape hard breaks at the end of a paragraph in containers"
+    );
+
     assert_eq!(
         to_html_with_options(
             "a\\\nb",
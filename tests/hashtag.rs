@@ -0,0 +1,111 @@
+use markdown::{
+    mdast::{Heading, Node, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn hashtag() -> Result<(), message::Message> {
+    let hashtag = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                hashtag: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        compile: CompileOptions {
+            hashtag_url: Some("/t/{tag}".into()),
+            ..CompileOptions::gfm()
+        },
+    };
+
+    assert_eq!(
+        to_html("#tag"),
+        "<p>#tag</p>",
+        "should not support hashtags by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a #tag b", &hashtag)?,
+        "<p>a <a href=\"/t/tag\">#tag</a> b</p>",
+        "should support hashtags"
+    );
+
+    assert_eq!(
+        to_html_with_options("#tag.", &hashtag)?,
+        "<p><a href=\"/t/tag\">#tag</a>.</p>",
+        "should not include a trailing dot"
+    );
+
+    assert_eq!(
+        to_html_with_options("#a-b_c #d- #e_", &hashtag)?,
+        "<p><a href=\"/t/a-b_c\">#a-b_c</a> <a href=\"/t/d\">#d</a>- <a href=\"/t/e\">#e</a>_</p>",
+        "should support dashes and underscores in tags, but not at their end"
+    );
+
+    assert_eq!(
+        to_html_with_options("#1 #1a", &hashtag)?,
+        "<p>#1 <a href=\"/t/1a\">#1a</a></p>",
+        "should not support tags of only digits"
+    );
+
+    assert_eq!(
+        to_html_with_options("#café", &hashtag)?,
+        "<p><a href=\"/t/caf%C3%A9\">#café</a></p>",
+        "should support non-ASCII letters in tags"
+    );
+
+    assert_eq!(
+        to_html_with_options("a#b ##c #d#e", &hashtag)?,
+        "<p>a#b ##c #d#e</p>",
+        "should not support hashtags next to alphanumericals or other hashes"
+    );
+
+    assert_eq!(
+        to_html_with_options("&#35;a &#x; \\#b", &hashtag)?,
+        "<p>#a &amp;#x; #b</p>",
+        "should not support hashtags in character references or escapes"
+    );
+
+    assert_eq!(
+        to_html_with_options("`#a` www.b.com/#c [d #e](f)", &hashtag)?,
+        "<p><code>#a</code> <a href=\"http://www.b.com/#c\">www.b.com/#c</a> <a href=\"f\">d #e</a></p>",
+        "should not link hashtags in code, autolinks, or links"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "#tag",
+            &Options::builder()
+                .constructs(Constructs {
+                    hashtag: true,
+                    ..Default::default()
+                })
+                .hashtag_url("/search?q=%23{tag}")
+                .build()
+        )?,
+        "<p><a href=\"/search?q=%23tag\">#tag</a></p>",
+        "should support `hashtag_url` w/ the builder"
+    );
+
+    assert_eq!(
+        to_mdast("# a #b", &hashtag.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Heading(Heading {
+                depth: 1,
+                children: vec![Node::Text(Text {
+                    value: "a #b".into(),
+                    position: Some(Position::new(1, 3, 2, 1, 7, 6))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 7, 6))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 7, 6))
+        }),
+        "should support hashtags as text in mdast"
+    );
+
+    Ok(())
+}
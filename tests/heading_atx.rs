@@ -2,7 +2,7 @@ use markdown::{
     mdast::{Heading, Node, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -242,3 +242,152 @@ fn heading_atx() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn heading_atx_attributes() -> Result<(), message::Message> {
+    let attributes = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                attributes: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("# Title {#t .big}"),
+        "<h1>Title {#t .big}</h1>",
+        "should not support attributes by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("# Title {#t .big}", &attributes)?,
+        "<h1 id=\"t\" class=\"big\">Title</h1>",
+        "should support attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("## a {.b .c d=e f=\"g h\" i='j'} ##", &attributes)?,
+        "<h2 class=\"b c\" d=\"e\" f=\"g h\" i=\"j\">a</h2>",
+        "should support attributes before a closing sequence"
+    );
+
+    assert_eq!(
+        to_html_with_options("# {#a}", &attributes)?,
+        "<h1 id=\"a\"></h1>",
+        "should support attributes w/o text"
+    );
+
+    assert_eq!(
+        to_html_with_options("# *a* `b` {.c}", &attributes)?,
+        "<h1 class=\"c\"><em>a</em> <code>b</code></h1>",
+        "should support attributes after other text"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a{#b}", &attributes)?,
+        "<h1>a{#b}</h1>",
+        "should not support attributes w/o whitespace before them"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a \\{#b}", &attributes)?,
+        "<h1>a {#b}</h1>",
+        "should not support attributes w/ an escaped brace"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a {#b} c", &attributes)?,
+        "<h1>a {#b} c</h1>",
+        "should not support attributes w/ text after them"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a {b} {}", &attributes)?,
+        "<h1>a {b} {}</h1>",
+        "should not support invalid or empty attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a {.b} {.c}", &attributes)?,
+        "<h1 class=\"c\">a {.b}</h1>",
+        "should only support the last attribute list"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a {title=\"<&>\"}", &attributes)?,
+        "<h1 title=\"&lt;&amp;&gt;\">a</h1>",
+        "should encode attribute values"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# a {onclick=\"b()\" style=\"c:url(d)\" style=\"e:f\"}",
+            &attributes
+        )?,
+        "<h1 style=\"e:f\">a</h1>",
+        "should drop unsafe attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# a {onclick=\"b()\"}",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        attributes: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..Default::default()
+                },
+            }
+        )?,
+        "<h1 onclick=\"b()\">a</h1>",
+        "should keep unsafe attributes w/ `allow_dangerous_html`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# a b {#c}\n# d e",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        attributes: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    heading_ids: true,
+                    ..Default::default()
+                },
+            }
+        )?,
+        "<h1 id=\"c\">a b</h1>\n<h1 id=\"d-e\">d e</h1>",
+        "should prefer an `id` in attributes over `heading_ids`"
+    );
+
+    assert_eq!(
+        to_mdast("# a {#b}", &attributes.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Heading(Heading {
+                depth: 1,
+                children: vec![Node::Text(Text {
+                    value: "a".into(),
+                    position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                }),],
+                position: Some(Position::new(1, 1, 0, 1, 9, 8))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 9, 8))
+        }),
+        "should not include attributes in mdast"
+    );
+
+    Ok(())
+}
@@ -26,6 +26,42 @@ fn heading_setext() -> Result<(), message::Message> {
         "should support line endings in setext headings"
     );
 
+    assert_eq!(
+        to_html("- a\n-"),
+        "<ul>\n<li>a</li>\n<li></li>\n</ul>",
+        "should not form a setext heading w/ a lazy `-`, but start a list item"
+    );
+
+    assert_eq!(
+        to_html("- a\n  b\n-"),
+        "<ul>\n<li>a\nb</li>\n<li></li>\n</ul>",
+        "should not form a setext heading w/ a lazy `-` after several lines"
+    );
+
+    assert_eq!(
+        to_html("- a\n  -"),
+        "<ul>\n<li>\n<h2>a</h2>\n</li>\n</ul>",
+        "should form a setext heading w/ a `-` in the list item, as an empty list item cannot interrupt a paragraph"
+    );
+
+    assert_eq!(
+        to_html("- a\n  b\n  ---"),
+        "<ul>\n<li>\n<h2>a\nb</h2>\n</li>\n</ul>",
+        "should form a multiline setext heading in a list item"
+    );
+
+    assert_eq!(
+        to_html("> - a\n-"),
+        "<blockquote>\n<ul>\n<li>a</li>\n</ul>\n</blockquote>\n<ul>\n<li></li>\n</ul>",
+        "should not form a setext heading w/ a lazy `-` in nested containers"
+    );
+
+    assert_eq!(
+        to_html("> a\n-"),
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<ul>\n<li></li>\n</ul>",
+        "should not form a setext heading w/ a lazy `-` after a block quote"
+    );
+
     assert_eq!(
         to_html("  Foo *bar\nbaz*\t\n===="),
         "<h1>Foo <em>bar\nbaz</em></h1>",
@@ -315,3 +351,55 @@ fn heading_setext() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn heading_setext_off() -> Result<(), message::Message> {
+    let off = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                heading_setext: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("a\n===", &off)?,
+        "<p>a\n===</p>",
+        "should support `=` underlines as paragraph continuation when off"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\n==", &off)?,
+        "<p>a\nb\n==</p>",
+        "should support `=` underlines after several lines as paragraph continuation when off"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n---", &off)?,
+        "<p>a</p>\n<hr />",
+        "should support `-` underlines as thematic breaks when off"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n  - - -  ", &off)?,
+        "<p>a</p>\n<hr />",
+        "should support `-` underlines w/ whitespace as thematic breaks when off"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n---", &off)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<hr />",
+        "should support `-` underlines as thematic breaks after a block quote when off"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n\nb\n=", &off)?,
+        "<h1>a</h1>\n<p>b\n=</p>",
+        "should still support heading (atx) when off"
+    );
+
+    Ok(())
+}
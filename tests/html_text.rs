@@ -80,6 +80,45 @@ fn html_text() -> Result<(), message::Message> {
         "should not support nonconforming whitespace"
     );
 
+    assert_eq!(
+        to_html_with_options("a < b", &danger)?,
+        "<p>a &lt; b</p>",
+        "should not support a less than followed by whitespace"
+    );
+
+    assert_eq!(
+        to_html_with_options("3<5", &danger)?,
+        "<p>3&lt;5</p>",
+        "should not support a less than followed by a digit"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <3 *b*", &danger)?,
+        "<p>a &lt;3 <em>b</em></p>",
+        "should not support a less than followed by a digit and whitespace"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <!notacomment *b*", &danger)?,
+        "<p>a &lt;!notacomment <em>b</em></p>",
+        "should not support an unclosed declaration-like text"
+    );
+
+    assert_eq!(
+        to_mdast("a <3 b", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "a <3 b".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 7, 6))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 7, 6))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 7, 6))
+        }),
+        "should keep a less than that is not HTML as text in mdast"
+    );
+
     assert_eq!(
         to_html_with_options("<a href='bar'title=title>", &danger)?,
         "<p>&lt;a href='bar'title=title&gt;</p>",
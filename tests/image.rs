@@ -121,6 +121,36 @@ fn image() -> Result<(), message::Message> {
         "should support shortcut references (2)"
     );
 
+    assert_eq!(
+        to_html("[foo]: /a\n[bar]: /b\n\n![foo][bar]"),
+        "<p><img src=\"/b\" alt=\"foo\" /></p>",
+        "should use the reference, not the label, in full references"
+    );
+
+    assert_eq!(
+        to_html("[foo]: /url\n\n![foo][bar]"),
+        "<p>![foo][bar]</p>",
+        "should not support full references to undefined labels, even if the text is defined"
+    );
+
+    assert_eq!(
+        to_html("[bar]: /url\n\n![foo][]"),
+        "<p>![foo][]</p>",
+        "should not support collapsed references to undefined labels"
+    );
+
+    assert_eq!(
+        to_html("[bar]: /url\n\n![foo]"),
+        "<p>![foo]</p>",
+        "should not support shortcut references to undefined labels"
+    );
+
+    assert_eq!(
+        to_html("![foo] ![foo][] ![foo][bar]\n\n[foo]: /a\n[bar]: /b"),
+        "<p><img src=\"/a\" alt=\"foo\" /> <img src=\"/a\" alt=\"foo\" /> <img src=\"/b\" alt=\"foo\" /></p>\n",
+        "should support shortcut, collapsed, and full references, w/ definitions after them"
+    );
+
     assert_eq!(
         to_html("[[foo]]: /url \"title\"\n\n![[foo]]"),
         "<p>[[foo]]: /url &quot;title&quot;</p>\n<p>![[foo]]</p>",
@@ -214,6 +244,24 @@ fn image() -> Result<(), message::Message> {
         "should support turning off label start (image)"
     );
 
+    assert_eq!(
+        to_html("![a\"b&c<d>](e)"),
+        "<p><img src=\"e\" alt=\"a&quot;b&amp;c&lt;d&gt;\" /></p>",
+        "should encode quotes, ampersands, and angle brackets in alt"
+    );
+
+    assert_eq!(
+        to_html("![a *\"b\"* `c&`](d)"),
+        "<p><img src=\"d\" alt=\"a &quot;b&quot; c&amp;\" /></p>",
+        "should encode the text of phrasing content in alt"
+    );
+
+    assert_eq!(
+        to_html("a\"b&c<d>e ![a\"b](c)"),
+        "<p>a&quot;b&amp;c&lt;d&gt;e <img src=\"c\" alt=\"a&quot;b\" /></p>",
+        "should encode the same characters in text and attributes, like cmark"
+    );
+
     assert_eq!(
         to_html("![](javascript:alert(1))"),
         "<p><img src=\"\" alt=\"\" /></p>",
@@ -235,6 +283,114 @@ fn image() -> Result<(), message::Message> {
         "should allow non-http protocols w/ `allowDangerousProtocol`"
     );
 
+    let lazy = Options {
+        compile: CompileOptions {
+            image_lazy_loading: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("![a *b*](c \"d\") ![e][] [f](g)\n\n[e]: h", &lazy)?,
+        "<p><img src=\"c\" alt=\"a b\" title=\"d\" loading=\"lazy\" /> <img src=\"h\" alt=\"e\" loading=\"lazy\" /> <a href=\"g\">f</a></p>\n",
+        "should add `loading=\"lazy\"` to resources and references w/ `image_lazy_loading`"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a ![b](c)](d)", &lazy)?
+            .matches("loading=")
+            .count(),
+        1,
+        "should add `loading` exactly once per image w/ `image_lazy_loading`"
+    );
+
+    let figure = Options {
+        compile: CompileOptions {
+            image_figure: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("![a](b)"),
+        "<p><img src=\"b\" alt=\"a\" /></p>",
+        "should not render lone images as figures by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a *b*](c)", &figure)?,
+        "<figure><img src=\"c\" alt=\"a b\" /><figcaption>a b</figcaption></figure>",
+        "should render a lone image as a figure w/ `image_figure`"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](b \"c\")", &figure)?,
+        "<figure><img src=\"b\" alt=\"a\" title=\"c\" /><figcaption>c</figcaption></figure>",
+        "should use the title as the caption w/ `image_figure`"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a][]\n\n[a]: b \"c\"", &figure)?,
+        "<figure><img src=\"b\" alt=\"a\" title=\"c\" /><figcaption>c</figcaption></figure>\n",
+        "should use the title of a definition as the caption w/ `image_figure`"
+    );
+
+    assert_eq!(
+        to_html_with_options("![](a)", &figure)?,
+        "<figure><img src=\"a\" alt=\"\" /></figure>",
+        "should not add a caption w/o title and alt w/ `image_figure`"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](b)\t ", &figure)?,
+        "<figure><img src=\"b\" alt=\"a\" /><figcaption>a</figcaption></figure>",
+        "should ignore trailing whitespace w/ `image_figure`"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](b) c", &figure)?,
+        "<p><img src=\"b\" alt=\"a\" /> c</p>",
+        "should not render an image w/ other content as a figure w/ `image_figure`"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](b)\n![c](d)", &figure)?,
+        "<p><img src=\"b\" alt=\"a\" />\n<img src=\"d\" alt=\"c\" /></p>",
+        "should not render several images as a figure w/ `image_figure`"
+    );
+
+    assert_eq!(
+        to_html_with_options("[![a](b)](c)", &figure)?,
+        "<p><a href=\"c\"><img src=\"b\" alt=\"a\" /></a></p>",
+        "should not render a linked image as a figure w/ `image_figure`"
+    );
+
+    assert_eq!(
+        to_html_with_options("> ![a](b)", &figure)?,
+        "<blockquote>\n<figure><img src=\"b\" alt=\"a\" /><figcaption>a</figcaption></figure>\n</blockquote>",
+        "should render a lone image in a container as a figure w/ `image_figure`"
+    );
+
+    assert_eq!(
+        to_html_with_options("  ![a](b)", &figure)?,
+        "<figure><img src=\"b\" alt=\"a\" /><figcaption>a</figcaption></figure>",
+        "should ignore leading whitespace w/ `image_figure`"
+    );
+
+    assert_eq!(
+        to_html_with_options("- ![a](b)\n- c", &figure)?,
+        "<ul>\n<li>\n<figure><img src=\"b\" alt=\"a\" /><figcaption>a</figcaption></figure>\n</li>\n<li>c</li>\n</ul>",
+        "should render a lone image in a tight list item as a block w/ `image_figure`"
+    );
+
+    assert_eq!(
+        to_html_with_options("- ![a](b)\n\n- c", &figure)?,
+        "<ul>\n<li>\n<figure><img src=\"b\" alt=\"a\" /><figcaption>a</figcaption></figure>\n</li>\n<li>\n<p>c</p>\n</li>\n</ul>",
+        "should render a lone image in a loose list item as a block w/ `image_figure`"
+    );
+
     assert_eq!(
         to_mdast(
             "a ![alpha]() b ![bravo](charlie 'delta') c.",
@@ -336,5 +492,23 @@ fn image() -> Result<(), message::Message> {
         }),
         "should support image (reference) as `ImageReference`s in mdast"
     );
+
+    assert_eq!(
+        to_mdast("![a ![b](c) *d*](e)", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Image(Image {
+                    alt: "a b d".into(),
+                    url: "e".into(),
+                    title: None,
+                    position: Some(Position::new(1, 1, 0, 1, 20, 19))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 20, 19))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 20, 19))
+        }),
+        "should include the alt of images in the alt of an image in mdast"
+    );
+
     Ok(())
 }
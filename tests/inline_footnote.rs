@@ -0,0 +1,211 @@
+use markdown::{
+    mdast::{InlineFootnote, Link, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn inline_footnote() -> Result<(), message::Message> {
+    let inline = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                label_start_inline_footnote: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("a ^[b] c"),
+        "<p>a ^[b] c</p>",
+        "should ignore inline footnotes by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[b] c", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> c</p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline-1\">
+<p>b <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^x] b^[c *d*] e[^x]\n\n[^x]: f", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-x\" id=\"user-content-fnref-x\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> b<sup><a href=\"#user-content-fn-inline-2\" id=\"user-content-fnref-inline-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup> e<sup><a href=\"#user-content-fn-x\" id=\"user-content-fnref-x-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-x\">
+<p>f <a href=\"#user-content-fnref-x\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a> <a href=\"#user-content-fnref-x-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩<sup>2</sup></a></p>
+</li>
+<li id=\"user-content-fn-inline-2\">
+<p>c <em>d</em> <a href=\"#user-content-fnref-inline-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should number inline footnotes together with footnote calls"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "^[a]",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        label_start_inline_footnote: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline-1\">
+<p>a <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support inline footnotes w/o GFM"
+    );
+
+    assert_eq!(
+        to_html_with_options("^[a [b] c]", &inline)?,
+        "<p><sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline-1\">
+<p>a [b] c <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support balanced brackets in inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("^[[a](b) www.c.com]", &inline)?,
+        "<p><sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline-1\">
+<p><a href=\"b\">a</a> <a href=\"http://www.c.com\">www.c.com</a> <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support links in inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a ^[b] c](d)", &inline)?,
+        "<p>[a <sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> c](d)</p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline-1\">
+<p>b <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should not support inline footnotes in links"
+    );
+
+    assert_eq!(
+        to_html_with_options("^[a ^[b] c]", &inline)?,
+        "<p>^[a <sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> c]</p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline-1\">
+<p>b <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should not support inline footnotes in inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("^[]", &inline)?,
+        "<p>^[]</p>",
+        "should not support empty inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("^ [a]", &inline)?,
+        "<p>^ [a]</p>",
+        "should not support whitespace between the caret and the bracket"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\^[a]", &inline)?,
+        "<p>^[a]</p>",
+        "should not support an escaped caret"
+    );
+
+    assert_eq!(
+        to_html_with_options("^[a\n\nb]", &inline)?,
+        "<p>^[a</p>\n<p>b]</p>",
+        "should not support inline footnotes across paragraphs"
+    );
+
+    assert_eq!(
+        to_mdast("a ^[b [c](d)] e", &inline.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::InlineFootnote(InlineFootnote {
+                        children: vec![
+                            Node::Text(Text {
+                                value: "b ".into(),
+                                position: Some(Position::new(1, 5, 4, 1, 7, 6))
+                            }),
+                            Node::Link(Link {
+                                children: vec![Node::Text(Text {
+                                    value: "c".into(),
+                                    position: Some(Position::new(1, 8, 7, 1, 9, 8))
+                                }),],
+                                url: "d".into(),
+                                title: None,
+                                position: Some(Position::new(1, 7, 6, 1, 13, 12))
+                            }),
+                        ],
+                        position: Some(Position::new(1, 3, 2, 1, 14, 13))
+                    }),
+                    Node::Text(Text {
+                        value: " e".into(),
+                        position: Some(Position::new(1, 14, 13, 1, 16, 15))
+                    }),
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 16, 15))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 16, 15))
+        }),
+        "should support inline footnotes as `InlineFootnote`s in mdast"
+    );
+
+    assert_eq!(
+        to_markdown("a ^[b *c*] d", &inline.parse)?,
+        "a ^[b *c*] d\n",
+        "should serialize inline footnotes"
+    );
+
+    Ok(())
+}
@@ -155,6 +155,60 @@ fn link_reference() -> Result<(), message::Message> {
         "should match references to definitions w/ matching escapes"
     );
 
+    assert_eq!(
+        to_html("[foo\\[bar]: /url\n\n[foo\\[bar]"),
+        "<p><a href=\"/url\">foo[bar</a></p>",
+        "should match shortcut references to definitions w/ escaped opening brackets"
+    );
+
+    assert_eq!(
+        to_html("[foo\\]bar]: /url\n\n[foo\\]bar][]"),
+        "<p><a href=\"/url\">foo]bar</a></p>",
+        "should match collapsed references to definitions w/ escaped closing brackets"
+    );
+
+    assert_eq!(
+        to_html("[foo\\[bar]: /url\n\n![foo\\[bar]"),
+        "<p><img src=\"/url\" alt=\"foo[bar\" /></p>",
+        "should match image references to definitions w/ escaped brackets"
+    );
+
+    assert_eq!(
+        to_html("[foo\\[bar]: /url\n\n[foo[bar]"),
+        "<p>[foo[bar]</p>",
+        "should not match references w/o escapes to definitions w/ escaped brackets"
+    );
+
+    assert_eq!(
+        to_mdast("[a\\[b]: c\n\n[a\\[b]", &Default::default())?,
+        Node::Root(Root {
+            children: vec![
+                Node::Definition(Definition {
+                    url: "c".into(),
+                    title: None,
+                    identifier: "a\\[b".into(),
+                    label: Some("a[b".into()),
+                    position: Some(Position::new(1, 1, 0, 1, 10, 9))
+                }),
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::LinkReference(LinkReference {
+                        reference_kind: ReferenceKind::Shortcut,
+                        identifier: "a\\[b".into(),
+                        label: Some("a[b".into()),
+                        children: vec![Node::Text(Text {
+                            value: "a[b".into(),
+                            position: Some(Position::new(3, 2, 12, 3, 6, 16))
+                        }),],
+                        position: Some(Position::new(3, 1, 11, 3, 7, 17))
+                    }),],
+                    position: Some(Position::new(3, 1, 11, 3, 7, 17))
+                }),
+            ],
+            position: Some(Position::new(1, 1, 0, 3, 7, 17))
+        }),
+        "should keep escapes in identifiers, but not labels, in mdast"
+    );
+
     assert_eq!(
         to_html("[bar\\\\]: /uri\n\n[bar\\\\]"),
         "<p><a href=\"/uri\">bar\\</a></p>",
@@ -425,6 +479,24 @@ fn link_reference() -> Result<(), message::Message> {
         "should support turning off label end"
     );
 
+    assert_eq!(
+        to_html("[a] [b][] ![c][d]\n\n[a]: /a\n[b]: /b\n[d]: /d"),
+        "<p><a href=\"/a\">a</a> <a href=\"/b\">b</a> <img src=\"/d\" alt=\"c\" /></p>\n",
+        "should support references before their definitions"
+    );
+
+    assert_eq!(
+        to_html("> [a]\n\n- [b]\n\n- > [a]: /a\n\n> - [b]: /b"),
+        "<blockquote>\n<p><a href=\"/a\">a</a></p>\n</blockquote>\n<ul>\n<li>\n<p><a href=\"/b\">b</a></p>\n</li>\n<li>\n<blockquote>\n</blockquote>\n</li>\n</ul>\n<blockquote>\n<ul>\n<li></li>\n</ul>\n</blockquote>",
+        "should support references before their definitions, in other containers"
+    );
+
+    assert_eq!(
+        to_html("[a]\n\n[a]: /x\n\n[a]: /y"),
+        "<p><a href=\"/x\">a</a></p>\n",
+        "should use the first definition, even if it comes after the reference"
+    );
+
     assert_eq!(
         to_mdast("[x]: y\n\na [x] b [x][] c [d][x] e.", &Default::default())?,
         Node::Root(Root {
@@ -167,6 +167,24 @@ fn link_resource() -> Result<(), message::Message> {
         "should support titles w/ parens"
     );
 
+    assert_eq!(
+        to_html("[link](/url (a (b) c))"),
+        "<p><a href=\"/url\" title=\"a (b) c\">link</a></p>",
+        "should support balanced parens in titles w/ parens"
+    );
+
+    assert_eq!(
+        to_html("[link](/url (a (b))"),
+        "<p>[link](/url (a (b))</p>",
+        "should not support unbalanced parens in titles w/ parens"
+    );
+
+    assert_eq!(
+        to_html("[link](/url (a \\( b))"),
+        "<p><a href=\"/url\" title=\"a ( b\">link</a></p>",
+        "should support escaped parens in titles w/ parens"
+    );
+
     assert_eq!(
         to_html("[link](/url \"title \\\"&quot;\")"),
         "<p><a href=\"/url\" title=\"title &quot;&quot;\">link</a></p>",
@@ -453,6 +471,30 @@ fn link_resource() -> Result<(), message::Message> {
         "should support an eol at the start of a title"
     );
 
+    assert_eq!(
+        to_html("[a](b \"c\\\"d\")"),
+        "<p><a href=\"b\" title=\"c&quot;d\">a</a></p>",
+        "should encode a quote in a title"
+    );
+
+    assert_eq!(
+        to_html("[a](b 'c\"d&e<f>')"),
+        "<p><a href=\"b\" title=\"c&quot;d&amp;e&lt;f&gt;\">a</a></p>",
+        "should encode quotes, ampersands, and angle brackets in a title"
+    );
+
+    assert_eq!(
+        to_html("[a](b \"c&amp;d&quot;e\")"),
+        "<p><a href=\"b\" title=\"c&amp;d&quot;e\">a</a></p>",
+        "should decode character references in a title, and encode the result"
+    );
+
+    assert_eq!(
+        to_html("[a](<b\"c&d>)"),
+        "<p><a href=\"b%22c&amp;d\">a</a></p>",
+        "should percent-encode a quote, and encode an ampersand, in a destination"
+    );
+
     assert_eq!(
         to_html("[a](b( \"c\")"),
         "<p>[a](b( &quot;c&quot;)</p>",
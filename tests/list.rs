@@ -108,6 +108,50 @@ fn list() -> Result<(), message::Message> {
         "should support ordered item values starting w/ `0`s"
     );
 
+    assert_eq!(
+        to_html("3. a\n4. b\n7. c"),
+        "<ol start=\"3\">\n<li>a</li>\n<li>b</li>\n<li>c</li>\n</ol>",
+        "should use the value of the first item as `start`, ignoring later items"
+    );
+
+    assert_eq!(
+        to_html("000. a\n1. b"),
+        "<ol start=\"0\">\n<li>a</li>\n<li>b</li>\n</ol>",
+        "should support `start` of `0` w/ `0`s"
+    );
+
+    assert_eq!(
+        to_html("> 3) a\n\n- 0. b"),
+        "<blockquote>\n<ol start=\"3\">\n<li>a</li>\n</ol>\n</blockquote>\n<ul>\n<li>\n<ol start=\"0\">\n<li>b</li>\n</ol>\n</li>\n</ul>",
+        "should support `start` in containers"
+    );
+
+    let debug_list_markers = Options {
+        compile: CompileOptions {
+            debug_list_markers: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("1) a", &debug_list_markers)?,
+        "<ol data-marker=\")\">\n<li>a</li>\n</ol>",
+        "should support `debug_list_markers` w/ `)`"
+    );
+
+    assert_eq!(
+        to_html_with_options("3. a\n4) b", &debug_list_markers)?,
+        "<ol start=\"3\" data-marker=\".\">\n<li>a</li>\n</ol>\n<ol start=\"4\" data-marker=\")\">\n<li>b</li>\n</ol>",
+        "should support `debug_list_markers` w/ `.`, and w/ `start`"
+    );
+
+    assert_eq!(
+        to_html_with_options("* a\n+ b\n- c", &debug_list_markers)?,
+        "<ul data-marker=\"*\">\n<li>a</li>\n</ul>\n<ul data-marker=\"+\">\n<li>b</li>\n</ul>\n<ul data-marker=\"-\">\n<li>c</li>\n</ul>",
+        "should support `debug_list_markers` in unordered lists"
+    );
+
     assert_eq!(
         to_html("-1. not ok"),
         "<p>-1. not ok</p>",
@@ -275,6 +319,42 @@ fn list() -> Result<(), message::Message> {
         "should support a definition, followed by a lazy paragraph"
     );
 
+    assert_eq!(
+        to_html("- a\nb"),
+        "<ul>\n<li>a\nb</li>\n</ul>",
+        "should support a lazy paragraph directly after an unordered list"
+    );
+
+    assert_eq!(
+        to_html("1. a\nb"),
+        "<ol>\n<li>a\nb</li>\n</ol>",
+        "should support a lazy paragraph directly after an ordered list"
+    );
+
+    assert_eq!(
+        to_html("- a\n- b\nc"),
+        "<ul>\n<li>a</li>\n<li>b\nc</li>\n</ul>",
+        "should support a lazy paragraph in the last item only"
+    );
+
+    assert_eq!(
+        to_html("- a\n\nb"),
+        "<ul>\n<li>a</li>\n</ul>\n<p>b</p>",
+        "should not support a lazy paragraph after a blank line"
+    );
+
+    assert_eq!(
+        to_html("- # a\nb"),
+        "<ul>\n<li>\n<h1>a</h1>\n</li>\n</ul>\n<p>b</p>",
+        "should not support a lazy paragraph after a heading in a list"
+    );
+
+    assert_eq!(
+        to_html("- ```\n  a\nb"),
+        "<ul>\n<li>\n<pre><code>a\n</code></pre>\n</li>\n</ul>\n<p>b</p>",
+        "should not support a lazy paragraph after code in a list"
+    );
+
     assert_eq!(
         to_html("- foo\n  - bar\n    - baz\n      - boo"),
         "<ul>\n<li>foo\n<ul>\n<li>bar\n<ul>\n<li>baz\n<ul>\n<li>boo</li>\n</ul>\n</li>\n</ul>\n</li>\n</ul>\n</li>\n</ul>",
@@ -511,6 +591,54 @@ fn list() -> Result<(), message::Message> {
         "should support empty items in a spread list"
     );
 
+    assert_eq!(
+        to_html("- a\n- b\n\n- c"),
+        "<ul>\n<li>\n<p>a</p>\n</li>\n<li>\n<p>b</p>\n</li>\n<li>\n<p>c</p>\n</li>\n</ul>",
+        "should make a whole list loose if there is a blank line between two items"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n\n- b"),
+        "<ul>\n<li>\n<p>a</p>\n</li>\n<li>\n<p>b</p>\n</li>\n</ul>",
+        "should make a whole list loose if there are blank lines between two items"
+    );
+
+    assert_eq!(
+        to_html("- a\n- b\n\n"),
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>\n",
+        "should not make a list loose w/ a blank line at the end"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n\n"),
+        "<ul>\n<li>a</li>\n</ul>\n",
+        "should not make a list loose w/ blank lines at the end"
+    );
+
+    assert_eq!(
+        to_html("- a\n- b\n\n\n> c"),
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>\n<blockquote>\n<p>c</p>\n</blockquote>",
+        "should not make a list loose w/ blank lines before what comes after it"
+    );
+
+    assert_eq!(
+        to_html("- a\n  - b\n\n\n- c"),
+        "<ul>\n<li>\n<p>a</p>\n<ul>\n<li>b</li>\n</ul>\n</li>\n<li>\n<p>c</p>\n</li>\n</ul>",
+        "should not make a nested list loose w/ blank lines at its end"
+    );
+
+    assert_eq!(
+        to_html("> - a\n>\n>"),
+        "<blockquote>\n<ul>\n<li>a</li>\n</ul>\n</blockquote>",
+        "should not make a list in a block quote loose w/ blank lines at the end"
+    );
+
+    assert_eq!(
+        to_html("1. a\n\n\n   b"),
+        "<ol>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ol>",
+        "should make a list loose if there are blank lines between two blocks in an item"
+    );
+
     assert_eq!(
         to_html("- ```\n\n  ```"),
         "<ul>\n<li>\n<pre><code>\n</code></pre>\n</li>\n</ul>",
@@ -655,6 +783,32 @@ fn list() -> Result<(), message::Message> {
         "should support `start` fields on `List` w/ `ordered: true` in mdast"
     );
 
+    assert_eq!(
+        to_mdast("0. a", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::List(List {
+                ordered: true,
+                spread: false,
+                start: Some(0),
+                children: vec![Node::ListItem(ListItem {
+                    checked: None,
+                    spread: false,
+                    children: vec![Node::Paragraph(Paragraph {
+                        children: vec![Node::Text(Text {
+                            value: "a".into(),
+                            position: Some(Position::new(1, 4, 3, 1, 5, 4))
+                        }),],
+                        position: Some(Position::new(1, 4, 3, 1, 5, 4))
+                    })],
+                    position: Some(Position::new(1, 1, 0, 1, 5, 4))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 5, 4))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 5, 4))
+        }),
+        "should support `start: Some(0)` on `List` in mdast"
+    );
+
     assert_eq!(
         to_mdast("* a\n\n  b\n* c", &Default::default())?,
         Node::Root(Root {
@@ -0,0 +1,141 @@
+use markdown::{
+    mdast::{Mark, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn mark() -> Result<(), message::Message> {
+    let mark = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                mark: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("a ==b== c"),
+        "<p>a ==b== c</p>",
+        "should ignore mark by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==b== c", &mark)?,
+        "<p>a <mark>b</mark> c</p>",
+        "should support mark w/ two equals signs"
+    );
+
+    assert_eq!(
+        to_html_with_options("==a== *b* **c** _d_ ~e~ ~~f~~", &mark)?,
+        "<p><mark>a</mark> <em>b</em> <strong>c</strong> <em>d</em> <del>e</del> <del>f</del></p>",
+        "should support mark alongside emphasis, strong, and strikethrough"
+    );
+
+    assert_eq!(
+        to_html_with_options("=a=", &mark)?,
+        "<p>=a=</p>",
+        "should not support mark w/ one equals sign"
+    );
+
+    assert_eq!(
+        to_html_with_options("===a===", &mark)?,
+        "<p>===a===</p>",
+        "should not support mark w/ three equals signs"
+    );
+
+    assert_eq!(
+        to_html_with_options("==a=", &mark)?,
+        "<p>==a=</p>",
+        "should not support mark w/ unbalanced sequences"
+    );
+
+    assert_eq!(
+        to_html_with_options("a==b==c", &mark)?,
+        "<p>a<mark>b</mark>c</p>",
+        "should support mark in words"
+    );
+
+    assert_eq!(
+        to_html_with_options("== a ==", &mark)?,
+        "<p>== a ==</p>",
+        "should not support mark w/ whitespace inside the sequences"
+    );
+
+    assert_eq!(
+        to_html_with_options("a = b == c", &mark)?,
+        "<p>a = b == c</p>",
+        "should not support mark w/o closing sequence"
+    );
+
+    assert_eq!(
+        to_html_with_options("*==a==* ==*b*== **==c==**", &mark)?,
+        "<p><em><mark>a</mark></em> <mark><em>b</em></mark> <strong><mark>c</mark></strong></p>",
+        "should support mark in and around emphasis and strong"
+    );
+
+    assert_eq!(
+        to_html_with_options("==a\nb==", &mark)?,
+        "<p><mark>a\nb</mark></p>",
+        "should support mark w/ a line ending"
+    );
+
+    assert_eq!(
+        to_html_with_options("![==a==](b)", &mark)?,
+        "<p><img src=\"b\" alt=\"a\" /></p>",
+        "should support mark in image alt"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n==", &mark)?,
+        "<h1>a</h1>",
+        "should prefer a setext heading underline"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\==a==", &mark)?,
+        "<p>==a==</p>",
+        "should not support mark if an equals sign in a sequence is escaped"
+    );
+
+    assert_eq!(
+        to_mdast("a ==alpha== b.", &mark.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::Mark(Mark {
+                        children: vec![Node::Text(Text {
+                            value: "alpha".into(),
+                            position: Some(Position::new(1, 5, 4, 1, 10, 9))
+                        }),],
+                        position: Some(Position::new(1, 3, 2, 1, 12, 11))
+                    }),
+                    Node::Text(Text {
+                        value: " b.".into(),
+                        position: Some(Position::new(1, 12, 11, 1, 15, 14))
+                    }),
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 15, 14))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 15, 14))
+        }),
+        "should support mark as `Mark`s in mdast"
+    );
+
+    assert_eq!(
+        to_markdown("a ==b== c = d", &mark.parse)?,
+        "a ==b== c \\= d\n",
+        "should serialize mark"
+    );
+
+    Ok(())
+}
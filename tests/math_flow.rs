@@ -176,6 +176,18 @@ fn math_flow() -> Result<(), message::Message> {
         "should not support meta string on closing sequences"
     );
 
+    assert_eq!(
+        to_html_with_options("$$$\naaa\n$$$", &math)?,
+        "<pre><code class=\"language-math math-display\">aaa\n</code></pre>",
+        "should support a closing sequence as long as a longer opening sequence"
+    );
+
+    assert_eq!(
+        to_html_with_options("$$$$\naaa\n$$$\n$$$$", &math)?,
+        "<pre><code class=\"language-math math-display\">aaa\n$$$\n</code></pre>",
+        "should not support a closing sequence shorter than the opening sequence"
+    );
+
     // Our own:
     assert_eq!(
         to_html_with_options("$$  ", &math)?,
@@ -207,6 +207,18 @@ fn math_text() -> Result<(), message::Message> {
         "should support an escaped initial dollar"
     );
 
+    assert_eq!(
+        to_html_with_options("\\$a\\$ \\$b$", &math)?,
+        "<p>$a$ $b$</p>",
+        "should support escaped dollars as literal text"
+    );
+
+    assert_eq!(
+        to_html_with_options("$$$a$$$", &math)?,
+        "<p><code class=\"language-math math-inline\">a</code></p>",
+        "should support math (text) w/ three dollars"
+    );
+
     assert_eq!(
         to_mdast("a $alpha$ b.", &math.parse)?,
         Node::Root(Root {
@@ -0,0 +1,141 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn mention() -> Result<(), message::Message> {
+    let mention = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                mention: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        compile: CompileOptions {
+            mention_url: Some("/u/{name}".into()),
+            ..CompileOptions::gfm()
+        },
+    };
+
+    assert_eq!(
+        to_html("@alice"),
+        "<p>@alice</p>",
+        "should not support mentions by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("@alice", &mention)?,
+        "<p><a href=\"/u/alice\">@alice</a></p>",
+        "should support mentions"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a @alice b",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        mention: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a @alice b</p>",
+        "should compile mentions as text w/o `mention_url`"
+    );
+
+    assert_eq!(
+        to_html_with_options("foo@bar", &mention)?,
+        "<p>foo@bar</p>",
+        "should not support mentions after alphanumericals"
+    );
+
+    assert_eq!(
+        to_html_with_options("alice@example.com", &mention)?,
+        "<p><a href=\"mailto:alice@example.com\">alice@example.com</a></p>",
+        "should not support mentions in email addresses"
+    );
+
+    assert_eq!(
+        to_html_with_options("@alice@example.com", &mention)?,
+        "<p>@<a href=\"mailto:alice@example.com\">alice@example.com</a></p>",
+        "should not support mentions followed by another `@`"
+    );
+
+    assert_eq!(
+        to_html_with_options("@alice. @bob_, @a.b-c_d", &mention)?,
+        "<p><a href=\"/u/alice\">@alice</a>. <a href=\"/u/bob\">@bob</a>_, <a href=\"/u/a.b-c_d\">@a.b-c_d</a></p>",
+        "should support dashes, dots, and underscores in names, but not at their end"
+    );
+
+    assert_eq!(
+        to_html_with_options("@ a @. @-a", &mention)?,
+        "<p>@ a @. @-a</p>",
+        "should not support mentions w/o name"
+    );
+
+    assert_eq!(
+        to_html_with_options("(@alice) *@bob*", &mention)?,
+        "<p>(<a href=\"/u/alice\">@alice</a>) <em><a href=\"/u/bob\">@bob</a></em></p>",
+        "should support mentions after punctuation"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\@alice", &mention)?,
+        "<p>@alice</p>",
+        "should not support an escaped `@`"
+    );
+
+    assert_eq!(
+        to_html_with_options("`@alice` <https://a.b/@alice>", &mention)?,
+        "<p><code>@alice</code> <a href=\"https://a.b/@alice\">https://a.b/@alice</a></p>",
+        "should not support mentions in code or autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a @alice](b) ![c @bob](d)", &mention)?,
+        "<p><a href=\"b\">a @alice</a> <img src=\"d\" alt=\"c @bob\" /></p>",
+        "should not link mentions in links or images"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "@alice",
+            &Options::builder()
+                .constructs(Constructs {
+                    mention: true,
+                    ..Default::default()
+                })
+                .mention_url("https://example.com/{name}")
+                .link_rel("nofollow")
+                .build()
+        )?,
+        "<p><a href=\"https://example.com/alice\" rel=\"nofollow\">@alice</a></p>",
+        "should support `mention_url` w/ the builder, and treat them like other links"
+    );
+
+    assert_eq!(
+        to_mdast("a @alice b", &mention.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "a @alice b".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 11, 10))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 11, 10))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 11, 10))
+        }),
+        "should support mentions as text in mdast"
+    );
+
+    Ok(())
+}
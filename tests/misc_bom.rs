@@ -1,8 +1,13 @@
-use markdown::to_html;
+use markdown::{
+    mdast::{Heading, Node, Root, Text},
+    to_html, to_mdast,
+    unist::Position,
+    ParseOptions,
+};
 use pretty_assertions::assert_eq;
 
 #[test]
-fn bom() {
+fn bom() -> Result<(), markdown::message::Message> {
     assert_eq!(to_html("\u{FEFF}"), "", "should ignore just a bom");
 
     assert_eq!(
@@ -10,4 +15,49 @@ fn bom() {
         "<h1>hea\u{FEFF}ding</h1>",
         "should ignore a bom"
     );
+
+    assert_eq!(
+        to_html("\u{FEFF}# Hi"),
+        "<h1>Hi</h1>",
+        "should ignore a bom before a heading"
+    );
+
+    assert_eq!(
+        to_mdast("\u{FEFF}# Hi", &ParseOptions::default())?,
+        Node::Root(Root {
+            children: vec![Node::Heading(Heading {
+                depth: 1,
+                children: vec![Node::Text(Text {
+                    value: "Hi".into(),
+                    position: Some(Position::new(1, 6, 5, 1, 8, 7))
+                })],
+                position: Some(Position::new(1, 4, 3, 1, 8, 7))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 8, 7))
+        }),
+        "should count the 3 bytes of a bom in positional info"
+    );
+
+    let value = "\u{FEFF}# Hi\n*a*";
+
+    if let Node::Root(root) = to_mdast(value, &ParseOptions::default())? {
+        let slices = root
+            .children
+            .iter()
+            .map(|child| {
+                let position = child.position().unwrap();
+                &value[position.start.offset..position.end.offset]
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            slices,
+            vec!["# Hi", "*a*"],
+            "should use offsets into the original value (w/ bom)"
+        );
+    } else {
+        unreachable!("expected root");
+    }
+
+    Ok(())
 }
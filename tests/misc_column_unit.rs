@@ -0,0 +1,157 @@
+use markdown::{
+    mdast::Node, message, to_mdast, unist::Position, ColumnUnit, Constructs, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+/// Get the position of the second node in the first paragraph.
+fn second(value: &str, options: &ParseOptions) -> Result<Position, message::Message> {
+    let tree = to_mdast(value, options)?;
+    let paragraph = &tree.children().unwrap()[0];
+    Ok(paragraph.children().unwrap()[1].position().unwrap().clone())
+}
+
+/// Get the start column of the second node in the first paragraph, for each
+/// unit: bytes, code points, UTF-16, and graphemes.
+fn columns(value: &str, options: &ParseOptions) -> Result<[usize; 4], message::Message> {
+    let mut result = [0; 4];
+    let units = [
+        ColumnUnit::Bytes,
+        ColumnUnit::CodePoints,
+        ColumnUnit::Utf16,
+        ColumnUnit::Graphemes,
+    ];
+
+    for (index, column_unit) in units.iter().enumerate() {
+        let options = ParseOptions {
+            column_unit: *column_unit,
+            constructs: options.constructs.clone(),
+            ..ParseOptions::default()
+        };
+        result[index] = second(value, &options)?.start.column;
+    }
+
+    Ok(result)
+}
+
+#[test]
+fn column_unit() -> Result<(), message::Message> {
+    let default = ParseOptions::default();
+
+    assert_eq!(
+        second("😀 *a*", &default)?,
+        Position::new(1, 6, 5, 1, 9, 8),
+        "should count columns in bytes by default"
+    );
+
+    assert_eq!(
+        columns("😀 *a*", &default)?,
+        [6, 3, 4, 3],
+        "should count columns after an emoji in each unit"
+    );
+
+    assert_eq!(
+        columns("ab *c*", &default)?,
+        [4, 4, 4, 4],
+        "should count columns after ASCII the same in each unit"
+    );
+
+    assert_eq!(
+        columns("日本 *a*", &default)?,
+        [8, 4, 4, 4],
+        "should count columns after CJK in each unit"
+    );
+
+    assert_eq!(
+        columns("e\u{301} *a*", &default)?,
+        [5, 4, 4, 3],
+        "should count columns after a combining mark in each unit"
+    );
+
+    assert_eq!(
+        columns("नमस्ते *a*", &default)?,
+        [20, 8, 8, 6],
+        "should count columns after Devanagari w/ vowel signs and viramas in each unit"
+    );
+
+    assert_eq!(
+        columns("👍🏽 *a*", &default)?,
+        [10, 4, 6, 3],
+        "should count columns after an emoji w/ a modifier in each unit"
+    );
+
+    assert_eq!(
+        columns("👩\u{200D}👧 *a*", &default)?,
+        [13, 5, 7, 3],
+        "should count columns after emoji w/ a zero-width joiner in each unit"
+    );
+
+    assert_eq!(
+        columns("🇳🇱🇧🇪 *a*", &default)?,
+        [18, 6, 10, 4],
+        "should count columns after flags in each unit"
+    );
+
+    assert_eq!(
+        columns("😀\t*a*", &default)?,
+        [9, 5, 5, 5],
+        "should expand tabs to tab stops based on the unit"
+    );
+
+    assert_eq!(
+        columns(
+            "😀 www.a.com",
+            &ParseOptions {
+                constructs: Constructs::gfm(),
+                ..ParseOptions::default()
+            }
+        )?,
+        [6, 3, 4, 3],
+        "should count columns in each unit for GFM autolink literals"
+    );
+
+    assert_eq!(
+        second(
+            "😀 *a*",
+            &ParseOptions {
+                column_unit: ColumnUnit::Utf16,
+                ..ParseOptions::default()
+            }
+        )?,
+        Position::new(1, 4, 5, 1, 7, 8),
+        "should not affect offsets"
+    );
+
+    assert_eq!(
+        to_mdast(
+            "नमस्ते a",
+            &ParseOptions {
+                column_unit: ColumnUnit::Graphemes,
+                ..ParseOptions::default()
+            }
+        )?
+        .children()
+        .unwrap()[0]
+            .position()
+            .unwrap()
+            .end
+            .column,
+        7,
+        "should count marks w/ the `Grapheme_Extend` property as part of the grapheme before them"
+    );
+
+    let tree = to_mdast(
+        "😀\n*a*",
+        &ParseOptions {
+            column_unit: ColumnUnit::Utf16,
+            ..ParseOptions::default()
+        },
+    )?;
+    let paragraph = &tree.children().unwrap()[0];
+
+    assert!(
+        matches!(&paragraph.children().unwrap()[1], Node::Emphasis(emphasis) if emphasis.position == Some(Position::new(2, 1, 5, 2, 4, 8))),
+        "should start at column 1 on the next line"
+    );
+
+    Ok(())
+}
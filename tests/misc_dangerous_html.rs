@@ -1,4 +1,4 @@
-use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
+use markdown::{message, to_html, to_html_with_options, CompileOptions, HtmlSanitization, Options};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -32,3 +32,188 @@ fn dangerous_html() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn dangerous_html_sanitization() -> Result<(), message::Message> {
+    let sanitize = &Options {
+        compile: CompileOptions {
+            html_sanitization: Some(HtmlSanitization {
+                tag_names: vec!["b".into(), "img".into(), "a".into(), "div".into()],
+                attribute_names: vec!["alt".into(), "href".into(), "src".into()],
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("<b>ok</b>", sanitize)?,
+        "<p><b>ok</b></p>",
+        "should keep allowed tags w/ `html_sanitization`"
+    );
+
+    assert_eq!(
+        to_html_with_options("<img src=\"a.png\" onerror=\"alert(1)\" alt=b>", sanitize)?,
+        "<img src=\"a.png\" alt=\"b\">",
+        "should drop disallowed attributes w/ `html_sanitization`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <i>b</i> <script>alert(1)</script>", sanitize)?,
+        "<p>a &lt;i&gt;b&lt;/i&gt; &lt;script&gt;alert(1)&lt;/script&gt;</p>",
+        "should encode disallowed tags w/ `html_sanitization`"
+    );
+
+    assert_eq!(
+        to_html_with_options("<div>\n<script>\n</div>\n\n*a*", sanitize)?,
+        "<div>\n&lt;script&gt;\n</div>\n<p><em>a</em></p>",
+        "should sanitize HTML (flow) w/ `html_sanitization`"
+    );
+
+    assert_eq!(
+        to_html_with_options("<B\nonclick=x ALT='a\"b'>c</B>", sanitize)?,
+        "<p><B ALT=\"a&quot;b\">c</B></p>",
+        "should support tags across lines, case-insensitive names, and quotes in values"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<a href=\"javascript:alert(1)\">a</a> <a href=\"javascript&#58;b\">c</a>",
+            sanitize
+        )?,
+        "<p><a href=\"\">a</a> <a href=\"\">c</a></p>",
+        "should sanitize URLs in `href` and `src`"
+    );
+
+    assert_eq!(
+        to_html_with_options("<!-- a --> <?b?> <![CDATA[c]]>", sanitize)?,
+        "&lt;!-- a --> &lt;?b?> &lt;![CDATA[c]]>",
+        "should encode comments, instructions, and cdata"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<i>",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    html_sanitization: Some(HtmlSanitization::default()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<i>",
+        "should not sanitize w/ `allow_dangerous_html`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn dangerous_html_normalize_tags() -> Result<(), message::Message> {
+    let normalize = &Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            normalize_html_tags: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options(
+            "<DIV Class=\"X\">",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<DIV Class=\"X\">",
+        "should keep the case of tags by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("<DIV Class=\"X\">", normalize)?,
+        "<div class=\"X\">",
+        "should lowercase tag and attribute names w/ `normalize_html_tags`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <SPAN TITLE='B' Hidden>C</SPAN> d", normalize)?,
+        "<p>a <span title='B' hidden>C</span> d</p>",
+        "should lowercase HTML (text), but not values or text"
+    );
+
+    assert_eq!(
+        to_html_with_options("<DIV\nCLASS=a\n>\n<BR/>\n</DIV>", normalize)?,
+        "<div\nclass=a\n>\n<br/>\n</div>",
+        "should lowercase tags across lines and self-closing tags"
+    );
+
+    assert_eq!(
+        to_html_with_options("<!-- <A> --> <?B C?> <![CDATA[<D>]]>", normalize)?,
+        "<!-- <A> --> <?B C?> <![CDATA[<D>]]>",
+        "should not change comments, instructions, and cdata"
+    );
+
+    assert_eq!(
+        to_html_with_options("<PRE>\n<B>A</B>\n</PRE>", normalize)?,
+        "<pre>\n<B>A</B>\n</pre>",
+        "should not change the content of `<pre>`"
+    );
+
+    assert_eq!(
+        to_html_with_options("<Script>\nif (a<B) {}\n</SCRIPT>\n<B>c</B>", normalize)?,
+        "<script>\nif (a<B) {}\n</script>\n<p><b>c</b></p>",
+        "should not change the content of `<script>`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<DIV>\n<PRE><B>a</B></PREFACE>\n</PRE><I>\n</DIV>",
+            normalize
+        )?,
+        "<div>\n<pre><B>a</B></PREFACE>\n</pre><i>\n</div>",
+        "should not change the content of `<pre>` in other HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<B ALT=a ONCLICK=b>c</B>",
+            &Options {
+                compile: CompileOptions {
+                    html_sanitization: Some(HtmlSanitization {
+                        tag_names: vec!["b".into()],
+                        attribute_names: vec!["alt".into()],
+                    }),
+                    normalize_html_tags: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><b alt=\"a\">c</b></p>",
+        "should lowercase tag and attribute names w/ `html_sanitization`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<DIV>",
+            &Options {
+                compile: CompileOptions {
+                    normalize_html_tags: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "&lt;DIV&gt;",
+        "should not change encoded HTML"
+    );
+
+    Ok(())
+}
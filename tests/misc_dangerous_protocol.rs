@@ -1,4 +1,7 @@
-use markdown::to_html;
+use markdown::{
+    message, sanitize_url, to_html, to_html_with_options, CompileOptions, DroppedLinkBehavior,
+    Options,
+};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -195,3 +198,211 @@ fn dangerous_protocol_link() {
         "should allow a colon in a path"
     );
 }
+
+#[test]
+fn dangerous_protocol_allowed_protocols() -> Result<(), message::Message> {
+    let allowed = Options {
+        compile: CompileOptions {
+            allowed_protocols: Some(vec![
+                "http".into(),
+                "https".into(),
+                "mailto".into(),
+                "App".into(),
+            ]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("<app:settings>", &allowed)?,
+        "<p><a href=\"app:settings\">app:settings</a></p>",
+        "should allow a custom protocol w/ `allowed_protocols`"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](APP:b) ![c](app:d)", &allowed)?,
+        "<p><a href=\"APP:b\">a</a> <img src=\"app:d\" alt=\"c\" /></p>",
+        "should compare protocols case-insensitively, in links and images"
+    );
+
+    assert_eq!(
+        to_html_with_options("<mailto:a> [b](https://c)", &allowed)?,
+        "<p><a href=\"mailto:a\">mailto:a</a> <a href=\"https://c\">b</a></p>",
+        "should allow listed protocols"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](JavaScript:alert(1))", &allowed)?,
+        "<p><a href=\"\">a</a></p>",
+        "should not allow `JavaScript:` (mixed case)"
+    );
+
+    assert_eq!(
+        to_html_with_options("<irc:///help> ![a](xmpp:b)", &allowed)?,
+        "<p><a href=\"\">irc:///help</a> <img src=\"\" alt=\"a\" /></p>",
+        "should not allow protocols that are safe by default but not listed"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](#b) [c](?d) [e](./f)", &allowed)?,
+        "<p><a href=\"#b\">a</a> <a href=\"?d\">c</a> <a href=\"./f\">e</a></p>",
+        "should allow URLs without protocol"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<javascript:a>",
+            &Options {
+                compile: CompileOptions {
+                    allowed_protocols: Some(vec!["app".into()]),
+                    allow_dangerous_protocol: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"javascript:a\">javascript:a</a></p>",
+        "should ignore `allowed_protocols` w/ `allow_dangerous_protocol`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn dangerous_protocol_dropped_link_behavior() -> Result<(), message::Message> {
+    let input =
+        "[a](javascript:alert(1)) <javascript:alert(1)> ![b](javascript:alert(1)) [c]() [d](#e)";
+    let with = |dropped_link_behavior| Options {
+        compile: CompileOptions {
+            dropped_link_behavior,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html(input),
+        "<p><a href=\"\">a</a> <a href=\"\">javascript:alert(1)</a> <img src=\"\" alt=\"b\" /> <a href=\"\">c</a> <a href=\"#e\">d</a></p>",
+        "should use empty attributes for dropped URLs by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(input, &with(DroppedLinkBehavior::Empty))?,
+        to_html(input),
+        "should use empty attributes for dropped URLs w/ `Empty`"
+    );
+
+    assert_eq!(
+        to_html_with_options(input, &with(DroppedLinkBehavior::Omit))?,
+        "<p><a>a</a> <a>javascript:alert(1)</a> <img alt=\"b\" /> <a href=\"\">c</a> <a href=\"#e\">d</a></p>",
+        "should leave out attributes for dropped URLs w/ `Omit`"
+    );
+
+    assert_eq!(
+        to_html_with_options(input, &with(DroppedLinkBehavior::Hash))?,
+        "<p><a href=\"#\">a</a> <a href=\"#\">javascript:alert(1)</a> <img src=\"#\" alt=\"b\" /> <a href=\"\">c</a> <a href=\"#e\">d</a></p>",
+        "should use `#` for dropped URLs w/ `Hash`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a]\n\n[a]: javascript:alert(1)",
+            &with(DroppedLinkBehavior::Omit)
+        )?,
+        "<p><a>a</a></p>\n",
+        "should support dropped URLs from definitions"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](javascript:alert(1) \"b\") <app:c>",
+            &Options {
+                compile: CompileOptions {
+                    dropped_link_behavior: DroppedLinkBehavior::Hash,
+                    allowed_protocols: Some(vec!["https".into()]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"#\" title=\"b\">a</a> <a href=\"#\">app:c</a></p>",
+        "should support `allowed_protocols`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](javascript:alert(1))",
+            &Options {
+                compile: CompileOptions {
+                    dropped_link_behavior: DroppedLinkBehavior::Omit,
+                    allow_dangerous_protocol: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"javascript:alert(1)\">a</a></p>",
+        "should not affect anything if dangerous protocols are allowed"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn dangerous_protocol_sanitize_url() {
+    let href = ["http", "https", "irc", "ircs", "mailto", "xmpp"];
+    let src = ["http", "https"];
+
+    assert_eq!(
+        sanitize_url("javascript:alert(1)", &href),
+        "",
+        "should drop `javascript:`"
+    );
+
+    assert_eq!(
+        sanitize_url("http://a", &href),
+        "http://a",
+        "should allow `http:`"
+    );
+
+    assert_eq!(
+        sanitize_url("irc:///help", &href),
+        "irc:///help",
+        "should allow `irc:` for links"
+    );
+
+    assert_eq!(
+        sanitize_url("irc:///help", &src),
+        "",
+        "should not allow `irc:` for images"
+    );
+
+    assert_eq!(
+        sanitize_url("a/b:c", &src),
+        "a/b:c",
+        "should allow a colon after a slash"
+    );
+
+    assert_eq!(
+        sanitize_url("HTTPS://a", &["HTTP", "https"]),
+        "HTTPS://a",
+        "should compare protocols case-insensitively"
+    );
+
+    assert_eq!(
+        sanitize_url("<a>", &src),
+        "%3Ca%3E",
+        "should encode unsafe characters"
+    );
+
+    assert_eq!(
+        to_html("[a](javascript:b) ![c](irc:d)"),
+        format!(
+            "<p><a href=\"{}\">a</a> <img src=\"{}\" alt=\"c\" /></p>",
+            sanitize_url("javascript:b", &href),
+            sanitize_url("irc:d", &src)
+        ),
+        "should match how the compiler sanitizes URLs"
+    );
+}
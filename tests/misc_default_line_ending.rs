@@ -32,7 +32,7 @@ fn default_line_ending() -> Result<(), message::Message> {
             "> a",
             &Options {
                 compile: CompileOptions {
-                    default_line_ending: LineEnding::CarriageReturn,
+                    default_line_ending: Some(LineEnding::CarriageReturn),
                     ..Default::default()
                 },
                 ..Default::default()
@@ -47,17 +47,106 @@ fn default_line_ending() -> Result<(), message::Message> {
             "> a\n",
             &Options {
                 compile: CompileOptions {
-                    default_line_ending: LineEnding::CarriageReturn,
+                    default_line_ending: Some(LineEnding::CarriageReturn),
                     ..Default::default()
                 },
                 ..Default::default()
             }
         )?,
-        // To do: is this a bug in `to_html.js` that it uses `\r` for earlier line endings?
-        // "<blockquote>\r<p>a</p>\r</blockquote>\n",
-        "<blockquote>\n<p>a</p>\n</blockquote>\n",
+        "<blockquote>\r<p>a</p>\r</blockquote>\r",
         "should support the given line ending, even if line endings exist"
     );
 
+    assert_eq!(
+        to_html_with_options(
+            "# a\r\nb\nc\r\n\n```\nd\r\ne\n```\n<div>\r\nf\n</div>",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    default_line_ending: Some(LineEnding::CarriageReturn),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<h1>a</h1>\r<p>b\rc</p>\r<pre><code>d\re\r</code></pre>\r<div>\rf\r</div>",
+        "should use the given line ending for all line endings, in all content"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a\r\nb",
+            &Options {
+                compile: CompileOptions {
+                    default_line_ending: Some(LineEnding::LineFeed),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a\nb</p>",
+        "should support an explicit line feed, even if other line endings exist"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "- a\r\n\r\n  b",
+            &Options {
+                compile: CompileOptions {
+                    default_line_ending: None,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<ul>\r\n<li>\r\n<p>a</p>\r\n<p>b</p>\r\n</li>\r\n</ul>",
+        "should infer the line ending w/o a configured one"
+    );
+
     Ok(())
 }
+
+#[test]
+fn infer_line_ending() {
+    assert_eq!(
+        LineEnding::infer("> a"),
+        None,
+        "should infer nothing w/o line endings"
+    );
+
+    assert_eq!(
+        LineEnding::infer(""),
+        None,
+        "should infer nothing for an empty document"
+    );
+
+    assert_eq!(
+        LineEnding::infer("> a\n"),
+        Some(LineEnding::LineFeed),
+        "should infer the first line ending (1)"
+    );
+
+    assert_eq!(
+        LineEnding::infer("> a\r"),
+        Some(LineEnding::CarriageReturn),
+        "should infer the first line ending (2)"
+    );
+
+    assert_eq!(
+        LineEnding::infer("> a\r\n"),
+        Some(LineEnding::CarriageReturnLineFeed),
+        "should infer the first line ending (3)"
+    );
+
+    assert_eq!(
+        LineEnding::infer("a\r\nb\rc\n"),
+        Some(LineEnding::CarriageReturnLineFeed),
+        "should infer the first line ending, if there are several"
+    );
+
+    assert_eq!(
+        LineEnding::infer("a\n\r\n"),
+        Some(LineEnding::LineFeed),
+        "should infer a line feed before a carriage return + line feed"
+    );
+}
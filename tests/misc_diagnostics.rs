@@ -0,0 +1,74 @@
+use markdown::{
+    message::{Message, Place},
+    to_html_with_diagnostics,
+    unist::{Point, Position},
+    Options,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn diagnostics() -> Result<(), Message> {
+    assert_eq!(
+        to_html_with_diagnostics("[a]", &Options::default())?,
+        (
+            "<p>[a]</p>".into(),
+            vec![Message {
+                place: Some(Box::new(Place::Position(Position {
+                    start: Point::new(1, 1, 0),
+                    end: Point::new(1, 4, 3)
+                }))),
+                reason: "Unexpected reference to undefined definition `a`".into(),
+                rule_id: Box::new("undefined-reference".into()),
+                source: Box::new("markdown-rs".into()),
+            }]
+        ),
+        "should warn about an undefined reference"
+    );
+
+    assert_eq!(
+        to_html_with_diagnostics("[a] [A][] ![a]\n\n[a]: b", &Options::default())?
+            .1
+            .len(),
+        0,
+        "should not warn about defined references"
+    );
+
+    assert_eq!(
+        to_html_with_diagnostics("[a](b) [] [ ] a[0]", &Options::default())?
+            .1
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec!["1:16-1:19: Unexpected reference to undefined definition `0` (markdown-rs:undefined-reference)"],
+        "should not warn about resources or empty labels"
+    );
+
+    assert_eq!(
+        to_html_with_diagnostics("[a][] [b][c] ![d]\n\n[b]: e", &Options::default())?
+            .1
+            .iter()
+            .map(|d| d.reason.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "Unexpected reference to undefined definition `a`",
+            "Unexpected reference to undefined definition `c`",
+            "Unexpected reference to undefined definition `d`",
+        ],
+        "should warn about collapsed, full, and image references"
+    );
+
+    assert_eq!(
+        to_html_with_diagnostics("> - *[a]*\n\n[^b] [^c]\n\n[^c]: d", &Options::gfm())?
+            .1
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec![
+            "1:6-1:9: Unexpected reference to undefined definition `a` (markdown-rs:undefined-reference)",
+            "3:1-3:5: Unexpected reference to undefined definition `^b` (markdown-rs:undefined-reference)",
+        ],
+        "should warn about references in containers, and footnotes (GFM)"
+    );
+
+    Ok(())
+}
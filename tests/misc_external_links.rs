@@ -0,0 +1,178 @@
+use markdown::{
+    message, to_html, to_html_with_options, CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn external_links() -> Result<(), message::Message> {
+    let new_tab = &Options {
+        compile: CompileOptions {
+            open_external_links_in_new_tab: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("[x](https://a)"),
+        "<p><a href=\"https://a\">x</a></p>",
+        "should not open external links in a new tab by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("[x](https://a) [y](HTTP://b \"c\")", new_tab)?,
+        "<p><a href=\"https://a\" target=\"_blank\" rel=\"noopener\">x</a> <a href=\"HTTP://b\" title=\"c\" target=\"_blank\" rel=\"noopener\">y</a></p>",
+        "should open external links in a new tab"
+    );
+
+    assert_eq!(
+        to_html_with_options("[x](/a) [y](#b) [z](?c) [w](d.html)", new_tab)?,
+        "<p><a href=\"/a\">x</a> <a href=\"#b\">y</a> <a href=\"?c\">z</a> <a href=\"d.html\">w</a></p>",
+        "should not change relative links"
+    );
+
+    assert_eq!(
+        to_html_with_options("[x](mailto:a@b.c)", new_tab)?,
+        "<p><a href=\"mailto:a@b.c\">x</a></p>",
+        "should not change links with other protocols"
+    );
+
+    assert_eq!(
+        to_html_with_options("[x]\n\n[x]: https://a", new_tab)?,
+        "<p><a href=\"https://a\" target=\"_blank\" rel=\"noopener\">x</a></p>\n",
+        "should support references"
+    );
+
+    assert_eq!(
+        to_html_with_options("![x](https://a)", new_tab)?,
+        "<p><img src=\"https://a\" alt=\"x\" /></p>",
+        "should not change images"
+    );
+
+    assert_eq!(
+        to_html_with_options("[![x](https://a)](https://b)", new_tab)?,
+        "<p><a href=\"https://b\" target=\"_blank\" rel=\"noopener\"><img src=\"https://a\" alt=\"x\" /></a></p>",
+        "should support images in links"
+    );
+
+    assert_eq!(
+        to_html_with_options("<https://a> <b@c.d>", new_tab)?,
+        "<p><a href=\"https://a\" target=\"_blank\" rel=\"noopener\">https://a</a> <a href=\"mailto:b@c.d\">b@c.d</a></p>",
+        "should support autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "www.a.com",
+            &Options {
+                parse: ParseOptions::gfm(),
+                compile: CompileOptions {
+                    open_external_links_in_new_tab: true,
+                    ..Default::default()
+                }
+            }
+        )?,
+        "<p><a href=\"http://www.a.com\" target=\"_blank\" rel=\"noopener\">www.a.com</a></p>",
+        "should support GFM autolink literals"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn link_rel() -> Result<(), message::Message> {
+    let rel = Options {
+        compile: CompileOptions {
+            link_rel: Some("nofollow ugc".into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("[x](/y)"),
+        "<p><a href=\"/y\">x</a></p>",
+        "should not add `rel` by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("[x](/y) <http://a> <a@b.com>", &rel)?,
+        "<p><a href=\"/y\" rel=\"nofollow ugc\">x</a> <a href=\"http://a\" rel=\"nofollow ugc\">http://a</a> <a href=\"mailto:a@b.com\" rel=\"nofollow ugc\">a@b.com</a></p>",
+        "should add `rel` to links, autolinks, and email autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options("[x][] ![y](z)\n\n[x]: /a", &rel)?,
+        "<p><a href=\"/a\" rel=\"nofollow ugc\">x</a> <img src=\"z\" alt=\"y\" /></p>\n",
+        "should add `rel` to references, but not images"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "www.a.com b@c.d [^e]\n\n[^e]: f",
+            &Options {
+                parse: ParseOptions::gfm(),
+                compile: CompileOptions {
+                    link_rel: Some("nofollow".into()),
+                    ..CompileOptions::gfm()
+                },
+            }
+        )?,
+        "<p><a href=\"http://www.a.com\" rel=\"nofollow\">www.a.com</a> <a href=\"mailto:b@c.d\" rel=\"nofollow\">b@c.d</a> <sup><a href=\"#user-content-fn-e\" id=\"user-content-fnref-e\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-e\">\n<p>f <a href=\"#user-content-fnref-e\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n",
+        "should add `rel` to GFM autolink literals, but not footnote calls and back references"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[[a]]",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        wikilink: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    link_rel: Some("nofollow".into()),
+                    ..Default::default()
+                },
+            }
+        )?,
+        "<p><a href=\"a\" rel=\"nofollow\">a</a></p>",
+        "should add `rel` to wikilinks"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[x](https://a) [y](/b)",
+            &Options {
+                compile: CompileOptions {
+                    link_rel: Some("nofollow  noopener ugc".into()),
+                    open_external_links_in_new_tab: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"https://a\" target=\"_blank\" rel=\"noopener nofollow ugc\">x</a> <a href=\"/b\" rel=\"nofollow noopener ugc\">y</a></p>",
+        "should merge `rel` w/ `open_external_links_in_new_tab`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[x](/y)",
+            &Options {
+                compile: CompileOptions {
+                    link_rel: Some("a\"b".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"/y\" rel=\"a&quot;b\">x</a></p>",
+        "should encode `rel`"
+    );
+
+    Ok(())
+}
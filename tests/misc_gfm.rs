@@ -0,0 +1,82 @@
+use markdown::{message, to_html_with_options, CompileOptions, LineEnding, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn gfm() -> Result<(), message::Message> {
+    let input = "| a | b |\n| - | :-: |\n| ~~c~~ | www.d.com |\n\n* [x] e\n* [ ] f[^g]\n\n[^g]: h";
+
+    assert_eq!(
+        to_html_with_options(input, &Options::default())?,
+        "<p>| a | b |\n| - | :-: |\n| ~~c~~ | www.d.com |</p>\n<ul>\n<li>[x] e</li>\n<li>[ ] f<a href=\"h\">^g</a></li>\n</ul>",
+        "should not support GFM by default (footnotes are references)"
+    );
+
+    assert_eq!(
+        to_html_with_options(input, &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n<th align=\"center\">b</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td><del>c</del></td>\n<td align=\"center\"><a href=\"http://www.d.com\">www.d.com</a></td>\n</tr>\n</tbody>\n</table>\n<ul>\n<li><input type=\"checkbox\" disabled=\"\" checked=\"\" /> e</li>\n<li><input type=\"checkbox\" disabled=\"\" /> f<sup><a href=\"#user-content-fn-g\" id=\"user-content-fnref-g\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></li>\n</ul>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-g\">\n<p>h <a href=\"#user-content-fnref-g\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n",
+        "should support tables, strikethrough, autolink literals, tasklists, and footnotes w/ `Options::gfm()`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<xmp>\n\n<b>",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..CompileOptions::gfm()
+                },
+                ..Options::gfm()
+            }
+        )?,
+        "&lt;xmp>\n<b>",
+        "should support the tagfilter w/ `Options::gfm()`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "~a~",
+            &Options {
+                parse: ParseOptions {
+                    gfm_strikethrough_single_tilde: false,
+                    ..ParseOptions::gfm()
+                },
+                compile: CompileOptions {
+                    default_line_ending: Some(LineEnding::CarriageReturnLineFeed),
+                    ..CompileOptions::gfm()
+                },
+            }
+        )?,
+        "<p>~a~</p>",
+        "should compose w/ other parse options"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "* [x] a",
+            &Options {
+                compile: CompileOptions {
+                    default_line_ending: Some(LineEnding::CarriageReturnLineFeed),
+                    ..CompileOptions::gfm()
+                },
+                ..Options::gfm()
+            }
+        )?,
+        "<ul>\r\n<li><input type=\"checkbox\" disabled=\"\" checked=\"\" /> a</li>\r\n</ul>",
+        "should compose w/ other compile options, such as `default_line_ending`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "* [x] a",
+            &Options::builder()
+                .parse(ParseOptions::gfm())
+                .compile(CompileOptions::gfm())
+                .default_line_ending(LineEnding::CarriageReturnLineFeed)
+                .build()
+        )?,
+        "<ul>\r\n<li><input type=\"checkbox\" disabled=\"\" checked=\"\" /> a</li>\r\n</ul>",
+        "should compose w/ the options builder"
+    );
+
+    Ok(())
+}
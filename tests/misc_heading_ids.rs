@@ -0,0 +1,180 @@
+use markdown::{
+    message, to_html, to_html_with_options, CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use std::rc::Rc;
+
+#[test]
+fn heading_ids() -> Result<(), message::Message> {
+    let ids = &Options {
+        compile: CompileOptions {
+            heading_ids: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("## a"),
+        "<h2>a</h2>",
+        "should not add IDs to headings by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("## My Heading", ids)?,
+        "<h2 id=\"my-heading\">My Heading</h2>",
+        "should add IDs to headings (atx) w/ `heading_ids`"
+    );
+
+    assert_eq!(
+        to_html_with_options("My Heading\n---", ids)?,
+        "<h2 id=\"my-heading\">My Heading</h2>",
+        "should add IDs to headings (setext) w/ `heading_ids`"
+    );
+
+    assert_eq!(
+        to_html_with_options("## *hi* there", ids)?,
+        "<h2 id=\"hi-there\"><em>hi</em> there</h2>",
+        "should use the text of headings, without markup"
+    );
+
+    assert_eq!(
+        to_html_with_options("# Hello, World!", ids)?,
+        "<h1 id=\"hello-world\">Hello, World!</h1>",
+        "should drop punctuation"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n# a\n\na\n=\n# a-1", ids)?,
+        "<h1 id=\"a\">a</h1>\n<h1 id=\"a-1\">a</h1>\n<h1 id=\"a-2\">a</h1>\n<h1 id=\"a-1-1\">a-1</h1>",
+        "should make IDs of repeated headings unique"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a `b` c", ids)?,
+        "<h1 id=\"a-b-c\">a <code>b</code> c</h1>",
+        "should support code (text) in headings"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a [b](c \"d\") [e][] f\n\n[e]: g", ids)?,
+        "<h1 id=\"a-b-e-f\">a <a href=\"c\" title=\"d\">b</a> <a href=\"g\">e</a> f</h1>\n",
+        "should support links in headings, ignoring their destination and title"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a &amp; b", ids)?,
+        "<h1 id=\"a--b\">a &amp; b</h1>",
+        "should support character references in headings"
+    );
+
+    assert_eq!(
+        to_html_with_options("#", ids)?,
+        "<h1></h1>",
+        "should not add IDs to empty headings"
+    );
+
+    let custom = &Options {
+        compile: CompileOptions {
+            heading_ids: true,
+            heading_id_fn: Some(Rc::new(|value| value.to_uppercase().replace(' ', "."))),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("# *Crème* brûlée\n# Crème brûlée", custom)?,
+        "<h1 id=\"CRÈME.BRÛLÉE\"><em>Crème</em> brûlée</h1>\n<h1 id=\"CRÈME.BRÛLÉE-1\">Crème brûlée</h1>",
+        "should support a custom function w/ `heading_id_fn`, still making IDs unique"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# a",
+            &Options {
+                compile: CompileOptions {
+                    heading_id_fn: Some(Rc::new(|value| value.to_uppercase())),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<h1>a</h1>",
+        "should not use `heading_id_fn` w/o `heading_ids`"
+    );
+
+    assert_eq!(
+        to_html_with_options(&"# a\n".repeat(5000), ids)?,
+        (0..5000)
+            .map(|d| {
+                if d == 0 {
+                    "<h1 id=\"a\">a</h1>\n".to_string()
+                } else {
+                    format!("<h1 id=\"a-{}\">a</h1>\n", d)
+                }
+            })
+            .collect::<String>(),
+        "should make IDs of thousands of repeated headings unique, fast"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn heading_ids_attributes() -> Result<(), message::Message> {
+    let ids = &Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                attributes: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        compile: CompileOptions {
+            heading_ids: true,
+            ..Default::default()
+        },
+    };
+
+    assert_eq!(
+        to_html_with_options("# Start {#intro}\n# Intro", ids)?,
+        "<h1 id=\"intro\">Start</h1>\n<h1 id=\"intro-1\">Intro</h1>",
+        "should not reuse an `id` from an attribute list before"
+    );
+
+    assert_eq!(
+        to_html_with_options("# Intro\n# Start {#intro}", ids)?,
+        "<h1 id=\"intro-1\">Intro</h1>\n<h1 id=\"intro\">Start</h1>",
+        "should not reuse an `id` from an attribute list after"
+    );
+
+    assert_eq!(
+        to_html_with_options("# Intro\n\nIntro\n=====\n\n# a {#intro-1}", ids)?,
+        "<h1 id=\"intro\">Intro</h1>\n<h1 id=\"intro-2\">Intro</h1>\n<h1 id=\"intro-1\">a</h1>",
+        "should not reuse an `id` from an attribute list when making headings unique"
+    );
+
+    assert_eq!(
+        to_html_with_options("~~~js {#intro}\n~~~\n# Intro", ids)?,
+        "<pre><code id=\"intro\" class=\"language-js\"></code></pre>\n<h1 id=\"intro-1\">Intro</h1>",
+        "should not reuse an `id` from an attribute list on code (fenced)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# Start {#intro}\n# Intro",
+            &Options {
+                compile: CompileOptions {
+                    heading_ids: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<h1 id=\"start-intro\">Start {#intro}</h1>\n<h1 id=\"intro\">Intro</h1>",
+        "should not reserve IDs w/o attributes"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use markdown::{message, to_html_blocks, to_html_with_options, BlockKind, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn html_blocks() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html_blocks("", &Options::default())?.collect::<Vec<_>>(),
+        vec![],
+        "should support an empty document"
+    );
+
+    assert_eq!(
+        to_html_blocks("# a\n\nb\n\n***\n\n- c\n- d", &Options::default())?.collect::<Vec<_>>(),
+        vec![
+            (BlockKind::HeadingAtx, "<h1>a</h1>\n".into()),
+            (BlockKind::Paragraph, "<p>b</p>\n".into()),
+            (BlockKind::ThematicBreak, "<hr />\n".into()),
+            (
+                BlockKind::ListUnordered,
+                "<ul>\n<li>c</li>\n<li>d</li>\n</ul>".into()
+            )
+        ],
+        "should yield top-level blocks, w/ line endings between them at the end of the first"
+    );
+
+    assert_eq!(
+        to_html_blocks("> a\n> - b\n\n    c", &Options::default())?.collect::<Vec<_>>(),
+        vec![
+            (
+                BlockKind::BlockQuote,
+                "<blockquote>\n<p>a</p>\n<ul>\n<li>b</li>\n</ul>\n</blockquote>\n".into()
+            ),
+            (
+                BlockKind::CodeIndented,
+                "<pre><code>c\n</code></pre>".into()
+            )
+        ],
+        "should not yield blocks in containers"
+    );
+
+    assert_eq!(
+        to_html_blocks("[a]\n\n[a]: b\n\nc\r\n", &Options::default())?.collect::<Vec<_>>(),
+        vec![
+            (BlockKind::Paragraph, "<p><a href=\"b\">a</a></p>\n".into()),
+            (BlockKind::Definition, "".into()),
+            (BlockKind::Paragraph, "<p>c</p>\r\n".into())
+        ],
+        "should yield definitions, w/o HTML, and support references before them"
+    );
+
+    assert_eq!(
+        to_html_blocks("a[^b]\n\n[^b]: c", &Options::gfm())?
+            .map(|d| (d.0, d.1.split('>').next().unwrap().to_string()))
+            .collect::<Vec<_>>(),
+        vec![
+            (BlockKind::Paragraph, "<p".into()),
+            (BlockKind::GfmFootnoteDefinition, "".into()),
+            (
+                BlockKind::GfmFootnoteSection,
+                "<section data-footnotes=\"\" class=\"footnotes\"".into()
+            )
+        ],
+        "should yield the footnote section last (GFM)"
+    );
+
+    let inputs = [
+        "# a\n\nb *c*\nd\n===\n\n> e\n\n1. f\n\n   g\n2. h\n\n```js\ni\n```\n\n<div>\nj\n</div>\n\n***\n",
+        "a\n- b\n\n\n\n- c\n\n    d\n\ne\n",
+        "[a]: b\n\n[a]\n\n  > c\n  d\n",
+        "| a |\n| - |\n| b |\n\nc[^d]\n\n[^d]: e\n\n- [x] f\n",
+    ];
+
+    for input in inputs {
+        assert_eq!(
+            to_html_blocks(input, &Options::gfm())?
+                .map(|d| d.1)
+                .collect::<String>(),
+            to_html_with_options(input, &Options::gfm())?,
+            "should be the same as the whole document when joined: `{:?}`",
+            input
+        );
+    }
+
+    Ok(())
+}
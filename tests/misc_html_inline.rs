@@ -0,0 +1,64 @@
+use markdown::{message, to_html_inline, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn html_inline() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html_inline("", &Options::default())?,
+        "",
+        "should support an empty value"
+    );
+
+    assert_eq!(
+        to_html_inline("*hi*", &Options::default())?,
+        "<em>hi</em>",
+        "should support emphasis, w/o paragraph"
+    );
+
+    assert_eq!(
+        to_html_inline(
+            "a **b** `c` [d](e) <https://f>",
+            &Options::default()
+        )?,
+        "a <strong>b</strong> <code>c</code> <a href=\"e\">d</a> <a href=\"https://f\">https://f</a>",
+        "should support strong, code (text), links, and autolinks"
+    );
+
+    assert_eq!(
+        to_html_inline("  a  ", &Options::default())?,
+        "a",
+        "should strip initial and final whitespace"
+    );
+
+    assert_eq!(
+        to_html_inline("a  \nb", &Options::default())?,
+        "a<br />\nb",
+        "should support hard breaks"
+    );
+
+    assert_eq!(
+        to_html_inline("# a\n- b\n> c\n\n***", &Options::default())?,
+        "# a\n- b\n&gt; c\n\n***",
+        "should not support flow (block) constructs"
+    );
+
+    assert_eq!(
+        to_html_inline("[a]\n\n[a]: b", &Options::default())?,
+        "[a]\n\n[a]: b",
+        "should not support definitions (or references to them)"
+    );
+
+    assert_eq!(
+        to_html_inline("<b>a</b>", &Options::default())?,
+        "&lt;b&gt;a&lt;/b&gt;",
+        "should be safe by default"
+    );
+
+    assert_eq!(
+        to_html_inline("~a~ www.b.com", &Options::gfm())?,
+        "<del>a</del> <a href=\"http://www.b.com\">www.b.com</a>",
+        "should support GFM"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use markdown::{message, to_html_with_map, unist::Point, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn html_map() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html_with_map("", &Options::default())?,
+        ("".into(), vec![]),
+        "should support an empty document"
+    );
+
+    assert_eq!(
+        to_html_with_map("# a\n\nb *c*", &Options::default())?,
+        (
+            "<h1>a</h1>\n<p>b <em>c</em></p>".into(),
+            vec![
+                (0, Point::new(1, 1, 0)),
+                (5, Point::new(1, 4, 3)),
+                (10, Point::new(1, 4, 3)),
+                (11, Point::new(3, 1, 5)),
+                (14, Point::new(3, 1, 5)),
+                (16, Point::new(3, 3, 7)),
+                (20, Point::new(3, 4, 8)),
+                (21, Point::new(3, 6, 10)),
+                (26, Point::new(3, 6, 10)),
+            ]
+        ),
+        "should map elements and text to where they start, closing tags to where things end"
+    );
+
+    let (html, map) = to_html_with_map("> a\n>\n> ```js\n> b\n> ```", &Options::default())?;
+    let find = |value: &str| {
+        let offset = html.find(value).unwrap();
+        map.iter().find(|d| d.0 == offset).map(|d| d.1.clone())
+    };
+
+    assert_eq!(
+        find("<blockquote>"),
+        Some(Point::new(1, 1, 0)),
+        "should map block quotes"
+    );
+
+    assert_eq!(
+        find("<pre>"),
+        Some(Point::new(3, 3, 8)),
+        "should map code (fenced)"
+    );
+
+    assert_eq!(find("b\n"), Some(Point::new(4, 3, 16)), "should map code");
+
+    let (html, map) = to_html_with_map("[a](b)", &Options::default())?;
+
+    assert_eq!(
+        (html.as_str(), map.len()),
+        ("<p><a href=\"b\">a</a></p>", 3),
+        "should not map the contents of links"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use markdown::{
+    message, to_html, to_html_with_options, CompileOptions, Constructs, HtmlSyntax, Options,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn html_syntax() -> Result<(), message::Message> {
+    let xhtml = &Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            html_syntax: HtmlSyntax::Xhtml,
+            ..Default::default()
+        },
+    };
+    let html = &Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            html_syntax: HtmlSyntax::Html,
+            ..Default::default()
+        },
+    };
+    let checkable = &Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            gfm_task_list_item_checkable: true,
+            html_syntax: HtmlSyntax::Html,
+            ..Default::default()
+        },
+    };
+
+    assert_eq!(
+        to_html("---"),
+        "<hr />",
+        "should close void elements by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("* [x] a", &Options::gfm())?,
+        "<ul>\n<li><input type=\"checkbox\" disabled=\"\" checked=\"\" /> a</li>\n</ul>",
+        "should use empty boolean attributes by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "* [x] a\n\n---",
+            &Options {
+                parse: ParseOptions::gfm(),
+                compile: CompileOptions {
+                    html_syntax: HtmlSyntax::Polyglot,
+                    ..Default::default()
+                },
+            }
+        )?,
+        to_html_with_options("* [x] a\n\n---", &Options::gfm())?,
+        "should use polyglot syntax by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("---", xhtml)?,
+        "<hr />",
+        "should close void elements in XHTML (thematic break)"
+    );
+
+    assert_eq!(
+        to_html_with_options("* [x] a\n* [ ] b", xhtml)?,
+        "<ul>\n<li><input type=\"checkbox\" disabled=\"disabled\" checked=\"checked\" /> a</li>\n<li><input type=\"checkbox\" disabled=\"disabled\" /> b</li>\n</ul>",
+        "should use XHTML boolean attributes (task list item)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\\\nb  \nc\n![d](e)", xhtml)?,
+        "<p>a<br />\nb<br />\nc\n<img src=\"e\" alt=\"d\" /></p>",
+        "should close void elements in XHTML (hard break, image)"
+    );
+
+    assert_eq!(
+        to_html_with_options("---", html)?,
+        "<hr>",
+        "should not close void elements in HTML (thematic break)"
+    );
+
+    assert_eq!(
+        to_html_with_options("* [x] a\n* [ ] b", html)?,
+        "<ul>\n<li><input type=\"checkbox\" disabled checked> a</li>\n<li><input type=\"checkbox\" disabled> b</li>\n</ul>",
+        "should use HTML boolean attributes (task list item)"
+    );
+
+    assert_eq!(
+        to_html_with_options("* [x] a", checkable)?,
+        "<ul>\n<li><input type=\"checkbox\" checked> a</li>\n</ul>",
+        "should use HTML boolean attributes (checkable task list item)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\\\nb  \nc\n![d](e)", html)?,
+        "<p>a<br>\nb<br>\nc\n<img src=\"e\" alt=\"d\"></p>",
+        "should not close void elements in HTML (hard break, image)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a\nb",
+            &Options {
+                compile: CompileOptions {
+                    hard_line_breaks: true,
+                    html_syntax: HtmlSyntax::Html,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a<br>\nb</p>",
+        "should not close void elements in HTML (hard line breaks)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<br/>\n\n---",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    html_syntax: HtmlSyntax::Html,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<br/>\n<hr>",
+        "should not change raw HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "* [x] a\n\n---",
+            &Options::builder()
+                .constructs(Constructs::gfm())
+                .html_syntax(HtmlSyntax::Html)
+                .build()
+        )?,
+        "<ul>\n<li><input type=\"checkbox\" disabled checked> a</li>\n</ul>\n<hr>",
+        "should support `html_syntax` in the builder"
+    );
+
+    Ok(())
+}
@@ -186,5 +186,41 @@ fn line_ending() -> Result<(), message::Message> {
         "should support a non-blank line w/ carriage return + line feed in html"
     );
 
+    assert_eq!(
+        to_html("a\r"),
+        "<p>a</p>\r",
+        "should support a final carriage return"
+    );
+
+    assert_eq!(
+        to_html("```\ra\r"),
+        "<pre><code>a\r</code></pre>\r",
+        "should support a final carriage return in fenced code"
+    );
+
+    assert_eq!(
+        to_html("`a\r`"),
+        "<p><code>a </code></p>",
+        "should support a carriage return in code (text)"
+    );
+
+    assert_eq!(
+        to_html("a\\\rb  \r\nc"),
+        "<p>a<br />\rb<br />\r\nc</p>",
+        "should support hard breaks before a carriage return (+ line feed)"
+    );
+
+    assert_eq!(
+        to_html("# a\r\n- b\n> c"),
+        "<h1>a</h1>\r\n<ul>\r\n<li>b</li>\r\n</ul>\n<blockquote>\r\n<p>c</p>\r\n</blockquote>",
+        "should use the first line ending (a carriage return + line feed) for added line endings in a document w/ mixed line endings"
+    );
+
+    assert_eq!(
+        to_html("a\rb\nc\r\nd\n\r\ne\r"),
+        "<p>a\rb\nc\r\nd</p>\n<p>e</p>\r",
+        "should keep each line ending in a document w/ mixed line endings"
+    );
+
     Ok(())
 }
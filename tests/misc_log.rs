@@ -0,0 +1,22 @@
+use markdown::{message, to_html_with_options, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn log() -> Result<(), message::Message> {
+    // With the `log` feature, the tokenizer logs what it does; without it,
+    // no logging code is compiled in.
+    // Either way, the output is the same.
+    #[cfg(feature = "log")]
+    let _ = env_logger::builder()
+        .is_test(true)
+        .filter_level(log::LevelFilter::Trace)
+        .try_init();
+
+    assert_eq!(
+        to_html_with_options("# a *b*\n\n> c", &Options::gfm())?,
+        "<h1>a <em>b</em></h1>\n<blockquote>\n<p>c</p>\n</blockquote>",
+        "should work w/ and w/o the `log` feature"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use markdown::{mdast::Node, message, to_html, to_mdast, ParseOptions};
+use pretty_assertions::assert_eq;
+
+/// Size of a line: 1 MB.
+const SIZE: usize = 1024 * 1024;
+
+#[test]
+fn long_line() -> Result<(), message::Message> {
+    let value = "alpha bravo ".repeat(SIZE / 12);
+
+    assert_eq!(
+        to_html(&value).len(),
+        value.len() - 1 + "<p></p>".len(),
+        "should support a 1 MB line"
+    );
+
+    let tree = to_mdast(&value, &ParseOptions::default())?;
+    let paragraph = &tree.children().unwrap()[0];
+
+    assert!(
+        matches!(paragraph.children().unwrap().as_slice(), [Node::Text(_)]),
+        "should support a 1 MB line in mdast"
+    );
+
+    let value = "a *b* ".repeat(SIZE / 6);
+
+    assert_eq!(
+        to_html(&value).matches("<em>").count(),
+        SIZE / 6,
+        "should support a 1 MB line w/ lots of attention"
+    );
+
+    let value = "a* ".repeat(SIZE / 3);
+
+    assert_eq!(
+        to_html(&value).len(),
+        value.len() - 1 + "<p></p>".len(),
+        "should support a 1 MB line w/ lots of unmatched attention"
+    );
+
+    let value = "[a](b) ".repeat(SIZE / 7);
+
+    assert_eq!(
+        to_html(&value).matches("<a href=\"b\">").count(),
+        SIZE / 7,
+        "should support a 1 MB line w/ lots of links"
+    );
+
+    Ok(())
+}
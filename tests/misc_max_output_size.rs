@@ -0,0 +1,112 @@
+use markdown::{
+    message, to_html, to_html_blocks, to_html_with_map, to_html_with_options, CompileOptions,
+    Options,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn max_output_size() -> Result<(), message::Message> {
+    let limited = Options {
+        compile: CompileOptions {
+            max_output_size: Some(64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let references = format!("[a]: {}\n\n{}", "b".repeat(32), "[a] ".repeat(32));
+
+    assert_eq!(
+        to_html(&references).len(),
+        1574,
+        "should not limit output by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n\n*b*", &limited)?,
+        "<h1>a</h1>\n<p><em>b</em></p>",
+        "should support output smaller than `max_output_size`"
+    );
+
+    assert_eq!(
+        to_html_with_options(&"a".repeat(57), &limited)?.len(),
+        64,
+        "should support output as big as `max_output_size`"
+    );
+
+    assert_eq!(
+        to_html_with_options(&"a".repeat(58), &limited)
+            .unwrap_err()
+            .to_string(),
+        "1:59: Unexpected HTML bigger than `max_output_size` (`64` bytes) (markdown-rs:max-output-size)",
+        "should error on output bigger than `max_output_size`"
+    );
+
+    assert_eq!(
+        to_html_with_options(&references, &limited)
+            .unwrap_err()
+            .to_string(),
+        "3:8: Unexpected HTML bigger than `max_output_size` (`64` bytes) (markdown-rs:max-output-size)",
+        "should stop as soon as output is bigger than `max_output_size`"
+    );
+
+    assert!(
+        to_html_with_options(
+            "a[^b]\n\n[^b]: c",
+            &Options {
+                compile: CompileOptions {
+                    max_output_size: Some(256),
+                    ..Default::default()
+                },
+                ..Options::gfm()
+            }
+        )
+        .is_err(),
+        "should check the footnote section"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            &format!("[a]: {}\n\n[^b]: {}", "b".repeat(32), "[a] ".repeat(32)),
+            &Options {
+                compile: CompileOptions {
+                    max_output_size: Some(64),
+                    ..Default::default()
+                },
+                ..Options::gfm()
+            }
+        )
+        .unwrap_err()
+        .to_string(),
+        "3:14: Unexpected HTML bigger than `max_output_size` (`64` bytes) (markdown-rs:max-output-size)",
+        "should check footnote definitions as they are compiled"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            &"[^a]: b\n\n".repeat(32),
+            &Options {
+                compile: CompileOptions {
+                    max_output_size: Some(64),
+                    ..Default::default()
+                },
+                ..Options::gfm()
+            }
+        )
+        .unwrap_err()
+        .to_string(),
+        "15:7: Unexpected HTML bigger than `max_output_size` (`64` bytes) (markdown-rs:max-output-size)",
+        "should check the total of footnote definitions"
+    );
+
+    assert!(
+        to_html_with_map(&references, &limited).is_err(),
+        "should check `to_html_with_map`"
+    );
+
+    assert!(
+        to_html_blocks(&references, &limited).is_err(),
+        "should check `to_html_blocks`"
+    );
+
+    Ok(())
+}
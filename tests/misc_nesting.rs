@@ -0,0 +1,87 @@
+use markdown::{message, to_html, to_html_with_options, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn nesting() -> Result<(), message::Message> {
+    let limited = Options {
+        parse: ParseOptions {
+            max_nesting_depth: Some(2),
+            ..Options::gfm().parse
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("> > > a\n\n***a***"),
+        "<blockquote>\n<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n</blockquote>\n</blockquote>\n<p><em><strong>a</strong></em></p>",
+        "should not limit nesting by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("> > > a", &limited)?,
+        "<blockquote>\n<blockquote>\n<p>&gt; a</p>\n</blockquote>\n</blockquote>",
+        "should not open block quotes deeper than `max_nesting_depth`"
+    );
+
+    assert_eq!(
+        to_html_with_options("- > - a\n\n> - b", &limited)?,
+        "<ul>\n<li>\n<blockquote>\n<p>- a</p>\n</blockquote>\n</li>\n</ul>\n<blockquote>\n<ul>\n<li>b</li>\n</ul>\n</blockquote>",
+        "should not open mixed containers deeper than `max_nesting_depth`"
+    );
+
+    assert_eq!(
+        to_html_with_options("[^a]\n\n[^a]: > > b", &limited)?,
+        "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<blockquote>\n<p>&gt; b</p>\n</blockquote>\n<a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a>\n</li>\n</ol>\n</section>\n",
+        "should count footnote definitions as containers"
+    );
+
+    assert_eq!(
+        to_html_with_options("**a** ***b*** *****c*****", &limited)?,
+        "<p><strong>a</strong> <em><strong>b</strong></em> *<strong><strong>c</strong></strong>*</p>",
+        "should not match attention sequences more than `max_nesting_depth` times"
+    );
+
+    assert_eq!(
+        to_html_with_options("~~a~~ *b **c** d*", &limited)?,
+        "<p><del>a</del> <em>b <strong>c</strong> d</em></p>",
+        "should support attention in separate sequences"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn nesting_pathological() -> Result<(), message::Message> {
+    let limited = Options {
+        parse: ParseOptions {
+            max_nesting_depth: Some(32),
+            ..Options::gfm().parse
+        },
+        ..Options::gfm()
+    };
+
+    let brackets = "[".repeat(5000);
+    assert_eq!(
+        to_html_with_options(&brackets, &limited)?,
+        format!("<p>{}</p>", brackets),
+        "should support thousands of `[`"
+    );
+
+    let value = format!("{}a{}", "*".repeat(5000), "*".repeat(5000));
+    let result = to_html_with_options(&value, &limited)?;
+    assert_eq!(
+        result.matches("<strong>").count(),
+        32,
+        "should stop matching long attention sequences"
+    );
+
+    let value = format!("{}a", "> ".repeat(5000));
+    let result = to_html_with_options(&value, &limited)?;
+    assert_eq!(
+        result.matches("<blockquote>").count(),
+        32,
+        "should stop opening deeply nested containers"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use markdown::{
+    event::Event, message, to_html_with_options, CompileOptions, Constructs, LineEnding, Options,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn options_builder() -> Result<(), message::Message> {
+    let input = "> <i>a</i> ~b~ [^c]\n\n[^c]: d";
+
+    assert_eq!(
+        to_html_with_options(input, &Options::builder().build())?,
+        to_html_with_options(input, &Options::default())?,
+        "should default to the default options"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            input,
+            &Options::builder()
+                .allow_dangerous_html(true)
+                .default_line_ending(LineEnding::CarriageReturn)
+                .build()
+        )?,
+        to_html_with_options(
+            input,
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    default_line_ending: Some(LineEnding::CarriageReturn),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "should support compile options"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            input,
+            &Options::builder()
+                .constructs(Constructs::gfm())
+                .gfm_strikethrough_single_tilde(false)
+                .gfm_footnote_label("Notes")
+                .gfm_footnote_clobber_prefix("")
+                .build()
+        )?,
+        to_html_with_options(
+            input,
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs::gfm(),
+                    gfm_strikethrough_single_tilde: false,
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    gfm_footnote_label: Some("Notes".into()),
+                    gfm_footnote_clobber_prefix: Some(String::new()),
+                    ..Default::default()
+                },
+            }
+        )?,
+        "should support parse options"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            input,
+            &Options::builder()
+                .resolvers(vec![(
+                    "a".into(),
+                    Box::new(|events: &mut Vec<Event>, _: &str| events.clear())
+                )])
+                .build()
+        )?,
+        to_html_with_options(
+            input,
+            &Options {
+                parse: ParseOptions {
+                    resolvers: vec![(
+                        "a".into(),
+                        Box::new(|events: &mut Vec<Event>, _: &str| events.clear())
+                    )],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "should support resolvers"
+    );
+
+    assert_eq!(
+        Options::builder()
+            .resolvers(vec![(
+                "a".into(),
+                Box::new(|_: &mut Vec<Event>, _: &str| {})
+            )])
+            .resolver("b", Box::new(|_: &mut Vec<Event>, _: &str| {}))
+            .build()
+            .parse
+            .resolvers
+            .iter()
+            .map(|d| d.0.as_str())
+            .collect::<Vec<_>>(),
+        vec!["a", "b"],
+        "should support adding a resolver after setting resolvers"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            input,
+            &Options::builder()
+                .parse(ParseOptions::gfm())
+                .compile(CompileOptions::gfm())
+                .build()
+        )?,
+        to_html_with_options(input, &Options::gfm())?,
+        "should support setting all parse and compile options"
+    );
+
+    Ok(())
+}
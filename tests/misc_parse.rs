@@ -0,0 +1,385 @@
+use markdown::{
+    event::{visit, Event, Kind, Name, Visitor},
+    events_to_html, message, parse, to_html_with_options,
+    unist::Position,
+    CodeFence, CompileOptions, Constructs, Definition, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn parse_definitions() -> Result<(), message::Message> {
+    assert_eq!(
+        parse("a", &ParseOptions::default())?.definitions.len(),
+        0,
+        "should support no definitions"
+    );
+
+    let result = parse("[a]: /x \"t\"", &ParseOptions::default())?;
+
+    assert_eq!(result.definitions.len(), 1, "should expose a definition");
+
+    assert_eq!(
+        result.definitions.get("a"),
+        Some(&Definition {
+            destination: "/x".into(),
+            title: Some("t".into())
+        }),
+        "should expose a definition by normalized identifier"
+    );
+
+    let result = parse(
+        "[A\t B]: <&amp;\\*> (\\(&quot;\\))\n[a b]: y\n\n> * [C]: z",
+        &ParseOptions::default(),
+    )?;
+
+    assert_eq!(
+        result.definitions.keys().collect::<Vec<_>>(),
+        vec!["a b", "c"],
+        "should normalize identifiers like references, and find definitions in containers"
+    );
+
+    assert_eq!(
+        result.definitions.get("a b"),
+        Some(&Definition {
+            destination: "&*".into(),
+            title: Some("(\")".into())
+        }),
+        "should use the first definition, w/ decoded destination and title"
+    );
+
+    assert_eq!(
+        result.definitions.get("c"),
+        Some(&Definition {
+            destination: "z".into(),
+            title: None
+        }),
+        "should support a definition w/o title"
+    );
+
+    assert_eq!(
+        parse("```\n[a]: b\n```", &ParseOptions::default())?
+            .definitions
+            .len(),
+        0,
+        "should not expose things that look like definitions in code"
+    );
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct Headings {
+    count: usize,
+    depth: usize,
+    names: Vec<Name>,
+}
+
+impl Visitor for Headings {
+    fn enter(&mut self, event: &Event) {
+        if matches!(event.name, Name::HeadingAtx | Name::HeadingSetext) {
+            self.count += 1;
+        }
+        self.depth += 1;
+        self.names.push(event.name.clone());
+    }
+
+    fn exit(&mut self, event: &Event) {
+        self.depth -= 1;
+        assert_eq!(
+            self.names.pop(),
+            Some(event.name.clone()),
+            "should pair enters and exits"
+        );
+    }
+}
+
+#[test]
+fn parse_events() -> Result<(), message::Message> {
+    let result = parse("# a", &ParseOptions::default())?;
+
+    assert_eq!(
+        result
+            .events
+            .iter()
+            .map(|event| (event.kind.clone(), event.name.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (Kind::Enter, Name::HeadingAtx),
+            (Kind::Enter, Name::HeadingAtxSequence),
+            (Kind::Exit, Name::HeadingAtxSequence),
+            (Kind::Enter, Name::SpaceOrTab),
+            (Kind::Exit, Name::SpaceOrTab),
+            (Kind::Enter, Name::HeadingAtxText),
+            (Kind::Enter, Name::Data),
+            (Kind::Exit, Name::Data),
+            (Kind::Exit, Name::HeadingAtxText),
+            (Kind::Exit, Name::HeadingAtx),
+        ],
+        "should expose events"
+    );
+
+    let mut visitor = Headings::default();
+    visit(
+        &parse("# a\n\nb\n=\n\n> ## *c*\n\nd", &ParseOptions::default())?.events,
+        &mut visitor,
+    );
+    assert_eq!(
+        visitor.count, 3,
+        "should visit headings, also in containers"
+    );
+    assert_eq!(visitor.depth, 0, "should exit everything that is entered");
+
+    let mut visitor = Headings::default();
+    visit(
+        &parse(
+            "# a\n\nb\n=",
+            &ParseOptions {
+                constructs: Constructs {
+                    heading_setext: false,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )?
+        .events,
+        &mut visitor,
+    );
+    assert_eq!(visitor.count, 1, "should visit what is parsed");
+
+    Ok(())
+}
+
+/// Slice the markdown of the first `name` in `value`.
+fn slice(value: &str, name: &Name) -> Result<String, message::Message> {
+    let result = parse(value, &ParseOptions::default())?;
+    let enter = result
+        .events
+        .iter()
+        .find(|event| event.kind == Kind::Enter && event.name == *name)
+        .unwrap();
+    let exit = result
+        .events
+        .iter()
+        .find(|event| event.kind == Kind::Exit && event.name == *name)
+        .unwrap();
+    Ok(result.slice(enter, exit))
+}
+
+#[test]
+fn parse_slice() -> Result<(), message::Message> {
+    assert_eq!(
+        slice("```js  \na\n```", &Name::CodeFencedFenceInfo)?,
+        "js",
+        "should slice the info string of fenced code"
+    );
+
+    assert_eq!(
+        slice("~~~ a&amp;\\* b\n~~~", &Name::CodeFencedFenceMeta)?,
+        "b",
+        "should slice the meta string of fenced code"
+    );
+
+    assert_eq!(
+        slice("~~~ a&amp;\\*\n~~~", &Name::CodeFencedFenceInfo)?,
+        "a&amp;\\*",
+        "should not decode character escapes and character references"
+    );
+
+    assert_eq!(
+        slice("> ```js\n> a\n> ```", &Name::CodeFenced)?,
+        "```js\n> a\n> ```",
+        "should slice across lines, including container prefixes"
+    );
+
+    assert_eq!(
+        slice("a\n\n# é *b*", &Name::HeadingAtxText)?,
+        "é *b*",
+        "should slice non-ASCII"
+    );
+
+    assert_eq!(
+        slice("\u{FEFF}# a", &Name::HeadingAtxText)?,
+        "a",
+        "should slice after a BOM"
+    );
+
+    assert_eq!(
+        slice(" -\t\ta", &Name::CodeFlowChunk)?,
+        "\ta",
+        "should include a whole tab if only part of it is in the events"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_code_fences() -> Result<(), message::Message> {
+    assert_eq!(
+        parse("a", &ParseOptions::default())?.code_fences(),
+        vec![],
+        "should support no code fences"
+    );
+
+    assert_eq!(
+        parse("~~~~js\na\n~~~~", &ParseOptions::default())?.code_fences(),
+        vec![CodeFence {
+            marker: '~',
+            size: 4,
+            position: Position::new(1, 1, 0, 1, 5, 4)
+        }],
+        "should support a fence of four tildes"
+    );
+
+    assert_eq!(
+        parse("```\na\n`````\n\n> ````b\n", &ParseOptions::default())?.code_fences(),
+        vec![
+            CodeFence {
+                marker: '`',
+                size: 3,
+                position: Position::new(1, 1, 0, 1, 4, 3)
+            },
+            CodeFence {
+                marker: '`',
+                size: 4,
+                position: Position::new(5, 3, 15, 5, 7, 19)
+            }
+        ],
+        "should support several fences, only opening ones, also in containers"
+    );
+
+    let value = "  ~~~~~ a\nb\n";
+    let result = parse(value, &ParseOptions::default())?;
+    let fence = &result.code_fences()[0];
+
+    assert_eq!(
+        &value[fence.position.start.offset..fence.position.end.offset],
+        "~~~~~",
+        "should support slicing the fence with its position"
+    );
+
+    assert_eq!(
+        parse(
+            "    ```\n\n$$\na\n$$",
+            &ParseOptions {
+                constructs: Constructs {
+                    math_flow: true,
+                    ..Constructs::default()
+                },
+                ..ParseOptions::default()
+            }
+        )?
+        .code_fences(),
+        vec![],
+        "should not include code (indented) or math (flow)"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_events_to_html() -> Result<(), message::Message> {
+    let values = [
+        "",
+        "# a *b* **c**\n\nd\n===",
+        "> - a\n>   b\n> 1. c\n\n***",
+        "[a] [b][] ![c][d]\n\n[a]: e\n[b]: <f> \"g\"\n[d]: h",
+        "```js\na\n```\n\n    b\n\n<div>\n*c*\n</div>",
+        "a\r\nb  \r\nc\\\r\n`d\r\ne`",
+        "&amp; &#x41; \\* <https://a.b>",
+        "a[^b] ~c~ www.d.e\n\n| f |\n| - |\n| g |\n\n* [x] h\n\n[^b]: i",
+    ];
+    let options = Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            ..CompileOptions::gfm()
+        },
+    };
+
+    for value in values {
+        let result = parse(value, &options.parse)?;
+
+        assert_eq!(
+            events_to_html(&result.events, value, &options)?,
+            to_html_with_options(value, &options)?,
+            "should compile the events of `parse` like `to_html_with_options` ({:?})",
+            value
+        );
+    }
+
+    let value = "*a*";
+    let mut events = parse(value, &ParseOptions::default())?.events;
+
+    for event in &mut events {
+        event.name = match event.name {
+            Name::Emphasis => Name::Strong,
+            Name::EmphasisSequence => Name::StrongSequence,
+            Name::EmphasisText => Name::StrongText,
+            _ => event.name.clone(),
+        };
+    }
+
+    assert_eq!(
+        events_to_html(&events, value, &Options::default())?,
+        "<p><strong>a</strong></p>",
+        "should compile changed events"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_event_names() -> Result<(), message::Message> {
+    assert_eq!(Name::ALL.len(), 209, "should list all names");
+
+    let mut index = 0;
+
+    while index < Name::ALL.len() {
+        assert!(
+            !Name::ALL[(index + 1)..].contains(&Name::ALL[index]),
+            "should list each name once ({:?})",
+            Name::ALL[index]
+        );
+        assert_eq!(
+            Name::ALL[index].index(),
+            index,
+            "should match the index of each name ({:?})",
+            Name::ALL[index]
+        );
+        index += 1;
+    }
+
+    let options = ParseOptions {
+        constructs: Constructs {
+            attributes: true,
+            directive_container: true,
+            directive_leaf: true,
+            directive_text: true,
+            frontmatter: true,
+            hashtag: true,
+            label_start_inline_footnote: true,
+            mark: true,
+            math_flow: true,
+            math_text: true,
+            mention: true,
+            wikilink: true,
+            ..Constructs::gfm()
+        },
+        ..ParseOptions::gfm()
+    };
+    let result = parse(
+        "---\na: b\n---\n\n# a {#b}\n\n> *c* __d__ `e` $f$ ==g== ~h~ <i> <https://j> &amp; \\*\n\n- [x] k[^l] ^[m] [[n]] #o @p :q[r]\n\n| s |\n| - |\n\n```t\nu\n```\n\n$$\nv\n$$\n\n:::w\nx\n:::\n\n[^l]: y\n[z]: /a",
+        &options,
+    )?;
+
+    for event in &result.events {
+        assert_eq!(
+            Name::ALL[event.name.index()],
+            event.name,
+            "should list names that are used ({:?})",
+            event.name
+        );
+    }
+
+    Ok(())
+}
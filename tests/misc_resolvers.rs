@@ -0,0 +1,152 @@
+use markdown::{
+    event::{Event, Name},
+    mdast::{Node, Paragraph, Root, Strong, Text},
+    message, parse, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Options, ParseOptions, Resolver,
+};
+use pretty_assertions::assert_eq;
+use std::{cell::RefCell, rc::Rc};
+
+fn rename(from: Name, to: Name) -> Box<Resolver> {
+    Box::new(move |events: &mut Vec<Event>, _value: &str| {
+        for event in events {
+            if event.name == from {
+                event.name = to.clone();
+            }
+        }
+    })
+}
+
+#[test]
+fn resolvers() -> Result<(), message::Message> {
+    let strong = ParseOptions {
+        resolvers: vec![("strong".into(), rename(Name::Emphasis, Name::Strong))],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("*a*"),
+        "<p><em>a</em></p>",
+        "should not change events by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "*a* b",
+            &Options {
+                parse: ParseOptions {
+                    resolvers: vec![("strong".into(), rename(Name::Emphasis, Name::Strong))],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><strong>a</strong> b</p>",
+        "should support a resolver that renames a token type"
+    );
+
+    assert_eq!(
+        to_mdast("*a*", &strong)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Strong(Strong {
+                    children: vec![Node::Text(Text {
+                        value: "a".into(),
+                        position: Some(Position::new(1, 2, 1, 1, 3, 2))
+                    }),],
+                    position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                }),],
+                position: Some(Position::new(1, 1, 0, 1, 4, 3))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 4, 3))
+        }),
+        "should support resolvers in mdast"
+    );
+
+    assert!(
+        parse("*a*", &strong)?
+            .events
+            .iter()
+            .all(|event| event.name != Name::Emphasis),
+        "should support resolvers in `parse`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "*a* **b**",
+            &Options {
+                parse: ParseOptions {
+                    resolvers: vec![
+                        ("strong".into(), rename(Name::Emphasis, Name::Strong)),
+                        ("emphasis".into(), rename(Name::Strong, Name::Emphasis)),
+                    ],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><em>a</em> <em>b</em></p>",
+        "should call resolvers in order"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "*a* **b**",
+            &Options {
+                parse: ParseOptions {
+                    resolvers: vec![
+                        ("x".into(), rename(Name::Emphasis, Name::Strong)),
+                        ("x".into(), rename(Name::Strong, Name::Emphasis)),
+                    ],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><strong>a</strong> <strong>b</strong></p>",
+        "should only call the first resolver w/ an ID"
+    );
+
+    let seen = Rc::new(RefCell::new(vec![]));
+    let seen_in_resolver = Rc::clone(&seen);
+
+    to_html_with_options(
+        "a\n\n*b*",
+        &Options {
+            parse: ParseOptions {
+                resolvers: vec![(
+                    "seen".into(),
+                    Box::new(move |events: &mut Vec<Event>, value: &str| {
+                        seen_in_resolver.borrow_mut().push(format!(
+                            "{} ({} events)",
+                            value,
+                            events.len()
+                        ));
+                    }),
+                )],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )?;
+
+    assert_eq!(
+        *seen.borrow(),
+        vec!["a\n\n*b* (20 events)".to_string()],
+        "should call a resolver once, w/ all events and the markdown"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "*a*",
+            &Options::builder()
+                .resolver("strong", rename(Name::Emphasis, Name::Strong))
+                .build()
+        )?,
+        "<p><strong>a</strong></p>",
+        "should support adding resolvers w/ the builder"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use markdown::{message, to_html_with_options, Constructs, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn safe() -> Result<(), message::Message> {
+    let safe = Options::safe();
+
+    assert_eq!(
+        to_html_with_options("<script>alert(1)</script>", &safe)?,
+        "&lt;script&gt;alert(1)&lt;/script&gt;",
+        "should encode HTML (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <script>alert(1)</script> b", &safe)?,
+        "<p>a &lt;script&gt;alert(1)&lt;/script&gt; b</p>",
+        "should encode HTML (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](javascript:alert(1))", &safe)?,
+        "<p><a href=\"\">a</a></p>",
+        "should drop dangerous protocols in links"
+    );
+
+    assert_eq!(
+        to_html_with_options("<javascript:alert(1)>", &safe)?,
+        "<p><a href=\"\">javascript:alert(1)</a></p>",
+        "should drop dangerous protocols in autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](javascript:alert(1))", &safe)?,
+        "<p><img src=\"\" alt=\"a\" /></p>",
+        "should drop dangerous protocols in images"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](https://example.com) *b*", &safe)?,
+        "<p><a href=\"https://example.com\">a</a> <em>b</em></p>",
+        "should support normal markdown"
+    );
+
+    assert_eq!(
+        to_html_with_options(&format!("{}a", "> ".repeat(40)), &safe)?,
+        format!(
+            "{}<p>{}a</p>\n{}",
+            "<blockquote>\n".repeat(32),
+            "&gt; ".repeat(8),
+            "</blockquote>\n".repeat(31) + "</blockquote>"
+        ),
+        "should limit nesting"
+    );
+
+    let mut dangerous = Options::safe();
+    dangerous.compile.allow_dangerous_html = true;
+
+    assert_eq!(
+        to_html_with_options("<script>alert(1)</script>", &dangerous)?,
+        "&lt;script>alert(1)&lt;/script>",
+        "should use the GFM tagfilter if HTML is allowed later"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "~a~",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs::gfm(),
+                    ..Options::safe().parse
+                },
+                ..Options::safe()
+            }
+        )?,
+        "<p><del>a</del></p>",
+        "should support turning on more constructs"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn smart_punctuation() -> Result<(), message::Message> {
+    let smart = Options {
+        parse: ParseOptions {
+            smart_punctuation: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("\"a\" -- b's..."),
+        "<p>&quot;a&quot; -- b's...</p>",
+        "should not support smart punctuation by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("\"a\" 'b'", &smart)?,
+        "<p>“a” ‘b’</p>",
+        "should support quotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("\"*hi*\"", &smart)?,
+        "<p>“<em>hi</em>”</p>",
+        "should support quotes around emphasis"
+    );
+
+    assert_eq!(
+        to_html_with_options("a **\"b\"** \"c\".", &smart)?,
+        "<p>a <strong>“b”</strong> “c”.</p>",
+        "should support quotes in emphasis and before punctuation"
+    );
+
+    assert_eq!(
+        to_html_with_options("don't, it's", &smart)?,
+        "<p>don’t, it’s</p>",
+        "should support apostrophes in contractions"
+    );
+
+    assert_eq!(
+        to_html_with_options("1--2, 1---2", &smart)?,
+        "<p>1–2, 1—2</p>",
+        "should support dashes adjacent to numbers"
+    );
+
+    assert_eq!(
+        to_html_with_options("a - b", &smart)?,
+        "<p>a - b</p>",
+        "should not support a single dash"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ---- b ----- c ------ d", &smart)?,
+        "<p>a –– b —– c —— d</p>",
+        "should support longer sequences of dashes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a... b.... c..", &smart)?,
+        "<p>a… b…. c..</p>",
+        "should support ellipses"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\\"a\\\" \\-\\- b", &smart)?,
+        "<p>&quot;a&quot; -- b</p>",
+        "should not support escaped characters"
+    );
+
+    assert_eq!(
+        to_html_with_options("`\"a\" -- b...`", &smart)?,
+        "<p><code>&quot;a&quot; -- b...</code></p>",
+        "should not support smart punctuation in code (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("<https://a.b/c--d...>", &smart)?,
+        "<p><a href=\"https://a.b/c--d...\">https://a.b/c--d...</a></p>",
+        "should not support smart punctuation in autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "www.a--b.com \"c\"",
+            &Options {
+                parse: ParseOptions {
+                    smart_punctuation: true,
+                    ..ParseOptions::gfm()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"http://www.a--b.com\">www.a--b.com</a> “c”</p>",
+        "should not support smart punctuation in autolink literals"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a <b c=\"d\"> \"e\"",
+            &Options {
+                parse: ParseOptions {
+                    smart_punctuation: true,
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..Default::default()
+                }
+            }
+        )?,
+        "<p>a <b c=\"d\"> “e”</p>",
+        "should not support smart punctuation in HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options("[\"a\"](b \"c--d\")", &smart)?,
+        "<p><a href=\"b\" title=\"c--d\">“a”</a></p>",
+        "should support smart punctuation in labels, but not in titles"
+    );
+
+    assert_eq!(
+        to_mdast("a \"b\"...", &smart.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "a “b”…".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 9, 8))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 9, 8))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 9, 8))
+        }),
+        "should support smart punctuation as `Text`s in mdast"
+    );
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use markdown::to_html;
+use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -15,3 +15,65 @@ fn soft_break() {
         "should trim spaces around line endings"
     );
 }
+
+#[test]
+fn soft_break_hard_line_breaks() -> Result<(), message::Message> {
+    let hard = Options {
+        compile: CompileOptions {
+            hard_line_breaks: true,
+            allow_dangerous_html: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("foo\nbaz", &hard)?,
+        "<p>foo<br />\nbaz</p>",
+        "should support `hard_line_breaks` to turn line endings into breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\r\nb\rc", &hard)?,
+        "<p>a<br />\r\nb<br />\rc</p>",
+        "should keep the line ending after a break"
+    );
+
+    assert_eq!(
+        to_html_with_options("a  \nb\\\nc", &hard)?,
+        "<p>a<br />\nb<br />\nc</p>",
+        "should not add a break after a hard break"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a\nb* [c\nd](e)", &hard)?,
+        "<p><em>a<br />\nb</em> <a href=\"e\">c<br />\nd</a></p>",
+        "should support breaks in phrasing content"
+    );
+
+    assert_eq!(
+        to_html_with_options("`a\nb`", &hard)?,
+        "<p><code>a b</code></p>",
+        "should not add breaks in code (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a\nb](c \"d\ne\")", &hard)?,
+        "<p><img src=\"c\" alt=\"a\nb\" title=\"d\ne\" /></p>",
+        "should not add breaks in attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("<a\nb>", &hard)?,
+        "<p><a\nb></p>",
+        "should not add breaks in HTML (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\nb\n\n```\nc\nd\n```\n> e\n- f\n  g", &hard)?,
+        "<h1>a</h1>\n<p>b</p>\n<pre><code>c\nd\n</code></pre>\n<blockquote>\n<p>e</p>\n</blockquote>\n<ul>\n<li>f<br />\ng</li>\n</ul>",
+        "should not add breaks between blocks or in code (flow)"
+    );
+
+    Ok(())
+}
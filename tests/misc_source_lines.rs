@@ -0,0 +1,57 @@
+use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn source_lines() -> Result<(), message::Message> {
+    let lines = &Options {
+        compile: CompileOptions {
+            source_lines: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("a\n\nb"),
+        "<p>a</p>\n<p>b</p>",
+        "should not add lines by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\nb\n\nc", lines)?,
+        "<p data-line=\"1\">a</p>\n<p data-line=\"3\">b</p>\n<p data-line=\"5\">c</p>",
+        "should add lines to paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n\nb\n=", lines)?,
+        "<h1 data-line=\"1\">a</h1>\n<h1 data-line=\"3\">b</h1>",
+        "should add lines to headings (atx, setext)"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> b\n\n* c\n\n  d\n* e", lines)?,
+        "<blockquote data-line=\"1\">\n<p data-line=\"1\">a\nb</p>\n</blockquote>\n<ul>\n<li data-line=\"4\">\n<p data-line=\"4\">c</p>\n<p data-line=\"6\">d</p>\n</li>\n<li data-line=\"7\">\n<p data-line=\"7\">e</p>\n</li>\n</ul>",
+        "should add lines to block quotes, list items, and their content"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n- b", lines)?,
+        "<ul>\n<li data-line=\"1\">a</li>\n<li data-line=\"2\">b</li>\n</ul>",
+        "should not add lines to paragraphs in tight lists, as they are not shown"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js\na\n```\n\n    b", lines)?,
+        "<pre data-line=\"1\"><code class=\"language-js\">a\n</code></pre>\n<pre data-line=\"5\"><code>b\n</code></pre>",
+        "should add lines to code (fenced, indented)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a *b*\nc `d`", lines)?,
+        "<p data-line=\"1\">a <em>b</em>\nc <code>d</code></p>",
+        "should not add lines to text"
+    );
+
+    Ok(())
+}
@@ -1,4 +1,9 @@
-use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
+use markdown::{
+    mdast::{Emphasis, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Options, ParseOptions,
+};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -284,9 +289,138 @@ fn tabs_virtual_spaces() {
 
     assert_eq!(
         to_html("-\ta\n\n\tb"),
-        "<ul>\n<li>\n<p>a</p>\n<p>\tb</p>\n</li>\n</ul>",
-        // To do: CM.js does not output the tab before `b`. See if that makes sense?
-        // "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
+        "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
         "should support a part of a tab as a container, and the rest of a tab as flow"
     );
 }
+
+#[test]
+fn tabs_tab_size() -> Result<(), message::Message> {
+    let two = Options {
+        parse: ParseOptions {
+            tab_size: 2,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("  x", &two)?,
+        "<pre><code>x\n</code></pre>",
+        "should support 2*SP to start code w/ `tab_size: 2`"
+    );
+
+    assert_eq!(
+        to_html_with_options(" x", &two)?,
+        "<p>x</p>",
+        "should not support 1*SP to start code w/ `tab_size: 2`"
+    );
+
+    assert_eq!(
+        to_html_with_options("\t\tx", &two)?,
+        "<pre><code>\tx\n</code></pre>",
+        "should support a tab to start code, and keep the rest, w/ `tab_size: 2`"
+    );
+
+    assert_eq!(
+        to_html_with_options(" # a\n  # b", &two)?,
+        "<h1>a</h1>\n<pre><code># b\n</code></pre>",
+        "should prefer code over other flow constructs w/ `tab_size` indent"
+    );
+
+    assert_eq!(
+        to_html_with_options(">\t\tx", &two)?,
+        "<blockquote>\n<pre><code>x\n</code></pre>\n</blockquote>",
+        "should expand tabs to stops of `tab_size` in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options("-\ta\n\n\t\tb", &two)?,
+        "<ul>\n<li>\n<p>a</p>\n<pre><code>b\n</code></pre>\n</li>\n</ul>",
+        "should support code in list items w/ `tab_size: 2`"
+    );
+
+    assert_eq!(
+        to_mdast("a\tb *c*", &two.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a\tb ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 5, 4))
+                    }),
+                    Node::Emphasis(Emphasis {
+                        children: vec![Node::Text(Text {
+                            value: "c".into(),
+                            position: Some(Position::new(1, 6, 5, 1, 7, 6))
+                        })],
+                        position: Some(Position::new(1, 5, 4, 1, 8, 7))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 8, 7))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 8, 7))
+        }),
+        "should report columns w/ `tab_size`"
+    );
+
+    assert_eq!(
+        to_mdast("a\tb *c*", &ParseOptions::default())?
+            .children()
+            .unwrap()[0]
+            .children()
+            .unwrap()[1]
+            .position()
+            .unwrap()
+            .start
+            .column,
+        7,
+        "should report columns w/ a default tab size of 4"
+    );
+
+    assert_eq!(
+        to_html_with_options("   > > 1.  one\n>>\n>>     two\n", &two)?,
+        "<pre><code> &gt; &gt; 1.  one\n</code></pre>\n<blockquote>\n<blockquote>\n<pre><code>  two\n</code></pre>\n</blockquote>\n</blockquote>\n",
+        "should support code after an empty line in nested block quotes w/ `tab_size: 2`"
+    );
+
+    assert_eq!(
+        to_html_with_options(">   - foo\n\n\tbar\n", &two)?,
+        "<blockquote>\n<pre><code>- foo\n</code></pre>\n</blockquote>\n<pre><code>bar\n</code></pre>\n",
+        "should support code after an empty line after a block quote w/ `tab_size: 2`"
+    );
+
+    for tab_size in 1..4 {
+        let mut parse = ParseOptions::gfm();
+        parse.tab_size = tab_size;
+
+        for value in ["   > > 1.  one\n>>\n>>     two\n", ">   - foo\n\n\tbar\n"] {
+            assert!(
+                to_mdast(value, &parse).is_ok(),
+                "should support `{:?}` in mdast w/ `tab_size: {}`",
+                value,
+                tab_size
+            );
+        }
+    }
+
+    assert_eq!(
+        to_html_with_options(
+            "a",
+            &Options {
+                parse: ParseOptions {
+                    tab_size: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )
+        .err()
+        .unwrap()
+        .reason,
+        "Unexpected `tab_size` of `0`, expected a tab size of at least `1`",
+        "should crash on a tab size of 0"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,299 @@
+use markdown::{message, to_html_with_options, to_markdown, CompileOptions, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn to_markdown_normalize() -> Result<(), message::Message> {
+    assert_eq!(
+        to_markdown("", &ParseOptions::default())?,
+        "",
+        "should support an empty document"
+    );
+
+    assert_eq!(
+        to_markdown("a\nb\n\n\n\nc", &ParseOptions::default())?,
+        "a\nb\n\nc\n",
+        "should separate blocks with one blank line"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "Some *emphasis*, __strong__, and `code`.",
+            &ParseOptions::default()
+        )?,
+        "Some *emphasis*, **strong**, and `code`.\n",
+        "should use asterisks for emphasis and strong"
+    );
+
+    assert_eq!(
+        to_markdown("*_a_*", &ParseOptions::default())?,
+        "*_a_*\n",
+        "should use underscores for emphasis directly in emphasis"
+    );
+
+    assert_eq!(
+        to_markdown("a  \nb\\\nc", &ParseOptions::default())?,
+        "a\\\nb\\\nc\n",
+        "should use escapes for hard breaks"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "a\n===\n\n## b ##\n\nc\nd\n---\n\n### e\\\nf",
+            &ParseOptions::default()
+        )?,
+        "# a\n\n## b\n\nc\nd\n---\n\n### e\\\\\n\nf\n",
+        "should use ATX headings, unless a heading spans several lines"
+    );
+
+    assert_eq!(
+        to_markdown("# a #\\#", &ParseOptions::default())?,
+        "# a \\##\n",
+        "should escape what would otherwise be a closing sequence"
+    );
+
+    assert_eq!(
+        to_markdown("***\n___\n- - -", &ParseOptions::default())?,
+        "***\n\n***\n\n***\n",
+        "should use asterisks for thematic breaks"
+    );
+
+    assert_eq!(
+        to_markdown("> a\n> # b\n>\n> c", &ParseOptions::default())?,
+        "> a\n>\n> # b\n>\n> c\n",
+        "should support block quotes"
+    );
+
+    assert_eq!(
+        to_markdown("* a\n* b\n\n3) c\n1) d", &ParseOptions::default())?,
+        "- a\n- b\n\n3. c\n4. d\n",
+        "should use `-` and `.` for lists, counting up"
+    );
+
+    assert_eq!(
+        to_markdown("7. a\n\n   b\n8. c", &ParseOptions::default())?,
+        "7. a\n\n   b\n\n8. c\n",
+        "should indent the content of list items"
+    );
+
+    assert_eq!(
+        to_markdown("- a\n- b\n+ c\n+ d\n- e", &ParseOptions::default())?,
+        "- a\n- b\n\n* c\n* d\n\n- e\n",
+        "should alternate markers of lists next to each other"
+    );
+
+    assert_eq!(
+        to_markdown("* - + a", &ParseOptions::default())?,
+        "- * - a\n",
+        "should alternate markers of lists directly in list items"
+    );
+
+    assert_eq!(
+        to_markdown("    a\n\n    b", &ParseOptions::default())?,
+        "```\na\n\nb\n```\n",
+        "should use fenced code"
+    );
+
+    assert_eq!(
+        to_markdown("~~~js eval\n```\n~~~", &ParseOptions::default())?,
+        "````js eval\n```\n````\n",
+        "should use fences longer than the sequences in the code"
+    );
+
+    assert_eq!(
+        to_markdown("~~~ a`b\n~~~", &ParseOptions::default())?,
+        "~~~a`b\n~~~\n",
+        "should use tildes if there are grave accents in the info"
+    );
+
+    assert_eq!(
+        to_markdown("`` a`b `` ` `` `", &ParseOptions::default())?,
+        "``a`b`` ` `` `\n",
+        "should support code (text)"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "[a](<b c> \"d\\\"e\") ![f](g 'h')",
+            &ParseOptions::default()
+        )?,
+        "[a](<b c> \"d\\\"e\") ![f](g \"h\")\n",
+        "should support links and images"
+    );
+
+    assert_eq!(
+        to_markdown("<https://a.b> <c@d.e> [f](f:g)", &ParseOptions::default())?,
+        "<https://a.b> <c@d.e> [f](f:g)\n",
+        "should support autolinks"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "[a] [B][] [c][D]\n\n[A]: <> (t)\n[b]: c\n[d]: e",
+            &ParseOptions::default()
+        )?,
+        "[a] [B][] [c][D]\n\n[A]: <> \"t\"\n\n[b]: c\n\n[d]: e\n",
+        "should support references and definitions"
+    );
+
+    assert_eq!(
+        to_markdown("[*a*][]\n\n[*a*]: b", &ParseOptions::default())?,
+        "[*a*][]\n\n[*a*]: b\n",
+        "should support references w/ markup"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "1\\. a\n\\# b\n\\- c\n\\+ d\n\\> e",
+            &ParseOptions::default()
+        )?,
+        "1\\. a\n\\# b\n\\- c\n\\+ d\n\\> e\n",
+        "should escape what would otherwise start a block"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "\\*a\\* \\_b\\_ c_d \\`e\\` \\[f\\] \\<g> &amp;h; 1 < 2 & 3",
+            &ParseOptions::default()
+        )?,
+        "\\*a\\* \\_b\\_ c_d \\`e\\` \\[f\\] \\<g> \\&h; 1 < 2 & 3\n",
+        "should escape what would otherwise form inline syntax"
+    );
+
+    assert_eq!(
+        to_markdown("&#32;a&#10;&#10;b", &ParseOptions::default())?,
+        "&#x20;a&#xA;\nb\n",
+        "should use character references for initial whitespace and blank lines"
+    );
+
+    assert_eq!(
+        to_markdown("| a | b |\n| :- | -: |\n| c | d |", &ParseOptions::gfm())?,
+        "| a | b |\n| :-- | --: |\n| c | d |\n",
+        "should support tables (GFM)"
+    );
+
+    assert_eq!(
+        to_markdown("* [x] a\n* [ ] b\n\n~c~ www.d.com", &ParseOptions::gfm())?,
+        "- [x] a\n- [ ] b\n\n~~c~~ www.d.com\n",
+        "should support tasks, strikethrough, and literal autolinks (GFM)"
+    );
+
+    assert_eq!(
+        to_markdown("a[^b]\n\n[^b]: c\n\n    d", &ParseOptions::gfm())?,
+        "a[^b]\n\n[^b]: c\n\n    d\n",
+        "should support footnotes (GFM)"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_round_trip() -> Result<(), message::Message> {
+    let inputs = [
+        "# a\n\nb *c* **d** `e`\n\n> f\n\n- g\n- h\n",
+        "Setext\n===\n\nHeading\n---\n\n###### ATX ######\n",
+        "1. a\n\n   b\n2. c\n   ```js\n   d\n   ```\n\n3) e\n",
+        "- a\n  - b\n    - c\n\n      d\n- e\n\n* f\n",
+        "> # a\n> - b\n>   > c\n>\n> ```\n> d\n> ```\n",
+        "```\n````\n```\n\n    indented\n\n~~~\n```\n~~~\n",
+        "a\\\nb  \nc\n\n*a **b** c* ***d*** _e_f_ __g__\n",
+        "[a](b) [c](<d e> \"f\") ![g](h 'i') <j:k> <l@m.n>\n",
+        "[a] [b][] [c][a] ![d][a]\n\n[a]: b\n[B]: <c d> 'e'\n",
+        "\\*a\\* \\_b\\_ \\`c\\` \\[d\\] \\\\ \\# &amp; &copy; &#35; <x>\n",
+        "1\\. a\n\\- b\n\\+ c\n\\= d\n\\# e\n\\> f\n\\~~~ g\n",
+        "&#32;a\n&#9;b\n&#10;\nc\n",
+        "`` `a` `` ` b ` `  ` ``\na\n``\n",
+        "<div>\n*a*\n</div>\n\n<!-- b -->\n",
+        "***\n---\n___\n",
+        "[*a* **b**][] ![c *d*][]\n\n[*a* **b**]: e\n[c *d*]: f\n",
+        "![a ![b](c) *d*](e) ![f ![g][h]][h]\n\n[h]: i\n",
+    ];
+
+    for input in inputs {
+        let once = to_markdown(input, &ParseOptions::default())?;
+
+        assert_eq!(
+            to_markdown(&once, &ParseOptions::default())?,
+            once,
+            "should be stable: `{:?}`",
+            input
+        );
+
+        assert_eq!(
+            to_html_with_options(&once, &Options::default())?,
+            to_html_with_options(input, &Options::default())?,
+            "should turn into the same HTML: `{:?}`",
+            input
+        );
+    }
+
+    let inputs = [
+        "| a | b |\n| :- | :-: |\n| c \\| d | `e` |\n",
+        "* [x] a\n* [ ] b\n\n~a~ ~~b~~ www.c.com d@e.f\n",
+        "a[^b] c[^d]\n\n[^b]: e\n\n    f\n\n[^d]: g\n",
+    ];
+
+    for input in inputs {
+        let once = to_markdown(input, &ParseOptions::gfm())?;
+
+        assert_eq!(
+            to_markdown(&once, &ParseOptions::gfm())?,
+            once,
+            "should be stable (GFM): `{:?}`",
+            input
+        );
+
+        assert_eq!(
+            to_html_with_options(&once, &Options::gfm())?,
+            to_html_with_options(input, &Options::gfm())?,
+            "should turn into the same HTML (GFM): `{:?}`",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_commonmark() -> Result<(), message::Message> {
+    // Take the examples from the `CommonMark` test suite, which is generated.
+    let source = include_str!("commonmark.rs");
+    let prefix = "to_html_with_options(\n            r###\"";
+    let danger = Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            allow_dangerous_protocol: true,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+    let mut rest = source;
+    let mut count = 0;
+
+    while let Some(start) = rest.find(prefix) {
+        rest = &rest[(start + prefix.len())..];
+        let end = rest.find("\"###").unwrap();
+        let input = &rest[..end];
+        rest = &rest[end..];
+        count += 1;
+
+        let once = to_markdown(input, &danger.parse)?;
+
+        assert_eq!(
+            to_markdown(&once, &danger.parse)?,
+            once,
+            "should be stable (CommonMark): `{:?}`",
+            input
+        );
+
+        assert_eq!(
+            to_html_with_options(&once, &danger)?,
+            to_html_with_options(input, &danger)?,
+            "should turn into the same HTML (CommonMark): `{:?}`",
+            input
+        );
+    }
+
+    assert_eq!(count, 652, "should find all `CommonMark` examples");
+
+    Ok(())
+}
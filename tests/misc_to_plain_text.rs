@@ -0,0 +1,64 @@
+use markdown::{message, to_plain_text, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn to_plain_text_basic() -> Result<(), message::Message> {
+    assert_eq!(
+        to_plain_text("", &ParseOptions::default())?,
+        "",
+        "should support an empty document"
+    );
+
+    assert_eq!(
+        to_plain_text("# Hi **there**", &ParseOptions::default())?,
+        "Hi there",
+        "should drop markup"
+    );
+
+    assert_eq!(
+        to_plain_text("a\nb\n\n> c\n\n- d\n- e\n\n***\n\nf", &ParseOptions::default())?,
+        "a b\nc\nd\ne\nf",
+        "should separate blocks with a line ending, and turn line endings in paragraphs into spaces"
+    );
+
+    assert_eq!(
+        to_plain_text("a  \nb\\\nc", &ParseOptions::default())?,
+        "a\nb\nc",
+        "should turn hard breaks into line endings"
+    );
+
+    assert_eq!(
+        to_plain_text(
+            "![a](b) [c *d*](e) [f] ![g][f] `h\ni`\n\n[f]: x",
+            &ParseOptions::default()
+        )?,
+        "a c d f g h i",
+        "should use the alt of images, the text of links, and the content of code"
+    );
+
+    assert_eq!(
+        to_plain_text("```js\na\n  b\n```\n\n    c", &ParseOptions::default())?,
+        "a\n  b\nc",
+        "should use the content of code (flow)"
+    );
+
+    assert_eq!(
+        to_plain_text(
+            "<div>\n*a*\n</div>\n\nb <i>c</i> &amp; &copy;",
+            &ParseOptions::default()
+        )?,
+        "b c & ©",
+        "should drop HTML, and decode character references"
+    );
+
+    assert_eq!(
+        to_plain_text(
+            "| a | b |\n| - | - |\n| c | d |\n\ne[^f]\n\n[^f]: g",
+            &ParseOptions::gfm()
+        )?,
+        "a b\nc d\ne\ng",
+        "should support GFM"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use markdown::{
+    message, to_html_blocks, to_html_with_options, BlockKind, CompileOptions, Constructs, Options,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn trim_trailing_whitespace() -> Result<(), message::Message> {
+    let danger = &Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let trim = &Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                math_flow: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            trim_trailing_whitespace: true,
+            ..Default::default()
+        },
+    };
+
+    assert_eq!(
+        to_html_with_options("<div>  \n*a*  \n</div>  ", danger)?,
+        "<div>  \n*a*  \n</div>  ",
+        "should keep trailing whitespace by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("<div>  \n*a*  \n</div>  ", trim)?,
+        "<div>\n*a*\n</div>",
+        "should remove trailing whitespace from lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("<div> \t\r\n</div>", trim)?,
+        "<div>\r\n</div>",
+        "should remove trailing tabs, before CRLF"
+    );
+
+    assert_eq!(
+        to_html_with_options("<div>  \n\n*a*", trim)?,
+        "<div>\n<p><em>a</em></p>",
+        "should remove trailing whitespace before line endings that are added"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na  \n```", trim)?,
+        "<pre><code>a  \n</code></pre>",
+        "should keep trailing whitespace in code (fenced)"
+    );
+
+    assert_eq!(
+        to_html_with_options("    a  \n    b  ", trim)?,
+        "<pre><code>a  \nb  \n</code></pre>",
+        "should keep trailing whitespace in code (indented)"
+    );
+
+    assert_eq!(
+        to_html_with_options("> ```\n> a  ", trim)?,
+        "<blockquote>\n<pre><code>a  \n</code></pre>\n</blockquote>",
+        "should keep trailing whitespace in unclosed code in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options("$$\na  \n$$", trim)?,
+        "<pre><code class=\"language-math math-display\">a  \n</code></pre>",
+        "should keep trailing whitespace in math (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("<pre>  \na  \n</pre>  \n\nb", trim)?,
+        "<pre>  \na  \n</pre>\n<p>b</p>",
+        "should keep trailing whitespace in HTML (flow) for `<pre>`"
+    );
+
+    assert_eq!(
+        to_html_with_options("<TEXTAREA>\na  \n</TEXTAREA>", trim)?,
+        "<TEXTAREA>\na  \n</TEXTAREA>",
+        "should keep trailing whitespace in HTML (flow) for other raw tags"
+    );
+
+    assert_eq!(
+        to_html_blocks("<div>  \n\na", trim)?.collect::<Vec<_>>(),
+        vec![
+            (BlockKind::HtmlFlow, "<div>\n".into()),
+            (BlockKind::Paragraph, "<p>a</p>".into())
+        ],
+        "should remove trailing whitespace from blocks"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+// `try_to_html` needs `std`: run these tests with `--all-features` (as CI
+// does) or `--features std`.
+#![cfg(feature = "std")]
+
+use markdown::{event::Event, try_to_html, CompileOptions, Constructs, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+use std::rc::Rc;
+
+#[test]
+fn try_html() {
+    assert_eq!(
+        try_to_html("*a*", &Options::default()),
+        Ok("<p><em>a</em></p>".into()),
+        "should turn markdown into HTML"
+    );
+
+    assert_eq!(
+        try_to_html(
+            "{a",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs::mdx(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        ),
+        Err("1:3: Unexpected end of file in expression, expected a corresponding closing brace for `{` (markdown-rs:unexpected-eof)".into()),
+        "should turn messages into errors"
+    );
+
+    assert_eq!(
+        try_to_html(
+            "a",
+            &Options {
+                parse: ParseOptions {
+                    resolvers: vec![(
+                        "a".into(),
+                        Box::new(|_: &mut Vec<Event>, _: &str| panic!("b"))
+                    )],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        ),
+        Err("b".into()),
+        "should turn panics while parsing into errors"
+    );
+
+    assert_eq!(
+        try_to_html(
+            "# a",
+            &Options {
+                compile: CompileOptions {
+                    heading_ids: true,
+                    heading_id_fn: Some(Rc::new(|_| panic!("b"))),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        ),
+        Err("b".into()),
+        "should turn panics in functions in options into errors"
+    );
+}
@@ -0,0 +1,66 @@
+use markdown::{
+    message, to_html, to_html_with_options, CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn unsafe_raw_code() -> Result<(), message::Message> {
+    let raw = &Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                math_flow: true,
+                math_text: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        compile: CompileOptions {
+            unsafe_raw_code: true,
+            ..Default::default()
+        },
+    };
+
+    assert_eq!(
+        to_html("```\n<a & b>\n```"),
+        "<pre><code>&lt;a &amp; b&gt;\n</code></pre>",
+        "should encode code (flow) by default"
+    );
+
+    assert_eq!(
+        to_html("`<a & b>`"),
+        "<p><code>&lt;a &amp; b&gt;</code></p>",
+        "should encode code (text) by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\n<a & b>\n```", raw)?,
+        "<pre><code><a & b>\n</code></pre>",
+        "should not encode code (fenced) w/ `unsafe_raw_code`"
+    );
+
+    assert_eq!(
+        to_html_with_options("    <a & b>", raw)?,
+        "<pre><code><a & b>\n</code></pre>",
+        "should not encode code (indented) w/ `unsafe_raw_code`"
+    );
+
+    assert_eq!(
+        to_html_with_options("`<a & b>`", raw)?,
+        "<p><code><a & b></code></p>",
+        "should not encode code (text) w/ `unsafe_raw_code`"
+    );
+
+    assert_eq!(
+        to_html_with_options("```<x>\n<a>\n```\n\n<b> `<c>` &amp;", raw)?,
+        "<pre><code class=\"language-&lt;x&gt;\"><a>\n</code></pre>\n<p>&lt;b&gt; <code><c></code> &amp;</p>",
+        "should still encode other things w/ `unsafe_raw_code`"
+    );
+
+    assert_eq!(
+        to_html_with_options("$$\n<a>\n$$\n\n$<b>$", raw)?,
+        "<pre><code class=\"language-math math-display\">&lt;a&gt;\n</code></pre>\n<p><code class=\"language-math math-inline\">&lt;b&gt;</code></p>",
+        "should still encode math w/ `unsafe_raw_code`"
+    );
+
+    Ok(())
+}
@@ -27,6 +27,36 @@ fn url() {
         "should support incorrect percentage encoded values (3)"
     );
 
+    assert_eq!(
+        to_html("[x](<a b>)"),
+        "<p><a href=\"a%20b\">x</a></p>",
+        "should percent-encode spaces in an enclosed destination"
+    );
+
+    assert_eq!(
+        to_html("[x](a b)"),
+        "<p>[x](a b)</p>",
+        "should not support spaces in a raw destination (which is not a link)"
+    );
+
+    assert_eq!(
+        to_html("![x](<a b>)"),
+        "<p><img src=\"a%20b\" alt=\"x\" /></p>",
+        "should percent-encode spaces in an image source"
+    );
+
+    assert_eq!(
+        to_html("[x](/café/ü?q=ñ#ä)"),
+        "<p><a href=\"/caf%C3%A9/%C3%BC?q=%C3%B1#%C3%A4\">x</a></p>",
+        "should percent-encode non-ASCII characters"
+    );
+
+    assert_eq!(
+        to_html("[x](/caf%C3%A9/a%20b)"),
+        "<p><a href=\"/caf%C3%A9/a%20b\">x</a></p>",
+        "should not encode already percent-encoded sequences again"
+    );
+
     // Note: Surrogate handling not needed in Rust.
     //   assert_eq!(
     //     to_html("[](<foo\u{D800}bar>)"),
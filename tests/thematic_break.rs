@@ -104,6 +104,12 @@ fn thematic_break() -> Result<(), message::Message> {
         "should support thematic breaks w/ trailing spaces"
     );
 
+    assert_eq!(
+        to_html("*\u{00A0}*\u{00A0}*"),
+        "<p>*\u{00A0}*\u{00A0}*</p>",
+        "should not support thematic breaks w/ unicode whitespace"
+    );
+
     assert_eq!(
         to_html("_ _ _ _ a"),
         "<p>_ _ _ _ a</p>",
@@ -188,6 +194,56 @@ fn thematic_break() -> Result<(), message::Message> {
         "should support turning off thematic breaks"
     );
 
+    let min = Options {
+        parse: ParseOptions {
+            thematic_break_min: 4,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("****", &min)?,
+        "<hr />",
+        "should support thematic breaks w/ `thematic_break_min` markers"
+    );
+
+    assert_eq!(
+        to_html_with_options("_ _ _ _ _", &min)?,
+        "<hr />",
+        "should support thematic breaks w/ more than `thematic_break_min` markers"
+    );
+
+    assert_eq!(
+        to_html_with_options("___", &min)?,
+        "<p>___</p>",
+        "should not support thematic breaks w/ fewer than `thematic_break_min` markers"
+    );
+
+    assert_eq!(
+        to_html_with_options("* * *", &min)?,
+        "<ul>\n<li>\n<ul>\n<li>\n<ul>\n<li></li>\n</ul>\n</li>\n</ul>\n</li>\n</ul>",
+        "should not support thematic breaks w/ fewer than `thematic_break_min` markers and spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n---", &min)?,
+        "<h2>a</h2>",
+        "should support a heading (setext) underline w/ fewer than `thematic_break_min` markers"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n***", &min)?,
+        "<p>a\n***</p>",
+        "should support a paragraph continuation w/ fewer than `thematic_break_min` markers"
+    );
+
+    assert_eq!(
+        to_html_with_options("*-*-", &min)?,
+        "<p><em>-</em>-</p>",
+        "should not support mixed markers w/ `thematic_break_min`"
+    );
+
     assert_eq!(
         to_mdast("***", &Default::default())?,
         Node::Root(Root {
@@ -0,0 +1,279 @@
+use markdown::{
+    mdast::{Link, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use std::rc::Rc;
+
+#[test]
+fn wikilink() -> Result<(), message::Message> {
+    let wiki = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                wikilink: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("[[a]]"),
+        "<p>[[a]]</p>",
+        "should not support wikilinks by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[Page Name]]", &wiki)?,
+        "<p><a href=\"page-name\">Page Name</a></p>",
+        "should support a wikilink, slugging the target"
+    );
+
+    assert_eq!(
+        to_html_with_options("a [[b|c]] d", &wiki)?,
+        "<p>a <a href=\"b\">c</a> d</p>",
+        "should support a wikilink w/ label"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a|b|c]]", &wiki)?,
+        "<p><a href=\"a\">b|c</a></p>",
+        "should split target and label on the first pipe only"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[]]", &wiki)?,
+        "<p>[[]]</p>",
+        "should not support an empty target"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[|a]]", &wiki)?,
+        "<p>[[|a]]</p>",
+        "should not support an empty target w/ label"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a|]]", &wiki)?,
+        "<p>[[a|]]</p>",
+        "should not support an empty label"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a\nb]]", &wiki)?,
+        "<p>[[a\nb]]</p>",
+        "should not support a line ending"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a", &wiki)?,
+        "<p>[[a</p>",
+        "should not support an unclosed wikilink"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a|b [c]]]", &wiki)?,
+        "<p><a href=\"a\">b [c</a>]</p>",
+        "should close at the first `]]`"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a]b]]", &wiki)?,
+        "<p><a href=\"ab\">a]b</a></p>",
+        "should support a single `]` in a target"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a|b]c]]", &wiki)?,
+        "<p><a href=\"a\">b]c</a></p>",
+        "should support a single `]` in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a|*b* &amp;]]", &wiki)?,
+        "<p><a href=\"a\">*b* &amp;amp;</a></p>",
+        "should not support markdown in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b) [c] [d][]\n\n[c]: e\n[d]: f", &wiki)?,
+        "<p><a href=\"b\">a</a> <a href=\"e\">c</a> <a href=\"f\">d</a></p>\n",
+        "should not change links"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a [[b]]](c)", &wiki)?,
+        "<p>[a <a href=\"b\">b</a>](c)</p>",
+        "should not support a wikilink in a link (resource)"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a [[b]]][c]\n\n[c]: d", &wiki)?,
+        "<p>[a <a href=\"b\">b</a>]<a href=\"d\">c</a></p>\n",
+        "should not support a wikilink in a link (reference)"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a [[b]]](c)", &wiki)?,
+        "<p><img src=\"c\" alt=\"a b\" /></p>",
+        "should support a wikilink in an image"
+    );
+
+    assert_eq!(
+        to_html_with_options(&format!("[[{}]]", "a".repeat(999)), &wiki)?,
+        format!("<p><a href=\"{0}\">{0}</a></p>", "a".repeat(999)),
+        "should support a target of 999 bytes"
+    );
+
+    assert_eq!(
+        to_html_with_options(&format!("[[{}]]", "a".repeat(1000)), &wiki)?,
+        format!("<p>[[{}]]</p>", "a".repeat(1000)),
+        "should not support a target of 1000 bytes"
+    );
+
+    assert_eq!(
+        to_html_with_options(&format!("[[a|{}]]", "b".repeat(1000)), &wiki)?,
+        format!("<p>[[a|{}]]</p>", "b".repeat(1000)),
+        "should not support a label of 1000 bytes"
+    );
+
+    assert_eq!(
+        to_html_with_options(&"[[a ".repeat(20000), &wiki)?,
+        format!("<p>{}</p>", "[[a ".repeat(20000).trim_end()),
+        "should not be slow on many unclosed wikilinks"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[javascript:alert(1)|a]]", &wiki)?,
+        "<p><a href=\"javascriptalert1\">a</a></p>",
+        "should slug dangerous targets"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[[Page Name]]",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        wikilink: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    wikilink_href_fn: Some(Rc::new(|value| format!("/wiki/{}", value))),
+                    ..Default::default()
+                }
+            }
+        )?,
+        "<p><a href=\"/wiki/Page%20Name\">Page Name</a></p>",
+        "should support `wikilink_href_fn`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[[javascript:alert(1)|a]]",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        wikilink: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    wikilink_href_fn: Some(Rc::new(|value| value.into())),
+                    ..Default::default()
+                }
+            }
+        )?,
+        "<p><a href=\"\">a</a></p>",
+        "should sanitize the result of `wikilink_href_fn`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[[A & B|a]]",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        wikilink: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    wikilink_href_fn: Some(Rc::new(|value| format!("/w?q={}&b=\"c\"", value))),
+                    ..Default::default()
+                }
+            }
+        )?,
+        "<p><a href=\"/w?q=A%20&amp;%20B&amp;b=%22c%22\">a</a></p>",
+        "should encode the result of `wikilink_href_fn` once"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# a [[B c|D e]]",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        wikilink: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    heading_ids: true,
+                    ..Default::default()
+                }
+            }
+        )?,
+        "<h1 id=\"a-d-e\">a <a href=\"b-c\">D e</a></h1>",
+        "should use the label in heading IDs"
+    );
+
+    assert_eq!(
+        to_mdast("a [[b c|d]] [[e]]", &wiki.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::Link(Link {
+                        url: "b c".into(),
+                        title: None,
+                        children: vec![Node::Text(Text {
+                            value: "d".into(),
+                            position: Some(Position::new(1, 9, 8, 1, 10, 9))
+                        })],
+                        position: Some(Position::new(1, 3, 2, 1, 12, 11))
+                    }),
+                    Node::Text(Text {
+                        value: " ".into(),
+                        position: Some(Position::new(1, 12, 11, 1, 13, 12))
+                    }),
+                    Node::Link(Link {
+                        url: "e".into(),
+                        title: None,
+                        children: vec![Node::Text(Text {
+                            value: "e".into(),
+                            position: Some(Position::new(1, 15, 14, 1, 16, 15))
+                        })],
+                        position: Some(Position::new(1, 13, 12, 1, 18, 17))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 18, 17))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 18, 17))
+        }),
+        "should support wikilinks as `Link`s in mdast"
+    );
+
+    Ok(())
+}